@@ -0,0 +1,94 @@
+//! Compares the object map of the default build, a `BTreeMap`, with the insertion ordered map of
+//! the `preserve_order` feature:
+//!
+//! ```text
+//! cargo bench --bench object_map -- --save-baseline btree_map
+//! cargo bench --bench object_map --features preserve_order -- --baseline btree_map
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use vrl::value::{KeyString, ObjectMap, Value};
+
+static SIZES: [usize; 3] = [8, 64, 512];
+
+fn keys(size: usize) -> Vec<KeyString> {
+    // Not inserted in sorted order, so the maps cannot take a shortcut.
+    (0..size)
+        .map(|i| format!("field_{:04}", (i * 7919) % size).into())
+        .collect()
+}
+
+fn object(size: usize) -> ObjectMap {
+    keys(size)
+        .into_iter()
+        .enumerate()
+        .map(|(i, key)| (key, Value::from(i)))
+        .collect()
+}
+
+fn json(size: usize) -> String {
+    serde_json::to_string(&Value::Object(object(size))).unwrap()
+}
+
+fn benchmark_object_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vrl/value::ObjectMap");
+
+    for size in SIZES {
+        group.bench_with_input(BenchmarkId::new("insert", size), &keys(size), |b, keys| {
+            b.iter(|| {
+                let mut object = ObjectMap::new();
+                for key in keys {
+                    object.insert(key.clone(), Value::Null);
+                }
+                object
+            });
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("get", size),
+            &(object(size), keys(size)),
+            |b, (object, keys)| {
+                b.iter(|| {
+                    for key in keys {
+                        black_box(object.get(key.as_str()));
+                    }
+                });
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("remove", size), &keys(size), |b, keys| {
+            b.iter_batched(
+                || object(size),
+                |mut object| {
+                    for key in keys {
+                        black_box(object.remove(key));
+                    }
+                },
+                BatchSize::SmallInput,
+            );
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("parse_json", size),
+            &json(size),
+            |b, json| {
+                b.iter(|| serde_json::from_str::<Value>(json).unwrap());
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("encode_json", size),
+            &Value::Object(object(size)),
+            |b, value| {
+                b.iter(|| serde_json::to_string(value).unwrap());
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_object_map);
+criterion_main!(benches);
@@ -92,6 +92,7 @@ criterion_group!(
               r#match,
               match_any,
               match_array,
+              match_datadog_queries,
               match_datadog_query,
               md5,
               merge,
@@ -1325,6 +1326,24 @@ bench_function! {
     }
 }
 
+bench_function! {
+    match_datadog_queries => vrl::stdlib::MatchDatadogQueries;
+
+    route {
+        args: func_args![
+            value: value!({"service": "web", "status": "error", "http": {"status_code": 502}}),
+            queries: value!({
+                "web_errors": "service:web AND status:error",
+                "web_warnings": "service:web AND status:warn",
+                "api_errors": "service:api AND status:error",
+                "server_errors": "@http.status_code:[500 TO 599]",
+                "web_server_errors": "service:web AND @http.status_code:[500 TO 599]",
+            }),
+        ],
+        want: Ok(value!(["server_errors", "web_errors", "web_server_errors"])),
+    }
+}
+
 bench_function! {
     md5  => vrl::stdlib::Md5;
 
@@ -8,15 +8,15 @@ use std::{
     path::Path,
 };
 
-#[cfg(feature = "stdlib-base")]
+#[cfg(feature = "parsing")]
 use std::borrow::Cow;
-#[cfg(feature = "stdlib-base")]
+#[cfg(feature = "parsing")]
 use ua_parser::device::Flag;
 
 fn main() {
     read_grok_patterns();
 
-    #[cfg(feature = "stdlib-base")]
+    #[cfg(feature = "parsing")]
     convert_user_agent_regexes();
 
     println!("cargo:rerun-if-changed=src/parser/parser.lalrpop");
@@ -66,7 +66,7 @@ fn read_grok_patterns() {
     fs::write(dest_path, output).expect("'patterns.rs' wasn't generated");
 }
 
-#[cfg(feature = "stdlib-base")]
+#[cfg(feature = "parsing")]
 fn convert_user_agent_regexes() {
     let regexes = fs::read("data/user_agent_regexes.yaml").expect("Could not read regexes");
     let regexes: ua_parser::Regexes =
@@ -1,8 +1,6 @@
 #[macro_use]
 extern crate afl;
 
-use std::collections::BTreeMap;
-
 use vrl::compiler::state::RuntimeState;
 use vrl::compiler::{CompileConfig, TargetValue};
 use vrl::prelude::state::ExternalEnv;
@@ -36,7 +34,7 @@ fn fuzz(src: &str) {
     if let Ok(result) = vrl::compiler::compile_with_external(src, &fns, &external, config) {
         let mut target = TargetValue {
             value: value!({}),
-            metadata: Value::Object(BTreeMap::new()),
+            metadata: Value::Object(ObjectMap::new()),
             secrets: Secrets::default(),
         };
 
@@ -0,0 +1,278 @@
+//! Reading and writing of Avro [object container files](https://avro.apache.org/docs/1.11.1/specification/#object-container-files).
+
+use std::io::Read;
+
+use bytes::Bytes;
+use flate2::Crc;
+use flate2::read::DeflateDecoder;
+
+use super::decode::{decode, encodes_nothing, max_items};
+use super::encode::{encode, encode_bytes, encode_long};
+use super::schema::{AvroSchema, Schema};
+use crate::value::{ObjectMap, Value};
+
+const MAGIC: &[u8] = b"Obj\x01";
+const SYNC_SIZE: usize = 16;
+
+/// Decodes the records of an object container file, which must have been written with the
+/// same types as `schema`.
+///
+/// # Errors
+///
+/// Returns an error if the file is invalid, was written with a different schema, or uses an
+/// unsupported codec.
+pub fn decode_container(schema: &AvroSchema, mut bytes: &[u8]) -> Result<Vec<Value>, String> {
+    bytes = bytes
+        .strip_prefix(MAGIC)
+        .ok_or("not an Avro object container file")?;
+
+    let metadata = AvroSchema::anonymous(Schema::Map(Box::new(Schema::Bytes)));
+    let Value::Object(metadata) = decode(&metadata, &mut bytes)? else {
+        unreachable!("metadata is a map");
+    };
+    let writer_schema = match metadata.get("avro.schema") {
+        Some(Value::Bytes(json)) => AvroSchema::parse(&String::from_utf8_lossy(json))
+            .map_err(|e| format!("invalid schema in file: {e}"))?,
+        _ => return Err("the file has no schema".to_owned()),
+    };
+    if !writer_schema.same_types(schema) {
+        return Err("the file was written with a different schema".to_owned());
+    }
+    let codec = match metadata.get("avro.codec") {
+        Some(Value::Bytes(codec)) => String::from_utf8_lossy(codec).into_owned(),
+        _ => "null".to_owned(),
+    };
+
+    let sync = take(&mut bytes, SYNC_SIZE)?.to_vec();
+    let empty = encodes_nothing(schema, schema.root(), &mut Vec::new());
+    let mut records = Vec::new();
+    while !bytes.is_empty() {
+        let count = long(&mut bytes)?;
+        let size = long(&mut bytes)?;
+        let size = usize::try_from(size).map_err(|_| format!("invalid block size `{size}`"))?;
+        let block = decompress(&codec, take(&mut bytes, size)?)?;
+        if take(&mut bytes, SYNC_SIZE)? != sync {
+            return Err("invalid sync marker".to_owned());
+        }
+
+        let count = usize::try_from(count)
+            .ok()
+            .filter(|count| *count <= max_items(empty, block.len(), records.len()))
+            .ok_or_else(|| format!("invalid block count `{count}`"))?;
+
+        let mut block = block.as_slice();
+        for _ in 0..count {
+            records.push(decode(schema, &mut block)?);
+        }
+        if !block.is_empty() {
+            return Err("block has more data than its records".to_owned());
+        }
+    }
+
+    Ok(records)
+}
+
+/// Encodes the records as an object container file, without compression.
+///
+/// # Errors
+///
+/// Returns an error if a record does not match the schema.
+pub fn encode_container(schema: &AvroSchema, records: &[Value]) -> Result<Bytes, String> {
+    let mut buffer = MAGIC.to_vec();
+
+    let metadata = ObjectMap::from([
+        ("avro.schema".into(), schema.json().into()),
+        ("avro.codec".into(), "null".into()),
+    ]);
+    encode(
+        &AvroSchema::anonymous(Schema::Map(Box::new(Schema::Bytes))),
+        &Value::Object(metadata),
+        &mut buffer,
+    )?;
+    let sync: [u8; SYNC_SIZE] = rand::random();
+    buffer.extend_from_slice(&sync);
+
+    if !records.is_empty() {
+        let mut block = Vec::new();
+        for record in records {
+            encode(schema, record, &mut block)?;
+        }
+        encode_long(
+            i64::try_from(records.len()).expect("length fits in a long"),
+            &mut buffer,
+        );
+        encode_bytes(&block, &mut buffer);
+        buffer.extend_from_slice(&sync);
+    }
+
+    Ok(buffer.into())
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if bytes.len() < len {
+        return Err("unexpected end of data".to_owned());
+    }
+    let (taken, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(taken)
+}
+
+fn long(bytes: &mut &[u8]) -> Result<i64, String> {
+    match decode(&AvroSchema::anonymous(Schema::Long), bytes)? {
+        Value::Integer(long) => Ok(long),
+        _ => unreachable!("decoded a long"),
+    }
+}
+
+fn decompress(codec: &str, block: &[u8]) -> Result<Vec<u8>, String> {
+    let error = |e: &dyn std::fmt::Display| format!("unable to decompress {codec} block: {e}");
+    match codec {
+        "null" => Ok(block.to_vec()),
+        "deflate" => {
+            let mut decompressed = Vec::new();
+            DeflateDecoder::new(block)
+                .read_to_end(&mut decompressed)
+                .map_err(|e| error(&e))?;
+            Ok(decompressed)
+        }
+        "snappy" => {
+            // The compressed data is followed by the CRC32 checksum of the uncompressed data.
+            let (data, checksum) = block
+                .split_last_chunk::<4>()
+                .ok_or_else(|| error(&"missing checksum"))?;
+            let decompressed = snap::raw::Decoder::new()
+                .decompress_vec(data)
+                .map_err(|e| error(&e))?;
+            let mut crc = Crc::new();
+            crc.update(&decompressed);
+            if crc.sum().to_be_bytes() != *checksum {
+                return Err(error(&"checksum mismatch"));
+            }
+            Ok(decompressed)
+        }
+        "zstandard" => zstd::decode_all(block).map_err(|e| error(&e)),
+        codec => Err(format!("unsupported codec `{codec}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use std::io::Write;
+
+    fn schema() -> AvroSchema {
+        AvroSchema::parse(
+            r#"{"type": "record", "name": "Log", "fields": [{"name": "message", "type": "string"}]}"#,
+        )
+        .unwrap()
+    }
+
+    /// Rewrites a container file written by `encode_container` to use another codec.
+    fn recompress(file: &[u8], codec: &str, compress: fn(&[u8]) -> Vec<u8>) -> Vec<u8> {
+        let mut bytes = file.strip_prefix(MAGIC).unwrap();
+        let metadata = AvroSchema::anonymous(Schema::Map(Box::new(Schema::Bytes)));
+        let Value::Object(mut metadata) = decode(&metadata, &mut bytes).unwrap() else {
+            panic!("metadata is a map");
+        };
+        metadata.insert("avro.codec".into(), codec.into());
+        let sync = take(&mut bytes, SYNC_SIZE).unwrap();
+        let count = long(&mut bytes).unwrap();
+        let size = long(&mut bytes).unwrap();
+        let block = take(&mut bytes, usize::try_from(size).unwrap()).unwrap();
+
+        let mut file = MAGIC.to_vec();
+        encode(
+            &AvroSchema::anonymous(Schema::Map(Box::new(Schema::Bytes))),
+            &Value::Object(metadata),
+            &mut file,
+        )
+        .unwrap();
+        file.extend_from_slice(sync);
+        encode_long(count, &mut file);
+        encode_bytes(&compress(block), &mut file);
+        file.extend_from_slice(sync);
+        file
+    }
+
+    #[test]
+    fn roundtrips() {
+        let records = vec![value!({ message: "one" }), value!({ message: "two" })];
+        let file = encode_container(&schema(), &records).unwrap();
+
+        assert_eq!(decode_container(&schema(), &file).unwrap(), records);
+    }
+
+    #[test]
+    fn decodes_compressed_blocks() {
+        let records = vec![value!({ message: "compressed" })];
+        let file = encode_container(&schema(), &records).unwrap();
+
+        let deflate = recompress(&file, "deflate", |block| {
+            let mut encoder =
+                flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(block).unwrap();
+            encoder.finish().unwrap()
+        });
+        let snappy = recompress(&file, "snappy", |block| {
+            let mut compressed = snap::raw::Encoder::new().compress_vec(block).unwrap();
+            let mut crc = Crc::new();
+            crc.update(block);
+            compressed.extend(crc.sum().to_be_bytes());
+            compressed
+        });
+        let zstandard = recompress(&file, "zstandard", |block| {
+            zstd::encode_all(block, 0).unwrap()
+        });
+
+        for file in [deflate, snappy, zstandard] {
+            assert_eq!(decode_container(&schema(), &file).unwrap(), records);
+        }
+    }
+
+    #[test]
+    fn rejects_other_schemas() {
+        let file = encode_container(&AvroSchema::parse(r#""string""#).unwrap(), &[]).unwrap();
+
+        assert_eq!(
+            decode_container(&schema(), &file).unwrap_err(),
+            "the file was written with a different schema"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_sync_marker() {
+        let mut file = encode_container(&schema(), &[value!({ message: "one" })])
+            .unwrap()
+            .to_vec();
+        *file.last_mut().unwrap() ^= 1;
+
+        assert_eq!(
+            decode_container(&schema(), &file).unwrap_err(),
+            "invalid sync marker"
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_blocks() {
+        let schema = AvroSchema::parse(r#""null""#).unwrap();
+        let file = encode_container(&schema, &[Value::Null, Value::Null]).unwrap();
+        assert_eq!(
+            decode_container(&schema, &file).unwrap(),
+            vec![Value::Null, Value::Null]
+        );
+
+        // The only block holds its count and (empty) size before the sync marker.
+        let (header, sync) = file.split_at(file.len() - 2 - SYNC_SIZE);
+        let sync = &sync[2..];
+        let mut oversized = header.to_vec();
+        encode_long(1 << 40, &mut oversized);
+        encode_long(0, &mut oversized);
+        oversized.extend_from_slice(sync);
+
+        assert_eq!(
+            decode_container(&schema, &oversized).unwrap_err(),
+            "invalid block count `1099511627776`"
+        );
+    }
+}
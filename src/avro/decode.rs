@@ -0,0 +1,246 @@
+//! Decoding of Avro binary encoded data into VRL values.
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeDelta};
+use rust_decimal::Decimal;
+
+use super::schema::{AvroSchema, Definition, Logical, Schema};
+use crate::value::{ObjectMap, Value};
+
+/// The most items of an array, or records of an object container file, that are encoded without
+/// any data, such as `null`.
+const MAX_EMPTY_ITEMS: usize = 1 << 20;
+
+/// Decodes a single value from the start of `bytes`, advancing it past the value.
+///
+/// # Errors
+///
+/// Returns an error if `bytes` is not a valid encoding of a value of the schema.
+pub fn decode(schema: &AvroSchema, bytes: &mut &[u8]) -> Result<Value, String> {
+    Decoder { schema, bytes }.value(schema.root())
+}
+
+struct Decoder<'a, 'b> {
+    schema: &'a AvroSchema,
+    bytes: &'a mut &'b [u8],
+}
+
+impl Decoder<'_, '_> {
+    fn value(&mut self, schema: &Schema) -> Result<Value, String> {
+        Ok(match schema {
+            Schema::Null => Value::Null,
+            Schema::Boolean => match self.take(1)?[0] {
+                0 => false.into(),
+                1 => true.into(),
+                byte => return Err(format!("invalid boolean `{byte}`")),
+            },
+            Schema::Int => self.int()?.into(),
+            Schema::Long => self.long()?.into(),
+            Schema::Float => {
+                let bytes = self.take(4)?.try_into().expect("four bytes");
+                Value::from_f64_or_zero(f32::from_le_bytes(bytes).into())
+            }
+            Schema::Double => {
+                let bytes = self.take(8)?.try_into().expect("eight bytes");
+                Value::from_f64_or_zero(f64::from_le_bytes(bytes))
+            }
+            Schema::Bytes | Schema::String => Value::Bytes(self.bytes()?),
+            Schema::Array(items) => {
+                let mut array = Vec::new();
+                let empty = encodes_nothing(self.schema, items, &mut Vec::new());
+                while let Some(count) = self.block(empty, array.len())? {
+                    for _ in 0..count {
+                        array.push(self.value(items)?);
+                    }
+                }
+                Value::Array(array)
+            }
+            Schema::Map(values) => {
+                let mut object = ObjectMap::new();
+                while let Some(count) = self.block(false, object.len())? {
+                    for _ in 0..count {
+                        let key = String::from_utf8_lossy(&self.bytes()?).into_owned();
+                        object.insert(key.into(), self.value(values)?);
+                    }
+                }
+                Value::Object(object)
+            }
+            Schema::Union(branches) => {
+                let index = self.long()?;
+                let branch = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| branches.get(index))
+                    .ok_or_else(|| format!("invalid union branch `{index}`"))?;
+                self.value(branch)?
+            }
+            Schema::Named(index) => {
+                let schema = self.schema;
+                match &schema.named(*index).definition {
+                    Definition::Record(fields) => fields
+                        .iter()
+                        .map(|field| Ok((field.name.as_str().into(), self.value(&field.schema)?)))
+                        .collect::<Result<ObjectMap, String>>()?
+                        .into(),
+                    Definition::Enum(symbols) => {
+                        let index = self.int()?;
+                        let symbol = usize::try_from(index)
+                            .ok()
+                            .and_then(|index| symbols.get(index))
+                            .ok_or_else(|| format!("invalid enum symbol `{index}`"))?;
+                        symbol.as_str().into()
+                    }
+                    Definition::Fixed(size) => {
+                        Value::Bytes(Bytes::copy_from_slice(self.take(*size)?))
+                    }
+                }
+            }
+            Schema::Logical(logical, schema) => logical_value(*logical, self.value(schema)?)?,
+        })
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        if self.bytes.len() < len {
+            return Err("unexpected end of data".to_owned());
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        *self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn long(&mut self) -> Result<i64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                #[allow(clippy::cast_possible_wrap)]
+                return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+            }
+        }
+        Err("invalid variable-length integer".to_owned())
+    }
+
+    fn int(&mut self) -> Result<i64, String> {
+        let value = self.long()?;
+        i32::try_from(value)
+            .map(i64::from)
+            .map_err(|_| format!("int `{value}` is out of range"))
+    }
+
+    fn len(&mut self) -> Result<usize, String> {
+        let len = self.long()?;
+        usize::try_from(len).map_err(|_| format!("invalid length `{len}`"))
+    }
+
+    fn bytes(&mut self) -> Result<Bytes, String> {
+        let len = self.len()?;
+        Ok(Bytes::copy_from_slice(self.take(len)?))
+    }
+
+    /// Reads the item count of the next block of an array or map, or `None` at the end.
+    fn block(&mut self, empty: bool, decoded: usize) -> Result<Option<usize>, String> {
+        let count = self.long()?;
+        if count < 0 {
+            // A negative count is followed by the size of the block in bytes.
+            let size = self.len()?;
+            if size > self.bytes.len() {
+                return Err(format!("invalid block size `{size}`"));
+            }
+        }
+        if count == 0 {
+            return Ok(None);
+        }
+
+        usize::try_from(count.unsigned_abs())
+            .ok()
+            .filter(|count| *count <= max_items(empty, self.bytes.len(), decoded))
+            .map(Some)
+            .ok_or_else(|| format!("invalid block count `{count}`"))
+    }
+}
+
+/// The most items that can follow `decoded` items in `len` bytes of input.
+///
+/// Every item takes at least a byte unless it's `empty`, in which case the items are limited to
+/// [`MAX_EMPTY_ITEMS`] in total.
+pub(super) fn max_items(empty: bool, len: usize, decoded: usize) -> usize {
+    if empty {
+        MAX_EMPTY_ITEMS.saturating_sub(decoded)
+    } else {
+        len
+    }
+}
+
+/// Whether values of the schema are encoded without any data, `records` being the records that
+/// are being checked already.
+pub(super) fn encodes_nothing(
+    avro: &AvroSchema,
+    schema: &Schema,
+    records: &mut Vec<usize>,
+) -> bool {
+    match schema {
+        Schema::Null => true,
+        Schema::Logical(_, schema) => encodes_nothing(avro, schema, records),
+        Schema::Named(index) => match &avro.named(*index).definition {
+            // A record that contains itself can't be encoded at all.
+            Definition::Record(_) if records.contains(index) => false,
+            Definition::Record(fields) => {
+                records.push(*index);
+                let empty = fields
+                    .iter()
+                    .all(|field| encodes_nothing(avro, &field.schema, records));
+                records.pop();
+                empty
+            }
+            Definition::Enum(_) => false,
+            Definition::Fixed(size) => *size == 0,
+        },
+        _ => false,
+    }
+}
+
+fn logical_value(logical: Logical, value: Value) -> Result<Value, String> {
+    let timestamp = match (logical, value) {
+        (Logical::Decimal { scale, .. }, Value::Bytes(bytes)) => return decimal(&bytes, scale),
+        (Logical::Uuid, Value::Bytes(bytes)) if bytes.len() == 16 => {
+            let uuid = uuid::Uuid::from_slice(&bytes).expect("16 bytes");
+            return Ok(uuid.hyphenated().to_string().into());
+        }
+        (Logical::Date, Value::Integer(days)) => {
+            TimeDelta::try_days(days).and_then(|days| DateTime::UNIX_EPOCH.checked_add_signed(days))
+        }
+        (Logical::TimestampMillis, Value::Integer(millis)) => {
+            DateTime::from_timestamp_millis(millis)
+        }
+        (Logical::TimestampMicros, Value::Integer(micros)) => {
+            DateTime::from_timestamp_micros(micros)
+        }
+        (Logical::TimestampNanos, Value::Integer(nanos)) => {
+            Some(DateTime::from_timestamp_nanos(nanos))
+        }
+        (_, value) => return Ok(value),
+    };
+
+    timestamp
+        .map(Value::Timestamp)
+        .ok_or_else(|| "timestamp is out of range".to_owned())
+}
+
+/// Converts the big-endian two's complement unscaled value of a decimal to a decimal.
+fn decimal(bytes: &[u8], scale: u32) -> Result<Value, String> {
+    let out_of_range = || "decimal is out of range".to_owned();
+    if bytes.len() > 16 {
+        return Err(out_of_range());
+    }
+    let fill = if bytes.first().is_some_and(|byte| byte & 0x80 != 0) {
+        0xff
+    } else {
+        0
+    };
+    let mut unscaled = [fill; 16];
+    unscaled[16 - bytes.len()..].copy_from_slice(bytes);
+
+    Decimal::try_from_i128_with_scale(i128::from_be_bytes(unscaled), scale)
+        .map(Value::Decimal)
+        .map_err(|_| out_of_range())
+}
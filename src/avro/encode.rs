@@ -0,0 +1,272 @@
+//! Encoding of VRL values with the Avro binary encoding.
+
+use bytes::Bytes;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use super::schema::{AvroSchema, Definition, Logical, Schema};
+use crate::value::Value;
+
+/// Appends the encoding of `value` to `buffer`.
+///
+/// # Errors
+///
+/// Returns an error if the value does not match the schema.
+pub fn encode(schema: &AvroSchema, value: &Value, buffer: &mut Vec<u8>) -> Result<(), String> {
+    Encoder { schema, buffer }.value(schema.root(), value)
+}
+
+/// Appends the zig-zag variable-length encoding of a long to `buffer`.
+pub(super) fn encode_long(value: i64, buffer: &mut Vec<u8>) {
+    #[allow(clippy::cast_sign_loss)]
+    let mut value = ((value << 1) ^ (value >> 63)) as u64;
+    while value > 0x7f {
+        #[allow(clippy::cast_possible_truncation)]
+        buffer.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    buffer.push(value as u8);
+}
+
+/// Appends the length-prefixed encoding of bytes or a string to `buffer`.
+pub(super) fn encode_bytes(bytes: &[u8], buffer: &mut Vec<u8>) {
+    encode_long(len(bytes.len()), buffer);
+    buffer.extend_from_slice(bytes);
+}
+
+fn len(len: usize) -> i64 {
+    i64::try_from(len).expect("length fits in a long")
+}
+
+struct Encoder<'a> {
+    schema: &'a AvroSchema,
+    buffer: &'a mut Vec<u8>,
+}
+
+impl Encoder<'_> {
+    fn value(&mut self, schema: &Schema, value: &Value) -> Result<(), String> {
+        match (schema, value) {
+            (Schema::Null, Value::Null) => {}
+            (Schema::Boolean, Value::Boolean(boolean)) => self.buffer.push(u8::from(*boolean)),
+            (Schema::Int, Value::Integer(int)) => {
+                let int = i32::try_from(*int)
+                    .map_err(|_| format!("`{int}` is out of range for an int"))?;
+                encode_long(int.into(), self.buffer);
+            }
+            (Schema::Long, Value::Integer(long)) => encode_long(*long, self.buffer),
+            #[allow(clippy::cast_possible_truncation)]
+            (Schema::Float, Value::Float(float)) => {
+                self.buffer
+                    .extend((float.into_inner() as f32).to_le_bytes());
+            }
+            #[allow(clippy::cast_precision_loss)]
+            (Schema::Float, Value::Integer(int)) => self.buffer.extend((*int as f32).to_le_bytes()),
+            (Schema::Double, Value::Float(float)) => {
+                self.buffer.extend(float.into_inner().to_le_bytes());
+            }
+            #[allow(clippy::cast_precision_loss)]
+            (Schema::Double, Value::Integer(int)) => {
+                self.buffer.extend((*int as f64).to_le_bytes())
+            }
+            (Schema::Bytes | Schema::String, Value::Bytes(bytes)) => {
+                encode_bytes(bytes, self.buffer)
+            }
+            (Schema::Array(items), Value::Array(array)) => {
+                if !array.is_empty() {
+                    encode_long(len(array.len()), self.buffer);
+                    for item in array {
+                        self.value(items, item)?;
+                    }
+                }
+                self.buffer.push(0);
+            }
+            (Schema::Map(values), Value::Object(object)) => {
+                if !object.is_empty() {
+                    encode_long(len(object.len()), self.buffer);
+                    for (key, value) in object {
+                        encode_bytes(key.as_bytes(), self.buffer);
+                        self.value(values, value)
+                            .map_err(|e| format!("{e} at key `{key}`"))?;
+                    }
+                }
+                self.buffer.push(0);
+            }
+            (Schema::Union(branches), value) => self.union(branches, value)?,
+            (Schema::Named(index), value) => self.named(*index, value)?,
+            (Schema::Logical(logical, schema), value) => self.logical(*logical, schema, value)?,
+            (schema, value) => return Err(mismatch(self.schema, schema, value)),
+        }
+        Ok(())
+    }
+
+    /// Encodes the value with the first branch of the union it matches.
+    fn union(&mut self, branches: &[Schema], value: &Value) -> Result<(), String> {
+        let start = self.buffer.len();
+        for (index, branch) in branches.iter().enumerate() {
+            encode_long(len(index), self.buffer);
+            if self.value(branch, value).is_ok() {
+                return Ok(());
+            }
+            self.buffer.truncate(start);
+        }
+        Err(format!(
+            "{} does not match any type of the union",
+            value.kind_str()
+        ))
+    }
+
+    fn named(&mut self, index: usize, value: &Value) -> Result<(), String> {
+        let named = self.schema.named(index);
+        match (&named.definition, value) {
+            (Definition::Record(fields), Value::Object(object)) => {
+                for field in fields {
+                    // Missing fields are encoded as null, which is valid for optional fields.
+                    let value = object.get(field.name.as_str()).unwrap_or(&Value::Null);
+                    self.value(&field.schema, value).map_err(|e| {
+                        format!("{e} in field `{}` of `{}`", field.name, named.name)
+                    })?;
+                }
+            }
+            (Definition::Enum(symbols), Value::Bytes(symbol)) => {
+                let index = symbols
+                    .iter()
+                    .position(|candidate| candidate.as_bytes() == symbol)
+                    .ok_or_else(|| {
+                        format!(
+                            "`{}` is not a symbol of `{}`",
+                            String::from_utf8_lossy(symbol),
+                            named.name
+                        )
+                    })?;
+                encode_long(len(index), self.buffer);
+            }
+            (Definition::Fixed(size), Value::Bytes(bytes)) if bytes.len() == *size => {
+                self.buffer.extend_from_slice(bytes);
+            }
+            (Definition::Fixed(size), Value::Bytes(bytes)) => {
+                return Err(format!(
+                    "`{}` has a size of {size} bytes, not {}",
+                    named.name,
+                    bytes.len()
+                ));
+            }
+            _ => return Err(mismatch(self.schema, &Schema::Named(index), value)),
+        }
+        Ok(())
+    }
+
+    fn logical(&mut self, logical: Logical, schema: &Schema, value: &Value) -> Result<(), String> {
+        let value = match (logical, value) {
+            (Logical::Decimal { precision, scale }, value) => {
+                let size = match schema {
+                    Schema::Named(index) => match self.schema.named(*index).definition {
+                        Definition::Fixed(size) => Some(size),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                decimal(value, precision, scale, size)?
+            }
+            (Logical::Uuid, Value::Bytes(bytes)) => {
+                let uuid = uuid::Uuid::try_parse_ascii(bytes)
+                    .map_err(|_| format!("`{}` is not a UUID", String::from_utf8_lossy(bytes)))?;
+                match schema {
+                    Schema::String => value.clone(),
+                    _ => Value::Bytes(Bytes::copy_from_slice(uuid.as_bytes())),
+                }
+            }
+            (Logical::Date, Value::Timestamp(timestamp)) => {
+                timestamp.timestamp().div_euclid(86_400).into()
+            }
+            (Logical::TimestampMillis, Value::Timestamp(timestamp)) => {
+                timestamp.timestamp_millis().into()
+            }
+            (Logical::TimestampMicros, Value::Timestamp(timestamp)) => {
+                timestamp.timestamp_micros().into()
+            }
+            (Logical::TimestampNanos, Value::Timestamp(timestamp)) => timestamp
+                .timestamp_nanos_opt()
+                .ok_or("timestamp is out of range for nanoseconds")?
+                .into(),
+            (_, value) => value.clone(),
+        };
+
+        self.value(schema, &value)
+    }
+}
+
+/// Converts a float, integer, decimal or decimal string to the big-endian two's complement unscaled
+/// value of a decimal.
+fn decimal(
+    value: &Value,
+    precision: u32,
+    scale: u32,
+    size: Option<usize>,
+) -> Result<Value, String> {
+    let decimal = match value {
+        Value::Integer(int) => Decimal::from(*int),
+        Value::Decimal(decimal) => *decimal,
+        Value::Float(float) => Decimal::from_f64(float.into_inner())
+            .ok_or_else(|| format!("`{float}` is out of range for a decimal"))?,
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes)
+            .parse::<Decimal>()
+            .map_err(|_| format!("`{}` is not a decimal", String::from_utf8_lossy(bytes)))?,
+        value => return Err(format!("cannot encode {} as a decimal", value.kind_str())),
+    };
+
+    let mut decimal = decimal.round_dp_with_strategy(scale, RoundingStrategy::MidpointNearestEven);
+    decimal.rescale(scale);
+    let unscaled = decimal.mantissa();
+    if unscaled
+        .unsigned_abs()
+        .checked_ilog10()
+        .map_or(1, |digits| digits + 1)
+        > precision
+        || decimal.scale() != scale
+    {
+        return Err(format!(
+            "`{decimal}` does not fit in a decimal with precision {precision} and scale {scale}"
+        ));
+    }
+
+    let bytes = unscaled.to_be_bytes();
+    // The shortest encoding keeps a single leading sign byte.
+    let fill = if unscaled < 0 { 0xff } else { 0 };
+    let start = bytes
+        .windows(2)
+        .position(|pair| pair[0] != fill || (pair[1] & 0x80 != 0) != (fill != 0))
+        .unwrap_or(bytes.len() - 1);
+    let bytes = &bytes[start..];
+
+    Ok(Value::Bytes(match size {
+        Some(size) if bytes.len() > size => {
+            return Err(format!("`{decimal}` does not fit in {size} bytes"));
+        }
+        Some(size) => {
+            let mut fixed = vec![fill; size - bytes.len()];
+            fixed.extend_from_slice(bytes);
+            fixed.into()
+        }
+        None => Bytes::copy_from_slice(bytes),
+    }))
+}
+
+fn mismatch(avro: &AvroSchema, schema: &Schema, value: &Value) -> String {
+    let expected = match schema {
+        Schema::Null => "null",
+        Schema::Boolean => "boolean",
+        Schema::Int => "int",
+        Schema::Long => "long",
+        Schema::Float => "float",
+        Schema::Double => "double",
+        Schema::Bytes => "bytes",
+        Schema::String => "string",
+        Schema::Array(_) => "array",
+        Schema::Map(_) => "map",
+        Schema::Union(_) => "union",
+        Schema::Logical(_, schema) => return mismatch(avro, schema, value),
+        Schema::Named(index) => &avro.named(*index).name,
+    };
+    format!("cannot encode {} as `{expected}`", value.kind_str())
+}
@@ -0,0 +1,382 @@
+//! Support for [Apache Avro](https://avro.apache.org/) data.
+
+pub mod container;
+pub mod decode;
+pub mod encode;
+pub mod schema;
+
+use std::fmt;
+use std::str::FromStr;
+
+use bytes::Bytes;
+
+use self::container::{decode_container, encode_container};
+use self::decode::decode;
+use self::encode::encode;
+use self::schema::AvroSchema;
+use crate::value::Value;
+
+/// The magic byte that starts a message in the Confluent wire format.
+const CONFLUENT_MAGIC: u8 = 0;
+
+/// How Avro data is framed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireFormat {
+    /// A single binary encoded value.
+    Raw,
+    /// A single value, prefixed with a magic byte and the big-endian ID of its schema in a
+    /// Confluent schema registry.
+    Confluent,
+    /// An object container file, holding any number of records.
+    ObjectContainer,
+}
+
+impl FromStr for WireFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raw" => Ok(Self::Raw),
+            "confluent" => Ok(Self::Confluent),
+            "object_container" => Ok(Self::ObjectContainer),
+            _ => Err(format!("unknown Avro wire format `{s}`")),
+        }
+    }
+}
+
+impl fmt::Display for WireFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Raw => "raw",
+            Self::Confluent => "confluent",
+            Self::ObjectContainer => "object_container",
+        })
+    }
+}
+
+/// Decodes Avro data in the given wire format. An object container file is decoded to an
+/// array of its records.
+///
+/// # Errors
+///
+/// Returns an error if the data is not valid for the schema and wire format.
+pub fn decode_avro(
+    schema: &AvroSchema,
+    mut bytes: &[u8],
+    wire_format: WireFormat,
+) -> Result<Value, String> {
+    let value = match wire_format {
+        WireFormat::Raw => decode(schema, &mut bytes)?,
+        WireFormat::Confluent => {
+            bytes = match bytes.split_first() {
+                Some((&CONFLUENT_MAGIC, rest)) if rest.len() >= 4 => &rest[4..],
+                _ => return Err("missing Confluent wire format header".to_owned()),
+            };
+            decode(schema, &mut bytes)?
+        }
+        WireFormat::ObjectContainer => return decode_container(schema, bytes).map(Value::Array),
+    };
+
+    if !bytes.is_empty() {
+        return Err("unexpected trailing data after the value".to_owned());
+    }
+    Ok(value)
+}
+
+/// Encodes a value in the given wire format. An object container file is encoded from an
+/// array of records. The Confluent wire format requires the `schema_id`.
+///
+/// # Errors
+///
+/// Returns an error if the value does not match the schema.
+pub fn encode_avro(
+    schema: &AvroSchema,
+    value: &Value,
+    wire_format: WireFormat,
+    schema_id: Option<u32>,
+) -> Result<Bytes, String> {
+    let mut buffer = Vec::new();
+    match wire_format {
+        WireFormat::Raw => {}
+        WireFormat::Confluent => {
+            let schema_id = schema_id.ok_or("the Confluent wire format requires a schema ID")?;
+            buffer.push(CONFLUENT_MAGIC);
+            buffer.extend(schema_id.to_be_bytes());
+        }
+        WireFormat::ObjectContainer => {
+            let Value::Array(records) = value else {
+                return Err(format!(
+                    "expected an array of records, found {}",
+                    value.kind_str()
+                ));
+            };
+            return encode_container(schema, records);
+        }
+    }
+
+    encode(schema, value, &mut buffer)?;
+    Ok(buffer.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use chrono::DateTime;
+    use rust_decimal::Decimal;
+
+    fn roundtrip(schema: &str, value: Value) -> Value {
+        let schema = AvroSchema::parse(schema).unwrap();
+        let bytes = encode_avro(&schema, &value, WireFormat::Raw, None).unwrap();
+        decode_avro(&schema, &bytes, WireFormat::Raw).unwrap()
+    }
+
+    #[test]
+    fn decodes_specification_example() {
+        let schema = AvroSchema::parse(
+            r#"{"type": "record", "name": "test", "fields": [
+                {"name": "a", "type": "long"},
+                {"name": "b", "type": "string"}
+            ]}"#,
+        )
+        .unwrap();
+        let bytes = b"\x36\x06foo";
+
+        let value = decode_avro(&schema, bytes, WireFormat::Raw).unwrap();
+        assert_eq!(value, value!({ a: 27, b: "foo" }));
+        assert_eq!(
+            encode_avro(&schema, &value, WireFormat::Raw, None).unwrap(),
+            bytes.as_slice()
+        );
+    }
+
+    #[test]
+    fn roundtrips_types() {
+        let schema = r#"{"type": "record", "name": "Event", "fields": [
+            {"name": "null", "type": "null"},
+            {"name": "boolean", "type": "boolean"},
+            {"name": "int", "type": "int"},
+            {"name": "long", "type": "long"},
+            {"name": "float", "type": "float"},
+            {"name": "double", "type": "double"},
+            {"name": "bytes", "type": "bytes"},
+            {"name": "tags", "type": {"type": "array", "items": "string"}},
+            {"name": "labels", "type": {"type": "map", "values": "long"}},
+            {"name": "level", "type": {"type": "enum", "name": "Level", "symbols": ["INFO", "WARN"]}},
+            {"name": "id", "type": {"type": "fixed", "name": "Id", "size": 2}},
+            {"name": "parent", "type": ["null", "Event"]}
+        ]}"#;
+        let value = value!({
+            null: null,
+            boolean: true,
+            int: (-3),
+            long: 1_234_567_890_123_i64,
+            float: 1.5,
+            double: 0.1,
+            bytes: "\u{0}\u{1}",
+            tags: ["a", "b"],
+            labels: { x: 1, y: (-1) },
+            level: "WARN",
+            id: "ab",
+            parent: {
+                null: null,
+                boolean: false,
+                int: 0,
+                long: 0,
+                float: 0.0,
+                double: 0.0,
+                bytes: "",
+                tags: [],
+                labels: {},
+                level: "INFO",
+                id: "cd",
+                parent: null,
+            },
+        });
+
+        assert_eq!(roundtrip(schema, value.clone()), value);
+    }
+
+    #[test]
+    fn roundtrips_logical_types() {
+        let schema = r#"{"type": "record", "name": "Payment", "fields": [
+            {"name": "id", "type": {"type": "string", "logicalType": "uuid"}},
+            {"name": "key", "type": {"type": "fixed", "name": "Key", "size": 16, "logicalType": "uuid"}},
+            {"name": "amount", "type": {"type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2}},
+            {"name": "fee", "type": {"type": "fixed", "name": "Fee", "size": 4, "logicalType": "decimal", "precision": 6, "scale": 3}},
+            {"name": "day", "type": {"type": "int", "logicalType": "date"}},
+            {"name": "at", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            {"name": "at_micros", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+        ]}"#;
+        let value = value!({
+            id: "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            key: "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            amount: (Decimal::new(-123_450, 2)),
+            fee: (Decimal::new(125, 3)),
+            day: (DateTime::from_timestamp(-86_400, 0).unwrap()),
+            at: (DateTime::from_timestamp_millis(1_700_000_000_123).unwrap()),
+            at_micros: (DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap()),
+        });
+
+        assert_eq!(roundtrip(schema, value.clone()), value);
+    }
+
+    #[test]
+    fn decodes_decimals() {
+        let schema = r#"{"type": "bytes", "logicalType": "decimal", "precision": 30, "scale": 2}"#;
+        let decimal = Value::Decimal("12345678901234567.89".parse().unwrap());
+        assert_eq!(roundtrip(schema, decimal.clone()), decimal);
+
+        let schema = AvroSchema::parse(schema).unwrap();
+        let mut bytes = b"\x1a\x7f".to_vec();
+        bytes.extend([0xff; 12]);
+        assert_eq!(
+            decode_avro(&schema, &bytes, WireFormat::Raw).unwrap_err(),
+            "decimal is out of range"
+        );
+    }
+
+    #[test]
+    fn encodes_decimals() {
+        let schema = AvroSchema::parse(
+            r#"{"type": "bytes", "logicalType": "decimal", "precision": 4, "scale": 2}"#,
+        )
+        .unwrap();
+        let encode = |value: Value| encode_avro(&schema, &value, WireFormat::Raw, None);
+
+        assert_eq!(encode(value!("1.28")).unwrap(), b"\x04\x00\x80".as_slice());
+        assert_eq!(encode(value!(-1)).unwrap(), b"\x02\x9c".as_slice());
+        assert_eq!(encode(value!(0.005)).unwrap(), b"\x02\x00".as_slice());
+        assert_eq!(
+            encode(Value::Decimal(Decimal::new(128, 2))).unwrap(),
+            b"\x04\x00\x80".as_slice()
+        );
+        assert_eq!(
+            encode(value!(100)).unwrap_err(),
+            "`100.00` does not fit in a decimal with precision 4 and scale 2"
+        );
+    }
+
+    #[test]
+    fn encodes_unions_with_first_matching_type() {
+        let schema = AvroSchema::parse(r#"["null", "long", "double", "string"]"#).unwrap();
+        let encode = |value: Value| encode_avro(&schema, &value, WireFormat::Raw, None).unwrap();
+
+        assert_eq!(encode(value!(null)), b"\x00".as_slice());
+        assert_eq!(encode(value!(1)), b"\x02\x02".as_slice());
+        assert_eq!(encode(value!("a")), b"\x06\x02a".as_slice());
+        assert_eq!(
+            encode_avro(&schema, &value!(true), WireFormat::Raw, None).unwrap_err(),
+            "boolean does not match any type of the union"
+        );
+    }
+
+    #[test]
+    fn reports_mismatched_fields() {
+        let schema = AvroSchema::parse(
+            r#"{"type": "record", "name": "Log", "fields": [{"name": "level", "type": "int"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            encode_avro(&schema, &value!({}), WireFormat::Raw, None).unwrap_err(),
+            "cannot encode null as `int` in field `level` of `Log`"
+        );
+        assert_eq!(
+            encode_avro(
+                &schema,
+                &value!({ level: 3_000_000_000_i64 }),
+                WireFormat::Raw,
+                None
+            )
+            .unwrap_err(),
+            "`3000000000` is out of range for an int in field `level` of `Log`"
+        );
+    }
+
+    #[test]
+    fn confluent_wire_format() {
+        let schema = AvroSchema::parse(r#""string""#).unwrap();
+        let bytes = encode_avro(&schema, &value!("hi"), WireFormat::Confluent, Some(42)).unwrap();
+
+        assert_eq!(bytes, b"\x00\x00\x00\x00\x2a\x04hi".as_slice());
+        assert_eq!(
+            decode_avro(&schema, &bytes, WireFormat::Confluent).unwrap(),
+            value!("hi")
+        );
+        assert_eq!(
+            decode_avro(&schema, b"\x04hi", WireFormat::Confluent).unwrap_err(),
+            "missing Confluent wire format header"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_data() {
+        let schema = AvroSchema::parse(r#"{"type": "array", "items": "long"}"#).unwrap();
+
+        for (bytes, error) in [
+            (b"\x04\x02\x02".as_slice(), "unexpected end of data"),
+            (
+                b"\x02\x02\x00\x00",
+                "unexpected trailing data after the value",
+            ),
+            (
+                b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff",
+                "invalid variable-length integer",
+            ),
+        ] {
+            assert_eq!(
+                decode_avro(&schema, bytes, WireFormat::Raw).unwrap_err(),
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_blocks() {
+        for (schema, bytes, error) in [
+            (
+                r#"{"type": "array", "items": "null"}"#,
+                b"\x80\x80\x80\x80\x80\x40".as_slice(),
+                "invalid block count `1099511627776`",
+            ),
+            (
+                r#"{"type": "array", "items": "null"}"#,
+                b"\x80\x80\x40\x80\x80\x40\x02\x00",
+                "invalid block count `1`",
+            ),
+            (
+                r#"{"type": "array", "items": "long"}"#,
+                b"\x06\x02\x02",
+                "invalid block count `3`",
+            ),
+            (
+                r#"{"type": "array", "items": "long"}"#,
+                b"\x81\x80\x80\x80\x80\x40\x00",
+                "invalid block count `-1099511627777`",
+            ),
+            (
+                r#"{"type": "array", "items": "long"}"#,
+                b"\x01\x7e\x02",
+                "invalid block size `63`",
+            ),
+            (
+                r#"{"type": "map", "values": "null"}"#,
+                b"\x80\x80\x80\x80\x80\x40",
+                "invalid block count `1099511627776`",
+            ),
+        ] {
+            let schema = AvroSchema::parse(schema).unwrap();
+            assert_eq!(
+                decode_avro(&schema, bytes, WireFormat::Raw).unwrap_err(),
+                error
+            );
+        }
+
+        let schema = AvroSchema::parse(r#"{"type": "array", "items": "null"}"#).unwrap();
+        assert_eq!(
+            decode_avro(&schema, b"\x80\x80\x40\x00", WireFormat::Raw).unwrap(),
+            Value::Array(vec![Value::Null; 1 << 19])
+        );
+    }
+}
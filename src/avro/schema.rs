@@ -0,0 +1,601 @@
+//! Parsing and validation of [Avro schemas](https://avro.apache.org/docs/1.11.1/specification/).
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde_json::{Map, Value as JsonValue};
+
+use crate::value::Kind;
+use crate::value::kind::{Collection, Field as KindField};
+
+/// A parsed Avro schema, with its named types resolved.
+#[derive(Clone, Debug)]
+pub struct AvroSchema {
+    root: Schema,
+    named: Vec<Named>,
+    json: String,
+}
+
+/// The type of an Avro value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Schema {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Array(Box<Schema>),
+    Map(Box<Schema>),
+    Union(Vec<Schema>),
+    /// A record, enum or fixed type, as an index into the named types of its [`AvroSchema`].
+    Named(usize),
+    /// An underlying type annotated with a logical type.
+    Logical(Logical, Box<Schema>),
+}
+
+/// The logical types that are mapped to a different VRL value than their underlying type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Logical {
+    Decimal { precision: u32, scale: u32 },
+    Uuid,
+    Date,
+    TimestampMillis,
+    TimestampMicros,
+    TimestampNanos,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Named {
+    pub name: String,
+    pub definition: Definition,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Definition {
+    Record(Vec<RecordField>),
+    Enum(Vec<String>),
+    Fixed(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordField {
+    pub name: String,
+    pub schema: Schema,
+}
+
+impl AvroSchema {
+    /// Parses an Avro schema from its JSON representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid, or if it is not a valid Avro schema.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let value: JsonValue =
+            serde_json::from_str(json).map_err(|e| format!("invalid JSON: {e}"))?;
+        let mut parser = Parser::default();
+        let root = parser.parse(&value, None)?;
+
+        Ok(Self {
+            root,
+            named: parser.named,
+            json: value.to_string(),
+        })
+    }
+
+    /// A schema for a single value of the given type, without any named types.
+    pub(super) fn anonymous(root: Schema) -> Self {
+        Self {
+            root,
+            named: Vec::new(),
+            json: String::new(),
+        }
+    }
+
+    pub fn root(&self) -> &Schema {
+        &self.root
+    }
+
+    pub fn named(&self, index: usize) -> &Named {
+        &self.named[index]
+    }
+
+    /// The schema as compact JSON, as it is embedded in object container files.
+    pub fn json(&self) -> &str {
+        &self.json
+    }
+
+    /// Returns whether both schemas describe the same types, ignoring documentation, defaults
+    /// and aliases.
+    pub fn same_types(&self, other: &Self) -> bool {
+        self.root == other.root && self.named == other.named
+    }
+
+    /// The kind of the VRL values decoded with this schema.
+    pub fn kind(&self) -> Kind {
+        self.schema_kind(&self.root, &mut Vec::new())
+    }
+
+    fn schema_kind(&self, schema: &Schema, records: &mut Vec<usize>) -> Kind {
+        match schema {
+            Schema::Null => Kind::null(),
+            Schema::Boolean => Kind::boolean(),
+            Schema::Int | Schema::Long => Kind::integer(),
+            Schema::Float | Schema::Double => Kind::float(),
+            Schema::Logical(Logical::Decimal { .. }, _) => Kind::decimal(),
+            Schema::Bytes | Schema::String | Schema::Logical(Logical::Uuid, _) => Kind::bytes(),
+            Schema::Logical(_, _) => Kind::timestamp(),
+            Schema::Array(items) => {
+                Kind::array(Collection::from_unknown(self.schema_kind(items, records)))
+            }
+            Schema::Map(values) => {
+                Kind::object(Collection::from_unknown(self.schema_kind(values, records)))
+            }
+            Schema::Union(branches) => branches
+                .iter()
+                .map(|branch| self.schema_kind(branch, records))
+                .reduce(|kind, branch| kind.union(branch))
+                .unwrap_or_else(Kind::never),
+            Schema::Named(index) => match &self.named[*index].definition {
+                // Recursive records can nest arbitrarily deep.
+                Definition::Record(_) if records.contains(index) => Kind::object(Collection::any()),
+                Definition::Record(fields) => {
+                    records.push(*index);
+                    let fields = fields
+                        .iter()
+                        .map(|field| {
+                            (
+                                KindField::from(field.name.as_str()),
+                                self.schema_kind(&field.schema, records),
+                            )
+                        })
+                        .collect::<BTreeMap<_, _>>();
+                    records.pop();
+                    Kind::object(fields)
+                }
+                Definition::Enum(_) | Definition::Fixed(_) => Kind::bytes(),
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+struct Parser {
+    named: Vec<Named>,
+}
+
+impl Parser {
+    fn parse(&mut self, value: &JsonValue, namespace: Option<&str>) -> Result<Schema, String> {
+        match value {
+            JsonValue::String(name) => self.reference(name, namespace),
+            JsonValue::Array(branches) => self.union(branches, namespace),
+            JsonValue::Object(object) => self.complex(object, namespace),
+            _ => Err(format!("expected a schema, found `{value}`")),
+        }
+    }
+
+    fn reference(&self, name: &str, namespace: Option<&str>) -> Result<Schema, String> {
+        if let Some(primitive) = primitive(name) {
+            return Ok(primitive);
+        }
+
+        let qualified = match namespace {
+            Some(namespace) if !name.contains('.') => format!("{namespace}.{name}"),
+            _ => name.to_owned(),
+        };
+        [qualified.as_str(), name]
+            .into_iter()
+            .find_map(|name| self.named.iter().position(|named| named.name == name))
+            .map(Schema::Named)
+            .ok_or_else(|| format!("unknown type `{name}`"))
+    }
+
+    fn union(&mut self, branches: &[JsonValue], namespace: Option<&str>) -> Result<Schema, String> {
+        let mut seen = HashSet::new();
+        let branches = branches
+            .iter()
+            .map(|branch| {
+                let schema = self.parse(branch, namespace)?;
+                let key = match underlying(&schema) {
+                    Schema::Union(_) => {
+                        return Err("unions may not immediately contain other unions".to_owned());
+                    }
+                    Schema::Named(index) => self.named[*index].name.clone(),
+                    Schema::Array(_) => "array".to_owned(),
+                    Schema::Map(_) => "map".to_owned(),
+                    primitive => format!("{primitive:?}"),
+                };
+                if !seen.insert(key) {
+                    return Err(format!("union contains `{branch}` more than once"));
+                }
+                Ok(schema)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Schema::Union(branches))
+    }
+
+    fn complex(
+        &mut self,
+        object: &Map<String, JsonValue>,
+        namespace: Option<&str>,
+    ) -> Result<Schema, String> {
+        let kind = match object.get("type") {
+            Some(JsonValue::String(kind)) => kind.as_str(),
+            // A nested type definition, such as `{"type": {"type": "array", ...}}`.
+            Some(kind) => return self.parse(kind, namespace),
+            None => {
+                return Err(format!(
+                    "schema `{}` has no type",
+                    JsonValue::from(object.clone())
+                ));
+            }
+        };
+
+        let schema = match kind {
+            "record" | "error" => self.record(object, namespace)?,
+            "enum" => self.enumeration(object, namespace)?,
+            "fixed" => self.fixed(object, namespace)?,
+            "array" => Schema::Array(Box::new(
+                self.parse(attribute(object, "items", kind)?, namespace)?,
+            )),
+            "map" => Schema::Map(Box::new(
+                self.parse(attribute(object, "values", kind)?, namespace)?,
+            )),
+            name => self.reference(name, namespace)?,
+        };
+
+        Ok(
+            match object.get("logicalType").and_then(JsonValue::as_str) {
+                Some(logical) => self.logical(logical, object, schema),
+                None => schema,
+            },
+        )
+    }
+
+    /// Annotates the schema with a logical type. As required by the specification, logical
+    /// types that are unknown or invalid for the schema are ignored.
+    fn logical(&self, logical: &str, object: &Map<String, JsonValue>, schema: Schema) -> Schema {
+        let logical = match (logical, underlying(&schema)) {
+            ("decimal", Schema::Bytes) => decimal(object, None),
+            ("decimal", Schema::Named(index)) => match self.named[*index].definition {
+                Definition::Fixed(size) => decimal(object, Some(size)),
+                _ => None,
+            },
+            ("uuid", Schema::String) => Some(Logical::Uuid),
+            ("uuid", Schema::Named(index)) => {
+                matches!(self.named[*index].definition, Definition::Fixed(16))
+                    .then_some(Logical::Uuid)
+            }
+            ("date", Schema::Int) => Some(Logical::Date),
+            ("timestamp-millis" | "local-timestamp-millis", Schema::Long) => {
+                Some(Logical::TimestampMillis)
+            }
+            ("timestamp-micros" | "local-timestamp-micros", Schema::Long) => {
+                Some(Logical::TimestampMicros)
+            }
+            ("timestamp-nanos" | "local-timestamp-nanos", Schema::Long) => {
+                Some(Logical::TimestampNanos)
+            }
+            _ => None,
+        };
+
+        match logical {
+            Some(logical) => Schema::Logical(logical, Box::new(schema)),
+            None => schema,
+        }
+    }
+
+    fn record(
+        &mut self,
+        object: &Map<String, JsonValue>,
+        namespace: Option<&str>,
+    ) -> Result<Schema, String> {
+        // The record is registered before its fields are parsed, so they can refer to it.
+        let index = self.define(object, namespace, Definition::Record(Vec::new()))?;
+        let namespace = self.named[index]
+            .name
+            .rsplit_once('.')
+            .map(|(namespace, _)| namespace.to_owned());
+
+        let JsonValue::Array(fields) = attribute(object, "fields", "record")? else {
+            return Err("record fields must be an array".to_owned());
+        };
+        let mut names = HashSet::new();
+        let mut record_fields = Vec::with_capacity(fields.len());
+        for field in fields {
+            let JsonValue::Object(field) = field else {
+                return Err(format!("expected a record field, found `{field}`"));
+            };
+            let name = name(field)?;
+            if !names.insert(name.clone()) {
+                return Err(format!("record field `{name}` is defined more than once"));
+            }
+            let schema = self.parse(
+                attribute(field, "type", "record field")?,
+                namespace.as_deref(),
+            )?;
+            record_fields.push(RecordField { name, schema });
+        }
+
+        self.named[index].definition = Definition::Record(record_fields);
+        Ok(Schema::Named(index))
+    }
+
+    fn enumeration(
+        &mut self,
+        object: &Map<String, JsonValue>,
+        namespace: Option<&str>,
+    ) -> Result<Schema, String> {
+        let JsonValue::Array(symbols) = attribute(object, "symbols", "enum")? else {
+            return Err("enum symbols must be an array".to_owned());
+        };
+        let mut seen = HashSet::new();
+        let symbols = symbols
+            .iter()
+            .map(|symbol| match symbol.as_str() {
+                Some(symbol) if !valid_name(symbol) => {
+                    Err(format!("invalid enum symbol `{symbol}`"))
+                }
+                Some(symbol) if !seen.insert(symbol) => {
+                    Err(format!("enum symbol `{symbol}` is defined more than once"))
+                }
+                Some(symbol) => Ok(symbol.to_owned()),
+                None => Err(format!("invalid enum symbol `{symbol}`")),
+            })
+            .collect::<Result<_, _>>()?;
+
+        self.define(object, namespace, Definition::Enum(symbols))
+            .map(Schema::Named)
+    }
+
+    fn fixed(
+        &mut self,
+        object: &Map<String, JsonValue>,
+        namespace: Option<&str>,
+    ) -> Result<Schema, String> {
+        let size = attribute(object, "size", "fixed")?
+            .as_u64()
+            .and_then(|size| usize::try_from(size).ok())
+            .ok_or("fixed size must be a non-negative integer")?;
+
+        self.define(object, namespace, Definition::Fixed(size))
+            .map(Schema::Named)
+    }
+
+    /// Registers a named type under its full name.
+    fn define(
+        &mut self,
+        object: &Map<String, JsonValue>,
+        namespace: Option<&str>,
+        definition: Definition,
+    ) -> Result<usize, String> {
+        let name = name(object)?;
+        let namespace = match object.get("namespace") {
+            _ if name.contains('.') => None,
+            Some(JsonValue::String(namespace)) if namespace.is_empty() => None,
+            Some(JsonValue::String(namespace)) => Some(namespace.as_str()),
+            Some(namespace) => return Err(format!("invalid namespace `{namespace}`")),
+            None => namespace,
+        };
+        let name = match namespace {
+            Some(namespace) => format!("{namespace}.{name}"),
+            None => name,
+        };
+
+        if !name.split('.').all(valid_name) {
+            return Err(format!("invalid name `{name}`"));
+        }
+        if primitive(&name).is_some() || self.named.iter().any(|named| named.name == name) {
+            return Err(format!("type `{name}` is defined more than once"));
+        }
+
+        self.named.push(Named { name, definition });
+        Ok(self.named.len() - 1)
+    }
+}
+
+fn primitive(name: &str) -> Option<Schema> {
+    Some(match name {
+        "null" => Schema::Null,
+        "boolean" => Schema::Boolean,
+        "int" => Schema::Int,
+        "long" => Schema::Long,
+        "float" => Schema::Float,
+        "double" => Schema::Double,
+        "bytes" => Schema::Bytes,
+        "string" => Schema::String,
+        _ => return None,
+    })
+}
+
+fn underlying(schema: &Schema) -> &Schema {
+    match schema {
+        Schema::Logical(_, schema) => schema,
+        schema => schema,
+    }
+}
+
+fn attribute<'a>(
+    object: &'a Map<String, JsonValue>,
+    attribute: &str,
+    kind: &str,
+) -> Result<&'a JsonValue, String> {
+    object
+        .get(attribute)
+        .ok_or_else(|| format!("{kind} has no `{attribute}` attribute"))
+}
+
+fn name(object: &Map<String, JsonValue>) -> Result<String, String> {
+    match object.get("name") {
+        Some(JsonValue::String(name)) => Ok(name.clone()),
+        Some(name) => Err(format!("invalid name `{name}`")),
+        None => Err(format!("`{}` has no name", JsonValue::from(object.clone()))),
+    }
+}
+
+fn valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Returns the decimal logical type, if its precision and scale are valid for the underlying
+/// type.
+fn decimal(object: &Map<String, JsonValue>, fixed_size: Option<usize>) -> Option<Logical> {
+    let precision = u32::try_from(object.get("precision")?.as_u64()?).ok()?;
+    let scale = match object.get("scale") {
+        Some(scale) => u32::try_from(scale.as_u64()?).ok()?,
+        None => 0,
+    };
+    // The largest number of decimal digits that fit in `size` bytes of two's complement.
+    let max_precision = fixed_size.map_or(u32::MAX, |size| {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let digits = ((2f64.powf(8.0 * size as f64 - 1.0) - 1.0).log10()).floor() as u32;
+        digits
+    });
+
+    (precision > 0 && scale <= precision && precision <= max_precision)
+        .then_some(Logical::Decimal { precision, scale })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_named_types() {
+        let schema = AvroSchema::parse(
+            r#"{
+                "type": "record",
+                "name": "Event",
+                "namespace": "com.example",
+                "fields": [
+                    {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"]}},
+                    {"name": "other", "type": "Kind"},
+                    {"name": "id", "type": {"type": "fixed", "name": "other.Id", "size": 4}},
+                    {"name": "next", "type": ["null", "com.example.Event"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(schema.root(), &Schema::Named(0));
+        let names = (0..3)
+            .map(|i| schema.named(i).name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["com.example.Event", "com.example.Kind", "other.Id"]);
+        let Definition::Record(fields) = &schema.named(0).definition else {
+            panic!("not a record");
+        };
+        assert_eq!(fields[1].schema, Schema::Named(1));
+        assert_eq!(
+            fields[3].schema,
+            Schema::Union(vec![Schema::Null, Schema::Named(0)])
+        );
+    }
+
+    #[test]
+    fn parses_logical_types() {
+        let schema = AvroSchema::parse(
+            r#"{"type": "array", "items": [
+                {"type": "bytes", "logicalType": "decimal", "precision": 9, "scale": 2},
+                {"type": "long", "logicalType": "timestamp-millis"},
+                {"type": "string", "logicalType": "uuid"},
+                {"type": "int", "logicalType": "timestamp-millis"}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            schema.root(),
+            &Schema::Array(Box::new(Schema::Union(vec![
+                Schema::Logical(
+                    Logical::Decimal {
+                        precision: 9,
+                        scale: 2
+                    },
+                    Box::new(Schema::Bytes)
+                ),
+                Schema::Logical(Logical::TimestampMillis, Box::new(Schema::Long)),
+                Schema::Logical(Logical::Uuid, Box::new(Schema::String)),
+                // Invalid logical types are ignored.
+                Schema::Int,
+            ])))
+        );
+    }
+
+    #[test]
+    fn ignores_decimals_too_large_for_fixed() {
+        let schema = AvroSchema::parse(
+            r#"{"type": "fixed", "name": "D", "size": 2, "logicalType": "decimal", "precision": 5}"#,
+        )
+        .unwrap();
+
+        assert_eq!(schema.root(), &Schema::Named(0));
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        for (schema, error) in [
+            (
+                "{",
+                "invalid JSON: EOF while parsing an object at line 1 column 1",
+            ),
+            (r#""Missing""#, "unknown type `Missing`"),
+            (r#"{"type": "array"}"#, "array has no `items` attribute"),
+            (
+                r#"["null", "null"]"#,
+                "union contains `\"null\"` more than once",
+            ),
+            (
+                r#"["null", ["int"]]"#,
+                "unions may not immediately contain other unions",
+            ),
+            (
+                r#"{"type": "enum", "name": "E", "symbols": ["A", "A"]}"#,
+                "enum symbol `A` is defined more than once",
+            ),
+            (
+                r#"{"type": "fixed", "name": "1F", "size": 1}"#,
+                "invalid name `1F`",
+            ),
+            (
+                r#"{"type": "record", "name": "R", "fields": [{"name": "a", "type": "int"}, {"name": "a", "type": "int"}]}"#,
+                "record field `a` is defined more than once",
+            ),
+            (
+                r#"{"type": "record", "name": "R", "fields": [{"name": "a", "type": {"type": "fixed", "name": "R", "size": 1}}]}"#,
+                "type `R` is defined more than once",
+            ),
+        ] {
+            assert_eq!(AvroSchema::parse(schema).unwrap_err(), error, "{schema}");
+        }
+    }
+
+    #[test]
+    fn kind_of_recursive_record() {
+        let schema = AvroSchema::parse(
+            r#"{"type": "record", "name": "Node", "fields": [
+                {"name": "value", "type": "long"},
+                {"name": "next", "type": ["null", "Node"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            schema.kind(),
+            Kind::object(BTreeMap::from([
+                ("value".into(), Kind::integer()),
+                ("next".into(), Kind::null().or_object(Collection::any())),
+            ]))
+        );
+    }
+}
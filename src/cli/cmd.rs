@@ -1,15 +1,20 @@
 use crate::compiler::TargetValueRef;
 use std::{
     collections::BTreeMap,
+    fmt,
     fs::File,
-    io::{self, Read},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
     iter::IntoIterator,
-    path::PathBuf,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        mpsc::{self, Receiver, SyncSender},
+    },
 };
 
 use crate::compiler::TimeZone;
-use crate::compiler::runtime::Runtime;
-use crate::compiler::state::RuntimeState;
+use crate::compiler::runtime::{Runtime, Terminate};
 use crate::compiler::{
     CompilationResult, CompileConfig, Function, Program, Target, TypeState, VrlRuntime,
     compile_with_state,
@@ -17,29 +22,65 @@ use crate::compiler::{
 use crate::diagnostic::Formatter;
 use crate::owned_metadata_path;
 use crate::value::Secrets;
-use crate::value::Value;
-use clap::Parser;
+use crate::value::{ObjectMap, Value};
+use clap::{Parser, Subcommand};
+use serde_json::json;
 
 use super::Error;
+use super::diff;
+use super::format::{self, Decoder, Event, InputFormat, Output, OutputFormat, Record, Undecodable};
+use super::grok_debug;
+use super::import_datadog;
 use super::repl::Repl;
 
 #[derive(Parser, Debug)]
-#[command(name = "VRL", about = "Vector Remap Language CLI")]
+#[command(
+    name = "VRL",
+    about = "Vector Remap Language CLI",
+    args_conflicts_with_subcommands = true
+)]
 pub struct Opts {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The VRL program to execute. The program ".foo = true", for example, sets the event object's
     /// `foo` field to `true`.
     #[arg(id = "PROGRAM")]
     program: Option<String>,
 
-    /// The file containing the event object(s) to handle. JSON events should be one per line..
+    /// The file containing the event object(s) to handle. Events are read from stdin if no file,
+    /// or `-`, is provided.
     #[arg(short, long = "input")]
     input_file: Option<PathBuf>,
 
+    /// The format of the input events.
+    #[arg(long, value_enum, default_value_t)]
+    input_format: InputFormat,
+
+    /// The format used to print results. If not set, results are printed using their VRL
+    /// representation.
+    #[arg(long, value_enum)]
+    output_format: Option<OutputFormat>,
+
+    /// A file containing a JSON object used as the initial metadata of every event.
+    #[arg(long)]
+    metadata: Option<PathBuf>,
+
+    /// A file containing a JSON object of secret names to (string) values, made available to
+    /// every event.
+    #[arg(long)]
+    secrets: Option<PathBuf>,
+
+    /// The file to write aborted, errored and undecodable events to, one JSON record per line. If
+    /// not set, the errors are printed to stderr.
+    #[arg(long)]
+    dead_letter: Option<PathBuf>,
+
     /// The file containing the VRL program to execute. This can be used instead of `PROGRAM`.
     #[arg(short, long = "program", conflicts_with("PROGRAM"))]
     program_file: Option<PathBuf>,
 
-    // Don't print banner and other help messages on startup
+    // Don't print banner and other help messages on startup, or the summary after processing events
     #[arg(short = 'q', long)]
     quiet: bool,
 
@@ -56,19 +97,33 @@ pub struct Opts {
     #[arg(short, long = "runtime", default_value_t)]
     runtime: VrlRuntime,
 
+    /// The number of threads used to process events. The program is compiled once and shared by
+    /// all threads, results are printed in the same order as the input events.
+    #[arg(long, default_value = "1")]
+    threads: NonZeroUsize,
+
     // Should the CLI emit warnings
     #[arg(long = "print-warnings")]
     print_warnings: bool,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run two VRL programs over the same events and report the events for which their outcome
+    /// differs.
+    Diff(diff::Opts),
+
+    /// Translate an exported Datadog log pipeline into an equivalent VRL program.
+    ImportDatadog(import_datadog::Opts),
+
+    /// Match lines against Datadog grok patterns, reporting the longest prefix of each pattern
+    /// that matched, where it failed and the values captured so far.
+    GrokDebug(grok_debug::Opts),
+}
+
 impl Opts {
     fn timezone(&self) -> Result<TimeZone, Error> {
-        if let Some(ref tz) = self.timezone {
-            TimeZone::parse(tz)
-                .ok_or_else(|| Error::Parse(format!("unable to parse timezone: {tz}")))
-        } else {
-            Ok(TimeZone::default())
-        }
+        parse_timezone(self.timezone.as_deref())
     }
 
     fn read_program(&self) -> Result<String, Error> {
@@ -81,19 +136,17 @@ impl Opts {
         }
     }
 
-    fn read_into_objects(&self) -> Result<Vec<Value>, Error> {
-        let input = match self.input_file.as_ref() {
-            Some(path) => read(File::open(path)?),
-            None => read(io::stdin()),
-        }?;
-
-        match input.as_str() {
-            "" => Ok(vec![Value::Object(BTreeMap::default())]),
-            _ => input
-                .lines()
-                .map(|line| Ok(serde_to_vrl(serde_json::from_str(line)?)))
-                .collect::<Result<Vec<Value>, Error>>(),
-        }
+    fn read_into_objects(
+        &self,
+        functions: &[Box<dyn Function>],
+        timezone: TimeZone,
+    ) -> Result<Vec<Event>, Error> {
+        read_into_objects(
+            self.input_file.as_deref(),
+            self.input_format,
+            functions,
+            timezone,
+        )
     }
 
     fn should_open_repl(&self) -> bool {
@@ -103,8 +156,17 @@ impl Opts {
 
 #[must_use]
 pub fn cmd(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> exitcode::ExitCode {
+    if let Some(command) = &opts.command {
+        return match command {
+            Command::Diff(opts) => diff::cmd(opts, &stdlib_functions),
+            Command::ImportDatadog(opts) => import_datadog::cmd(opts),
+            Command::GrokDebug(opts) => grok_debug::cmd(opts),
+        };
+    }
+
     match run(opts, stdlib_functions) {
-        Ok(()) => exitcode::OK,
+        Ok(summary) if summary.errored > 0 => exitcode::DATAERR,
+        Ok(_) => exitcode::OK,
         Err(err) => {
             #[allow(clippy::print_stderr)]
             {
@@ -115,78 +177,346 @@ pub fn cmd(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> exitcode::E
     }
 }
 
-fn run(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> Result<(), Error> {
+fn run(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> Result<Summary, Error> {
     let tz = opts.timezone()?;
     // Run the REPL if no program or program file is specified
     if opts.should_open_repl() {
         // If an input file is provided, use that for the REPL objects, otherwise provide a
         // generic default object.
         let repl_objects = if opts.input_file.is_some() {
-            opts.read_into_objects()?
+            opts.read_into_objects(&stdlib_functions, tz)?
+                .into_iter()
+                .map(|event| event.map_err(|Undecodable { error, .. }| Error::Input(error)))
+                .collect::<Result<_, _>>()?
         } else {
             default_objects()
         };
 
-        repl(opts.quiet, repl_objects, tz, opts.runtime, stdlib_functions)
+        let metadata = read_metadata(opts.metadata.as_deref())?;
+        let secrets = read_secrets(opts.secrets.as_deref())?;
+
+        repl(
+            opts.quiet,
+            repl_objects,
+            &metadata,
+            &secrets,
+            tz,
+            opts.runtime,
+            stdlib_functions,
+        )?;
+
+        Ok(Summary::default())
     } else {
-        let objects = opts.read_into_objects()?;
-        let source = opts.read_program()?;
+        run_batch(opts, &stdlib_functions, tz)
+    }
+}
 
-        // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
-        let mut config = CompileConfig::default();
-        config.set_read_only_path(owned_metadata_path!("vector"), true);
+/// Run the program over every input event, writing the results to stdout.
+fn run_batch(
+    opts: &Opts,
+    stdlib_functions: &[Box<dyn Function>],
+    tz: TimeZone,
+) -> Result<Summary, Error> {
+    let source = opts.read_program()?;
+    let initial_metadata = read_metadata(opts.metadata.as_deref())?;
+    let initial_secrets = read_secrets(opts.secrets.as_deref())?;
+
+    // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
+    let mut config = CompileConfig::default();
+    config.set_read_only_path(owned_metadata_path!("vector"), true);
+
+    let state = TypeState::default();
+
+    let CompilationResult {
+        program,
+        warnings,
+        config: _,
+    } = compile_with_state(&source, stdlib_functions, &state, config).map_err(|diagnostics| {
+        Error::Parse(Formatter::new(&source, diagnostics).colored().to_string())
+    })?;
+
+    #[allow(clippy::print_stderr)]
+    if opts.print_warnings {
+        let warnings = Formatter::new(&source, warnings).colored().to_string();
+        eprintln!("{warnings}");
+    }
 
-        let state = TypeState::default();
+    let decoder = Decoder::new(opts.input_format, stdlib_functions, tz)?;
+    let records = format::records(open_input(opts.input_file.as_deref())?, opts.input_format);
+
+    let mut output = Output::new(opts.output_format);
+    let mut dead_letter = opts
+        .dead_letter
+        .as_ref()
+        .map(File::create)
+        .transpose()?
+        .map(BufWriter::new);
+    let mut summary = Summary::default();
+
+    let process = |record, runtime: &mut Runtime| {
+        process_event(
+            decoder.decode(record, runtime),
+            &program,
+            &initial_metadata,
+            &initial_secrets,
+            opts,
+            tz,
+            runtime,
+        )
+    };
+    let handle = |result: Result<Processed, Error>| -> Result<(), Error> {
+        summary.processed += 1;
+
+        match result? {
+            Processed::Value(value) => output.write(&value)?,
+            Processed::Undecodable(Undecodable { input, error }) => {
+                summary.errored += 1;
+                write_dead_letter(
+                    dead_letter.as_mut(),
+                    &json!({ "status": "undecodable", "message": error, "input": input }),
+                    &error,
+                )?;
+            }
+            Processed::Terminated { terminate, event } => {
+                let status = match terminate {
+                    Terminate::Abort(_) => {
+                        summary.aborted += 1;
+                        "abort"
+                    }
+                    Terminate::Error(_) => {
+                        summary.errored += 1;
+                        "error"
+                    }
+                };
+                let message = terminate.to_string();
+
+                write_dead_letter(
+                    dead_letter.as_mut(),
+                    &json!({ "status": status, "message": message, "event": event }),
+                    &message,
+                )?;
+            }
+        }
 
-        let CompilationResult {
-            program,
-            warnings,
-            config: _,
-        } = compile_with_state(&source, &stdlib_functions, &state, CompileConfig::default())
-            .map_err(|diagnostics| {
-                Error::Parse(Formatter::new(&source, diagnostics).colored().to_string())
-            })?;
+        Ok(())
+    };
 
-        #[allow(clippy::print_stderr)]
-        if opts.print_warnings {
-            let warnings = Formatter::new(&source, warnings).colored().to_string();
-            eprintln!("{warnings}");
+    if opts.threads.get() > 1 {
+        process_parallel(records, opts.threads, process, handle)?;
+    } else {
+        let mut handle = handle;
+        let mut runtime = Runtime::default();
+        for record in records {
+            handle(process(record?, &mut runtime))?;
         }
+    }
 
-        for mut object in objects {
-            let mut metadata = Value::Object(BTreeMap::new());
-            let mut secrets = Secrets::new();
-            let mut target = TargetValueRef {
-                value: &mut object,
-                metadata: &mut metadata,
-                secrets: &mut secrets,
-            };
-            let state = RuntimeState::default();
-            let runtime = Runtime::new(state);
-
-            let result = execute(&mut target, &program, tz, runtime, opts.runtime).map(|v| {
-                if opts.print_object {
-                    object.to_string()
-                } else {
-                    v.to_string()
+    output.finish()?;
+    if let Some(mut dead_letter) = dead_letter {
+        dead_letter.flush()?;
+    }
+
+    #[allow(clippy::print_stderr)]
+    if !opts.quiet {
+        eprintln!("{summary}");
+    }
+
+    Ok(summary)
+}
+
+/// The result of running the program over a single input event.
+enum Processed {
+    /// The program completed, holding either the resulting object or the value of the final
+    /// expression.
+    Value(Value),
+
+    /// The program aborted or errored. The original event is only kept if it is written to the
+    /// dead-letter file.
+    Terminated {
+        terminate: Terminate,
+        event: Option<Value>,
+    },
+
+    /// The input could not be decoded into an event.
+    Undecodable(Undecodable),
+}
+
+fn process_event(
+    event: Event,
+    program: &Program,
+    metadata: &Value,
+    secrets: &Secrets,
+    opts: &Opts,
+    tz: TimeZone,
+    runtime: &mut Runtime,
+) -> Result<Processed, Error> {
+    let mut object = match event {
+        Ok(object) => object,
+        Err(undecodable) => return Ok(Processed::Undecodable(undecodable)),
+    };
+
+    let original = opts.dead_letter.is_some().then(|| object.clone());
+    let mut metadata = metadata.clone();
+    let mut secrets = secrets.clone();
+    let mut target = TargetValueRef {
+        value: &mut object,
+        metadata: &mut metadata,
+        secrets: &mut secrets,
+    };
+    let result = execute(&mut target, program, tz, runtime, opts.runtime);
+    runtime.clear();
+
+    match result {
+        Ok(_) if opts.print_object => Ok(Processed::Value(object)),
+        Ok(value) => Ok(Processed::Value(value)),
+        Err(Error::Runtime(terminate)) => Ok(Processed::Terminated {
+            terminate,
+            event: original,
+        }),
+        Err(err) => Err(err),
+    }
+}
+
+/// The number of records handed to a worker thread at a time.
+const BATCH_SIZE: usize = 1024;
+
+/// The results of processing a batch of records, in the same order as the records.
+type BatchResults = Vec<Result<Processed, Error>>;
+
+/// Process the records on `threads` worker threads, each reusing its own [`Runtime`], handing the
+/// results to `handle` in the same order as the input records.
+///
+/// The input is read on its own thread, in batches of [`BATCH_SIZE`] records. Each batch is sent
+/// to the workers along with a channel for its results, and these channels are queued in input
+/// order for the results to be handled. Both queues are bounded, so only a few batches per thread
+/// are held in memory at a time, however large the input.
+fn process_parallel<P, H>(
+    records: impl Iterator<Item = Result<Record, Error>> + Send,
+    threads: NonZeroUsize,
+    process: P,
+    mut handle: H,
+) -> Result<(), Error>
+where
+    P: Fn(Record, &mut Runtime) -> Result<Processed, Error> + Sync,
+    H: FnMut(Result<Processed, Error>) -> Result<(), Error>,
+{
+    let (work_tx, work_rx) =
+        mpsc::sync_channel::<(Vec<Record>, SyncSender<BatchResults>)>(threads.get());
+    let work_rx = Mutex::new(work_rx);
+    let (results_tx, results_rx) =
+        mpsc::sync_channel::<Result<Receiver<BatchResults>, Error>>(threads.get());
+
+    let process = &process;
+    let work_rx = &work_rx;
+    std::thread::scope(|scope| {
+        for _ in 0..threads.get() {
+            scope.spawn(move || {
+                let mut runtime = Runtime::default();
+                loop {
+                    // The lock is released as soon as a batch is received, before processing it.
+                    let received = work_rx.lock().expect("lock poisoned").recv();
+                    let Ok((batch, results)) = received else {
+                        break;
+                    };
+                    let batch = batch
+                        .into_iter()
+                        .map(|record| process(record, &mut runtime))
+                        .collect();
+                    // The results are no longer wanted if handling an earlier one failed.
+                    let _ = results.send(batch);
                 }
             });
+        }
 
-            #[allow(clippy::print_stdout)]
-            #[allow(clippy::print_stderr)]
-            match result {
-                Ok(ok) => println!("{ok}"),
-                Err(err) => eprintln!("{err}"),
+        scope.spawn(move || {
+            let mut records = records;
+            loop {
+                let mut batch = Vec::with_capacity(BATCH_SIZE);
+                let mut error = None;
+                for record in records.by_ref() {
+                    match record {
+                        Ok(record) => batch.push(record),
+                        Err(err) => error = Some(err),
+                    }
+                    if error.is_some() || batch.len() == BATCH_SIZE {
+                        break;
+                    }
+                }
+                let done = error.is_some() || batch.len() < BATCH_SIZE;
+
+                if !batch.is_empty() {
+                    let (tx, rx) = mpsc::sync_channel(1);
+                    if results_tx.send(Ok(rx)).is_err() || work_tx.send((batch, tx)).is_err() {
+                        return;
+                    }
+                }
+                if let Some(err) = error {
+                    let _ = results_tx.send(Err(err));
+                }
+                if done {
+                    return;
+                }
+            }
+        });
+
+        // Returning early drops the queue of results, which stops the input thread and, in turn,
+        // the workers.
+        for results in results_rx {
+            let Ok(results) = results?.recv() else {
+                // The worker panicked, which is raised when the scope ends.
+                break;
+            };
+            for result in results {
+                handle(result)?;
             }
         }
 
         Ok(())
+    })
+}
+
+/// Write a failed event to the dead-letter file as a line of JSON, or print the error message to
+/// stderr if no dead-letter file is configured.
+fn write_dead_letter(
+    writer: Option<&mut BufWriter<File>>,
+    record: &serde_json::Value,
+    message: &str,
+) -> Result<(), Error> {
+    match writer {
+        Some(writer) => writeln!(writer, "{record}")?,
+        #[allow(clippy::print_stderr)]
+        None => eprintln!("{message}"),
+    }
+
+    Ok(())
+}
+
+/// The number of events handled by a batch run, by outcome.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Summary {
+    processed: usize,
+    aborted: usize,
+    errored: usize,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "processed {} event(s): {} succeeded, {} aborted, {} errored",
+            self.processed,
+            self.processed - self.aborted - self.errored,
+            self.aborted,
+            self.errored
+        )
     }
 }
 
 fn repl(
     quiet: bool,
     objects: Vec<Value>,
+    metadata: &Value,
+    secrets: &Secrets,
     timezone: TimeZone,
     vrl_runtime: VrlRuntime,
     stdlib_functions: Vec<Box<dyn Function>>,
@@ -197,8 +527,8 @@ fn repl(
         .into_iter()
         .map(|value| TargetValue {
             value,
-            metadata: Value::Object(BTreeMap::new()),
-            secrets: Secrets::new(),
+            metadata: metadata.clone(),
+            secrets: secrets.clone(),
         })
         .collect();
 
@@ -211,7 +541,7 @@ fn execute(
     object: &mut impl Target,
     program: &Program,
     timezone: TimeZone,
-    mut runtime: Runtime,
+    runtime: &mut Runtime,
     vrl_runtime: VrlRuntime,
 ) -> Result<Value, Error> {
     match vrl_runtime {
@@ -221,25 +551,68 @@ fn execute(
     }
 }
 
-fn serde_to_vrl(value: serde_json::Value) -> Value {
-    use serde_json::Value as JsonValue;
+pub(super) fn parse_timezone(timezone: Option<&str>) -> Result<TimeZone, Error> {
+    if let Some(tz) = timezone {
+        TimeZone::parse(tz).ok_or_else(|| Error::Parse(format!("unable to parse timezone: {tz}")))
+    } else {
+        Ok(TimeZone::default())
+    }
+}
 
-    match value {
-        JsonValue::Null => crate::value::Value::Null,
-        JsonValue::Object(v) => v
-            .into_iter()
-            .map(|(k, v)| (k.into(), serde_to_vrl(v)))
-            .collect::<BTreeMap<_, _>>()
-            .into(),
-        JsonValue::Bool(v) => v.into(),
-        JsonValue::Number(v) if v.is_f64() => Value::from_f64_or_zero(v.as_f64().unwrap()),
-        JsonValue::Number(v) => v.as_i64().unwrap_or(i64::MAX).into(),
-        JsonValue::String(v) => v.into(),
-        JsonValue::Array(v) => v.into_iter().map(serde_to_vrl).collect::<Vec<_>>().into(),
+/// Read the initial event metadata from the given JSON file, or return empty metadata if no file is
+/// provided.
+pub(super) fn read_metadata(path: Option<&Path>) -> Result<Value, Error> {
+    let Some(path) = path else {
+        return Ok(Value::Object(ObjectMap::new()));
+    };
+
+    match serde_json::from_str(&read(File::open(path)?)?)? {
+        metadata @ Value::Object(_) => Ok(metadata),
+        _ => Err(Error::Input(format!(
+            "metadata in {} must be a JSON object",
+            path.display()
+        ))),
     }
 }
 
-fn read<R: Read>(mut reader: R) -> Result<String, Error> {
+/// Read secrets from the given JSON file, or return no secrets if no file is provided.
+pub(super) fn read_secrets(path: Option<&Path>) -> Result<Secrets, Error> {
+    let mut secrets = Secrets::new();
+
+    if let Some(path) = path {
+        let values: BTreeMap<String, String> = serde_json::from_str(&read(File::open(path)?)?)?;
+        for (key, value) in values {
+            secrets.insert(key, value);
+        }
+    }
+
+    Ok(secrets)
+}
+
+/// Open the given file for reading, or stdin if no file (or `-`) is provided.
+fn open_input(input_file: Option<&Path>) -> Result<Box<dyn BufRead + Send>, Error> {
+    Ok(match input_file {
+        Some(path) if path != Path::new("-") => Box::new(BufReader::new(File::open(path)?)),
+        _ => Box::new(BufReader::new(io::stdin())),
+    })
+}
+
+/// Read and decode the events in the given file, or from stdin if no file (or `-`) is provided.
+pub(super) fn read_into_objects(
+    input_file: Option<&Path>,
+    format: InputFormat,
+    functions: &[Box<dyn Function>],
+    timezone: TimeZone,
+) -> Result<Vec<Event>, Error> {
+    let input = match input_file {
+        Some(path) if path != Path::new("-") => read(File::open(path)?),
+        _ => read(io::stdin()),
+    }?;
+
+    format::decode(&input, format, functions, timezone)
+}
+
+pub(super) fn read<R: Read>(mut reader: R) -> Result<String, Error> {
     let mut buffer = String::new();
     reader.read_to_string(&mut buffer)?;
 
@@ -247,5 +620,5 @@ fn read<R: Read>(mut reader: R) -> Result<String, Error> {
 }
 
 fn default_objects() -> Vec<Value> {
-    vec![Value::Object(BTreeMap::new())]
+    vec![Value::Object(ObjectMap::new())]
 }
@@ -0,0 +1,370 @@
+use std::{fs::File, path::PathBuf};
+
+use clap::Args;
+use serde_json::json;
+
+use crate::compiler::runtime::{Runtime, Terminate};
+use crate::compiler::state::RuntimeState;
+use crate::compiler::{
+    CompilationResult, CompileConfig, Function, Program, TargetValue, TimeZone, TypeState,
+    compile_with_state,
+};
+use crate::diagnostic::Formatter;
+use crate::path::{OwnedTargetPath, OwnedValuePath};
+use crate::value::Value;
+
+use super::Error;
+use super::cmd::{parse_timezone, read, read_into_objects, read_metadata, read_secrets};
+use super::format::{InputFormat, Undecodable};
+
+#[derive(Args, Debug)]
+pub struct Opts {
+    /// The file containing the original VRL program.
+    old: PathBuf,
+
+    /// The file containing the updated VRL program.
+    new: PathBuf,
+
+    /// The file containing the event object(s) to compare the programs against. JSON events
+    /// should be one per line. Events are read from stdin if no file is provided.
+    #[arg(short, long = "input")]
+    input_file: Option<PathBuf>,
+
+    /// The format of the input events.
+    #[arg(long, value_enum, default_value_t)]
+    input_format: InputFormat,
+
+    /// The timezone used to parse dates.
+    #[arg(short = 'z', long)]
+    timezone: Option<String>,
+
+    /// A file containing a JSON object used as the initial metadata of every event.
+    #[arg(long)]
+    metadata: Option<PathBuf>,
+
+    /// A file containing a JSON object of secret names to (string) values, made available to
+    /// every event.
+    #[arg(long)]
+    secrets: Option<PathBuf>,
+
+    /// Print a structured JSON diff, one per line, for each event with a differing outcome
+    /// instead of a human-readable diff.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Compare two programs, returning `exitcode::OK` if both programs behave identically for all
+/// events, or `exitcode::DATAERR` if one or more events differ.
+#[must_use]
+pub fn cmd(opts: &Opts, stdlib_functions: &[Box<dyn Function>]) -> exitcode::ExitCode {
+    match run(opts, stdlib_functions) {
+        Ok(0) => exitcode::OK,
+        Ok(_) => exitcode::DATAERR,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{err}");
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+fn run(opts: &Opts, functions: &[Box<dyn Function>]) -> Result<usize, Error> {
+    let tz = parse_timezone(opts.timezone.as_deref())?;
+    let old = compile(&read(File::open(&opts.old)?)?, functions)?;
+    let new = compile(&read(File::open(&opts.new)?)?, functions)?;
+    let objects = read_into_objects(opts.input_file.as_deref(), opts.input_format, functions, tz)?
+        .into_iter()
+        .map(|event| event.map_err(|Undecodable { error, .. }| Error::Input(error)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let total = objects.len();
+    let metadata = read_metadata(opts.metadata.as_deref())?;
+    let secrets = read_secrets(opts.secrets.as_deref())?;
+
+    let mut differing = 0;
+    for (index, object) in objects.into_iter().enumerate() {
+        let target = TargetValue {
+            value: object,
+            metadata: metadata.clone(),
+            secrets: secrets.clone(),
+        };
+        let old = Outcome::new(target.clone(), &old, tz);
+        let new = Outcome::new(target, &new, tz);
+
+        let differences = old.differences(&new);
+        if differences.is_empty() {
+            continue;
+        }
+        differing += 1;
+
+        #[allow(clippy::print_stdout)]
+        if opts.json {
+            let differences = differences
+                .into_iter()
+                .map(Difference::into_json)
+                .collect::<Vec<_>>();
+
+            println!(
+                "{}",
+                json!({
+                    "event": index,
+                    "old": old.to_json(),
+                    "new": new.to_json(),
+                    "differences": differences,
+                })
+            );
+        } else {
+            let old = serde_json::to_string_pretty(&old.to_json())?;
+            let new = serde_json::to_string_pretty(&new.to_json())?;
+
+            println!("event {index} differs:");
+            println!("{}", prettydiff::diff_lines(&old, &new));
+        }
+    }
+
+    #[allow(clippy::print_stderr)]
+    {
+        eprintln!("{differing} of {total} event(s) differ");
+    }
+
+    Ok(differing)
+}
+
+fn compile(source: &str, functions: &[Box<dyn Function>]) -> Result<Program, Error> {
+    let state = TypeState::default();
+
+    compile_with_state(source, functions, &state, CompileConfig::default())
+        .map(|CompilationResult { program, .. }| program)
+        .map_err(|diagnostics| {
+            Error::Parse(Formatter::new(source, diagnostics).colored().to_string())
+        })
+}
+
+/// The observable result of running a single event through a program.
+struct Outcome {
+    target: TargetValue,
+    terminate: Option<Terminate>,
+}
+
+impl Outcome {
+    fn new(mut target: TargetValue, program: &Program, timezone: TimeZone) -> Self {
+        let mut runtime = Runtime::new(RuntimeState::default());
+        let terminate = runtime.resolve(&mut target, program, &timezone).err();
+
+        Self { target, terminate }
+    }
+
+    fn status(&self) -> &'static str {
+        match self.terminate {
+            None => "ok",
+            Some(Terminate::Abort(_)) => "abort",
+            Some(Terminate::Error(_)) => "error",
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut outcome = json!({
+            "status": self.status(),
+            "target": self.target.value,
+            "metadata": self.target.metadata,
+        });
+
+        if let Some(terminate) = &self.terminate {
+            outcome["message"] = terminate.to_string().into();
+        }
+
+        outcome
+    }
+
+    fn differences(&self, other: &Self) -> Vec<Difference> {
+        let mut differences = vec![];
+
+        let old_message = self.terminate.as_ref().map(ToString::to_string);
+        let new_message = other.terminate.as_ref().map(ToString::to_string);
+        if self.status() != other.status() || old_message != new_message {
+            differences.push(Difference::Outcome {
+                old: (self.status(), old_message),
+                new: (other.status(), new_message),
+            });
+        }
+
+        diff_values(
+            &mut OwnedValuePath::root(),
+            &self.target.value,
+            &other.target.value,
+            &mut |path, old, new| {
+                differences.push(Difference::Value {
+                    path: OwnedTargetPath::event(path),
+                    old,
+                    new,
+                });
+            },
+        );
+
+        diff_values(
+            &mut OwnedValuePath::root(),
+            &self.target.metadata,
+            &other.target.metadata,
+            &mut |path, old, new| {
+                differences.push(Difference::Value {
+                    path: OwnedTargetPath::metadata(path),
+                    old,
+                    new,
+                });
+            },
+        );
+
+        differences
+    }
+}
+
+enum Difference {
+    /// The programs terminated differently, e.g. one aborted while the other completed.
+    /// Each side holds the status and, if the program terminated, the error message.
+    Outcome {
+        old: (&'static str, Option<String>),
+        new: (&'static str, Option<String>),
+    },
+
+    /// A value in the event or its metadata differs. A missing value is represented as `None`.
+    Value {
+        path: OwnedTargetPath,
+        old: Option<Value>,
+        new: Option<Value>,
+    },
+}
+
+impl Difference {
+    fn into_json(self) -> serde_json::Value {
+        match self {
+            Difference::Outcome { old, new } => json!({
+                "kind": "outcome",
+                "old": { "status": old.0, "message": old.1 },
+                "new": { "status": new.0, "message": new.1 },
+            }),
+            Difference::Value { path, old, new } => json!({
+                "kind": "value",
+                "path": path.to_string(),
+                "old": old,
+                "new": new,
+            }),
+        }
+    }
+}
+
+/// Recursively compare two values, calling `report` with the path of every leaf value that is
+/// added, removed or changed.
+fn diff_values(
+    path: &mut OwnedValuePath,
+    old: &Value,
+    new: &Value,
+    report: &mut impl FnMut(OwnedValuePath, Option<Value>, Option<Value>),
+) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for key in old
+                .keys()
+                .chain(new.keys().filter(|k| !old.contains_key(k)))
+            {
+                path.push_field(key);
+                match (old.get(key), new.get(key)) {
+                    (Some(old), Some(new)) => diff_values(path, old, new, report),
+                    (old, new) => report(path.clone(), old.cloned(), new.cloned()),
+                }
+                path.segments.pop();
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                #[allow(clippy::cast_possible_wrap)]
+                path.push_index(index as isize);
+                match (old.get(index), new.get(index)) {
+                    (Some(old), Some(new)) => diff_values(path, old, new, report),
+                    (old, new) => report(path.clone(), old.cloned(), new.cloned()),
+                }
+                path.segments.pop();
+            }
+        }
+        (old, new) if old != new => report(path.clone(), Some(old.clone()), Some(new.clone())),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use crate::value::Secrets;
+
+    fn differences(old: &Value, new: &Value) -> Vec<(String, Option<Value>, Option<Value>)> {
+        let mut differences = vec![];
+        diff_values(
+            &mut OwnedValuePath::root(),
+            old,
+            new,
+            &mut |path, old, new| {
+                differences.push((OwnedTargetPath::event(path).to_string(), old, new));
+            },
+        );
+        // The fields are visited in insertion order with the `preserve_order` feature.
+        differences.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+        differences
+    }
+
+    #[test]
+    fn equal_values() {
+        let value = value!({ "foo": [1, { "bar": true }] });
+
+        assert!(differences(&value, &value).is_empty());
+    }
+
+    #[test]
+    fn changed_added_and_removed_fields() {
+        let old = value!({ "foo": 1, "bar": { "baz": "a" }, "removed": true });
+        let new = value!({ "foo": 2, "bar": { "baz": "a", "qux": [1] } });
+
+        assert_eq!(
+            differences(&old, &new),
+            vec![
+                (".bar.qux".to_owned(), None, Some(value!([1]))),
+                (".foo".to_owned(), Some(value!(1)), Some(value!(2))),
+                (".removed".to_owned(), Some(value!(true)), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn array_elements() {
+        let old = value!({ "foo": [1, 2] });
+        let new = value!({ "foo": [1, 3, 4] });
+
+        assert_eq!(
+            differences(&old, &new),
+            vec![
+                (".foo[1]".to_owned(), Some(value!(2)), Some(value!(3))),
+                (".foo[2]".to_owned(), None, Some(value!(4))),
+            ]
+        );
+    }
+
+    #[test]
+    fn terminate_outcome() {
+        let tz = TimeZone::default();
+        let old = compile(".foo = 1", &[]).unwrap();
+        let new = compile("abort", &[]).unwrap();
+
+        let target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        let old = Outcome::new(target.clone(), &old, tz);
+        let new = Outcome::new(target, &new, tz);
+
+        let differences = old.differences(&new);
+        assert_eq!(differences.len(), 2);
+        assert!(matches!(differences[0], Difference::Outcome { .. }));
+        assert_eq!(new.status(), "abort");
+    }
+}
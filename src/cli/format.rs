@@ -0,0 +1,409 @@
+use std::io::{BufRead, Write};
+use std::iter;
+
+use clap::ValueEnum;
+
+use crate::compiler::runtime::Runtime;
+use crate::compiler::{
+    CompilationResult, CompileConfig, Function, Program, TargetValue, TimeZone, TypeState,
+    compile_with_state,
+};
+use crate::core::encode_logfmt;
+use crate::diagnostic::Formatter;
+use crate::value::{KeyString, ObjectMap, Secrets, Value};
+
+use super::Error;
+
+/// The format of the events read by the CLI.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputFormat {
+    /// A single JSON value. An array is handled as a list of events.
+    Json,
+
+    /// Newline-delimited JSON, one event per line.
+    #[default]
+    Ndjson,
+
+    /// Plain text, each line is placed at the `.message` field of an event.
+    Text,
+
+    /// CSV with a header row, each row is an event keyed by the header fields.
+    Csv,
+
+    /// Logfmt, one event per line, decoded using `parse_logfmt`.
+    Logfmt,
+
+    /// Syslog, one event per line, decoded using `parse_syslog`.
+    Syslog,
+}
+
+/// The format in which the CLI prints program results.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// A single JSON array containing all results.
+    Json,
+
+    /// Newline-delimited JSON, one result per line.
+    Ndjson,
+
+    /// Pretty-printed JSON, one result after the other.
+    Pretty,
+
+    /// Logfmt, one result per line.
+    Logfmt,
+}
+
+/// Input that could not be decoded into an event.
+pub(super) struct Undecodable {
+    pub(super) input: String,
+    pub(super) error: String,
+}
+
+pub(super) type Event = Result<Value, Undecodable>;
+
+/// The input of a single event, as read by [`records`] and decoded by [`Decoder::decode`].
+pub(super) enum Record {
+    /// A line of input, for the line-based formats.
+    Line(String),
+
+    /// An event that was already decoded while reading the input.
+    Event(Event),
+}
+
+/// Read the input one record at a time, so that it never needs to be held in memory at once.
+///
+/// Empty input results in a single, empty event. The `json` format is a single value, so it is
+/// read at once, and results in an error if it can't be decoded.
+pub(super) fn records<'a>(
+    mut reader: impl BufRead + Send + 'a,
+    format: InputFormat,
+) -> Box<dyn Iterator<Item = Result<Record, Error>> + Send + 'a> {
+    match reader.fill_buf() {
+        Ok([]) => {
+            return Box::new(iter::once(Ok(Record::Event(Ok(Value::Object(
+                ObjectMap::new(),
+            ))))));
+        }
+        Ok(_) => {}
+        Err(err) => return Box::new(iter::once(Err(err.into()))),
+    }
+
+    match format {
+        InputFormat::Json => match serde_json::from_reader(reader) {
+            Ok(Value::Array(events)) => {
+                Box::new(events.into_iter().map(|event| Ok(Record::Event(Ok(event)))))
+            }
+            Ok(event) => Box::new(iter::once(Ok(Record::Event(Ok(event))))),
+            Err(err) => Box::new(iter::once(Err(err.into()))),
+        },
+        InputFormat::Text => Box::new(reader.lines().map(|line| Ok(Record::Line(line?)))),
+        InputFormat::Csv => Box::new(decode_csv(reader).map(|event| Ok(Record::Event(event)))),
+        InputFormat::Ndjson | InputFormat::Logfmt | InputFormat::Syslog => Box::new(
+            reader
+                .lines()
+                .filter(|line| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+                .map(|line| Ok(Record::Line(line?))),
+        ),
+    }
+}
+
+/// Decodes [`Record`]s into events.
+pub(super) struct Decoder {
+    format: InputFormat,
+
+    /// The program running the parse function of the `logfmt` and `syslog` formats, so that the
+    /// decoded events are identical to those produced by calling the function in a program.
+    program: Option<Program>,
+
+    timezone: TimeZone,
+}
+
+impl Decoder {
+    pub(super) fn new(
+        format: InputFormat,
+        functions: &[Box<dyn Function>],
+        timezone: TimeZone,
+    ) -> Result<Self, Error> {
+        let program = match format {
+            InputFormat::Logfmt => Some(compile("parse_logfmt!(.message)", functions)?),
+            InputFormat::Syslog => Some(compile("parse_syslog!(.message)", functions)?),
+            InputFormat::Json | InputFormat::Ndjson | InputFormat::Text | InputFormat::Csv => None,
+        };
+
+        Ok(Self {
+            format,
+            program,
+            timezone,
+        })
+    }
+
+    /// Decode the record into an event, using the runtime to run the parse function of the
+    /// format, if it has one.
+    pub(super) fn decode(&self, record: Record, runtime: &mut Runtime) -> Event {
+        let line = match record {
+            Record::Line(line) => line,
+            Record::Event(event) => return event,
+        };
+
+        if let Some(program) = &self.program {
+            let mut target = TargetValue {
+                value: message(&line),
+                metadata: Value::Object(ObjectMap::new()),
+                secrets: Secrets::new(),
+            };
+            let result = runtime.resolve(&mut target, program, &self.timezone);
+            runtime.clear();
+
+            return result.map_err(|err| Undecodable {
+                input: line,
+                error: err.to_string(),
+            });
+        }
+
+        match self.format {
+            InputFormat::Text => Ok(message(&line)),
+            _ => serde_json::from_str(&line).map_err(|err| Undecodable {
+                input: line,
+                error: err.to_string(),
+            }),
+        }
+    }
+}
+
+/// Decode the raw input into a list of events.
+///
+/// Empty input results in a single, empty event. Input that can't be decoded results in an error
+/// when the input format is `json`, otherwise only the affected line or row is returned as
+/// [`Undecodable`].
+pub(super) fn decode(
+    input: &str,
+    format: InputFormat,
+    functions: &[Box<dyn Function>],
+    timezone: TimeZone,
+) -> Result<Vec<Event>, Error> {
+    let decoder = Decoder::new(format, functions, timezone)?;
+    let mut runtime = Runtime::default();
+
+    records(input.as_bytes(), format)
+        .map(|record| Ok(decoder.decode(record?, &mut runtime)))
+        .collect()
+}
+
+fn message(line: &str) -> Value {
+    Value::from(ObjectMap::from([(KeyString::from("message"), line.into())]))
+}
+
+fn decode_csv<'a>(reader: impl BufRead + Send + 'a) -> Box<dyn Iterator<Item = Event> + Send + 'a> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+
+    let headers = match reader
+        .byte_headers()
+        .map_err(|err| csv_error(&err))
+        .and_then(|headers| csv_record(headers.clone()))
+    {
+        Ok(headers) => headers,
+        Err(undecodable) => return Box::new(iter::once(Err(undecodable))),
+    };
+
+    Box::new(reader.into_byte_records().map(move |record| {
+        let record = csv_record(record.map_err(|err| csv_error(&err))?)?;
+
+        Ok(headers
+            .iter()
+            .zip(record.iter())
+            .map(|(key, value)| (KeyString::from(key), Value::from(value)))
+            .collect::<ObjectMap>()
+            .into())
+    }))
+}
+
+/// Decode a raw CSV record, keeping its (lossily decoded) fields as the input if it isn't UTF-8.
+fn csv_record(record: csv::ByteRecord) -> Result<csv::StringRecord, Undecodable> {
+    let line = record.position().map(csv::Position::line);
+
+    csv::StringRecord::from_byte_record(record).map_err(|err| {
+        let error = match line {
+            Some(line) => format!("CSV record at line {line}: {}", err.utf8_error()),
+            None => err.utf8_error().to_string(),
+        };
+        let input = err
+            .into_byte_record()
+            .iter()
+            .map(String::from_utf8_lossy)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Undecodable { input, error }
+    })
+}
+
+/// A record that couldn't be read at all only has its position to point at the input.
+fn csv_error(err: &csv::Error) -> Undecodable {
+    let input = err
+        .position()
+        .map(|position| format!("line {}", position.line()))
+        .unwrap_or_default();
+
+    Undecodable {
+        input,
+        error: err.to_string(),
+    }
+}
+
+fn compile(source: &str, functions: &[Box<dyn Function>]) -> Result<Program, Error> {
+    compile_with_state(
+        source,
+        functions,
+        &TypeState::default(),
+        CompileConfig::default(),
+    )
+    .map(|CompilationResult { program, .. }| program)
+    .map_err(|diagnostics| Error::Parse(Formatter::new(source, diagnostics).to_string()))
+}
+
+/// Writes results in the configured [`OutputFormat`].
+///
+/// Without an explicit format, values are printed using their VRL representation.
+pub(super) struct Output {
+    format: Option<OutputFormat>,
+
+    /// The number of results written so far.
+    written: usize,
+}
+
+impl Output {
+    pub(super) fn new(format: Option<OutputFormat>) -> Self {
+        Self { format, written: 0 }
+    }
+
+    pub(super) fn write(&mut self, value: &Value) -> Result<(), Error> {
+        let output = match self.format {
+            None => value.to_string(),
+            Some(OutputFormat::Json) => {
+                // The elements of the array are written as they come, so that results don't
+                // need to be held in memory.
+                let separator = if self.written == 0 { '[' } else { ',' };
+                let mut stdout = std::io::stdout().lock();
+                write!(stdout, "{separator}")?;
+                serde_json::to_writer(&mut stdout, value)?;
+                self.written += 1;
+                return Ok(());
+            }
+            Some(OutputFormat::Ndjson) => serde_json::to_string(value)?,
+            Some(OutputFormat::Pretty) => serde_json::to_string_pretty(value)?,
+            Some(OutputFormat::Logfmt) => {
+                encode_logfmt::encode_value(value).map_err(|err| Error::Output(err.to_string()))?
+            }
+        };
+
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{output}");
+        }
+        self.written += 1;
+
+        Ok(())
+    }
+
+    /// Finish the output, closing the array of the `json` format.
+    pub(super) fn finish(self) -> Result<(), Error> {
+        if self.format == Some(OutputFormat::Json) {
+            let close = if self.written == 0 { "[]" } else { "]" };
+            writeln!(std::io::stdout().lock(), "{close}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    fn decode_ok(input: &str, format: InputFormat) -> Vec<Value> {
+        decode(input, format, &[], TimeZone::default())
+            .unwrap()
+            .into_iter()
+            .map(|event| event.unwrap_or_else(|err| panic!("{}", err.error)))
+            .collect()
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(decode_ok("", InputFormat::Text), vec![value!({})]);
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            decode_ok(r#"[{"a": 1}, {"b": 2}]"#, InputFormat::Json),
+            vec![value!({ "a": 1 }), value!({ "b": 2 })]
+        );
+        assert_eq!(
+            decode_ok(r#"{"a": 1}"#, InputFormat::Json),
+            vec![value!({ "a": 1 })]
+        );
+    }
+
+    #[test]
+    fn ndjson() {
+        let events = decode(
+            "{\"a\": 1}\n\nnot json\n",
+            InputFormat::Ndjson,
+            &[],
+            TimeZone::default(),
+        )
+        .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_ref().ok(), Some(&value!({ "a": 1 })));
+        assert_eq!(events[1].as_ref().err().unwrap().input, "not json");
+    }
+
+    #[test]
+    fn csv_invalid_utf8() {
+        let events = decode_csv(&b"a,b\n1,2\n3,\xff\n"[..]).collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].as_ref().ok(),
+            Some(&value!({ "a": "1", "b": "2" }))
+        );
+
+        let err = events[1].as_ref().err().unwrap();
+        assert_eq!(err.input, "3,\u{fffd}");
+        assert!(
+            err.error.starts_with("CSV record at line 3: "),
+            "{}",
+            err.error
+        );
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            decode_ok("foo\nbar", InputFormat::Text),
+            vec![value!({ "message": "foo" }), value!({ "message": "bar" })]
+        );
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            decode_ok("name,age\nfoo,1\nbar,2\n", InputFormat::Csv),
+            vec![
+                value!({ "name": "foo", "age": "1" }),
+                value!({ "name": "bar", "age": "2" })
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_parse_function() {
+        assert!(matches!(
+            decode("foo=bar", InputFormat::Logfmt, &[], TimeZone::default()),
+            Err(Error::Parse(_))
+        ));
+    }
+}
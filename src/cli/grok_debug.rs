@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::{fs::File, io, path::PathBuf};
+
+use clap::Args;
+use serde_json::json;
+
+use crate::datadog_grok::parse_grok::{GrokRuleDebug, debug_grok, parse_grok_debug_rules};
+use crate::value::KeyString;
+
+use super::Error;
+use super::cmd::read;
+
+#[derive(Args, Debug)]
+pub struct Opts {
+    /// The file containing the grok patterns to debug, one per line.
+    patterns: PathBuf,
+
+    /// A file containing the aliases referenced by the patterns, one `name pattern` definition
+    /// per line.
+    #[arg(short, long)]
+    aliases: Option<PathBuf>,
+
+    /// The file containing the lines to match against the patterns. Lines are read from stdin
+    /// if no file is provided.
+    #[arg(short, long = "input")]
+    input_file: Option<PathBuf>,
+
+    /// Print the result of each line as a JSON object, one per line, instead of a
+    /// human-readable report.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Match each input line against every grok pattern, reporting how much of each pattern matched.
+/// Returns `exitcode::OK` if every line is matched by a pattern, or `exitcode::DATAERR` if one
+/// or more lines are not.
+#[must_use]
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match run(opts) {
+        Ok(0) => exitcode::OK,
+        Ok(_) => exitcode::DATAERR,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{err}");
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+fn run(opts: &Opts) -> Result<usize, Error> {
+    let patterns = read(File::open(&opts.patterns)?)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+    let aliases = match &opts.aliases {
+        Some(path) => parse_aliases(&read(File::open(path)?)?)?,
+        None => BTreeMap::new(),
+    };
+    let rules = parse_grok_debug_rules(&patterns, &aliases)
+        .map_err(|err| Error::Input(format!("invalid grok pattern: {err}")))?;
+    let input = match &opts.input_file {
+        Some(path) => read(File::open(path)?)?,
+        None => read(io::stdin())?,
+    };
+
+    let mut unmatched = 0;
+    for (index, line) in input.lines().enumerate() {
+        let results = debug_grok(line, &rules);
+        if !results.iter().any(|result| result.matched) {
+            unmatched += 1;
+        }
+
+        #[allow(clippy::print_stdout)]
+        if opts.json {
+            let rules = results.iter().map(to_json).collect::<Vec<_>>();
+            println!(
+                "{}",
+                json!({ "line": index, "input": line, "rules": rules })
+            );
+        } else {
+            println!("line {index}: {line:?}");
+            for (rule, result) in results.iter().enumerate() {
+                report(rule, result);
+            }
+        }
+    }
+
+    Ok(unmatched)
+}
+
+/// Parse newline separated `name pattern` alias definitions.
+fn parse_aliases(source: &str) -> Result<BTreeMap<KeyString, String>, Error> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (name, pattern) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| Error::Input(format!("invalid alias definition: {line}")))?;
+            Ok((name.into(), pattern.trim_start().to_owned()))
+        })
+        .collect()
+}
+
+#[allow(clippy::print_stdout)]
+fn report(rule: usize, result: &GrokRuleDebug) {
+    let status = if result.matched { "match" } else { "no match" };
+    println!("  rule {rule}: {status}: {}", result.rule);
+
+    if !result.matched {
+        println!("    matched pattern: {}", result.matched_pattern);
+        println!("    matched input:   {:?}", result.matched_input);
+        match (&result.failed_pattern, &result.failed_field) {
+            (Some(pattern), Some(field)) => {
+                println!("    failed at:       {pattern} (field `{field}`)");
+            }
+            (Some(pattern), None) => println!("    failed at:       {pattern}"),
+            (None, _) => println!("    failed at:       end of pattern, input left over"),
+        }
+    }
+
+    println!("    captured:        {}", result.parsed);
+    for error in &result.internal_errors {
+        println!("    filter error:    {error}");
+    }
+}
+
+fn to_json(result: &GrokRuleDebug) -> serde_json::Value {
+    json!({
+        "rule": result.rule,
+        "matched": result.matched,
+        "matched_pattern": result.matched_pattern,
+        "matched_input": result.matched_input,
+        "failed_pattern": result.failed_pattern,
+        "failed_field": result.failed_field,
+        "parsed": result.parsed,
+        "internal_errors": result
+            .internal_errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+    })
+}
@@ -0,0 +1,48 @@
+use std::{fs::File, io::Write, path::PathBuf};
+
+use clap::Args;
+
+use crate::datadog_pipeline::import;
+
+use super::Error;
+use super::cmd::read;
+
+#[derive(Args, Debug)]
+pub struct Opts {
+    /// The file containing the exported Datadog pipeline definition (JSON). This can be a single
+    /// pipeline or a list of pipelines.
+    pipeline: PathBuf,
+
+    /// The file to write the VRL program to. The program is printed to stdout if not set.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// Translate a Datadog pipeline definition into a VRL program. Processors without a VRL
+/// equivalent are flagged as comments in the program.
+#[must_use]
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match run(opts) {
+        Ok(()) => exitcode::OK,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{err}");
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+fn run(opts: &Opts) -> Result<(), Error> {
+    let program =
+        import(&read(File::open(&opts.pipeline)?)?).map_err(|err| Error::Input(err.to_string()))?;
+
+    match &opts.output {
+        Some(path) => File::create(path)?.write_all(program.as_bytes())?,
+        #[allow(clippy::print_stdout)]
+        None => print!("{program}"),
+    }
+
+    Ok(())
+}
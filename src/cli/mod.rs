@@ -1,5 +1,9 @@
 #![deny(warnings, clippy::pedantic)]
 pub mod cmd;
+pub mod diff;
+pub mod format;
+pub mod grok_debug;
+pub mod import_datadog;
 mod repl;
 
 use crate::compiler::runtime::Terminate;
@@ -20,6 +24,12 @@ pub enum Error {
     #[error("input error: {}", .0)]
     Json(#[from] serde_json::Error),
 
+    #[error("input error: {}", .0)]
+    Input(String),
+
+    #[error("output error: {}", .0)]
+    Output(String),
+
     #[error("repl feature disabled, program input required")]
     ReplFeature,
 
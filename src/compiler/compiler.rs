@@ -211,7 +211,9 @@ impl<'a> Compiler<'a> {
     }
 
     fn compile_literal(&mut self, node: Node<ast::Literal>, state: &mut TypeState) -> Option<Expr> {
-        use ast::Literal::{Boolean, Float, Integer, Null, RawString, Regex, String, Timestamp};
+        use ast::Literal::{
+            Boolean, Duration, Float, Integer, Null, RawString, Regex, String, Timestamp,
+        };
         use bytes::Bytes;
 
         let (span, lit) = node.take();
@@ -240,6 +242,9 @@ impl<'a> Compiler<'a> {
                 .parse()
                 .map(Literal::Timestamp)
                 .map_err(|err| literal::Error::from((span, err))),
+            Duration(v) => crate::value::value::parse_duration(&v)
+                .map(Literal::Duration)
+                .map_err(|err| literal::Error::invalid_duration(span, err)),
             Null => Ok(Literal::Null),
         };
 
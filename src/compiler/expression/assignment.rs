@@ -265,9 +265,23 @@ fn verify_overwritable(
 
                 ("array", segment_span, parent_kind.contains_array())
             }
+            segment @ (OwnedSegment::Wildcard | OwnedSegment::Slice { .. }) => {
+                let segment_str = segment.to_string();
+                let segment_start = parent_span.end().saturating_sub(segment_str.len());
+                let segment_span = Span::new(segment_start, parent_span.end());
+
+                parent_span = Span::new(parent_span.start(), segment_start);
+                remainder_str.insert_str(0, &segment_str);
+
+                let valid = parent_kind.contains_array()
+                    || (segment.is_wildcard() && parent_kind.contains_object());
+                ("array", segment_span, valid)
+            }
         };
 
-        if valid {
+        // Segments after a wildcard or slice are applied to each of the matched values, which
+        // are not verified.
+        if valid || path.matches_many() {
             continue;
         }
 
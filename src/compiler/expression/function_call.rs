@@ -13,7 +13,7 @@ use crate::compiler::{
     parser::{Ident, Node},
     state::LocalEnv,
     type_def::Details,
-    value::Kind,
+    value::{Collection, Kind},
 };
 use crate::diagnostic::{DiagnosticMessage, Label, Note, Severity, Urls};
 use crate::prelude::Note::SeeErrorDocs;
@@ -329,15 +329,19 @@ impl<'a> Builder<'a> {
                                 // the reduced kind of all values within the
                                 // target collection type.
                                 //
+                                // If the target can be either an object or an
+                                // array, the element kinds of both are merged.
+                                //
                                 // This assumes the target is a collection type,
                                 // or else it'll return "any".
                                 VariableKind::TargetInnerValue => {
-                                    let kind = if let Some(object) = type_def.as_object() {
-                                        object.reduced_kind()
-                                    } else if let Some(array) = type_def.as_array() {
-                                        array.reduced_kind()
-                                    } else {
-                                        Kind::any()
+                                    let object = type_def.as_object().map(Collection::reduced_kind);
+                                    let array = type_def.as_array().map(Collection::reduced_kind);
+
+                                    let kind = match (object, array) {
+                                        (Some(object), Some(array)) => object.union(array),
+                                        (Some(kind), None) | (None, Some(kind)) => kind,
+                                        (None, None) => Kind::any(),
                                     };
 
                                     (kind.into(), None)
@@ -1308,6 +1312,47 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct TypedFn;
+
+    impl Function for TypedFn {
+        fn identifier(&self) -> &'static str {
+            "typed"
+        }
+
+        fn usage(&self) -> &'static str {
+            "Typed test function"
+        }
+
+        fn category(&self) -> &'static str {
+            Category::Debug.as_ref()
+        }
+
+        fn return_kind(&self) -> u16 {
+            kind::NULL
+        }
+
+        fn examples(&self) -> &'static [crate::compiler::function::Example] {
+            &[]
+        }
+
+        fn parameters(&self) -> &'static [Parameter] {
+            const PARAMETERS: &[Parameter] = &[Parameter::required("value", kind::ARRAY, "value")
+                .typed(|| Kind::array(Collection::from_unknown(Kind::bytes())))];
+
+            PARAMETERS
+        }
+
+        fn compile(
+            &self,
+            _state: &TypeState,
+            _ctx: &mut FunctionCompileContext,
+            _arguments: ArgumentList,
+        ) -> crate::compiler::function::Compiled {
+            Ok(Fn.as_expr())
+        }
+    }
+
     fn create_node<T>(inner: T) -> Node<T> {
         Node::new(Span::new(0, 0), inner)
     }
@@ -1347,6 +1392,19 @@ mod tests {
         .function_call
     }
 
+    #[test]
+    fn typed_parameter_element_kind() {
+        let fns = [Box::new(TypedFn) as _];
+
+        assert!(crate::compiler::compile(r#"typed(["foo", "bar"])"#, &fns).is_ok());
+
+        let Err(diagnostics) = crate::compiler::compile("typed([1, 2])", &fns) else {
+            panic!("expected compilation to fail");
+        };
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, 110);
+    }
+
     #[test]
     fn resolve_arguments_simple() {
         let call = create_function_call(vec![
@@ -3,9 +3,10 @@ use std::{borrow::Cow, convert::TryFrom, fmt, sync::Arc};
 use crate::diagnostic::{DiagnosticMessage, Label, Note, Urls};
 use crate::value::{Value, ValueRegex};
 use bytes::Bytes;
-use chrono::{DateTime, SecondsFormat, Utc};
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
 use ordered_float::NotNan;
 use regex::Regex;
+use rust_decimal::Decimal;
 
 use crate::compiler::{
     Context, Expression, Span, TypeDef,
@@ -18,9 +19,11 @@ pub enum Literal {
     String(Bytes),
     Integer(i64),
     Float(NotNan<f64>),
+    Decimal(Decimal),
     Boolean(bool),
     Regex(ValueRegex),
     Timestamp(DateTime<Utc>),
+    Duration(TimeDelta),
     Null,
 }
 
@@ -32,15 +35,17 @@ impl Literal {
     /// the case of `Literal` means it always returns `Some(Value)`, requiring
     /// an extra `unwrap()`.
     pub fn to_value(&self) -> Value {
-        use Literal::{Boolean, Float, Integer, Null, Regex, String, Timestamp};
+        use Literal::{Boolean, Decimal, Duration, Float, Integer, Null, Regex, String, Timestamp};
 
         match self {
             String(v) => Value::Bytes(v.clone()),
             Integer(v) => Value::Integer(*v),
             Float(v) => Value::Float(*v),
+            Decimal(v) => Value::Decimal(*v),
             Boolean(v) => Value::Boolean(*v),
             Regex(v) => Value::Regex(v.clone()),
             Timestamp(v) => Value::Timestamp(*v),
+            Duration(v) => Value::Duration(*v),
             Null => Value::Null,
         }
     }
@@ -56,15 +61,17 @@ impl Expression for Literal {
     }
 
     fn type_info(&self, state: &TypeState) -> TypeInfo {
-        use Literal::{Boolean, Float, Integer, Null, Regex, String, Timestamp};
+        use Literal::{Boolean, Decimal, Duration, Float, Integer, Null, Regex, String, Timestamp};
 
         let type_def = match self {
             String(_) => TypeDef::bytes(),
             Integer(_) => TypeDef::integer(),
             Float(_) => TypeDef::float(),
+            Decimal(_) => TypeDef::decimal(),
             Boolean(_) => TypeDef::boolean(),
             Regex(_) => TypeDef::regex(),
             Timestamp(_) => TypeDef::timestamp(),
+            Duration(_) => TypeDef::duration(),
             Null => TypeDef::null(),
         };
 
@@ -74,15 +81,17 @@ impl Expression for Literal {
 
 impl fmt::Display for Literal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Literal::{Boolean, Float, Integer, Null, Regex, String, Timestamp};
+        use Literal::{Boolean, Decimal, Duration, Float, Integer, Null, Regex, String, Timestamp};
 
         match self {
             String(v) => write!(f, r#""{}""#, std::string::String::from_utf8_lossy(v)),
             Integer(v) => v.fmt(f),
             Float(v) => v.fmt(f),
+            Decimal(v) => v.fmt(f),
             Boolean(v) => v.fmt(f),
             Regex(v) => v.fmt(f),
             Timestamp(v) => write!(f, "t'{}'", v.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+            Duration(v) => write!(f, "d'{}'", crate::value::value::format_duration(*v)),
             Null => f.write_str("null"),
         }
     }
@@ -197,6 +206,14 @@ impl TryFrom<f64> for Literal {
     }
 }
 
+// Literal::Decimal ------------------------------------------------------------
+
+impl From<Decimal> for Literal {
+    fn from(v: Decimal) -> Self {
+        Literal::Decimal(v)
+    }
+}
+
 // Literal::Boolean ------------------------------------------------------------
 
 impl From<bool> for Literal {
@@ -236,7 +253,7 @@ impl<T: Into<Literal>> From<Option<T>> for Literal {
     }
 }
 
-// Literal::Regex --------------------------------------------------------------
+// Literal::Timestamp ----------------------------------------------------------
 
 impl From<DateTime<Utc>> for Literal {
     fn from(dt: DateTime<Utc>) -> Self {
@@ -244,6 +261,14 @@ impl From<DateTime<Utc>> for Literal {
     }
 }
 
+// Literal::Duration -----------------------------------------------------------
+
+impl From<TimeDelta> for Literal {
+    fn from(duration: TimeDelta) -> Self {
+        Literal::Duration(duration)
+    }
+}
+
 // -----------------------------------------------------------------------------
 
 #[derive(Debug)]
@@ -262,6 +287,9 @@ pub(crate) enum ErrorVariant {
 
     #[error("float literal can't be NaN")]
     NanFloat,
+
+    #[error("invalid duration")]
+    InvalidDuration(String),
 }
 
 impl fmt::Display for Error {
@@ -278,17 +306,18 @@ impl std::error::Error for Error {
 
 impl DiagnosticMessage for Error {
     fn code(&self) -> usize {
-        use ErrorVariant::{InvalidRegex, InvalidTimestamp, NanFloat};
+        use ErrorVariant::{InvalidDuration, InvalidRegex, InvalidTimestamp, NanFloat};
 
         match &self.variant {
             InvalidRegex(..) => 101,
             InvalidTimestamp(..) => 601,
             NanFloat => 602,
+            InvalidDuration(..) => 603,
         }
     }
 
     fn labels(&self) -> Vec<Label> {
-        use ErrorVariant::{InvalidRegex, InvalidTimestamp, NanFloat};
+        use ErrorVariant::{InvalidDuration, InvalidRegex, InvalidTimestamp, NanFloat};
 
         match &self.variant {
             InvalidRegex(err) => {
@@ -315,13 +344,17 @@ impl DiagnosticMessage for Error {
                 format!("invalid timestamp format: {err}"),
                 self.span,
             )],
+            InvalidDuration(err) => vec![Label::primary(
+                format!("invalid duration format: {err}"),
+                self.span,
+            )],
 
             NanFloat => vec![],
         }
     }
 
     fn notes(&self) -> Vec<Note> {
-        use ErrorVariant::{InvalidRegex, InvalidTimestamp, NanFloat};
+        use ErrorVariant::{InvalidDuration, InvalidRegex, InvalidTimestamp, NanFloat};
 
         match &self.variant {
             InvalidRegex(_) => vec![Note::SeeDocs(
@@ -336,6 +369,10 @@ impl DiagnosticMessage for Error {
                 "floats".to_owned(),
                 Urls::expression_docs_url("#float"),
             )],
+            InvalidDuration(_) => vec![Note::SeeDocs(
+                "durations".to_owned(),
+                Urls::expression_docs_url("#duration"),
+            )],
         }
     }
 }
@@ -358,6 +395,15 @@ impl From<(Span, chrono::ParseError)> for Error {
     }
 }
 
+impl Error {
+    pub(crate) fn invalid_duration(span: Span, err: String) -> Self {
+        Self {
+            variant: ErrorVariant::InvalidDuration(err),
+            span,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::compiler::TypeDef;
@@ -373,5 +419,10 @@ mod tests {
             expr: |_| expr!(12),
             want: TypeDef::integer(),
         }
+
+        duration {
+            expr: |_| expr!((chrono::TimeDelta::minutes(5))),
+            want: TypeDef::duration(),
+        }
     ];
 }
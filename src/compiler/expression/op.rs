@@ -227,8 +227,10 @@ impl Expression for Op {
                 // t'2023-05-04T22:22:22.234142Z' >= t'2023-04-04T22:22:22.234142Z'
                 // t'2023-05-04T22:22:22.234142Z' <  t'2023-04-04T22:22:22.234142Z'
                 // t'2023-05-04T22:22:22.234142Z' <= t'2023-04-04T22:22:22.234142Z'
+                // d'1h' > d'30m'
                 if (lhs_def.is_bytes() && rhs_def.is_bytes())
                     || (lhs_def.is_timestamp() && rhs_def.is_timestamp())
+                    || (lhs_def.is_duration() && rhs_def.is_duration())
                 {
                     lhs_def.union(rhs_def).with_kind(K::boolean())
                 }
@@ -238,14 +240,47 @@ impl Expression for Op {
                 // ... <= ...
                 else {
                     lhs_def
-                        .fallible_unless(K::integer().or_float())
-                        .union(rhs_def.fallible_unless(K::integer().or_float()))
+                        .fallible_unless(K::integer().or_float().or_decimal())
+                        .union(rhs_def.fallible_unless(K::integer().or_float().or_decimal()))
                         .with_kind(K::boolean())
                 }
             }
 
             // ... / ...
             Div => {
+                let rhs_def = self.rhs.type_info(&state).result;
+
+                // d'1h' / 2
+                // d'1h' / d'30m'
+                // Dividing a duration by a number returns a duration, while dividing it by another
+                // duration returns their ratio.
+                if lhs_def.contains_duration() {
+                    let kind = if lhs_def.is_duration() && rhs_def.is_duration() {
+                        K::float()
+                    } else if lhs_def.is_duration()
+                        && K::integer().or_float().is_superset(rhs_def.kind()).is_ok()
+                    {
+                        K::duration()
+                    } else {
+                        K::float().or_decimal().or_duration()
+                    };
+
+                    return TypeInfo::new(state, TypeDef::from(kind).fallible());
+                }
+
+                // Dividing by or into a decimal stays exact, unless the other side is a float.
+                if lhs_def.contains_decimal() || rhs_def.contains_decimal() {
+                    let kind = if lhs_def.is_float() || rhs_def.is_float() {
+                        K::float()
+                    } else if lhs_def.is_decimal() || rhs_def.is_decimal() {
+                        K::decimal()
+                    } else {
+                        K::float().or_decimal()
+                    };
+
+                    return TypeInfo::new(state, TypeDef::from(kind).fallible());
+                }
+
                 let td = TypeDef::float();
 
                 // Division is infallible if the rhs is a literal normal float or integer.
@@ -263,7 +298,41 @@ impl Expression for Op {
                 // none of these operations short-circuit, so the type of RHS can be applied
                 let rhs_def = self.rhs.apply_type_info(&mut state);
 
+                let numeric = K::integer().or_float();
+
                 match self.opcode {
+                    // t'2024-01-02T00:00:00Z' - t'2024-01-01T00:00:00Z'
+                    Sub if lhs_def.is_timestamp() && rhs_def.is_timestamp() => {
+                        lhs_def.union(rhs_def).with_kind(K::duration())
+                    }
+
+                    // t'2024-01-01T00:00:00Z' + d'1h'
+                    // d'1h' + t'2024-01-01T00:00:00Z'
+                    // t'2024-01-01T00:00:00Z' - d'1h'
+                    // The resulting timestamp can be out of range, so the operation is fallible.
+                    Add if (lhs_def.is_timestamp() && rhs_def.is_duration())
+                        || (lhs_def.is_duration() && rhs_def.is_timestamp()) =>
+                    {
+                        lhs_def.union(rhs_def).fallible().with_kind(K::timestamp())
+                    }
+                    Sub if lhs_def.is_timestamp() && rhs_def.is_duration() => {
+                        lhs_def.union(rhs_def).fallible().with_kind(K::timestamp())
+                    }
+
+                    // d'1h' + d'30m'
+                    // d'1h' * 2
+                    // 1.5 * d'1h'
+                    Add | Sub if lhs_def.is_duration() && rhs_def.is_duration() => {
+                        lhs_def.union(rhs_def).fallible().with_kind(K::duration())
+                    }
+                    Mul if (lhs_def.is_duration()
+                        && numeric.is_superset(rhs_def.kind()).is_ok())
+                        || (numeric.is_superset(lhs_def.kind()).is_ok()
+                            && rhs_def.is_duration()) =>
+                    {
+                        lhs_def.union(rhs_def).fallible().with_kind(K::duration())
+                    }
+
                     // "bar" + ...
                     // ... + "bar"
                     Add if lhs_def.is_bytes() || rhs_def.is_bytes() => lhs_def
@@ -280,10 +349,27 @@ impl Expression for Op {
                     // 1.0 * ...
                     // 1.0 % ...
                     Add | Sub | Mul if lhs_def.is_float() || rhs_def.is_float() => lhs_def
-                        .fallible_unless(K::integer().or_float())
-                        .union(rhs_def.fallible_unless(K::integer().or_float()))
+                        .fallible_unless(K::integer().or_float().or_decimal())
+                        .union(rhs_def.fallible_unless(K::integer().or_float().or_decimal()))
                         .with_kind(K::float()),
 
+                    // 1 + to_decimal!("0.1")
+                    // to_decimal!("0.1") * 3
+                    // A decimal result can overflow, so the operation stays fallible.
+                    Add | Sub | Mul
+                        if (lhs_def.is_decimal() || rhs_def.is_decimal())
+                            && K::integer()
+                                .or_decimal()
+                                .is_superset(lhs_def.kind())
+                                .is_ok()
+                            && K::integer()
+                                .or_decimal()
+                                .is_superset(rhs_def.kind())
+                                .is_ok() =>
+                    {
+                        lhs_def.union(rhs_def).fallible().with_kind(K::decimal())
+                    }
+
                     // 1 + 1
                     // 1 - 1
                     // 1 * 1
@@ -304,16 +390,34 @@ impl Expression for Op {
 
                     // ... + ...
                     // ... * ...
-                    Add | Mul => lhs_def
-                        .union(rhs_def)
-                        .fallible()
-                        .with_kind(K::bytes().or_integer().or_float()),
+                    Add | Mul => {
+                        let mut kind = K::bytes().or_integer().or_float().or_decimal();
+                        if lhs_def.contains_duration() || rhs_def.contains_duration() {
+                            kind.add_duration();
+                            if self.opcode == Add
+                                && (lhs_def.contains_timestamp() || rhs_def.contains_timestamp())
+                            {
+                                kind.add_timestamp();
+                            }
+                        }
+
+                        lhs_def.union(rhs_def).fallible().with_kind(kind)
+                    }
 
                     // ... - ...
-                    Sub => lhs_def
-                        .union(rhs_def)
-                        .fallible()
-                        .with_kind(K::integer().or_float()),
+                    Sub => {
+                        let mut kind = K::integer().or_float().or_decimal();
+                        if lhs_def.contains_timestamp() && rhs_def.contains_timestamp()
+                            || lhs_def.contains_duration() && rhs_def.contains_duration()
+                        {
+                            kind.add_duration();
+                        }
+                        if lhs_def.contains_timestamp() && rhs_def.contains_duration() {
+                            kind.add_timestamp();
+                        }
+
+                        lhs_def.union(rhs_def).fallible().with_kind(kind)
+                    }
                     _ => unreachable!("Add, Sub, or Mul operation not handled"),
                 }
             }
@@ -434,8 +538,9 @@ impl DiagnosticMessage for Error {
 mod tests {
     use std::convert::TryInto;
 
-    use chrono::Utc;
+    use chrono::{TimeDelta, Utc};
     use ordered_float::NotNan;
+    use rust_decimal::Decimal;
 
     use ast::{
         Ident,
@@ -508,9 +613,74 @@ mod tests {
             want: TypeDef::integer(),
         }
 
+        multiply_decimal_integer {
+            expr: |_| op(Mul, Decimal::new(15, 1), 2),
+            want: TypeDef::decimal().fallible(),
+        }
+
+        add_decimal_float {
+            expr: |_| op(Add, Decimal::new(15, 1), f(1.0)),
+            want: TypeDef::float(),
+        }
+
+        divide_integer_decimal {
+            expr: |_| op(Div, 3, Decimal::new(15, 1)),
+            want: TypeDef::decimal().fallible(),
+        }
+
+        greater_decimal_integer {
+            expr: |_| op(Gt, Decimal::new(15, 1), 1),
+            want: TypeDef::boolean(),
+        }
+
+        subtract_timestamps {
+            expr: |_| op(Sub, Utc::now(), Utc::now()),
+            want: TypeDef::duration(),
+        }
+
+        add_timestamp_duration {
+            expr: |_| op(Add, Utc::now(), TimeDelta::hours(1)),
+            want: TypeDef::timestamp().fallible(),
+        }
+
+        subtract_timestamp_duration {
+            expr: |_| op(Sub, Utc::now(), TimeDelta::hours(1)),
+            want: TypeDef::timestamp().fallible(),
+        }
+
+        add_durations {
+            expr: |_| op(Add, TimeDelta::hours(1), TimeDelta::minutes(30)),
+            want: TypeDef::duration().fallible(),
+        }
+
+        multiply_float_duration {
+            expr: |_| op(Mul, f(1.5), TimeDelta::hours(1)),
+            want: TypeDef::duration().fallible(),
+        }
+
+        divide_duration_integer {
+            expr: |_| op(Div, TimeDelta::hours(1), 2),
+            want: TypeDef::duration().fallible(),
+        }
+
+        divide_durations {
+            expr: |_| op(Div, TimeDelta::hours(1), TimeDelta::minutes(30)),
+            want: TypeDef::float().fallible(),
+        }
+
+        greater_durations {
+            expr: |_| op(Gt, TimeDelta::hours(1), TimeDelta::minutes(30)),
+            want: TypeDef::boolean(),
+        }
+
+        add_duration_other {
+            expr: |_| op(Add, TimeDelta::hours(1), ()),
+            want: TypeDef::bytes().or_integer().or_float().or_decimal().or_duration().fallible(),
+        }
+
         multiply_other {
             expr: |_| op(Mul, (), ()),
-            want: TypeDef::bytes().fallible().or_integer().or_float(),
+            want: TypeDef::bytes().fallible().or_integer().or_float().or_decimal(),
         }
 
         add_string_string {
@@ -560,7 +730,7 @@ mod tests {
 
         add_other {
             expr: |_| op(Add, (), ()),
-            want: TypeDef::bytes().or_integer().or_float().fallible(),
+            want: TypeDef::bytes().or_integer().or_float().or_decimal().fallible(),
         }
 
         subtract_integer {
@@ -580,7 +750,7 @@ mod tests {
 
         subtract_other {
             expr: |_| op(Sub, 1, ()),
-            want: TypeDef::integer().fallible().or_float(),
+            want: TypeDef::integer().fallible().or_float().or_decimal(),
         }
 
         divide_integer_literal {
@@ -111,10 +111,9 @@ impl Expression for Query {
                 };
                 return Ok(ctx
                     .target()
-                    .target_get(&path)
+                    .target_project(&path)
                     .ok()
                     .flatten()
-                    .cloned()
                     .unwrap_or(Value::Null));
             }
             Internal(variable) => variable.resolve(ctx)?,
@@ -122,14 +121,14 @@ impl Expression for Query {
             Container(container) => container.resolve(ctx)?,
         };
 
-        Ok(value.get(&self.path).cloned().unwrap_or(Value::Null))
+        Ok(value.project(&self.path).unwrap_or(Value::Null))
     }
 
     fn resolve_constant(&self, state: &TypeState) -> Option<Value> {
         match self.target {
             Target::Internal(ref variable) => variable
                 .resolve_constant(state)
-                .and_then(|v| v.get(self.path()).cloned()),
+                .and_then(|v| v.project(self.path())),
             _ => None,
         }
     }
@@ -160,7 +159,7 @@ impl fmt::Display for Query {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.target {
             Target::Internal(_)
-                if !self.path.is_root() && !self.path.segments.first().unwrap().is_index() =>
+                if !self.path.is_root() && self.path.segments.first().unwrap().is_field() =>
             {
                 write!(f, "{}.{}", self.target, self.path)
             }
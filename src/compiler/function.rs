@@ -1,5 +1,9 @@
 #![allow(clippy::missing_errors_doc)]
 pub mod closure;
+#[cfg(any(test, feature = "lua"))]
+pub mod lua;
+#[cfg(feature = "wasm_plugins")]
+pub mod wasm_plugin;
 
 use crate::diagnostic::{DiagnosticMessage, Label, Note};
 use crate::parser::ast::Ident;
@@ -247,7 +251,12 @@ pub struct EnumVariant {
     pub description: &'static str,
 }
 
+/// A parameter of a [`Function`].
+///
+/// Parameters are built with [`Parameter::required`] or [`Parameter::optional`] and the builder
+/// methods, so that fields can be added without breaking downstream crates.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct Parameter {
     /// The keyword of the parameter.
     ///
@@ -261,6 +270,16 @@ pub struct Parameter {
     /// error.
     pub kind: u16,
 
+    /// An optional, more precise type definition for this parameter.
+    ///
+    /// When set, this takes precedence over `kind` during compile-time type
+    /// checking, allowing a parameter to describe collection element types
+    /// (e.g. "an array of strings") that can't be expressed by the `kind`
+    /// bitmask. The `kind` bitmask is still used for documentation purposes.
+    ///
+    /// Set with [`Parameter::typed`] and read with [`Parameter::typed_kind`].
+    typed_kind: Option<TypedKind>,
+
     /// Whether or not this is a required parameter.
     ///
     /// If it isn't, the function can be called without errors, even if the
@@ -284,6 +303,19 @@ pub struct Parameter {
     pub enum_variants: Option<&'static [EnumVariant]>,
 }
 
+/// The function returning the precise kind of a parameter, compared by the kind it returns, as
+/// function pointers are not reliably equal across codegen units.
+#[derive(Debug, Copy, Clone)]
+struct TypedKind(fn() -> Kind);
+
+impl PartialEq for TypedKind {
+    fn eq(&self, other: &Self) -> bool {
+        (self.0)() == (other.0)()
+    }
+}
+
+impl Eq for TypedKind {}
+
 impl Parameter {
     /// Create a required parameter with default values for `default` and `enum_variants`.
     #[must_use]
@@ -291,6 +323,7 @@ impl Parameter {
         Self {
             keyword,
             kind,
+            typed_kind: None,
             required: true,
             description,
             default: None,
@@ -304,6 +337,7 @@ impl Parameter {
         Self {
             keyword,
             kind,
+            typed_kind: None,
             required: false,
             description,
             default: None,
@@ -318,6 +352,23 @@ impl Parameter {
         self
     }
 
+    /// Set a precise [`Kind`] for this parameter, used instead of the `kind`
+    /// bitmask when type checking function arguments.
+    ///
+    /// The provided function should return a kind that is a subset of the
+    /// kinds described by the bitmask.
+    #[must_use]
+    pub const fn typed(mut self, kind: fn() -> Kind) -> Self {
+        self.typed_kind = Some(TypedKind(kind));
+        self
+    }
+
+    /// The precise [`Kind`] set with [`Parameter::typed`], if any.
+    #[must_use]
+    pub fn typed_kind(&self) -> Option<Kind> {
+        self.typed_kind.map(|TypedKind(kind)| kind())
+    }
+
     /// Set the enum variants for this parameter.
     #[must_use]
     pub const fn enum_variants(mut self, variants: &'static [EnumVariant]) -> Self {
@@ -328,6 +379,10 @@ impl Parameter {
     #[allow(arithmetic_overflow)]
     #[must_use]
     pub fn kind(&self) -> Kind {
+        if let Some(typed_kind) = self.typed_kind() {
+            return typed_kind;
+        }
+
         let mut kind = Kind::never();
 
         let n = self.kind;
@@ -344,6 +399,10 @@ impl Parameter {
             kind.add_float();
         }
 
+        if (n & kind::DECIMAL) == kind::DECIMAL {
+            kind.add_decimal();
+        }
+
         if (n & kind::BOOLEAN) == kind::BOOLEAN {
             kind.add_boolean();
         }
@@ -360,6 +419,10 @@ impl Parameter {
             kind.add_timestamp();
         }
 
+        if (n & kind::DURATION) == kind::DURATION {
+            kind.add_duration();
+        }
+
         if (n & kind::REGEX) == kind::REGEX {
             kind.add_regex();
         }
@@ -457,9 +520,37 @@ impl ArgumentList {
         Ok(required(self.optional_enum(keyword, variants, state)?))
     }
 
+    /// A query of a single value, whose path has no wildcard or slice segments.
     pub fn optional_query(
         &self,
         keyword: &'static str,
+    ) -> Result<Option<crate::compiler::expression::Query>, Error> {
+        self.optional_wildcard_query(keyword)?
+            .map(|query| {
+                if query.path().matches_many() {
+                    Err(Error::UnexpectedExpression {
+                        keyword,
+                        expected: "query without wildcard or slice segments",
+                        expr: Expr::Query(query),
+                    })
+                } else {
+                    Ok(query)
+                }
+            })
+            .transpose()
+    }
+
+    pub fn required_query(
+        &self,
+        keyword: &'static str,
+    ) -> Result<crate::compiler::expression::Query, Error> {
+        Ok(required(self.optional_query(keyword)?))
+    }
+
+    /// A query whose path can have wildcard and slice segments, matching any number of values.
+    pub fn optional_wildcard_query(
+        &self,
+        keyword: &'static str,
     ) -> Result<Option<crate::compiler::expression::Query>, Error> {
         self.optional_expr(keyword)
             .map(|expr| match expr {
@@ -473,11 +564,11 @@ impl ArgumentList {
             .transpose()
     }
 
-    pub fn required_query(
+    pub fn required_wildcard_query(
         &self,
         keyword: &'static str,
     ) -> Result<crate::compiler::expression::Query, Error> {
-        Ok(required(self.optional_query(keyword)?))
+        Ok(required(self.optional_wildcard_query(keyword)?))
     }
 
     pub fn optional_regex(
@@ -812,4 +903,31 @@ mod tests {
             assert_eq!(parameter.kind(), kind, "{title}");
         }
     }
+
+    #[test]
+    fn test_parameter_typed_kind() {
+        let parameter = Parameter::required("", kind::ARRAY, "")
+            .typed(|| Kind::array(Collection::from_unknown(Kind::bytes())));
+
+        assert_eq!(
+            parameter.kind(),
+            Kind::array(Collection::from_unknown(Kind::bytes()))
+        );
+        assert_eq!(
+            parameter.typed_kind(),
+            Some(Kind::array(Collection::from_unknown(Kind::bytes())))
+        );
+        assert_eq!(Parameter::required("", kind::ARRAY, "").typed_kind(), None);
+        assert_eq!(parameter, parameter);
+        assert_eq!(
+            parameter,
+            Parameter::required("", kind::ARRAY, "")
+                .typed(|| Kind::array(Collection::from_unknown(Kind::bytes())))
+        );
+        assert_ne!(
+            parameter,
+            Parameter::required("", kind::ARRAY, "").typed(|| Kind::array(Collection::any()))
+        );
+        assert_ne!(parameter, Parameter::required("", kind::ARRAY, ""));
+    }
 }
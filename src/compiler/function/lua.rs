@@ -0,0 +1,276 @@
+//! Calling Lua functions from VRL.
+//!
+//! A [`LuaModule`] is loaded by the host and stored in the [`CompileConfig`] custom context.
+//! Each [`LuaFunction`] registered with the compiler then exposes the function of the same name
+//! from the module to VRL programs:
+//!
+//! ```ignore
+//! let module = LuaModule::load("helpers", r#"
+//!     local M = {}
+//!     function M.normalize_host(host) return string.lower(host) end
+//!     return M
+//! "#)?;
+//! let mut config = CompileConfig::default();
+//! config.set_custom(module);
+//!
+//! static PARAMETERS: &[Parameter] = &[Parameter::required("host", kind::BYTES, "The host.")];
+//! let functions: Vec<Box<dyn Function>> = vec![Box::new(LuaFunction::new("normalize_host", PARAMETERS))];
+//! ```
+//!
+//! [`CompileConfig`]: crate::compiler::CompileConfig
+
+use mlua::{FromLua, IntoLua, Lua, MultiValue, Table, Value as LuaValue};
+
+use crate::compiler::prelude::*;
+
+/// A Lua module whose functions can be called from VRL, stored in the compile context with
+/// [`CompileConfig::set_custom`](crate::compiler::CompileConfig::set_custom).
+#[derive(Debug, Clone)]
+pub struct LuaModule {
+    lua: Lua,
+    exports: Table,
+}
+
+impl LuaModule {
+    /// Runs the Lua chunk, which must return a table of the functions to export, as is
+    /// conventional for Lua modules.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk fails to run, or does not return a table.
+    pub fn load(name: &str, source: &str) -> mlua::Result<Self> {
+        let lua = Lua::new();
+        let exports = lua.load(source).set_name(name).eval::<Table>()?;
+        Ok(Self { lua, exports })
+    }
+
+    /// Creates a module from the table of functions exported by an existing Lua state.
+    #[must_use]
+    pub fn from_table(lua: Lua, exports: Table) -> Self {
+        Self { lua, exports }
+    }
+}
+
+/// A VRL function that calls the Lua function of the same name in the [`LuaModule`] of the
+/// compile context.
+///
+/// The arguments are passed to the Lua function in the order of the `parameters`, with `nil`
+/// for missing optional arguments, and the return value is converted back to a VRL value. A
+/// Lua error raised by the function is a runtime error of the VRL function call.
+#[derive(Debug, Clone, Copy)]
+pub struct LuaFunction {
+    identifier: &'static str,
+    parameters: &'static [Parameter],
+}
+
+impl LuaFunction {
+    #[must_use]
+    pub const fn new(identifier: &'static str, parameters: &'static [Parameter]) -> Self {
+        Self {
+            identifier,
+            parameters,
+        }
+    }
+}
+
+impl Function for LuaFunction {
+    fn identifier(&self) -> &'static str {
+        self.identifier
+    }
+
+    fn usage(&self) -> &'static str {
+        "Calls the Lua function of the same name from the Lua module of the compile context."
+    }
+
+    fn category(&self) -> &'static str {
+        Category::System.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &["The Lua function raises an error."]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ANY
+    }
+
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        self.parameters
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let module = ctx.get_external_context::<LuaModule>().ok_or_else(|| {
+            Box::new(ExpressionError::from("no Lua module was loaded"))
+                as Box<dyn DiagnosticMessage>
+        })?;
+        let Ok(LuaValue::Function(function)) = module.exports.get::<LuaValue>(self.identifier)
+        else {
+            return Err(Box::new(ExpressionError::from(format!(
+                "the Lua module has no function `{}`",
+                self.identifier
+            ))));
+        };
+
+        Ok(LuaFunctionFn {
+            identifier: self.identifier,
+            lua: module.lua.clone(),
+            function,
+            arguments: self
+                .parameters
+                .iter()
+                .map(|parameter| arguments.optional(parameter.keyword))
+                .collect(),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LuaFunctionFn {
+    identifier: &'static str,
+    /// Keeps the Lua state of the function alive.
+    lua: Lua,
+    function: mlua::Function,
+    arguments: Vec<Option<Box<dyn Expression>>>,
+}
+
+impl FunctionExpression for LuaFunctionFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        // Lua errors carry a stack traceback after the message, which is left out.
+        let error = |e: mlua::Error| {
+            let e = e.to_string();
+            let message = e.lines().next().unwrap_or_default();
+            format!("Lua function `{}` failed: {message}", self.identifier)
+        };
+
+        let mut arguments = MultiValue::with_capacity(self.arguments.len());
+        for argument in &self.arguments {
+            arguments.push_back(match argument {
+                Some(argument) => argument.resolve(ctx)?.into_lua(&self.lua).map_err(error)?,
+                None => LuaValue::Nil,
+            });
+        }
+
+        match self.function.call::<LuaValue>(arguments).map_err(error)? {
+            LuaValue::Nil => Ok(Value::Null),
+            value => Value::from_lua(value, &self.lua).map_err(|e| error(e).into()),
+        }
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::any().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::runtime::Runtime;
+    use crate::compiler::state::RuntimeState;
+    use crate::compiler::{CompileConfig, TargetValue, TimeZone, compile_with_state};
+    use crate::value;
+
+    static PARAMETERS: &[Parameter] = &[
+        Parameter::required("value", kind::ANY, "The value."),
+        Parameter::optional("suffix", kind::BYTES, "The suffix."),
+    ];
+
+    const MODULE: &str = r#"
+        local M = {}
+
+        function M.tag(value, suffix)
+            return { value = value, tagged = value .. (suffix or "!") }
+        end
+
+        function M.nothing(value)
+            return nil
+        end
+
+        function M.fail(value)
+            error("bad value " .. value)
+        end
+
+        M.not_a_function = 42
+
+        return M
+    "#;
+
+    fn run(source: &str, config: CompileConfig) -> Result<Value, String> {
+        let functions: Vec<Box<dyn Function>> = ["tag", "nothing", "fail", "not_a_function"]
+            .into_iter()
+            .map(|name| Box::new(LuaFunction::new(name, PARAMETERS)) as Box<dyn Function>)
+            .collect();
+        let program = compile_with_state(source, &functions, &state::TypeState::default(), config)
+            .map_err(|diagnostics| diagnostics.first().unwrap().message.clone())?
+            .program;
+
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: crate::value::Secrets::default(),
+        };
+        Runtime::new(RuntimeState::default())
+            .resolve(&mut target, &program, &TimeZone::default())
+            .map_err(|terminate| terminate.to_string())
+    }
+
+    fn config() -> CompileConfig {
+        let mut config = CompileConfig::default();
+        config.set_custom(LuaModule::load("module", MODULE).unwrap());
+        config
+    }
+
+    #[test]
+    fn calls_lua_functions() {
+        assert_eq!(
+            run(r#"tag!("a")"#, config()),
+            Ok(value!({ value: "a", tagged: "a!" }))
+        );
+        assert_eq!(
+            run(r#"tag!("a", suffix: "?")"#, config()),
+            Ok(value!({ value: "a", tagged: "a?" }))
+        );
+        assert_eq!(run("nothing!(1)", config()), Ok(Value::Null));
+    }
+
+    #[test]
+    fn surfaces_lua_errors() {
+        let error = run(r#"fail!("x")"#, config()).unwrap_err();
+        assert_eq!(
+            error,
+            r#"function call error for "fail" at (0:10): Lua function `fail` failed: runtime error: [string "module"]:13: bad value x"#
+        );
+        assert_eq!(
+            run(r#"fail("x") ?? "recovered""#, config()),
+            Ok(value!("recovered"))
+        );
+    }
+
+    #[test]
+    fn reports_missing_functions() {
+        assert_eq!(
+            run("not_a_function!(1)", config()),
+            Err(
+                "function compilation error: error[E0] the Lua module has no function `not_a_function`"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            run("tag!(1)", CompileConfig::default()),
+            Err("function compilation error: error[E0] no Lua module was loaded".to_owned())
+        );
+    }
+}
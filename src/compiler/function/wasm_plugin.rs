@@ -0,0 +1,676 @@
+//! VRL functions loaded from WebAssembly plugins at runtime.
+//!
+//! A [`WasmPlugin`] is a WebAssembly component without imports, implementing the `plugin` world
+//! of `wasm_plugin.wit` next to this module. It exports the manifest of its functions, and
+//! `resolve` to call them. Component model types cannot be recursive, so values are passed as a
+//! list of nodes, in which arrays and objects refer to their elements by index.
+//!
+//! Every call into the plugin is limited to the fuel given when loading it, so a plugin cannot
+//! hang the program calling it: a function running out of fuel fails with a runtime error. The
+//! linear memory of the plugin is limited in the same way.
+//!
+//! Each call runs in an instance of the plugin which no other call is using, so calls from
+//! several threads run in parallel. Instances are reused between calls, unless a call traps, in
+//! which case its instance is discarded and replaced.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use chrono::{DateTime, TimeDelta};
+use ordered_float::NotNan;
+use wasmtime::component::{Component, Linker};
+use wasmtime::{Config, Engine, Store, StoreLimits, StoreLimitsBuilder, Trap};
+
+use crate::compiler::prelude::*;
+use crate::value::ObjectMap;
+
+use bindings::{Datetime, Kinds, Node};
+
+mod bindings {
+    wasmtime::component::bindgen!({
+        path: "src/compiler/function/wasm_plugin.wit",
+    });
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum WasmPluginError {
+    #[error("unable to load the WebAssembly component: {0}")]
+    Load(String),
+
+    #[error("the component does not implement the plugin world: {0}")]
+    World(String),
+
+    #[error("invalid plugin manifest: {0}")]
+    Manifest(String),
+}
+
+/// A WebAssembly plugin providing VRL functions.
+#[derive(Debug, Clone)]
+pub struct WasmPlugin {
+    functions: Vec<WasmFunction>,
+}
+
+impl WasmPlugin {
+    /// Loads the plugin from the binary WebAssembly component, limiting every call into it to
+    /// `fuel` units of fuel, and each of its instances to `memory` bytes of linear memory.
+    ///
+    /// The identifiers, usage and parameters of the functions are leaked, as the [`Function`]
+    /// trait requires them to be static. They are only leaked once however many times they are
+    /// loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component is invalid, does not implement the plugin world, or
+    /// fails to return its manifest.
+    pub fn load(wasm: &[u8], fuel: u64, memory: usize) -> Result<Self, WasmPluginError> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        config.wasm_component_model(true);
+        let engine = Engine::new(&config).map_err(|e| WasmPluginError::Load(e.to_string()))?;
+        let component = Component::from_binary(&engine, wasm)
+            .map_err(|e| WasmPluginError::Load(e.to_string()))?;
+        let pre = Linker::new(&engine)
+            .instantiate_pre(&component)
+            .and_then(bindings::PluginPre::new)
+            .map_err(|e| WasmPluginError::World(format!("{e:#}")))?;
+
+        let plugin = Arc::new(Plugin {
+            engine,
+            pre,
+            fuel,
+            memory,
+            idle: Mutex::new(Vec::new()),
+        });
+        let manifest = plugin
+            .call(|instance, store| instance.call_functions(store))
+            .map_err(WasmPluginError::Manifest)?;
+
+        let functions = manifest
+            .into_iter()
+            .enumerate()
+            .map(|(index, function)| {
+                Ok(WasmFunction {
+                    plugin: plugin.clone(),
+                    index: u32::try_from(index)
+                        .map_err(|_| WasmPluginError::Manifest("too many functions".to_owned()))?,
+                    identifier: intern(function.identifier),
+                    usage: intern(function.usage),
+                    parameters: intern_parameters(function.parameters),
+                    return_kind: bits(function.return_kinds),
+                })
+            })
+            .collect::<Result<_, WasmPluginError>>()?;
+
+        Ok(Self { functions })
+    }
+
+    /// The functions of the plugin, to be passed to the compiler along with the other functions
+    /// programs can call.
+    #[must_use]
+    pub fn functions(&self) -> Vec<Box<dyn Function>> {
+        self.functions
+            .iter()
+            .map(|function| Box::new(function.clone()) as Box<dyn Function>)
+            .collect()
+    }
+}
+
+/// Returns `string` as a static string, leaking it unless an equal string already was.
+fn intern(string: String) -> &'static str {
+    static STRINGS: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Mutex::default);
+
+    let mut strings = STRINGS.lock().expect("lock poisoned");
+    if let Some(interned) = strings.get(string.as_str()) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(string.into_boxed_str());
+    strings.insert(interned);
+    interned
+}
+
+/// The keyword, kind, whether it is required, and description of a parameter.
+type ParameterKey = (&'static str, u16, bool, &'static str);
+
+/// Returns `parameters` as a static slice, leaking it unless equal parameters already were.
+fn intern_parameters(parameters: Vec<bindings::Parameter>) -> &'static [Parameter] {
+    static PARAMETERS: LazyLock<Mutex<HashMap<Vec<ParameterKey>, &'static [Parameter]>>> =
+        LazyLock::new(Mutex::default);
+
+    let key = parameters
+        .into_iter()
+        .map(|parameter| {
+            (
+                intern(parameter.keyword),
+                bits(parameter.kinds),
+                parameter.required,
+                intern(parameter.description),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut interned = PARAMETERS.lock().expect("lock poisoned");
+    interned.entry(key).or_insert_with_key(|key| {
+        key.iter()
+            .map(|&(keyword, kind, required, description)| Parameter {
+                required,
+                ..Parameter::optional(keyword, kind, description)
+            })
+            .collect::<Vec<_>>()
+            .leak()
+    })
+}
+
+fn bits(kinds: Kinds) -> u16 {
+    if kinds == Kinds::all() {
+        return kind::ANY;
+    }
+
+    [
+        (Kinds::BYTES, kind::BYTES),
+        (Kinds::INTEGER, kind::INTEGER),
+        (Kinds::FLOAT, kind::FLOAT),
+        (Kinds::DECIMAL, kind::DECIMAL),
+        (Kinds::BOOLEAN, kind::BOOLEAN),
+        (Kinds::OBJECT, kind::OBJECT),
+        (Kinds::ARRAY, kind::ARRAY),
+        (Kinds::TIMESTAMP, kind::TIMESTAMP),
+        (Kinds::DURATION, kind::DURATION),
+        (Kinds::REGEX, kind::REGEX),
+        (Kinds::NULL, kind::NULL),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| kinds.contains(*flag))
+    .fold(0, |bits, (_, kind)| bits | kind)
+}
+
+/// Appends the nodes of `value` to `nodes`, returning the index of its own.
+fn to_nodes(value: &Value, nodes: &mut Vec<Node>) -> Result<u32, String> {
+    let index = u32::try_from(nodes.len()).map_err(|_| "the value is too large".to_owned())?;
+    nodes.push(Node::Null);
+
+    let node = match value {
+        Value::Bytes(bytes) => Node::Bytes(bytes.to_vec()),
+        Value::Regex(regex) => Node::Regex(regex.as_str().to_owned()),
+        Value::Integer(integer) => Node::Integer(*integer),
+        Value::Float(float) => Node::Float(float.into_inner()),
+        Value::Decimal(decimal) => Node::Decimal(decimal.to_string()),
+        Value::Boolean(boolean) => Node::Boolean(*boolean),
+        Value::Timestamp(timestamp) => Node::Timestamp(Datetime {
+            seconds: timestamp.timestamp(),
+            nanoseconds: timestamp.timestamp_subsec_nanos(),
+        }),
+        Value::Duration(duration) => Node::Duration(
+            duration
+                .num_nanoseconds()
+                .ok_or_else(|| format!("the duration {duration} is too long"))?,
+        ),
+        Value::Object(object) => Node::Object(
+            object
+                .iter()
+                .map(|(key, value)| Ok((key.to_string(), to_nodes(value, nodes)?)))
+                .collect::<Result<_, String>>()?,
+        ),
+        Value::Array(array) => Node::Array(
+            array
+                .iter()
+                .map(|value| to_nodes(value, nodes))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Null => Node::Null,
+    };
+    nodes[index as usize] = node;
+
+    Ok(index)
+}
+
+/// Builds the value of the first of `nodes`.
+fn from_nodes(nodes: &[Node]) -> Result<Value, String> {
+    let mut used = vec![false; nodes.len()];
+    from_node(nodes, 0, 0, &mut used)
+}
+
+/// The deepest nesting of arrays and objects in a value returned by a plugin.
+const MAX_DEPTH: usize = 128;
+
+/// Builds the value of the node at `index`, nested `depth` levels deep. Each node can only be used
+/// once, and only by a node before it, so that the nodes form a tree.
+fn from_node(nodes: &[Node], index: u32, depth: usize, used: &mut [bool]) -> Result<Value, String> {
+    let position = index as usize;
+    let node = nodes
+        .get(position)
+        .ok_or_else(|| format!("node {index} does not exist"))?;
+    if std::mem::replace(&mut used[position], true) {
+        return Err(format!("node {index} is used more than once"));
+    }
+
+    let mut child = |child: u32| {
+        if child <= index {
+            return Err(format!("node {index} refers to node {child} before it"));
+        }
+        if depth == MAX_DEPTH {
+            return Err(format!(
+                "node {child} is nested more than {MAX_DEPTH} levels deep"
+            ));
+        }
+        from_node(nodes, child, depth + 1, used)
+    };
+
+    Ok(match node {
+        Node::Null => Value::Null,
+        Node::Boolean(boolean) => Value::Boolean(*boolean),
+        Node::Integer(integer) => Value::Integer(*integer),
+        Node::Float(float) => {
+            Value::Float(NotNan::new(*float).map_err(|_| "NaN is not a valid float".to_owned())?)
+        }
+        Node::Decimal(decimal) => Value::Decimal(
+            decimal
+                .parse()
+                .map_err(|e| format!("invalid decimal \"{decimal}\": {e}"))?,
+        ),
+        Node::Bytes(bytes) => Value::Bytes(Bytes::copy_from_slice(bytes)),
+        Node::Timestamp(Datetime {
+            seconds,
+            nanoseconds,
+        }) => DateTime::from_timestamp(*seconds, *nanoseconds)
+            .ok_or_else(|| format!("invalid timestamp of {seconds}s and {nanoseconds}ns"))?
+            .into(),
+        Node::Duration(nanoseconds) => Value::Duration(TimeDelta::nanoseconds(*nanoseconds)),
+        Node::Regex(regex) => regex::Regex::new(regex)
+            .map_err(|e| format!("invalid regex: {e}"))?
+            .into(),
+        Node::Array(elements) => Value::Array(
+            elements
+                .iter()
+                .map(|element| child(*element))
+                .collect::<Result<_, _>>()?,
+        ),
+        Node::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, element)| Ok((key.as_str().into(), child(*element)?)))
+                .collect::<Result<ObjectMap, String>>()?,
+        ),
+    })
+}
+
+/// A loaded plugin, shared by its functions.
+struct Plugin {
+    engine: Engine,
+    pre: bindings::PluginPre<StoreLimits>,
+    fuel: u64,
+    memory: usize,
+    /// The instances which no call is using.
+    idle: Mutex<Vec<PluginInstance>>,
+}
+
+struct PluginInstance {
+    store: Store<StoreLimits>,
+    instance: bindings::Plugin,
+}
+
+impl fmt::Debug for Plugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Plugin")
+            .field("fuel", &self.fuel)
+            .field("memory", &self.memory)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Plugin {
+    fn instantiate(&self) -> wasmtime::Result<PluginInstance> {
+        let limits = StoreLimitsBuilder::new().memory_size(self.memory).build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.fuel)?;
+        let instance = self.pre.instantiate(&mut store)?;
+        Ok(PluginInstance { store, instance })
+    }
+
+    /// Calls into an idle instance of the plugin with a full tank of fuel, instantiating one if
+    /// there are none. An instance which traps is discarded, as it can't be entered again.
+    fn call<T>(
+        &self,
+        call: impl FnOnce(&bindings::Plugin, &mut Store<StoreLimits>) -> wasmtime::Result<T>,
+    ) -> Result<T, String> {
+        let idle = self.idle.lock().expect("plugin lock poisoned").pop();
+        let mut instance = match idle {
+            Some(instance) => instance,
+            None => self
+                .instantiate()
+                .map_err(|e| format!("unable to instantiate the plugin: {e}"))?,
+        };
+
+        instance
+            .store
+            .set_fuel(self.fuel)
+            .map_err(|e| e.to_string())?;
+        let output = call(&instance.instance, &mut instance.store).map_err(|e| {
+            match e.downcast_ref::<Trap>() {
+                Some(Trap::OutOfFuel) => "the plugin ran out of fuel".to_owned(),
+                _ => format!("the plugin trapped: {e}"),
+            }
+        })?;
+
+        self.idle
+            .lock()
+            .expect("plugin lock poisoned")
+            .push(instance);
+        Ok(output)
+    }
+}
+
+/// A function of a [`WasmPlugin`].
+#[derive(Debug, Clone)]
+pub struct WasmFunction {
+    plugin: Arc<Plugin>,
+    index: u32,
+    identifier: &'static str,
+    usage: &'static str,
+    parameters: &'static [Parameter],
+    return_kind: u16,
+}
+
+impl Function for WasmFunction {
+    fn identifier(&self) -> &'static str {
+        self.identifier
+    }
+
+    fn usage(&self) -> &'static str {
+        self.usage
+    }
+
+    fn category(&self) -> &'static str {
+        Category::System.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &[
+            "The plugin returns an error.",
+            "The plugin traps or runs out of fuel.",
+        ]
+    }
+
+    fn return_kind(&self) -> u16 {
+        self.return_kind
+    }
+
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        self.parameters
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        Ok(WasmFunctionFn {
+            function: self.clone(),
+            arguments: self
+                .parameters
+                .iter()
+                .map(|parameter| arguments.optional(parameter.keyword))
+                .collect(),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WasmFunctionFn {
+    function: WasmFunction,
+    arguments: Vec<Option<Box<dyn Expression>>>,
+}
+
+impl FunctionExpression for WasmFunctionFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let identifier = self.function.identifier;
+
+        let mut arguments = Vec::with_capacity(self.arguments.len());
+        for argument in &self.arguments {
+            arguments.push(match argument {
+                Some(argument) => {
+                    let mut nodes = Vec::new();
+                    to_nodes(&argument.resolve(ctx)?, &mut nodes).map_err(|e| {
+                        format!("unable to pass the arguments to `{identifier}`: {e}")
+                    })?;
+                    Some(nodes)
+                }
+                None => None,
+            });
+        }
+
+        let index = self.function.index;
+        let output = self
+            .function
+            .plugin
+            .call(|instance, store| instance.call_resolve(store, index, &arguments))
+            .map_err(|e| format!("plugin function `{identifier}` failed: {e}"))?;
+
+        match output {
+            Ok(nodes) => from_nodes(&nodes).map_err(|e| {
+                format!("plugin function `{identifier}` returned an invalid value: {e}").into()
+            }),
+            Err(message) => Err(message.into()),
+        }
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        let kind = Parameter::required("", self.function.return_kind, "").kind();
+        TypeDef::from(kind).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::runtime::Runtime;
+    use crate::compiler::state::RuntimeState;
+    use crate::compiler::{CompileConfig, TargetValue, TimeZone, compile_with_state};
+    use crate::value;
+
+    const FUEL: u64 = 100_000;
+    const MEMORY: usize = 1 << 20;
+
+    fn plugin(memory: usize) -> WasmPlugin {
+        let wasm = wat::parse_file("tests/data/wasm_plugins/plugin.wat").unwrap();
+        WasmPlugin::load(&wasm, FUEL, memory).unwrap()
+    }
+
+    fn run_with(plugin: &WasmPlugin, source: &str) -> Result<Value, String> {
+        let program = compile_with_state(
+            source,
+            &plugin.functions(),
+            &state::TypeState::default(),
+            CompileConfig::default(),
+        )
+        .map_err(|diagnostics| diagnostics.first().unwrap().message.clone())?
+        .program;
+
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: crate::value::Secrets::default(),
+        };
+        Runtime::new(RuntimeState::default())
+            .resolve(&mut target, &program, &TimeZone::default())
+            .map_err(|terminate| terminate.to_string())
+    }
+
+    fn run(source: &str) -> Result<Value, String> {
+        run_with(&plugin(MEMORY), source)
+    }
+
+    #[test]
+    fn loads_manifest() {
+        let functions = plugin(MEMORY).functions();
+        let identifiers: Vec<_> = functions.iter().map(|f| f.identifier()).collect();
+        assert_eq!(
+            identifiers,
+            [
+                "wasm_echo",
+                "wasm_fail",
+                "wasm_spin",
+                "wasm_count",
+                "wasm_grow"
+            ]
+        );
+
+        let echo = &functions[0];
+        assert_eq!(echo.usage(), "Returns the value.");
+        assert_eq!(echo.return_kind(), kind::ANY);
+        assert_eq!(echo.parameters().len(), 1);
+        assert_eq!(echo.parameters()[0].keyword, "value");
+        assert!(echo.parameters()[0].required);
+        assert_eq!(functions[1].return_kind(), kind::NULL);
+        assert_eq!(functions[3].return_kind(), kind::INTEGER);
+    }
+
+    #[test]
+    fn interns_manifest() {
+        let (first, second) = (plugin(MEMORY).functions(), plugin(MEMORY).functions());
+        assert!(std::ptr::eq(first[0].identifier(), second[0].identifier()));
+        assert!(std::ptr::eq(first[0].parameters(), second[0].parameters()));
+    }
+
+    #[test]
+    fn calls_functions() {
+        assert_eq!(
+            run(
+                r#"wasm_echo!({"message": "hello", "tags": [1, 2.5, true, null], "at": t'2021-01-01T00:00:00.5Z', "took": d'1s500ms', "pattern": r'^a+$'})"#
+            ),
+            run(
+                r#"{"message": "hello", "tags": [1, 2.5, true, null], "at": t'2021-01-01T00:00:00.5Z', "took": d'1s500ms', "pattern": r'^a+$'}"#
+            )
+        );
+        assert_eq!(
+            run(r#"[wasm_echo!("a"), wasm_echo!("b")]"#),
+            Ok(value!(["a", "b"]))
+        );
+    }
+
+    #[test]
+    fn converts_values() {
+        let value = value!({
+            decimal: (rust_decimal::Decimal::new(1250, 2)),
+            nested: { array: [[], {}, [null]] },
+            timestamp: (DateTime::from_timestamp(-1, 5).unwrap()),
+            duration: (TimeDelta::nanoseconds(-1)),
+        });
+        let mut nodes = Vec::new();
+        to_nodes(&value, &mut nodes).unwrap();
+        assert_eq!(from_nodes(&nodes), Ok(value));
+
+        assert_eq!(
+            from_nodes(&[Node::Array(vec![0])]),
+            Err("node 0 refers to node 0 before it".to_owned())
+        );
+        assert_eq!(
+            from_nodes(&[Node::Array(vec![1, 1]), Node::Null]),
+            Err("node 1 is used more than once".to_owned())
+        );
+        assert_eq!(
+            from_nodes(&[Node::Array(vec![2]), Node::Null]),
+            Err("node 2 does not exist".to_owned())
+        );
+        assert_eq!(from_nodes(&[]), Err("node 0 does not exist".to_owned()));
+        let nested = |depth: u32| {
+            (1..=depth)
+                .map(|child| Node::Array(vec![child]))
+                .chain([Node::Null])
+                .collect::<Vec<_>>()
+        };
+        assert!(from_nodes(&nested(128)).is_ok());
+        assert_eq!(
+            from_nodes(&nested(129)),
+            Err("node 129 is nested more than 128 levels deep".to_owned())
+        );
+        assert_eq!(
+            from_nodes(&[Node::Float(f64::NAN)]),
+            Err("NaN is not a valid float".to_owned())
+        );
+    }
+
+    #[test]
+    fn surfaces_errors() {
+        assert_eq!(
+            run("wasm_fail!()"),
+            Err(r#"function call error for "wasm_fail" at (0:12): the plugin failed"#.to_owned())
+        );
+        assert_eq!(
+            run("wasm_spin!()"),
+            Err(r#"function call error for "wasm_spin" at (0:12): plugin function `wasm_spin` failed: the plugin ran out of fuel"#.to_owned())
+        );
+        assert_eq!(
+            run(r#"wasm_spin() ?? wasm_echo!("recovered")"#),
+            Ok(value!("recovered"))
+        );
+    }
+
+    #[test]
+    fn replaces_trapped_instances() {
+        assert_eq!(
+            run("[wasm_count!(), wasm_count!(), wasm_spin() ?? 0, wasm_count!()]"),
+            Ok(value!([1, 2, 0, 1]))
+        );
+    }
+
+    #[test]
+    fn limits_memory() {
+        assert_eq!(
+            run("wasm_grow!()"),
+            Err(
+                r#"function call error for "wasm_grow" at (0:12): the plugin is out of memory"#
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            run_with(&plugin(8 * MEMORY), "wasm_grow!()"),
+            Ok(Value::Null)
+        );
+    }
+
+    #[test]
+    fn calls_from_threads() {
+        let plugin = plugin(MEMORY);
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let plugin = &plugin;
+                scope.spawn(move || {
+                    for call in 0..10 {
+                        assert_eq!(
+                            run_with(plugin, &format!("wasm_echo!([{thread}, {call}])")),
+                            Ok(value!([thread, call]))
+                        );
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn rejects_invalid_plugins() {
+        assert!(matches!(
+            WasmPlugin::load(b"not wasm", FUEL, MEMORY),
+            Err(WasmPluginError::Load(_))
+        ));
+        assert!(matches!(
+            WasmPlugin::load(&wat::parse_str("(module)").unwrap(), FUEL, MEMORY),
+            Err(WasmPluginError::Load(_))
+        ));
+        assert!(matches!(
+            WasmPlugin::load(&wat::parse_str("(component)").unwrap(), FUEL, MEMORY),
+            Err(WasmPluginError::World(_))
+        ));
+    }
+}
@@ -100,9 +100,9 @@ impl Runtime {
         timezone: &TimeZone,
     ) -> RuntimeResult {
         // Validate that the path is a value.
-        match target.target_get(&OwnedTargetPath::event_root()) {
-            Ok(Some(_)) => {}
-            Ok(None) => {
+        match target.target_contains(&OwnedTargetPath::event_root()) {
+            Ok(true) => {}
+            Ok(false) => {
                 return Err(Terminate::Error(
                     "expected target object, got nothing".to_owned().into(),
                 ));
@@ -3,8 +3,15 @@ use std::convert::AsRef;
 
 use crate::path::OwnedTargetPath;
 use crate::path::PathPrefix;
+use crate::path::{OwnedSegment, OwnedValuePath};
 use crate::value::{Secrets, Value};
 
+mod json;
+mod view;
+
+pub use json::TargetJson;
+pub use view::{TargetJsonView, TargetValueView};
+
 /// Any target object you want to remap using VRL has to implement this trait.
 pub trait Target: std::fmt::Debug + SecretTarget {
     /// Insert a given [`Value`] in the provided [`Target`].
@@ -45,6 +52,40 @@ pub trait Target: std::fmt::Debug + SecretTarget {
     /// Error indicating retrieval failure.
     fn target_get(&self, path: &OwnedTargetPath) -> Result<Option<&Value>, String>;
 
+    /// Get a value for a path that can contain wildcard and slice segments, or `None` if no
+    /// value is found.
+    ///
+    /// The values matched by wildcard and slice segments are collected into arrays, as done by
+    /// [`Value::project`].
+    ///
+    /// # Errors
+    /// Error indicating retrieval failure.
+    fn target_project(&self, path: &OwnedTargetPath) -> Result<Option<Value>, String> {
+        let segments = &path.path.segments;
+        let Some(position) = segments.iter().position(OwnedSegment::matches_many) else {
+            return Ok(self.target_get(path)?.cloned());
+        };
+
+        let parent = OwnedTargetPath {
+            prefix: path.prefix,
+            path: OwnedValuePath::from(segments[..position].to_vec()),
+        };
+        Ok(self
+            .target_get(&parent)?
+            .and_then(|value| value.project(&segments[position..])))
+    }
+
+    /// Returns whether there is a value at the given path.
+    ///
+    /// Targets that materialize values lazily can override this to check for the value
+    /// without materializing it.
+    ///
+    /// # Errors
+    /// Error indicating retrieval failure.
+    fn target_contains(&self, path: &OwnedTargetPath) -> Result<bool, String> {
+        Ok(self.target_get(path)?.is_some())
+    }
+
     /// Get a mutable reference to the value for a given path, or `None` if no
     /// value is found.
     ///
@@ -159,7 +200,7 @@ impl SecretTarget for TargetValueRef<'_> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TargetValue {
     pub value: Value,
     pub metadata: Value,
@@ -0,0 +1,650 @@
+use std::fmt;
+use std::io;
+use std::ops::Index;
+
+use elsa::FrozenMap;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::value::RawValue;
+
+use super::view::json_get;
+use super::{SecretTarget, Target};
+use crate::compiler::ProgramInfo;
+use crate::path::{OwnedSegment, OwnedTargetPath, OwnedValuePath, PathPrefix};
+use crate::value::{KeyString, ObjectMap, Secrets, Value};
+
+/// A [`Target`] over a JSON encoded event, which only parses the parts of the event a program
+/// reads or changes.
+///
+/// Objects are split into their fields as paths into them are accessed, and the values at the
+/// paths queried by the program, as listed by [`ProgramInfo::target_queries`], are parsed when
+/// the target is created. Reading other paths parses the value at the path on the first read, and
+/// keeps it for later reads of the path until the event is changed.
+///
+/// When the event is written back with [`TargetJson::write_json`], the parts the program did not
+/// touch are copied verbatim.
+pub struct TargetJson<'a> {
+    root: Node<'a>,
+    /// The values read from paths which were not parsed in place.
+    values: FrozenMap<OwnedValuePath, Box<Value>>,
+    pub metadata: Value,
+    pub secrets: Secrets,
+}
+
+impl<'a> TargetJson<'a> {
+    /// Creates a target over the JSON encoded event, with empty metadata and secrets, parsing
+    /// the paths of the event queried by the program.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event is not valid JSON.
+    pub fn new(json: &'a [u8], info: &ProgramInfo) -> Result<Self, serde_json::Error> {
+        let mut root = Node::Raw(serde_json::from_slice(json)?);
+        for path in &info.target_queries {
+            if path.prefix == PathPrefix::Event {
+                root.decode(&path.path.segments)?;
+            }
+        }
+
+        Ok(Self {
+            root,
+            values: FrozenMap::new(),
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: Secrets::new(),
+        })
+    }
+
+    /// Writes the event as JSON, copying the parts of the event that were not changed verbatim.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_json(&self, writer: impl io::Write) -> Result<(), serde_json::Error> {
+        serde_json::to_writer(writer, &self.root)
+    }
+
+    /// Parses the whole event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event is not valid JSON.
+    pub fn into_value(self) -> Result<Value, serde_json::Error> {
+        self.root.into_value()
+    }
+}
+
+impl fmt::Debug for TargetJson<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TargetJson")
+            .field("root", &self.root)
+            .field("metadata", &self.metadata)
+            .field("secrets", &self.secrets)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Target for TargetJson<'_> {
+    fn target_insert(&mut self, target_path: &OwnedTargetPath, value: Value) -> Result<(), String> {
+        match target_path.prefix {
+            PathPrefix::Event => {
+                self.values = FrozenMap::new();
+                self.root
+                    .insert(&target_path.path.segments, value)
+                    .map_err(|e| e.to_string())
+            }
+            PathPrefix::Metadata => {
+                self.metadata.insert(&target_path.path, value);
+                Ok(())
+            }
+        }
+    }
+
+    fn target_get(&self, target_path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+        match target_path.prefix {
+            PathPrefix::Event => {
+                if let Some(value) = self.values.get(&target_path.path) {
+                    return Ok(Some(value));
+                }
+                let value = match self.root.get(&target_path.path.segments)? {
+                    Some(Found::Value(value)) => return Ok(Some(value)),
+                    Some(Found::Node(node)) => node.to_value(),
+                    Some(Found::Raw(json)) => serde_json::from_str(json.get()),
+                    None => return Ok(None),
+                }
+                .map_err(|e| e.to_string())?;
+                Ok(Some(
+                    self.values
+                        .insert(target_path.path.clone(), Box::new(value)),
+                ))
+            }
+            PathPrefix::Metadata => Ok(self.metadata.get(&target_path.path)),
+        }
+    }
+
+    fn target_contains(&self, target_path: &OwnedTargetPath) -> Result<bool, String> {
+        match target_path.prefix {
+            PathPrefix::Event => Ok(self.root.get(&target_path.path.segments)?.is_some()),
+            PathPrefix::Metadata => Ok(self.metadata.get(&target_path.path).is_some()),
+        }
+    }
+
+    fn target_get_mut(
+        &mut self,
+        target_path: &OwnedTargetPath,
+    ) -> Result<Option<&mut Value>, String> {
+        match target_path.prefix {
+            PathPrefix::Event => {
+                self.values = FrozenMap::new();
+                self.root
+                    .get_mut(&target_path.path.segments)
+                    .map_err(|e| e.to_string())
+            }
+            PathPrefix::Metadata => Ok(self.metadata.get_mut(&target_path.path)),
+        }
+    }
+
+    fn target_remove(
+        &mut self,
+        target_path: &OwnedTargetPath,
+        compact: bool,
+    ) -> Result<Option<Value>, String> {
+        match target_path.prefix {
+            PathPrefix::Event => {
+                self.values = FrozenMap::new();
+                self.root
+                    .remove(&target_path.path.segments, compact)
+                    .map_err(|e| e.to_string())
+            }
+            PathPrefix::Metadata => Ok(self.metadata.remove(&target_path.path, compact)),
+        }
+    }
+}
+
+impl SecretTarget for TargetJson<'_> {
+    fn get_secret(&self, key: &str) -> Option<&str> {
+        self.secrets.get_secret(key)
+    }
+
+    fn insert_secret(&mut self, key: &str, value: &str) {
+        self.secrets.insert_secret(key, value);
+    }
+
+    fn remove_secret(&mut self, key: &str) {
+        self.secrets.remove_secret(key);
+    }
+}
+
+/// A part of the event, parsed as far as it was accessed.
+///
+/// Only objects are split into their fields. Going into any other value, or changing it,
+/// parses the whole value.
+#[derive(Debug)]
+enum Node<'a> {
+    Raw(&'a RawValue),
+    Object(Fields<'a>),
+    Value(Value),
+}
+
+/// The fields of a split object, in the order of the JSON. Duplicate keys are kept, so that an
+/// object is written back as it was, but as when parsing the whole object, the last of them wins.
+#[derive(Debug)]
+struct Fields<'a>(Vec<(KeyString, Node<'a>)>);
+
+impl<'a> Fields<'a> {
+    fn get(&self, key: &str) -> Option<&Node<'a>> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(field, _)| field == key)
+            .map(|(_, node)| node)
+    }
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Node<'a>> {
+        self.0
+            .iter_mut()
+            .rev()
+            .find(|(field, _)| field == key)
+            .map(|(_, node)| node)
+    }
+
+    /// Returns the node of the field, adding it after the others as null if it is missing.
+    fn get_or_insert(&mut self, key: &KeyString) -> &mut Node<'a> {
+        let position = self.0.iter().rposition(|(field, _)| field == key);
+        let position = position.unwrap_or_else(|| {
+            self.0.push((key.clone(), Node::Value(Value::Null)));
+            self.0.len() - 1
+        });
+        &mut self.0[position].1
+    }
+
+    /// Removes all the fields with the key, returning the last of them.
+    fn remove(&mut self, key: &str) -> Option<Node<'a>> {
+        let mut removed = None;
+        let mut position = 0;
+        while position < self.0.len() {
+            if self.0[position].0 == *key {
+                removed = Some(self.0.remove(position).1);
+            } else {
+                position += 1;
+            }
+        }
+        removed
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a> Index<&str> for Fields<'a> {
+    type Output = Node<'a>;
+
+    fn index(&self, key: &str) -> &Node<'a> {
+        self.get(key)
+            .unwrap_or_else(|| panic!("no field named {key:?}"))
+    }
+}
+
+/// Collects the fields of a JSON object without parsing their values.
+struct FieldsVisitor;
+
+impl<'de> Visitor<'de> for FieldsVisitor {
+    type Value = Fields<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut fields = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some((key, json)) = map.next_entry::<KeyString, &'de RawValue>()? {
+            fields.push((key, Node::Raw(json)));
+        }
+        Ok(Fields(fields))
+    }
+}
+
+/// What a path points to: a parsed value, a partly parsed node, or JSON inside a raw node.
+enum Found<'n, 'a> {
+    Value(&'n Value),
+    Node(&'n Node<'a>),
+    Raw(&'a RawValue),
+}
+
+impl<'a> Node<'a> {
+    /// Splits a raw object into its fields, leaving other values as they are.
+    fn split(&mut self) -> Result<(), serde_json::Error> {
+        if let Node::Raw(json) = self
+            && json.get().starts_with('{')
+        {
+            *self = Node::Object(
+                serde_json::Deserializer::from_str(json.get()).deserialize_map(FieldsVisitor)?,
+            );
+        }
+        Ok(())
+    }
+
+    /// Parses the node into a value in place.
+    fn materialize(&mut self) -> Result<&mut Value, serde_json::Error> {
+        if !matches!(self, Node::Value(_)) {
+            let node = std::mem::replace(self, Node::Value(Value::Null));
+            *self = Node::Value(node.into_value()?);
+        }
+        match self {
+            Node::Value(value) => Ok(value),
+            _ => unreachable!("node was materialized"),
+        }
+    }
+
+    fn to_value(&self) -> Result<Value, serde_json::Error> {
+        match self {
+            Node::Raw(json) => serde_json::from_str(json.get()),
+            Node::Object(fields) => fields
+                .0
+                .iter()
+                .map(|(key, node)| Ok((key.clone(), node.to_value()?)))
+                .collect::<Result<ObjectMap, _>>()
+                .map(Value::Object),
+            Node::Value(value) => Ok(value.clone()),
+        }
+    }
+
+    fn into_value(self) -> Result<Value, serde_json::Error> {
+        match self {
+            Node::Raw(json) => serde_json::from_str(json.get()),
+            Node::Object(fields) => fields
+                .0
+                .into_iter()
+                .map(|(key, node)| Ok((key, node.into_value()?)))
+                .collect::<Result<ObjectMap, _>>()
+                .map(Value::Object),
+            Node::Value(value) => Ok(value),
+        }
+    }
+
+    fn is_empty_collection(&self) -> bool {
+        match self {
+            Node::Raw(json) => {
+                let json = json.get();
+                json.starts_with(['{', '['])
+                    && json
+                        .chars()
+                        .skip(1)
+                        .find(|c| !c.is_ascii_whitespace())
+                        .is_some_and(|c| c == '}' || c == ']')
+            }
+            Node::Object(fields) => fields.is_empty(),
+            Node::Value(Value::Object(object)) => object.is_empty(),
+            Node::Value(Value::Array(array)) => array.is_empty(),
+            Node::Value(_) => false,
+        }
+    }
+
+    /// Splits the objects along the path, and parses the value at the path.
+    fn decode(&mut self, segments: &[OwnedSegment]) -> Result<(), serde_json::Error> {
+        match segments.split_first() {
+            None | Some((OwnedSegment::Wildcard | OwnedSegment::Slice { .. }, _)) => {
+                self.materialize()?;
+            }
+            Some((OwnedSegment::Field(field), rest)) => {
+                self.split()?;
+                if let Node::Object(fields) = self
+                    && let Some(node) = fields.get_mut(field)
+                {
+                    node.decode(rest)?;
+                }
+            }
+            Some((OwnedSegment::Index(_), _)) => {
+                if let Node::Raw(json) = self
+                    && json.get().starts_with('[')
+                {
+                    self.materialize()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get(&self, segments: &[OwnedSegment]) -> Result<Option<Found<'_, 'a>>, String> {
+        match (self, segments.split_first()) {
+            (Node::Value(value), _) => Ok(value.get(segments).map(Found::Value)),
+            (node, None) => Ok(Some(Found::Node(node))),
+            (Node::Object(fields), Some((OwnedSegment::Field(field), rest))) => {
+                match fields.get(field) {
+                    Some(node) => node.get(rest),
+                    None => Ok(None),
+                }
+            }
+            (
+                Node::Object(_),
+                Some((
+                    OwnedSegment::Index(_) | OwnedSegment::Wildcard | OwnedSegment::Slice { .. },
+                    _,
+                )),
+            ) => Ok(None),
+            (Node::Raw(json), Some(_)) => Ok(json_get(json, segments)?.map(Found::Raw)),
+        }
+    }
+
+    fn insert(&mut self, segments: &[OwnedSegment], value: Value) -> Result<(), serde_json::Error> {
+        match segments.split_first() {
+            None => *self = Node::Value(value),
+            Some((OwnedSegment::Field(field), rest)) => {
+                self.split()?;
+                if let Node::Object(fields) = self {
+                    fields.get_or_insert(field).insert(rest, value)?;
+                } else {
+                    self.materialize()?.insert(segments, value);
+                }
+            }
+            Some((
+                OwnedSegment::Index(_) | OwnedSegment::Wildcard | OwnedSegment::Slice { .. },
+                _,
+            )) => {
+                self.materialize()?.insert(segments, value);
+            }
+        }
+        Ok(())
+    }
+
+    fn get_mut(
+        &mut self,
+        segments: &[OwnedSegment],
+    ) -> Result<Option<&mut Value>, serde_json::Error> {
+        if let Some((OwnedSegment::Field(field), rest)) = segments.split_first() {
+            self.split()?;
+            if let Node::Object(fields) = self {
+                return match fields.get_mut(field) {
+                    Some(node) => node.get_mut(rest),
+                    None => Ok(None),
+                };
+            }
+        }
+        Ok(self.materialize()?.get_mut(segments))
+    }
+
+    fn remove(
+        &mut self,
+        segments: &[OwnedSegment],
+        compact: bool,
+    ) -> Result<Option<Value>, serde_json::Error> {
+        if let Some((OwnedSegment::Field(field), rest)) = segments.split_first() {
+            self.split()?;
+            if let Node::Object(fields) = self {
+                if rest.is_empty() {
+                    return fields.remove(field).map(Node::into_value).transpose();
+                }
+                let Some(node) = fields.get_mut(field) else {
+                    return Ok(None);
+                };
+                let removed = node.remove(rest, compact)?;
+                if compact && removed.is_some() && node.is_empty_collection() {
+                    fields.remove(field);
+                }
+                return Ok(removed);
+            }
+        }
+        Ok(self.materialize()?.remove(segments, compact))
+    }
+}
+
+impl Serialize for Node<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Node::Raw(json) => json.serialize(serializer),
+            Node::Object(fields) => {
+                let mut map = serializer.serialize_map(Some(fields.0.len()))?;
+                for (key, node) in &fields.0 {
+                    map.serialize_entry(key, node)?;
+                }
+                map.end()
+            }
+            Node::Value(value) => value.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::runtime::Runtime;
+    use crate::compiler::state::RuntimeState;
+    use crate::compiler::{CompileConfig, TimeZone, TypeState, compile_with_state};
+    use crate::owned_value_path;
+    use crate::value;
+
+    const EVENT: &[u8] = br#"{"message": "hello", "kept": {"n": 1.50, "s": "x"},
+        "nested": {"a": {"b": 1, "c": [1, 2]}, "d": {}}, "list": [1, {"x": true}]}"#;
+
+    fn run<'a>(source: &str, json: &'a [u8]) -> (Result<Value, String>, TargetJson<'a>) {
+        let program =
+            compile_with_state(source, &[], &TypeState::default(), CompileConfig::default())
+                .map_err(|diagnostics| diagnostics.first().unwrap().message.clone())
+                .unwrap()
+                .program;
+        let mut target = TargetJson::new(json, program.info()).unwrap();
+        let result = Runtime::new(RuntimeState::default())
+            .resolve(&mut target, &program, &TimeZone::default())
+            .map_err(|terminate| terminate.to_string());
+        (result, target)
+    }
+
+    fn json(target: &TargetJson<'_>) -> String {
+        let mut json = Vec::new();
+        target.write_json(&mut json).unwrap();
+        String::from_utf8(json).unwrap()
+    }
+
+    #[test]
+    fn parses_queried_paths() {
+        let (result, target) = run(".nested.a.b == 1 && .list[1].x == true", EVENT);
+        assert_eq!(result, Ok(value!(true)));
+
+        let Node::Object(root) = &target.root else {
+            panic!("root not split: {:?}", target.root);
+        };
+        assert!(matches!(root["message"], Node::Raw(_)));
+        assert!(matches!(root["list"], Node::Value(_)));
+        let Node::Object(nested) = &root["nested"] else {
+            panic!("nested not split: {:?}", root["nested"]);
+        };
+        assert!(matches!(nested["d"], Node::Raw(_)));
+        let Node::Object(a) = &nested["a"] else {
+            panic!("a not split: {:?}", nested["a"]);
+        };
+        assert!(matches!(a["b"], Node::Value(Value::Integer(1))));
+        assert!(matches!(a["c"], Node::Raw(_)));
+    }
+
+    #[test]
+    fn writes_untouched_parts_verbatim() {
+        let (result, target) = run(r#".message, _err = .message + "!"; .nested.a.b = 2"#, EVENT);
+        assert_eq!(result, Ok(value!(2)));
+        assert_eq!(
+            json(&target),
+            r#"{"message":"hello!","kept":{"n": 1.50, "s": "x"},"nested":{"a":{"b":2,"c":[1, 2]},"d":{}},"list":[1, {"x": true}]}"#
+        );
+    }
+
+    #[test]
+    fn keeps_duplicate_fields() {
+        let event = br#"{"z": 1, "a": {"k": 1, "k": 2}, "z": 3}"#;
+        let (result, mut target) = run(".a.new = .a.k; .y = .z", event);
+        assert_eq!(result, Ok(value!(3)));
+        assert_eq!(
+            target.target_remove(&OwnedTargetPath::event(owned_value_path!("z")), false),
+            Ok(Some(value!(3)))
+        );
+        assert_eq!(json(&target), r#"{"a":{"k":1,"k":2,"new":2},"y":3}"#);
+    }
+
+    #[test]
+    fn reads_unplanned_paths() {
+        let target = TargetJson::new(
+            EVENT,
+            &ProgramInfo {
+                fallible: false,
+                abortable: false,
+                target_queries: vec![],
+                target_assignments: vec![],
+            },
+        )
+        .unwrap();
+
+        let get = |path| {
+            target
+                .target_get(&OwnedTargetPath::event(path))
+                .unwrap()
+                .cloned()
+        };
+        assert_eq!(
+            get(owned_value_path!("nested", "a", "c", -1)),
+            Some(value!(2))
+        );
+        assert_eq!(get(owned_value_path!("kept", "s")), Some(value!("x")));
+        assert_eq!(get(owned_value_path!("kept", "missing")), None);
+        assert!(matches!(target.root, Node::Raw(_)));
+    }
+
+    #[test]
+    fn keeps_read_values_until_changed() {
+        let mut target = TargetJson::new(
+            EVENT,
+            &ProgramInfo {
+                fallible: false,
+                abortable: false,
+                target_queries: vec![],
+                target_assignments: vec![],
+            },
+        )
+        .unwrap();
+        let path = OwnedTargetPath::event(owned_value_path!("kept", "n"));
+
+        let first: *const Value = target.target_get(&path).unwrap().unwrap();
+        let second: *const Value = target.target_get(&path).unwrap().unwrap();
+        assert_eq!(first, second);
+
+        target.target_insert(&path, value!(2)).unwrap();
+        assert_eq!(target.target_get(&path).unwrap(), Some(&value!(2)));
+        target
+            .target_insert(
+                &OwnedTargetPath::event(owned_value_path!("kept")),
+                value!({}),
+            )
+            .unwrap();
+        assert_eq!(target.target_get(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn matches_value_target() {
+        let mut expected: Value = serde_json::from_slice(EVENT).unwrap();
+        let mut target = TargetJson::new(
+            EVENT,
+            &ProgramInfo {
+                fallible: false,
+                abortable: false,
+                target_queries: vec![],
+                target_assignments: vec![],
+            },
+        )
+        .unwrap();
+
+        let inserts = [
+            (owned_value_path!("nested", "new", "field"), value!(1)),
+            (owned_value_path!("list", 3), value!("x")),
+            (owned_value_path!("message", "inner"), value!(true)),
+            (owned_value_path!("kept", "n"), value!(2)),
+        ];
+        for (path, value) in inserts {
+            expected.insert(&path, value.clone());
+            target
+                .target_insert(&OwnedTargetPath::event(path), value)
+                .unwrap();
+        }
+
+        let removals = [
+            (owned_value_path!("nested", "a", "b"), false),
+            (owned_value_path!("nested", "a", "c", 0), true),
+            (owned_value_path!("nested", "d", "missing"), true),
+            (owned_value_path!("kept", "s"), true),
+            (owned_value_path!("kept", "n"), true),
+        ];
+        for (path, compact) in removals {
+            assert_eq!(
+                target
+                    .target_remove(&OwnedTargetPath::event(path.clone()), compact)
+                    .unwrap(),
+                expected.remove(&path, compact),
+                "{path}"
+            );
+        }
+
+        *target
+            .target_get_mut(&OwnedTargetPath::event(owned_value_path!("list", 0)))
+            .unwrap()
+            .unwrap() = value!(0);
+        *expected.get_mut(&owned_value_path!("list", 0)).unwrap() = value!(0);
+
+        assert_eq!(target.into_value().unwrap(), expected);
+    }
+}
@@ -0,0 +1,411 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use elsa::FrozenMap;
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::value::RawValue;
+
+use super::{SecretTarget, Target};
+use crate::path::{BorrowedSegment, OwnedTargetPath, OwnedValuePath, PathPrefix, ValuePath};
+use crate::value::{ObjectMap, Secrets, Value};
+
+const READ_ONLY: &str = "the target is read-only";
+
+/// A read-only [`Target`] borrowing the event and metadata, to run programs compiled with
+/// [`CompileConfig::set_read_only`](crate::compiler::CompileConfig::set_read_only) without
+/// cloning the event.
+///
+/// Inserting or removing values fails. Secrets can still be changed, as they are cloned on the
+/// first change.
+#[derive(Debug, Clone)]
+pub struct TargetValueView<'a> {
+    pub value: &'a Value,
+    pub metadata: &'a Value,
+    pub secrets: Cow<'a, Secrets>,
+}
+
+impl Target for TargetValueView<'_> {
+    fn target_insert(&mut self, _: &OwnedTargetPath, _: Value) -> Result<(), String> {
+        Err(READ_ONLY.to_owned())
+    }
+
+    fn target_get(&self, target_path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+        let value = match target_path.prefix {
+            PathPrefix::Event => self.value.get(&target_path.path),
+            PathPrefix::Metadata => self.metadata.get(&target_path.path),
+        };
+        Ok(value)
+    }
+
+    fn target_get_mut(&mut self, _: &OwnedTargetPath) -> Result<Option<&mut Value>, String> {
+        Err(READ_ONLY.to_owned())
+    }
+
+    fn target_remove(&mut self, _: &OwnedTargetPath, _: bool) -> Result<Option<Value>, String> {
+        Err(READ_ONLY.to_owned())
+    }
+}
+
+impl SecretTarget for TargetValueView<'_> {
+    fn get_secret(&self, key: &str) -> Option<&str> {
+        self.secrets.get_secret(key)
+    }
+
+    fn insert_secret(&mut self, key: &str, value: &str) {
+        self.secrets.to_mut().insert_secret(key, value);
+    }
+
+    fn remove_secret(&mut self, key: &str) {
+        self.secrets.to_mut().remove_secret(key);
+    }
+}
+
+/// A read-only [`Target`] over a JSON encoded event, to run programs compiled with
+/// [`CompileConfig::set_read_only`](crate::compiler::CompileConfig::set_read_only) without
+/// parsing the whole event.
+///
+/// The JSON is only validated when the view is created. The first time the program reads a path
+/// of the event, only the value at that path is parsed, and it is kept for later reads of the
+/// path until the view is dropped.
+pub struct TargetJsonView<'a> {
+    json: &'a RawValue,
+    values: FrozenMap<OwnedValuePath, Box<Value>>,
+    pub metadata: Cow<'a, Value>,
+    pub secrets: Cow<'a, Secrets>,
+}
+
+impl<'a> TargetJsonView<'a> {
+    /// Creates a view over the JSON encoded event, with empty metadata and secrets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event is not valid JSON.
+    pub fn new(json: &'a [u8]) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            json: serde_json::from_slice(json)?,
+            values: FrozenMap::new(),
+            metadata: Cow::Owned(Value::Object(ObjectMap::new())),
+            secrets: Cow::Owned(Secrets::new()),
+        })
+    }
+
+    /// Returns the JSON encoded value at the path of the event, or `None` if there is no value
+    /// at the path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event is not valid JSON at the path.
+    pub fn get_raw<'p>(&self, path: impl ValuePath<'p>) -> Result<Option<&'a RawValue>, String> {
+        json_get(self.json, path)
+    }
+}
+
+impl fmt::Debug for TargetJsonView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TargetJsonView")
+            .field("json", &self.json)
+            .field("metadata", &self.metadata)
+            .field("secrets", &self.secrets)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Target for TargetJsonView<'_> {
+    fn target_insert(&mut self, _: &OwnedTargetPath, _: Value) -> Result<(), String> {
+        Err(READ_ONLY.to_owned())
+    }
+
+    fn target_get(&self, target_path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+        match target_path.prefix {
+            PathPrefix::Event => {
+                if let Some(value) = self.values.get(&target_path.path) {
+                    return Ok(Some(value));
+                }
+                match self.get_raw(&target_path.path)? {
+                    Some(json) => {
+                        let value = serde_json::from_str(json.get()).map_err(|e| e.to_string())?;
+                        Ok(Some(
+                            self.values
+                                .insert(target_path.path.clone(), Box::new(value)),
+                        ))
+                    }
+                    None => Ok(None),
+                }
+            }
+            PathPrefix::Metadata => Ok(self.metadata.get(&target_path.path)),
+        }
+    }
+
+    fn target_contains(&self, target_path: &OwnedTargetPath) -> Result<bool, String> {
+        match target_path.prefix {
+            PathPrefix::Event => Ok(self.get_raw(&target_path.path)?.is_some()),
+            PathPrefix::Metadata => Ok(self.metadata.get(&target_path.path).is_some()),
+        }
+    }
+
+    fn target_get_mut(&mut self, _: &OwnedTargetPath) -> Result<Option<&mut Value>, String> {
+        Err(READ_ONLY.to_owned())
+    }
+
+    fn target_remove(&mut self, _: &OwnedTargetPath, _: bool) -> Result<Option<Value>, String> {
+        Err(READ_ONLY.to_owned())
+    }
+}
+
+impl SecretTarget for TargetJsonView<'_> {
+    fn get_secret(&self, key: &str) -> Option<&str> {
+        self.secrets.get_secret(key)
+    }
+
+    fn insert_secret(&mut self, key: &str, value: &str) {
+        self.secrets.to_mut().insert_secret(key, value);
+    }
+
+    fn remove_secret(&mut self, key: &str) {
+        self.secrets.to_mut().remove_secret(key);
+    }
+}
+
+/// Returns the JSON encoded value at the path, or `None` if there is no value at the path.
+#[allow(clippy::needless_pass_by_value)]
+pub(super) fn json_get<'a, 'p>(
+    mut json: &'a RawValue,
+    path: impl ValuePath<'p>,
+) -> Result<Option<&'a RawValue>, String> {
+    for segment in path.segment_iter() {
+        let next = match segment {
+            BorrowedSegment::Field(field) => json_field(json, &field),
+            BorrowedSegment::Index(index) => json_index(json, index),
+            BorrowedSegment::Wildcard
+            | BorrowedSegment::Slice { .. }
+            | BorrowedSegment::Invalid => {
+                return Ok(None);
+            }
+        };
+        match next.map_err(|e| e.to_string())? {
+            Some(next) => json = next,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(json))
+}
+
+/// Returns the value of the field of a JSON object, skipping over the other fields without
+/// parsing them, or `None` if the value is not an object or has no such field.
+fn json_field<'a>(
+    json: &'a RawValue,
+    field: &str,
+) -> Result<Option<&'a RawValue>, serde_json::Error> {
+    struct FieldVisitor<'f>(&'f str);
+
+    impl<'de> Visitor<'de> for FieldVisitor<'_> {
+        type Value = Option<&'de RawValue>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an object")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            // As when parsing the whole object, the last of duplicate fields wins.
+            let mut found = None;
+            while let Some(matches) = map.next_key_seed(KeyMatches(self.0))? {
+                if matches {
+                    found = Some(map.next_value()?);
+                } else {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+            Ok(found)
+        }
+    }
+
+    if !json.get().starts_with('{') {
+        return Ok(None);
+    }
+    serde_json::Deserializer::from_str(json.get()).deserialize_map(FieldVisitor(field))
+}
+
+/// Compares an object key to the field, without allocating unless the key has escapes.
+struct KeyMatches<'f>(&'f str);
+
+impl<'de> DeserializeSeed<'de> for KeyMatches<'_> {
+    type Value = bool;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for KeyMatches<'_> {
+    type Value = bool;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, key: &str) -> Result<bool, E> {
+        Ok(key == self.0)
+    }
+}
+
+/// Returns the element of a JSON array at the index, counting from the end if it is negative,
+/// or `None` if the value is not an array or the index is out of bounds.
+fn json_index(json: &RawValue, index: isize) -> Result<Option<&RawValue>, serde_json::Error> {
+    struct ElementsVisitor;
+
+    impl<'de> Visitor<'de> for ElementsVisitor {
+        type Value = Vec<&'de RawValue>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an array")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+            while let Some(element) = seq.next_element()? {
+                elements.push(element);
+            }
+            Ok(elements)
+        }
+    }
+
+    if !json.get().starts_with('[') {
+        return Ok(None);
+    }
+    let elements =
+        serde_json::Deserializer::from_str(json.get()).deserialize_seq(ElementsVisitor)?;
+    let index = if index < 0 {
+        elements.len().checked_sub(index.unsigned_abs())
+    } else {
+        Some(index.unsigned_abs())
+    };
+    Ok(index.and_then(|index| elements.get(index).copied()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::runtime::Runtime;
+    use crate::compiler::state::RuntimeState;
+    use crate::compiler::{CompileConfig, TimeZone, TypeState, compile_with_state};
+    use crate::owned_value_path;
+    use crate::value;
+
+    const EVENT: &[u8] = br#"{
+        "message": "hello",
+        "tags": ["a", {"b": [1, 2]}],
+        "na\u006de": "escaped",
+        "dup": 1,
+        "dup": 2,
+        "nested": {"deep": {"value": true}}
+    }"#;
+
+    fn raw(view: &TargetJsonView<'_>, path: &str) -> Option<String> {
+        view.get_raw(path)
+            .unwrap()
+            .map(|json| json.get().to_owned())
+    }
+
+    #[test]
+    fn json_paths() {
+        let view = TargetJsonView::new(EVENT).unwrap();
+
+        assert_eq!(raw(&view, "message").as_deref(), Some(r#""hello""#));
+        assert_eq!(raw(&view, "tags[0]").as_deref(), Some(r#""a""#));
+        assert_eq!(raw(&view, "tags[-1].b[1]").as_deref(), Some("2"));
+        assert_eq!(raw(&view, "tags[-3]"), None);
+        assert_eq!(raw(&view, "tags[2]"), None);
+        assert_eq!(raw(&view, "name").as_deref(), Some(r#""escaped""#));
+        assert_eq!(raw(&view, "dup").as_deref(), Some("2"));
+        assert_eq!(
+            raw(&view, r#"nested."deep".value"#).as_deref(),
+            Some("true")
+        );
+        assert_eq!(raw(&view, "message.length"), None);
+        assert_eq!(raw(&view, "message[0]"), None);
+        assert_eq!(raw(&view, "missing"), None);
+    }
+
+    #[test]
+    fn json_target() {
+        let metadata = value!({ source: "kafka" });
+        let mut view = TargetJsonView::new(EVENT).unwrap();
+        view.metadata = Cow::Borrowed(&metadata);
+
+        assert_eq!(
+            view.target_get(&OwnedTargetPath::event(owned_value_path!("tags", 1)))
+                .unwrap(),
+            Some(&value!({ b: [1, 2] }))
+        );
+        assert_eq!(
+            view.target_get(&OwnedTargetPath::metadata(owned_value_path!("source")))
+                .unwrap(),
+            Some(&value!("kafka"))
+        );
+        assert!(
+            view.target_contains(&OwnedTargetPath::event_root())
+                .unwrap()
+        );
+        assert_eq!(
+            view.target_insert(&OwnedTargetPath::event_root(), value!(1)),
+            Err(READ_ONLY.to_owned())
+        );
+        assert!(TargetJsonView::new(b"{").is_err());
+
+        let path = OwnedTargetPath::event(owned_value_path!("nested", "deep"));
+        let first: *const Value = view.target_get(&path).unwrap().unwrap();
+        let second: *const Value = view.target_get(&path).unwrap().unwrap();
+        assert_eq!(first, second);
+    }
+
+    fn run(source: &str, target: &mut dyn Target) -> Result<Value, String> {
+        let mut config = CompileConfig::default();
+        config.set_read_only();
+        let program = compile_with_state(source, &[], &TypeState::default(), config)
+            .map_err(|diagnostics| diagnostics.first().unwrap().message.clone())?
+            .program;
+        Runtime::new(RuntimeState::default())
+            .resolve(target, &program, &TimeZone::default())
+            .map_err(|terminate| terminate.to_string())
+    }
+
+    #[test]
+    fn runs_read_only_programs() {
+        let source = r#".message == "hello" && .nested.deep.value == true && %source == "kafka""#;
+        let metadata = value!({ source: "kafka" });
+
+        let event = value!({ message: "hello", nested: { deep: { value: true } } });
+        let mut target = TargetValueView {
+            value: &event,
+            metadata: &metadata,
+            secrets: Cow::Owned(Secrets::new()),
+        };
+        assert_eq!(run(source, &mut target), Ok(value!(true)));
+
+        let mut target = TargetJsonView::new(EVENT).unwrap();
+        target.metadata = Cow::Borrowed(&metadata);
+        assert_eq!(run(source, &mut target), Ok(value!(true)));
+    }
+
+    #[test]
+    fn rejects_mutation() {
+        let event = value!({});
+        let metadata = value!({});
+        let mut target = TargetValueView {
+            value: &event,
+            metadata: &metadata,
+            secrets: Cow::Owned(Secrets::new()),
+        };
+        assert_eq!(
+            target.target_insert(&OwnedTargetPath::event_root(), value!(1)),
+            Err(READ_ONLY.to_owned())
+        );
+        assert_eq!(
+            target.target_remove(&OwnedTargetPath::event_root(), false),
+            Err(READ_ONLY.to_owned())
+        );
+
+        target.insert_secret("token", "abc");
+        assert_eq!(target.get_secret("token"), Some("abc"));
+    }
+}
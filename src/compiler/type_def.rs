@@ -251,6 +251,19 @@ impl TypeDef {
         self
     }
 
+    #[inline]
+    #[must_use]
+    pub fn decimal() -> Self {
+        Kind::decimal().into()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn or_decimal(mut self) -> Self {
+        self.kind.add_decimal();
+        self
+    }
+
     #[inline]
     #[must_use]
     pub fn boolean() -> Self {
@@ -277,6 +290,19 @@ impl TypeDef {
         self
     }
 
+    #[inline]
+    #[must_use]
+    pub fn duration() -> Self {
+        Kind::duration().into()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn or_duration(mut self) -> Self {
+        self.kind.add_duration();
+        self
+    }
+
     #[inline]
     #[must_use]
     pub fn regex() -> Self {
@@ -1,14 +1,18 @@
 #![deny(clippy::arithmetic_side_effects)]
 #![allow(clippy::cast_precision_loss, clippy::module_name_repetitions)]
 
+use std::cmp::Ordering;
 use std::ops::{Add, Mul, Rem};
 
 use crate::compiler::{
     ExpressionError,
     value::{Kind, VrlValueConvert},
 };
+use crate::value::value::{from_nanoseconds, to_nanoseconds};
 use crate::value::{ObjectMap, Value};
 use bytes::{BufMut, Bytes, BytesMut};
+use chrono::TimeDelta;
+use rust_decimal::Decimal;
 
 use super::ValueError;
 
@@ -70,6 +74,124 @@ fn safe_sub(lhv: f64, rhv: f64) -> Option<Value> {
     }
 }
 
+/// Returns the exact decimal value of an integer or decimal.
+fn exact(value: &Value) -> Option<Decimal> {
+    match value {
+        Value::Integer(v) => Some(Decimal::from(*v)),
+        Value::Decimal(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Applies an arithmetic operation where at least one side is a decimal.
+///
+/// A float on either side turns the result into a float, otherwise the operation is exact and
+/// fails if the result does not fit a decimal.
+fn decimal_op(
+    lhs: &Value,
+    rhs: &Value,
+    exact_op: fn(Decimal, Decimal) -> Option<Decimal>,
+    float_op: fn(f64, f64) -> f64,
+    err: impl Fn() -> ValueError,
+) -> Result<Value, ValueError> {
+    if lhs.is_float() || rhs.is_float() {
+        let lhv = lhs.try_into_f64().map_err(|_| err())?;
+        let rhv = rhs.try_into_f64().map_err(|_| err())?;
+        let result = float_op(lhv, rhv);
+
+        return if result.is_nan() {
+            Err(err())
+        } else {
+            Ok(Value::from_f64_or_zero(result))
+        };
+    }
+
+    let lhv = exact(lhs).ok_or_else(&err)?;
+    let rhv = exact(rhs).ok_or_else(&err)?;
+
+    exact_op(lhv, rhv)
+        .map(Value::Decimal)
+        .ok_or(ValueError::OutOfRange(Kind::decimal()))
+}
+
+/// Compares two numbers where at least one side is a decimal, exactly unless one side is a
+/// float.
+fn decimal_cmp(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (exact(lhs), exact(rhs)) {
+        (Some(lhv), Some(rhv)) => Some(lhv.cmp(&rhv)),
+        _ => lhs
+            .try_into_f64()
+            .ok()?
+            .partial_cmp(&rhs.try_into_f64().ok()?),
+    }
+}
+
+/// Returns the duration closest to a fractional number of nanoseconds, if it fits a duration.
+fn float_nanos_duration(nanos: f64) -> Option<TimeDelta> {
+    let nanos = nanos.round();
+
+    #[allow(clippy::cast_possible_truncation)] // the cast saturates, and is then out of range
+    nanos
+        .is_finite()
+        .then_some(nanos as i128)
+        .and_then(from_nanoseconds)
+}
+
+/// Scales a duration by an integer or float factor.
+///
+/// Fractions of a nanosecond are rounded, and the operation fails if the result does not fit a
+/// duration.
+fn scale_duration(
+    duration: TimeDelta,
+    factor: &Value,
+    err: impl Fn() -> ValueError,
+) -> Result<Value, ValueError> {
+    let nanos = to_nanoseconds(duration);
+
+    let scaled = match factor {
+        Value::Integer(factor) => nanos
+            .checked_mul(i128::from(*factor))
+            .and_then(from_nanoseconds),
+        Value::Float(factor) => float_nanos_duration(nanos as f64 * factor.into_inner()),
+        _ => return Err(err()),
+    };
+
+    scaled
+        .map(Value::Duration)
+        .ok_or(ValueError::OutOfRange(Kind::duration()))
+}
+
+/// Divides a duration by an integer or float, or by another duration, which returns their
+/// ratio as a float.
+fn divide_duration(
+    duration: TimeDelta,
+    divisor: &Value,
+    err: impl Fn() -> ValueError,
+) -> Result<Value, ValueError> {
+    let nanos = to_nanoseconds(duration);
+
+    match divisor {
+        Value::Duration(divisor) => {
+            let divisor = to_nanoseconds(*divisor);
+            if divisor == 0 {
+                return Err(ValueError::DivideByZero);
+            }
+            Ok(Value::from_f64_or_zero(nanos as f64 / divisor as f64))
+        }
+        Value::Integer(0) => Err(ValueError::DivideByZero),
+        Value::Integer(divisor) => nanos
+            .checked_div(i128::from(*divisor))
+            .and_then(from_nanoseconds)
+            .map(Value::Duration)
+            .ok_or(ValueError::OutOfRange(Kind::duration())),
+        Value::Float(divisor) if *divisor == 0.0 => Err(ValueError::DivideByZero),
+        Value::Float(divisor) => float_nanos_duration(nanos as f64 / divisor.into_inner())
+            .map(Value::Duration)
+            .ok_or(ValueError::OutOfRange(Kind::duration())),
+        _ => Err(err()),
+    }
+}
+
 impl VrlValueArithmetic for Value {
     /// Similar to [`std::ops::Mul`], but fallible (e.g. `TryMul`).
     fn try_mul(self, rhs: Self) -> Result<Self, ValueError> {
@@ -80,6 +202,17 @@ impl VrlValueArithmetic for Value {
         #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
         let as_usize = |num| if num < 0 { 0 } else { num as usize };
 
+        match (&self, &rhs) {
+            (Value::Duration(duration), factor) | (factor, Value::Duration(duration)) => {
+                return scale_duration(*duration, factor, err);
+            }
+            _ => {}
+        }
+
+        if self.is_decimal() || rhs.is_decimal() {
+            return decimal_op(&self, &rhs, Decimal::checked_mul, f64::mul, err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_bytes() => {
                 Bytes::from(rhs.try_bytes()?.repeat(as_usize(lhv))).into()
@@ -108,12 +241,20 @@ impl VrlValueArithmetic for Value {
     fn try_div(self, rhs: Self) -> Result<Self, ValueError> {
         let err = || ValueError::Div(self.kind(), rhs.kind());
 
+        if let Value::Duration(duration) = self {
+            return divide_duration(duration, &rhs, err);
+        }
+
         let rhv_f64 = rhs.try_into_f64().map_err(|_| err())?;
 
         if rhv_f64 == 0.0 {
             return Err(ValueError::DivideByZero);
         }
 
+        if self.is_decimal() || rhs.is_decimal() {
+            return decimal_op(&self, &rhs, Decimal::checked_div, |l, r| l / r, err);
+        }
+
         let value = match self {
             Value::Integer(lhv) => Value::from_f64_or_zero(lhv as f64 / rhv_f64),
             Value::Float(lhv) => Value::from_f64_or_zero(lhv.into_inner() / rhv_f64),
@@ -125,6 +266,28 @@ impl VrlValueArithmetic for Value {
 
     /// Similar to [`std::ops::Add`], but fallible (e.g. `TryAdd`).
     fn try_add(self, rhs: Self) -> Result<Self, ValueError> {
+        match (&self, &rhs) {
+            (Value::Timestamp(timestamp), Value::Duration(duration))
+            | (Value::Duration(duration), Value::Timestamp(timestamp)) => {
+                return timestamp
+                    .checked_add_signed(*duration)
+                    .map(Value::Timestamp)
+                    .ok_or(ValueError::OutOfRange(Kind::timestamp()));
+            }
+            (Value::Duration(lhv), Value::Duration(rhv)) => {
+                return lhv
+                    .checked_add(rhv)
+                    .map(Value::Duration)
+                    .ok_or(ValueError::OutOfRange(Kind::duration()));
+            }
+            _ => {}
+        }
+
+        if self.is_decimal() || rhs.is_decimal() {
+            let err = || ValueError::Add(self.kind(), rhs.kind());
+            return decimal_op(&self, &rhs, Decimal::checked_add, f64::add, err);
+        }
+
         let value = match (self, rhs) {
             (Value::Integer(lhs), Value::Float(rhs)) => Value::from_f64_or_zero(lhs as f64 + *rhs),
             (Value::Integer(lhs), rhs) => {
@@ -158,6 +321,29 @@ impl VrlValueArithmetic for Value {
     fn try_sub(self, rhs: Self) -> Result<Self, ValueError> {
         let err = || ValueError::Sub(self.kind(), rhs.kind());
 
+        match (&self, &rhs) {
+            (Value::Timestamp(lhv), Value::Timestamp(rhv)) => {
+                return Ok(lhv.signed_duration_since(rhv).into());
+            }
+            (Value::Timestamp(timestamp), Value::Duration(duration)) => {
+                return timestamp
+                    .checked_sub_signed(*duration)
+                    .map(Value::Timestamp)
+                    .ok_or(ValueError::OutOfRange(Kind::timestamp()));
+            }
+            (Value::Duration(lhv), Value::Duration(rhv)) => {
+                return lhv
+                    .checked_sub(rhv)
+                    .map(Value::Duration)
+                    .ok_or(ValueError::OutOfRange(Kind::duration()));
+            }
+            _ => {}
+        }
+
+        if self.is_decimal() || rhs.is_decimal() {
+            return decimal_op(&self, &rhs, Decimal::checked_sub, |l, r| l - r, err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => {
                 Value::from_f64_or_zero(lhv as f64 - rhs.try_float()?)
@@ -222,6 +408,10 @@ impl VrlValueArithmetic for Value {
             return Err(ValueError::DivideByZero);
         }
 
+        if self.is_decimal() || rhs.is_decimal() {
+            return decimal_op(&self, &rhs, Decimal::checked_rem, f64::rem, err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => {
                 Value::from_f64_or_zero(lhv as f64 % rhs.try_float()?)
@@ -244,12 +434,19 @@ impl VrlValueArithmetic for Value {
     fn try_gt(self, rhs: Self) -> Result<Self, ValueError> {
         let err = || ValueError::Rem(self.kind(), rhs.kind());
 
+        if self.is_decimal() || rhs.is_decimal() {
+            return decimal_cmp(&self, &rhs)
+                .map(|ordering| ordering.is_gt().into())
+                .ok_or_else(err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 > rhs.try_float()?).into(),
             Value::Integer(lhv) => (lhv > rhs.try_into_i64().map_err(|_| err())?).into(),
             Value::Float(lhv) => (lhv.into_inner() > rhs.try_into_f64().map_err(|_| err())?).into(),
             Value::Bytes(lhv) => (lhv > rhs.try_bytes()?).into(),
             Value::Timestamp(lhv) => (lhv > rhs.try_timestamp()?).into(),
+            Value::Duration(lhv) => (lhv > rhs.try_duration()?).into(),
             _ => return Err(err()),
         };
 
@@ -260,6 +457,12 @@ impl VrlValueArithmetic for Value {
     fn try_ge(self, rhs: Self) -> Result<Self, ValueError> {
         let err = || ValueError::Ge(self.kind(), rhs.kind());
 
+        if self.is_decimal() || rhs.is_decimal() {
+            return decimal_cmp(&self, &rhs)
+                .map(|ordering| ordering.is_ge().into())
+                .ok_or_else(err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 >= rhs.try_float()?).into(),
             Value::Integer(lhv) => (lhv >= rhs.try_into_i64().map_err(|_| err())?).into(),
@@ -268,6 +471,7 @@ impl VrlValueArithmetic for Value {
             }
             Value::Bytes(lhv) => (lhv >= rhs.try_bytes()?).into(),
             Value::Timestamp(lhv) => (lhv >= rhs.try_timestamp()?).into(),
+            Value::Duration(lhv) => (lhv >= rhs.try_duration()?).into(),
             _ => return Err(err()),
         };
 
@@ -278,12 +482,19 @@ impl VrlValueArithmetic for Value {
     fn try_lt(self, rhs: Self) -> Result<Self, ValueError> {
         let err = || ValueError::Ge(self.kind(), rhs.kind());
 
+        if self.is_decimal() || rhs.is_decimal() {
+            return decimal_cmp(&self, &rhs)
+                .map(|ordering| ordering.is_lt().into())
+                .ok_or_else(err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => ((lhv as f64) < rhs.try_float()?).into(),
             Value::Integer(lhv) => (lhv < rhs.try_into_i64().map_err(|_| err())?).into(),
             Value::Float(lhv) => (lhv.into_inner() < rhs.try_into_f64().map_err(|_| err())?).into(),
             Value::Bytes(lhv) => (lhv < rhs.try_bytes()?).into(),
             Value::Timestamp(lhv) => (lhv < rhs.try_timestamp()?).into(),
+            Value::Duration(lhv) => (lhv < rhs.try_duration()?).into(),
             _ => return Err(err()),
         };
 
@@ -294,6 +505,12 @@ impl VrlValueArithmetic for Value {
     fn try_le(self, rhs: Self) -> Result<Self, ValueError> {
         let err = || ValueError::Ge(self.kind(), rhs.kind());
 
+        if self.is_decimal() || rhs.is_decimal() {
+            return decimal_cmp(&self, &rhs)
+                .map(|ordering| ordering.is_le().into())
+                .ok_or_else(err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 <= rhs.try_float()?).into(),
             Value::Integer(lhv) => (lhv <= rhs.try_into_i64().map_err(|_| err())?).into(),
@@ -302,6 +519,7 @@ impl VrlValueArithmetic for Value {
             }
             Value::Bytes(lhv) => (lhv <= rhs.try_bytes()?).into(),
             Value::Timestamp(lhv) => (lhv <= rhs.try_timestamp()?).into(),
+            Value::Duration(lhv) => (lhv <= rhs.try_duration()?).into(),
             _ => return Err(err()),
         };
 
@@ -329,6 +547,10 @@ impl VrlValueArithmetic for Value {
     fn eq_lossy(&self, rhs: &Self) -> bool {
         use Value::{Float, Integer};
 
+        if self.is_decimal() || rhs.is_decimal() {
+            return decimal_cmp(self, rhs) == Some(Ordering::Equal);
+        }
+
         match self {
             Integer(lhv) => rhs
                 .try_into_f64()
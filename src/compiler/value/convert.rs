@@ -2,7 +2,8 @@ use std::borrow::Cow;
 
 use crate::value::{Value, ValueRegex, kind::Collection};
 use bytes::Bytes;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
 
 use crate::compiler::{
     Expression,
@@ -28,6 +29,7 @@ pub trait VrlValueConvert: Sized {
 
     fn try_integer(self) -> Result<i64, ValueError>;
     fn try_float(self) -> Result<f64, ValueError>;
+    fn try_decimal(self) -> Result<Decimal, ValueError>;
     fn try_bytes(self) -> Result<Bytes, ValueError>;
     fn try_boolean(self) -> Result<bool, ValueError>;
     fn try_regex(self) -> Result<ValueRegex, ValueError>;
@@ -35,6 +37,7 @@ pub trait VrlValueConvert: Sized {
     fn try_array(self) -> Result<Vec<Value>, ValueError>;
     fn try_object(self) -> Result<ObjectMap, ValueError>;
     fn try_timestamp(self) -> Result<DateTime<Utc>, ValueError>;
+    fn try_duration(self) -> Result<TimeDelta, ValueError>;
 
     fn try_into_i64(&self) -> Result<i64, ValueError>;
     fn try_into_f64(&self) -> Result<f64, ValueError>;
@@ -63,6 +66,7 @@ impl VrlValueConvert for Value {
             Value::Integer(v) => Ok(*v),
             #[allow(clippy::cast_possible_truncation)]
             Value::Float(v) => Ok(v.into_inner() as i64),
+            Value::Decimal(v) => v.to_i64().ok_or(ValueError::OutOfRange(Kind::decimal())),
             _ => Err(ValueError::Coerce(self.kind(), Kind::integer())),
         }
     }
@@ -77,11 +81,22 @@ impl VrlValueConvert for Value {
         }
     }
 
+    fn try_decimal(self) -> Result<Decimal, ValueError> {
+        match self {
+            Value::Decimal(v) => Ok(v),
+            _ => Err(ValueError::Expected {
+                got: self.kind(),
+                expected: Kind::decimal(),
+            }),
+        }
+    }
+
     fn try_into_f64(&self) -> Result<f64, ValueError> {
         match self {
             #[allow(clippy::cast_precision_loss)]
             Value::Integer(v) => Ok(*v as f64),
             Value::Float(v) => Ok(v.into_inner()),
+            Value::Decimal(v) => v.to_f64().ok_or(ValueError::OutOfRange(Kind::decimal())),
             _ => Err(ValueError::Coerce(self.kind(), Kind::float())),
         }
     }
@@ -162,6 +177,16 @@ impl VrlValueConvert for Value {
             }),
         }
     }
+
+    fn try_duration(self) -> Result<TimeDelta, ValueError> {
+        match self {
+            Value::Duration(v) => Ok(v),
+            _ => Err(ValueError::Expected {
+                got: self.kind(),
+                expected: Kind::duration(),
+            }),
+        }
+    }
 }
 
 /// Converts from an `Expr` into a `Value`. This is only possible if the expression represents
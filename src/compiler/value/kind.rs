@@ -15,10 +15,23 @@ pub const TIMESTAMP: u16 = 1 << 7;
 pub const REGEX: u16 = 1 << 8;
 pub const NULL: u16 = 1 << 9;
 pub const UNDEFINED: u16 = 1 << 10;
-
-pub const ANY: u16 =
-    BYTES | INTEGER | FLOAT | BOOLEAN | OBJECT | ARRAY | TIMESTAMP | REGEX | NULL | UNDEFINED;
-pub const SCALAR: u16 = BYTES | INTEGER | FLOAT | BOOLEAN | TIMESTAMP | REGEX | NULL;
+pub const DECIMAL: u16 = 1 << 11;
+pub const DURATION: u16 = 1 << 12;
+
+pub const ANY: u16 = BYTES
+    | INTEGER
+    | FLOAT
+    | DECIMAL
+    | BOOLEAN
+    | OBJECT
+    | ARRAY
+    | TIMESTAMP
+    | DURATION
+    | REGEX
+    | NULL
+    | UNDEFINED;
+pub const SCALAR: u16 =
+    BYTES | INTEGER | FLOAT | DECIMAL | BOOLEAN | TIMESTAMP | DURATION | REGEX | NULL;
 pub const CONTAINER: u16 = OBJECT | ARRAY;
 
 pub use crate::value::{
@@ -53,6 +66,10 @@ impl DefaultValue for Kind {
             return value!(NotNan::new(0.0).unwrap());
         }
 
+        if self.is_decimal() {
+            return Value::Decimal(rust_decimal::Decimal::ZERO);
+        }
+
         if self.is_boolean() {
             return value!(false);
         }
@@ -65,6 +82,10 @@ impl DefaultValue for Kind {
                 .into();
         }
 
+        if self.is_duration() {
+            return Value::Duration(chrono::TimeDelta::zero());
+        }
+
         if self.is_regex() {
             #[allow(clippy::trivial_regex)]
             return Regex::new("").unwrap().into();
@@ -117,6 +138,13 @@ mod tests {
                     want: Kind::float(),
                 },
             ),
+            (
+                "decimal",
+                TestCase {
+                    value: Value::Decimal(rust_decimal::Decimal::MAX),
+                    want: Kind::decimal(),
+                },
+            ),
             (
                 "boolean",
                 TestCase {
@@ -131,6 +159,13 @@ mod tests {
                     want: Kind::timestamp(),
                 },
             ),
+            (
+                "duration",
+                TestCase {
+                    value: chrono::TimeDelta::minutes(90).into(),
+                    want: Kind::duration(),
+                },
+            ),
             (
                 "regex",
                 TestCase {
@@ -1,7 +1,4 @@
-use std::{
-    collections::BTreeMap,
-    fmt::{self, Write},
-};
+use std::fmt::{self, Write};
 
 use serde::ser::{
     Error, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
@@ -37,8 +34,8 @@ impl Error for EncodingError {
 /// # Errors
 ///
 /// Returns an `EncodingError` if the input contains non-`String` map keys.
-pub fn to_string<V: Serialize>(
-    input: &BTreeMap<KeyString, V>,
+pub fn to_string<'a, V: Serialize + 'a>(
+    input: impl IntoIterator<Item = (&'a KeyString, &'a V)>,
     fields_order: &[KeyString],
     key_value_delimiter: &str,
     field_delimiter: &str,
@@ -49,7 +46,7 @@ pub fn to_string<V: Serialize>(
     let mut input = flatten(input, '.')?;
 
     for field in fields_order {
-        match (input.remove(field), flatten_boolean) {
+        match (remove_field(&mut input, field), flatten_boolean) {
             (Some(Data::Boolean(false)), true) | (None, _) => (),
             (Some(Data::Boolean(true)), true) => {
                 encode_string(&mut output, field);
@@ -83,11 +80,24 @@ pub fn to_string<V: Serialize>(
     Ok(output)
 }
 
+/// The flattened fields, in the order of the input map.
+#[cfg(not(feature = "preserve_order"))]
+type FlatMap = std::collections::BTreeMap<KeyString, Data>;
+#[cfg(feature = "preserve_order")]
+type FlatMap = indexmap::IndexMap<KeyString, Data>;
+
+fn remove_field(map: &mut FlatMap, key: &str) -> Option<Data> {
+    #[cfg(not(feature = "preserve_order"))]
+    return map.remove(key);
+    #[cfg(feature = "preserve_order")]
+    return map.shift_remove(key);
+}
+
 fn flatten<'a>(
-    input: impl IntoIterator<Item = (&'a KeyString, impl Serialize)> + 'a,
+    input: impl IntoIterator<Item = (&'a KeyString, impl Serialize)>,
     separator: char,
-) -> Result<BTreeMap<KeyString, Data>, EncodingError> {
-    let mut map = BTreeMap::new();
+) -> Result<FlatMap, EncodingError> {
+    let mut map = FlatMap::new();
     for (key, value) in input {
         value.serialize(KeyValueSerializer::new(key.clone(), separator, &mut map))?;
     }
@@ -156,11 +166,11 @@ impl fmt::Display for Data {
 struct KeyValueSerializer<'a> {
     key: KeyString,
     separator: char,
-    output: &'a mut BTreeMap<KeyString, Data>,
+    output: &'a mut FlatMap,
 }
 
 impl<'a> KeyValueSerializer<'a> {
-    fn new(key: KeyString, separator: char, output: &'a mut BTreeMap<KeyString, Data>) -> Self {
+    fn new(key: KeyString, separator: char, output: &'a mut FlatMap) -> Self {
         Self {
             key,
             separator,
@@ -502,6 +512,7 @@ mod tests {
 
     use super::*;
     use crate::btreemap;
+    use std::collections::BTreeMap;
 
     #[test]
     fn single_element() {
@@ -672,6 +683,8 @@ mod tests {
     }
 
     #[test]
+    // The nested `json!` objects keep their key order with the `preserve_order` feature.
+    #[cfg(not(feature = "preserve_order"))]
     fn nested_fields() {
         assert_eq!(
             &to_string::<Value>(
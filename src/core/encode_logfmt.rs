@@ -22,7 +22,7 @@ pub fn encode_map<V: Serialize>(input: &BTreeMap<KeyString, V>) -> Result<String
 /// Returns an `EncodingError` if any of the keys are not strings.
 pub fn encode_value(input: &Value) -> Result<String, EncodingError> {
     if let Some(map) = input.as_object() {
-        encode_map(map)
+        encode_key_value(map, &[], "=", " ", true)
     } else {
         let mut map = BTreeMap::new();
         map.insert("message".to_string().into(), &input);
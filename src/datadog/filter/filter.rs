@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use super::{Matcher, Run};
+use super::{ExactValues, Matcher, Run};
 use crate::datadog::search::{Comparison, ComparisonValue, Field};
 use crate::path::PathParseError;
 use dyn_clone::{DynClone, clone_trait_object};
@@ -24,6 +24,19 @@ pub trait Filter<V: Debug + Send + Sync + Clone + 'static>: DynClone {
     /// Will return `Err` if the query contains an invalid path.
     fn equals(&self, field: Field, to_match: &str) -> Result<Box<dyn Matcher<V>>, PathParseError>;
 
+    /// Returns the values of a field which `equals` compares exactly, if it does, so that
+    /// conditions on the same field can be looked up by value. By default, `None`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the query contains an invalid path.
+    fn exact_values(
+        &self,
+        _field: &Field,
+    ) -> Result<Option<Box<dyn ExactValues<V>>>, PathParseError> {
+        Ok(None)
+    }
+
     /// Determine whether a value starts with a prefix.
     ///
     /// # Errors
@@ -38,6 +51,44 @@ pub trait Filter<V: Debug + Send + Sync + Clone + 'static>: DynClone {
     fn wildcard(&self, field: Field, wildcard: &str)
     -> Result<Box<dyn Matcher<V>>, PathParseError>;
 
+    /// Determine whether a field value is within `distance` edits of `value`. By default, this
+    /// will use `self.equals`, only matching exactly.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the query contains an invalid path.
+    fn fuzzy(
+        &self,
+        field: Field,
+        value: &str,
+        _distance: u8,
+    ) -> Result<Box<dyn Matcher<V>>, PathParseError> {
+        self.equals(field, value)
+    }
+
+    /// Determine whether a field value matches a regular expression. By default, nothing matches.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the query contains an invalid path.
+    fn regex(&self, _field: Field, _regex: &str) -> Result<Box<dyn Matcher<V>>, PathParseError> {
+        Ok(Box::new(false))
+    }
+
+    /// Determine whether the value(s) of a field match the `matcher` of a nested query. By
+    /// default, nothing matches.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the query contains an invalid path.
+    fn nested(
+        &self,
+        _field: Field,
+        _matcher: Box<dyn Matcher<V>>,
+    ) -> Result<Box<dyn Matcher<V>>, PathParseError> {
+        Ok(Box::new(false))
+    }
+
     /// Compare a field value against `comparison_value`, using one of the `comparator` operators.
     ///
     /// # Errors
@@ -12,6 +12,15 @@ pub trait Matcher<V>: DynClone + fmt::Debug + Send + Sync {
 
 clone_trait_object!(<V>Matcher<V>);
 
+/// An `ExactValues` reads the values of a field which are compared exactly by `Filter::equals`,
+/// so that many conditions on the field can be looked up by value at once.
+pub trait ExactValues<V>: DynClone + fmt::Debug + Send + Sync {
+    /// Invokes `f` with each value of the field within `value`.
+    fn for_each(&self, value: &V, f: &mut dyn FnMut(&str));
+}
+
+clone_trait_object!(<V>ExactValues<V>);
+
 /// Implementing `Matcher` for bool allows a `Box::new(true|false)` convenience.
 impl<V> Matcher<V> for bool {
     fn run(&self, _value: &V) -> bool {
@@ -117,6 +126,7 @@ impl QueryNode {
     ///
     /// Will return `Err` if the query contains an invalid path.
     #[allow(clippy::module_name_repetitions)] // Renaming is a breaking change.
+    #[allow(clippy::too_many_lines)]
     pub fn build_matcher<V, F>(&self, filter: &F) -> Result<Box<dyn Matcher<V>>, PathParseError>
     where
         V: fmt::Debug + Send + Sync + Clone + 'static,
@@ -174,6 +184,37 @@ impl QueryNode {
 
                 Ok(any(matchers?))
             }
+            Self::AttributeFuzzy {
+                attr,
+                value,
+                distance,
+            } => {
+                let matchers: Result<Vec<_>, _> = filter
+                    .build_fields(attr)
+                    .into_iter()
+                    .map(|field| filter.fuzzy(field, value, *distance))
+                    .collect();
+
+                Ok(any(matchers?))
+            }
+            Self::AttributeRegex { attr, regex } => {
+                let matchers: Result<Vec<_>, _> = filter
+                    .build_fields(attr)
+                    .into_iter()
+                    .map(|field| filter.regex(field, regex))
+                    .collect();
+
+                Ok(any(matchers?))
+            }
+            Self::NestedQuery { attr, node } => {
+                let matchers: Result<Vec<_>, _> = filter
+                    .build_fields(attr)
+                    .into_iter()
+                    .map(|field| filter.nested(field, node.build_matcher(filter)?))
+                    .collect();
+
+                Ok(any(matchers?))
+            }
             Self::AttributeComparison {
                 attr,
                 comparator,
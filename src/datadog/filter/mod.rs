@@ -5,9 +5,12 @@ mod filter;
 mod matcher;
 pub mod regex;
 mod resolver;
+mod set;
+mod vrl;
 
 pub use filter::*;
 pub use matcher::*;
 pub use resolver::*;
+pub use set::*;
 
 use super::search::{BooleanType, Field, QueryNode, normalize_fields};
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{BooleanType, ExactValues, Field, Filter, Matcher, QueryNode, Resolver};
+use crate::path::PathParseError;
+
+/// A set of named queries compiled together, so that a value can be matched against all of
+/// them in one pass. Identical field conditions (such as the same field equalling the same
+/// value) across queries share a single matcher, which is run at most once per value. Where the
+/// `Filter` compares a field exactly, the terms on that field are indexed by value, so the field
+/// is read once and each of its values is looked up, rather than running every term.
+#[derive(Clone, Debug)]
+pub struct MatcherSet<V> {
+    names: Vec<String>,
+    conditions: Vec<Condition<V>>,
+    indexes: Vec<Index<V>>,
+    plans: Vec<Plan>,
+}
+
+/// A condition shared between the queries.
+#[derive(Clone, Debug)]
+enum Condition<V> {
+    Matcher(Box<dyn Matcher<V>>),
+    /// A term within the index at this position.
+    Indexed(usize),
+}
+
+/// The term conditions on a field, keyed by the value they match.
+#[derive(Clone, Debug)]
+struct Index<V> {
+    values: Box<dyn ExactValues<V>>,
+    conditions: HashMap<String, usize>,
+}
+
+/// The boolean structure of a query, over the indexes of the shared conditions.
+#[derive(Clone, Debug)]
+enum Plan {
+    Const(bool),
+    Condition(usize),
+    Not(Box<Plan>),
+    All(Vec<Plan>),
+    Any(Vec<Plan>),
+}
+
+/// The results of the conditions (and indexes) which have been run against a value.
+struct Results {
+    conditions: Vec<Option<bool>>,
+    indexes: Vec<bool>,
+}
+
+impl<V> MatcherSet<V>
+where
+    V: fmt::Debug + Send + Sync + Clone + 'static,
+{
+    /// Returns the names of the queries which match `value`, in the order the queries were
+    /// provided.
+    #[must_use]
+    pub fn matches(&self, value: &V) -> Vec<&str> {
+        let mut results = Results {
+            conditions: vec![None; self.conditions.len()],
+            indexes: vec![false; self.indexes.len()],
+        };
+
+        self.names
+            .iter()
+            .zip(&self.plans)
+            .filter(|(_, plan)| self.run(plan, value, &mut results))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// The number of distinct conditions shared between the queries.
+    #[must_use]
+    pub fn conditions(&self) -> usize {
+        self.conditions.len()
+    }
+
+    /// The number of fields whose terms are looked up by value.
+    #[must_use]
+    pub fn indexes(&self) -> usize {
+        self.indexes.len()
+    }
+
+    fn run(&self, plan: &Plan, value: &V, results: &mut Results) -> bool {
+        match plan {
+            Plan::Const(result) => *result,
+            Plan::Condition(index) => self.condition(*index, value, results),
+            Plan::Not(plan) => !self.run(plan, value, results),
+            Plan::All(plans) => plans.iter().all(|plan| self.run(plan, value, results)),
+            Plan::Any(plans) => plans.iter().any(|plan| self.run(plan, value, results)),
+        }
+    }
+
+    fn condition(&self, index: usize, value: &V, results: &mut Results) -> bool {
+        if let Some(result) = results.conditions[index] {
+            return result;
+        }
+
+        match &self.conditions[index] {
+            Condition::Matcher(matcher) => {
+                let result = matcher.run(value);
+                results.conditions[index] = Some(result);
+                result
+            }
+            // Looking up the values of the field resolves every term on it. Those which weren't
+            // found don't match.
+            Condition::Indexed(field) => {
+                if !results.indexes[*field] {
+                    let Index { values, conditions } = &self.indexes[*field];
+                    values.for_each(value, &mut |value| {
+                        if let Some(condition) = conditions.get(value) {
+                            results.conditions[*condition] = Some(true);
+                        }
+                    });
+                    results.indexes[*field] = true;
+                }
+
+                results.conditions[index].unwrap_or(false)
+            }
+        }
+    }
+}
+
+/// Build a `MatcherSet` from named `QueryNode`s, invoking the `Filter` + `Resolver` once for
+/// each distinct condition across all of the queries. Like `build_matcher`, this is intended to
+/// be used at boot-time and NOT in a hot path!
+///
+/// # Errors
+///
+/// Will return `Err` if a query contains an invalid path.
+pub fn build_matcher_set<V, F, N>(
+    queries: impl IntoIterator<Item = (N, QueryNode)>,
+    filter: &F,
+) -> Result<MatcherSet<V>, PathParseError>
+where
+    V: fmt::Debug + Send + Sync + Clone + 'static,
+    F: Filter<V> + Resolver,
+    N: Into<String>,
+{
+    let mut builder = Builder {
+        filter,
+        conditions: Vec::new(),
+        keys: HashMap::new(),
+        indexes: Vec::new(),
+        fields: HashMap::new(),
+    };
+
+    let mut names = Vec::new();
+    let mut plans = Vec::new();
+    for (name, node) in queries {
+        plans.push(builder.plan(&node)?);
+        names.push(name.into());
+    }
+
+    Ok(MatcherSet {
+        names,
+        conditions: builder.conditions,
+        indexes: builder.indexes,
+        plans,
+    })
+}
+
+struct Builder<'a, V, F> {
+    filter: &'a F,
+    conditions: Vec<Condition<V>>,
+    /// Condition indexes, keyed by the Lucene representation of their node.
+    keys: HashMap<String, usize>,
+    indexes: Vec<Index<V>>,
+    /// Indexes of the fields whose values are compared exactly, or `None` for those which aren't.
+    fields: HashMap<Field, Option<usize>>,
+}
+
+impl<V, F> Builder<'_, V, F>
+where
+    V: fmt::Debug + Send + Sync + Clone + 'static,
+    F: Filter<V> + Resolver,
+{
+    fn plan(&mut self, node: &QueryNode) -> Result<Plan, PathParseError> {
+        Ok(match node {
+            QueryNode::MatchAllDocs => Plan::Const(true),
+            QueryNode::MatchNoDocs => Plan::Const(false),
+            QueryNode::NegatedNode { node } => Plan::Not(Box::new(self.plan(node)?)),
+            QueryNode::Boolean { oper, nodes } => {
+                let plans = nodes
+                    .iter()
+                    .map(|node| self.plan(node))
+                    .collect::<Result<_, _>>()?;
+
+                match oper {
+                    BooleanType::And => Plan::All(plans),
+                    BooleanType::Or => Plan::Any(plans),
+                }
+            }
+            QueryNode::AttributeTerm { attr, value }
+            | QueryNode::QuotedAttribute {
+                attr,
+                phrase: value,
+            } => {
+                // Terms on a single field which is compared exactly are looked up by value.
+                if let [field] = self.filter.build_fields(attr).as_slice()
+                    && let Some(index) = self.index(field)?
+                {
+                    return Ok(Plan::Condition(self.indexed(index, value)));
+                }
+
+                self.condition(node)?
+            }
+            // Everything else is a condition on a field.
+            node => self.condition(node)?,
+        })
+    }
+
+    fn condition(&mut self, node: &QueryNode) -> Result<Plan, PathParseError> {
+        let key = node.to_lucene();
+        if let Some(index) = self.keys.get(&key) {
+            return Ok(Plan::Condition(*index));
+        }
+
+        self.conditions
+            .push(Condition::Matcher(node.build_matcher(self.filter)?));
+        self.keys.insert(key, self.conditions.len() - 1);
+        Ok(Plan::Condition(self.conditions.len() - 1))
+    }
+
+    /// Returns the index of the terms on `field`, if its values are compared exactly.
+    fn index(&mut self, field: &Field) -> Result<Option<usize>, PathParseError> {
+        if let Some(index) = self.fields.get(field) {
+            return Ok(*index);
+        }
+
+        let index = self.filter.exact_values(field)?.map(|values| {
+            self.indexes.push(Index {
+                values,
+                conditions: HashMap::new(),
+            });
+            self.indexes.len() - 1
+        });
+        self.fields.insert(field.clone(), index);
+        Ok(index)
+    }
+
+    /// Returns the condition for the term `value` within the index at `index`.
+    fn indexed(&mut self, index: usize, value: &str) -> usize {
+        if let Some(condition) = self.indexes[index].conditions.get(value) {
+            return *condition;
+        }
+
+        self.conditions.push(Condition::Indexed(index));
+        let condition = self.conditions.len() - 1;
+        self.indexes[index]
+            .conditions
+            .insert(value.to_owned(), condition);
+        condition
+    }
+}
@@ -0,0 +1,546 @@
+use std::fmt;
+
+use regex::Regex;
+
+use super::regex::{wildcard_regex, word_regex};
+use super::{BooleanType, Field, QueryNode, normalize_fields};
+use crate::datadog::search::{Comparison, ComparisonValue};
+use crate::datadog::string_literal;
+use crate::path::{OwnedTargetPath, PathParseError, parse_value_path};
+
+impl QueryNode {
+    /// Translate a Datadog Search Syntax `QueryNode` into a VRL boolean expression over the event
+    /// (`.`) that matches exactly the events `match_datadog_query(., query)` matches.
+    ///
+    /// The expression only checks the types of the event's fields at runtime, so it stays valid
+    /// when inlined into a program in which those types are already known.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the query contains an invalid path.
+    pub fn to_vrl(&self) -> Result<String, PathParseError> {
+        Ok(self.to_vrl_expr()?.to_string())
+    }
+
+    fn to_vrl_expr(&self) -> Result<Expr, PathParseError> {
+        match self {
+            Self::MatchAllDocs => Ok(Expr::Bool(true)),
+            Self::AttributeExists { attr } => any_field(attr, exists),
+            Self::AttributeMissing { attr } => Ok(Expr::all(
+                normalize_fields(attr)
+                    .into_iter()
+                    .map(|field| exists(field).map(Expr::not))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Self::AttributeTerm { attr, value }
+            | Self::QuotedAttribute {
+                attr,
+                phrase: value,
+            } => any_field(attr, |field| equals(field, value)),
+            Self::AttributePrefix { attr, prefix } => {
+                any_field(attr, |field| starts_with(field, prefix))
+            }
+            Self::AttributeWildcard { attr, wildcard } => {
+                any_field(attr, |field| matches_wildcard(field, wildcard))
+            }
+            // Without edit distances, fuzzy terms match exactly, like `Filter::fuzzy`.
+            Self::AttributeFuzzy { attr, value, .. } => {
+                any_field(attr, |field| equals(field, value))
+            }
+            // Like `Filter::regex` and `Filter::nested`, which `match_datadog_query` doesn't
+            // implement, regular expressions and nested queries never match.
+            Self::MatchNoDocs | Self::AttributeRegex { .. } | Self::NestedQuery { .. } => {
+                Ok(Expr::Bool(false))
+            }
+            Self::AttributeComparison {
+                attr,
+                comparator,
+                value,
+            } => any_field(attr, |field| compare(&field, *comparator, value)),
+            Self::AttributeRange {
+                attr,
+                lower,
+                lower_inclusive,
+                upper,
+                upper_inclusive,
+            } => any_field(attr, |field| {
+                range(field, lower, *lower_inclusive, upper, *upper_inclusive)
+            }),
+            Self::NegatedNode { node } => Ok(Expr::not(node.to_vrl_expr()?)),
+            Self::Boolean { oper, nodes } => {
+                let exprs = nodes
+                    .iter()
+                    .map(QueryNode::to_vrl_expr)
+                    .collect::<Result<_, _>>()?;
+
+                Ok(match oper {
+                    BooleanType::And => Expr::all(exprs),
+                    BooleanType::Or => Expr::any(exprs),
+                })
+            }
+        }
+    }
+}
+
+/// A VRL boolean expression. It is kept structured so that constant parts can be folded away and
+/// it can be printed with the parentheses VRL needs: `&&` and `||` have the same precedence.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Bool(bool),
+    /// A function call, which never needs parentheses.
+    Call(String),
+    /// A comparison, which binds tighter than `&&` and `||`, but not `!`.
+    Comparison(String),
+    Not(Box<Expr>),
+    All(Vec<Expr>),
+    Any(Vec<Expr>),
+}
+
+impl Expr {
+    fn call(call: impl Into<String>) -> Self {
+        Self::Call(call.into())
+    }
+
+    fn comparison(comparison: impl Into<String>) -> Self {
+        Self::Comparison(comparison.into())
+    }
+
+    fn not(expr: Self) -> Self {
+        match expr {
+            Self::Bool(value) => Self::Bool(!value),
+            Self::Not(expr) => *expr,
+            expr => Self::Not(Box::new(expr)),
+        }
+    }
+
+    fn all(exprs: Vec<Self>) -> Self {
+        let mut all = vec![];
+        for expr in exprs {
+            match expr {
+                Self::Bool(true) => {}
+                Self::Bool(false) => return Self::Bool(false),
+                Self::All(exprs) => all.extend(exprs),
+                expr => all.push(expr),
+            }
+        }
+
+        match all.len() {
+            0 => Self::Bool(true),
+            1 => all.remove(0),
+            _ => Self::All(all),
+        }
+    }
+
+    fn any(exprs: Vec<Self>) -> Self {
+        let mut any = vec![];
+        for expr in exprs {
+            match expr {
+                Self::Bool(false) => {}
+                Self::Bool(true) => return Self::Bool(true),
+                Self::Any(exprs) => any.extend(exprs),
+                expr => any.push(expr),
+            }
+        }
+
+        match any.len() {
+            0 => Self::Bool(false),
+            1 => any.remove(0),
+            _ => Self::Any(any),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |f: &mut fmt::Formatter<'_>, exprs: &[Self], operator: &str| {
+            for (i, expr) in exprs.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " {operator} ")?;
+                }
+                match expr {
+                    Self::All(_) | Self::Any(_) => write!(f, "({expr})")?,
+                    expr => write!(f, "{expr}")?,
+                }
+            }
+            Ok(())
+        };
+
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Call(expr) | Self::Comparison(expr) => f.write_str(expr),
+            Self::Not(expr) => match **expr {
+                Self::Bool(_) | Self::Call(_) => write!(f, "!{expr}"),
+                _ => write!(f, "!({expr})"),
+            },
+            Self::All(exprs) => join(f, exprs, "&&"),
+            Self::Any(exprs) => join(f, exprs, "||"),
+        }
+    }
+}
+
+/// Builds the expression of every field an attribute resolves to, matching if any of them does.
+fn any_field(
+    attr: &str,
+    build: impl Fn(Field) -> Result<Expr, PathParseError>,
+) -> Result<Expr, PathParseError> {
+    Ok(Expr::any(
+        normalize_fields(attr)
+            .into_iter()
+            .map(build)
+            .collect::<Result<_, _>>()?,
+    ))
+}
+
+fn exists(field: Field) -> Result<Expr, PathParseError> {
+    let path = path(&field)?;
+
+    Ok(match field {
+        // The tag matches using either 'key' or 'key:value' syntax.
+        Field::Tag(tag) => tags_match(&format!("^{}(:|$)", regex::escape(&tag))),
+        // Literal field 'tags' needs to be compared by key.
+        Field::Reserved(f) if f == "tags" => Expr::all(vec![
+            Expr::call("is_array(.tags)"),
+            Expr::call("includes(array!(.tags), .tags)"),
+        ]),
+        _ => Expr::call(format!("exists({path})")),
+    })
+}
+
+fn equals(field: Field, to_match: &str) -> Result<Expr, PathParseError> {
+    let path = path(&field)?;
+
+    Ok(match field {
+        // Default fields are compared by word boundary.
+        Field::Default(_) => Expr::all(vec![
+            Expr::call(format!("is_string({path})")),
+            Expr::call(format!(
+                "match(string!({path}), {})",
+                regex_literal(&word_regex(to_match))
+            )),
+        ]),
+        // A literal "tags" field should match by key.
+        Field::Reserved(f) if f == "tags" => tags_include(to_match),
+        // Individual tags are compared by element key:value.
+        Field::Tag(tag) => tags_include(&format!("{tag}:{to_match}")),
+        // Everything else is matched by string equality. Only the string representation of
+        // numbers, booleans and null can equal these unquoted values.
+        _ => {
+            let literal = string_literal(to_match);
+            let string = Expr::comparison(format!("{path} == {literal}"));
+
+            match to_match {
+                "true" | "false" => Expr::any(vec![
+                    string,
+                    Expr::comparison(format!("{path} == {to_match}")),
+                ]),
+                "null" => Expr::any(vec![
+                    string,
+                    Expr::all(vec![
+                        Expr::call(format!("exists({path})")),
+                        Expr::comparison(format!("{path} == null")),
+                    ]),
+                ]),
+                _ if to_match.parse::<f64>().is_ok() => Expr::any(vec![
+                    string,
+                    Expr::comparison(format!("encode_json({path}) == {literal}")),
+                ]),
+                _ => string,
+            }
+        }
+    })
+}
+
+fn starts_with(field: Field, prefix: &str) -> Result<Expr, PathParseError> {
+    let path = path(&field)?;
+
+    Ok(match field {
+        // Default fields are matched by word boundary.
+        Field::Default(_) => {
+            let regex = regex_literal(&word_regex(&format!("{prefix}*")));
+            string_value(&path, |value| {
+                Expr::call(format!("match({value}, {regex})"))
+            })
+        }
+        // Tags are recursed until a match is found.
+        Field::Tag(tag) => tags_match(&format!("^{}", regex::escape(&format!("{tag}:{prefix}")))),
+        // All other field types are compared by complete value.
+        _ => {
+            let prefix = string_literal(prefix);
+            string_value(&path, |value| {
+                Expr::call(format!("starts_with({value}, {prefix})"))
+            })
+        }
+    })
+}
+
+fn matches_wildcard(field: Field, wildcard: &str) -> Result<Expr, PathParseError> {
+    let path = path(&field)?;
+
+    Ok(match field {
+        Field::Default(_) => {
+            let regex = regex_literal(&word_regex(wildcard));
+            string_value(&path, |value| {
+                Expr::call(format!("match({value}, {regex})"))
+            })
+        }
+        Field::Tag(tag) => tags_match(wildcard_regex(&format!("{tag}:{wildcard}")).as_str()),
+        _ => {
+            let regex = regex_literal(&wildcard_regex(wildcard));
+            string_value(&path, |value| {
+                Expr::call(format!("match({value}, {regex})"))
+            })
+        }
+    })
+}
+
+fn compare(
+    field: &Field,
+    comparator: Comparison,
+    comparison_value: &ComparisonValue,
+) -> Result<Expr, PathParseError> {
+    let path = path(field)?;
+    let op = comparator.as_lucene();
+    let rhs = string_literal(&comparison_value.to_string());
+    let compare_strings = |path: &str| {
+        string_value(path, |value| {
+            Expr::comparison(format!("{value} {op} {rhs}"))
+        })
+    };
+
+    Ok(match field {
+        // Attributes are compared numerically if the value is numeric, or as strings otherwise.
+        Field::Attribute(_) => {
+            let number = match comparison_value {
+                ComparisonValue::Integer(number) => number.to_string(),
+                ComparisonValue::Float(number) => format!("{number:?}"),
+                ComparisonValue::String(_) | ComparisonValue::Unbounded => {
+                    return Ok(compare_strings(&path));
+                }
+            };
+
+            Expr::any(vec![
+                Expr::all(vec![
+                    Expr::call(format!("is_integer({path})")),
+                    Expr::comparison(format!("to_int!({path}) {op} {number}")),
+                ]),
+                Expr::all(vec![
+                    Expr::call(format!("is_float({path})")),
+                    Expr::comparison(format!("to_float!({path}) {op} {number}")),
+                ]),
+                Expr::all(vec![
+                    Expr::not(Expr::call(format!("is_integer({path})"))),
+                    Expr::not(Expr::call(format!("is_float({path})"))),
+                    compare_strings(&path),
+                ]),
+            ])
+        }
+        // Tag values need extracting by "key:value" to be compared.
+        Field::Tag(_) => Expr::all(vec![
+            Expr::call("is_array(.tags)"),
+            Expr::comparison(format!(
+                "length(filter(array!(.tags)) -> |_index, tag| {{ \
+                 tag = if is_string(tag) {{ string!(tag) }} else {{ encode_json(tag) }}; \
+                 contains(tag, \":\") && replace(tag, r'^[^:]*:', \"\", count: 1) {op} {rhs} \
+                 }}) > 0"
+            )),
+        ]),
+        // All other tag types are compared by string.
+        _ => compare_strings(&path),
+    })
+}
+
+/// Determine whether a field value falls within a range, the same way as `Filter::range`.
+fn range(
+    field: Field,
+    lower: &ComparisonValue,
+    lower_inclusive: bool,
+    upper: &ComparisonValue,
+    upper_inclusive: bool,
+) -> Result<Expr, PathParseError> {
+    let lower_op = if lower_inclusive {
+        Comparison::Gte
+    } else {
+        Comparison::Gt
+    };
+    let upper_op = if upper_inclusive {
+        Comparison::Lte
+    } else {
+        Comparison::Lt
+    };
+
+    match (lower, upper) {
+        (ComparisonValue::Unbounded, ComparisonValue::Unbounded) => exists(field),
+        (ComparisonValue::Unbounded, _) => compare(&field, upper_op, upper),
+        (_, ComparisonValue::Unbounded) => compare(&field, lower_op, lower),
+        _ => Ok(Expr::all(vec![
+            compare(&field, lower_op, lower)?,
+            compare(&field, upper_op, upper)?,
+        ])),
+    }
+}
+
+/// Matches the string representation of the value at `path`: strings as they are, and any other
+/// value as JSON.
+fn string_value(path: &str, matches: impl Fn(&str) -> Expr) -> Expr {
+    Expr::any(vec![
+        Expr::all(vec![
+            Expr::call(format!("is_string({path})")),
+            matches(&format!("string!({path})")),
+        ]),
+        Expr::all(vec![
+            Expr::call(format!("exists({path})")),
+            Expr::not(Expr::call(format!("is_string({path})"))),
+            matches(&format!("encode_json({path})")),
+        ]),
+    ])
+}
+
+/// Whether any of the event's tags matches the regular expression.
+fn tags_match(regex: &str) -> Expr {
+    Expr::all(vec![
+        Expr::call("is_array(.tags)"),
+        Expr::call(format!(
+            "match_array(array!(.tags), {})",
+            regex_literal(&Regex::new(regex).expect("invalid tag regex"))
+        )),
+    ])
+}
+
+/// Whether the event's tags include the given tag.
+fn tags_include(tag: &str) -> Expr {
+    Expr::all(vec![
+        Expr::call("is_array(.tags)"),
+        Expr::call(format!("includes(array!(.tags), {})", string_literal(tag))),
+    ])
+}
+
+/// The VRL path of a field. Tags are all stored in the `tags` field.
+fn path(field: &Field) -> Result<String, PathParseError> {
+    let path = match field {
+        Field::Default(p) | Field::Reserved(p) | Field::Attribute(p) => parse_value_path(p)?,
+        Field::Tag(_) => parse_value_path("tags")?,
+    };
+
+    Ok(OwnedTargetPath::event(path).to_string())
+}
+
+fn regex_literal(regex: &Regex) -> String {
+    format!("r'{}'", regex.as_str().replace('\'', r"\'"))
+}
+
+#[cfg(all(test, feature = "stdlib"))]
+mod tests {
+    use super::*;
+    use crate::compiler::runtime::Runtime;
+    use crate::compiler::state::RuntimeState;
+    use crate::compiler::{TargetValue, TimeZone, compile};
+    use crate::value::{ObjectMap, Secrets, Value};
+
+    fn run(source: &str, event: &Value) -> Value {
+        let program = compile(source, &crate::stdlib::all())
+            .unwrap_or_else(|diagnostics| panic!("{source}: {diagnostics:?}"))
+            .program;
+        let mut target = TargetValue {
+            value: event.clone(),
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: Secrets::new(),
+        };
+
+        Runtime::new(RuntimeState::default())
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn matches_like_match_datadog_query() {
+        let queries = [
+            "*",
+            "-*:*",
+            "error",
+            "erro*",
+            "e*r",
+            "\"bad request\"",
+            "_exists_:@a",
+            "_missing_:@a",
+            "_exists_:env",
+            "_exists_:tags",
+            "service:web",
+            "service:web*",
+            "service:w?b",
+            "host:5",
+            "@a:5",
+            "@a:true",
+            "@a:null",
+            "@a:>4",
+            "@a:<=4.5",
+            "@a:>=abc",
+            "@a:[4 TO 10]",
+            "@a:{4 TO *}",
+            "@a:[* TO *]",
+            "@b:>abc",
+            "@b.c:\"it's {{x}}\"",
+            "env:prod",
+            "env:pro*",
+            "env:*od",
+            "env:>oa",
+            "tags:x",
+            "service:web AND NOT @a:>4",
+            "service:web OR (env:prod AND error)",
+            "NOT (service:web OR @a:5)",
+        ];
+        let events = [
+            serde_json::json!({}),
+            serde_json::json!({
+                "message": "an error occurred: bad request",
+                "service": "web",
+                "host": 5,
+                "a": 5,
+                "b": "abd",
+                "tags": ["env:prod", "x"],
+            }),
+            serde_json::json!({
+                "message": 5,
+                "service": "webapp",
+                "a": 4.5,
+                "b": {"c": "it's {{x}}"},
+                "tags": ["env", "y:pro"],
+            }),
+            serde_json::json!({
+                "custom": {"title": "err"},
+                "service": true,
+                "a": "abc",
+                "b": null,
+                "tags": "env:prod",
+            }),
+            serde_json::json!({"a": true, "service": null, "host": "5"}),
+            serde_json::json!({"a": null}),
+        ];
+
+        for query in queries {
+            let node: QueryNode = query.parse().unwrap();
+            let expression = node.to_vrl().unwrap();
+            let literal = query.replace('\'', r"\'");
+
+            for event in &events {
+                let event = Value::from(event.clone());
+                let expected = run(&format!("match_datadog_query(., s'{literal}')"), &event);
+                let actual = run(&expression, &event);
+
+                assert_eq!(actual, expected, "{query} => {expression} on {event}");
+            }
+        }
+    }
+
+    #[test]
+    fn readable_expressions() {
+        let to_vrl = |query: &str| query.parse::<QueryNode>().unwrap().to_vrl().unwrap();
+
+        assert_eq!(to_vrl("*"), "true");
+        assert_eq!(to_vrl("service:web"), r#".service == "web""#);
+        assert_eq!(
+            to_vrl("service:web AND (_exists_:@http.status OR env:prod)"),
+            r#".service == "web" && (exists(.http.status) || (is_array(.tags) && includes(array!(.tags), "env:prod")))"#
+        );
+        assert_eq!(to_vrl("NOT source:nginx"), r#"!(.source == "nginx")"#);
+    }
+}
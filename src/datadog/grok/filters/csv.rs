@@ -0,0 +1,116 @@
+use std::fmt::Formatter;
+
+use crate::parsing::csv::parse_csv_record;
+use crate::value::{ObjectMap, Value};
+
+use super::super::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok::InternalError,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+const DEFAULT_SEPARATOR: u8 = b',';
+const DEFAULT_QUOTE: u8 = b'"';
+
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    let filter = CsvFilter::from_args(f.args.as_deref().unwrap_or_default())
+        .ok_or_else(|| GrokStaticError::InvalidFunctionArguments(f.name.clone()))?;
+    Ok(GrokFilter::Csv(filter))
+}
+
+/// Parses a CSV (or TSV) formatted row into an object keyed by the configured headers, as in
+/// `csv(headers[, separator[, quotingcharacter]])`.
+#[derive(Debug, Clone)]
+pub struct CsvFilter {
+    pub headers: Vec<String>,
+    pub separator: u8,
+    pub quote: u8,
+}
+
+impl std::fmt::Display for CsvFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "csv(\"{}\", \"{}\", \"{}\")",
+            self.headers.join(","),
+            self.separator as char,
+            self.quote as char
+        )
+    }
+}
+
+impl CsvFilter {
+    fn from_args(args: &[FunctionArgument]) -> Option<Self> {
+        let mut args = args.iter();
+
+        let headers = match args.next() {
+            Some(FunctionArgument::Arg(Value::Bytes(bytes))) => {
+                parse_csv_record(bytes, DEFAULT_SEPARATOR, DEFAULT_QUOTE)
+                    .ok()?
+                    .iter()
+                    .map(|header| String::from_utf8_lossy(header).into_owned())
+                    .collect::<Vec<_>>()
+            }
+            _ => return None,
+        };
+        if headers.is_empty() {
+            return None;
+        }
+
+        let separator = single_byte(args.next(), DEFAULT_SEPARATOR)?;
+        let quote = single_byte(args.next(), DEFAULT_QUOTE)?;
+
+        if args.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            headers,
+            separator,
+            quote,
+        })
+    }
+
+    /// Empty values are omitted from the result, as are values without a matching header.
+    /// Numeric values are converted to integers or floats.
+    pub fn apply_filter(&self, value: &Value) -> Result<Value, InternalError> {
+        let error = || InternalError::FailedToApplyFilter(self.to_string(), value.to_string());
+
+        let Value::Bytes(bytes) = value else {
+            return Err(error());
+        };
+
+        let fields = parse_csv_record(bytes, self.separator, self.quote).map_err(|_| error())?;
+
+        Ok(self
+            .headers
+            .iter()
+            .zip(fields)
+            .filter(|(_, field)| !field.is_empty())
+            .map(|(header, field)| (header.as_str().into(), parse_field(&field)))
+            .collect::<ObjectMap>()
+            .into())
+    }
+}
+
+fn single_byte(arg: Option<&FunctionArgument>, default: u8) -> Option<u8> {
+    match arg {
+        None => Some(default),
+        Some(FunctionArgument::Arg(Value::Bytes(bytes))) if bytes.as_ref() == b"tab" => Some(b'\t'),
+        Some(FunctionArgument::Arg(Value::Bytes(bytes))) if bytes.len() == 1 => Some(bytes[0]),
+        Some(_) => None,
+    }
+}
+
+fn parse_field(field: &[u8]) -> Value {
+    let field = String::from_utf8_lossy(field);
+
+    if let Ok(integer) = field.parse::<i64>() {
+        integer.into()
+    } else if let Ok(float) = field.parse::<f64>().map(Value::from_f64_or_zero) {
+        float
+    } else {
+        field.into_owned().into()
+    }
+}
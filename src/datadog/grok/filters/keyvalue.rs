@@ -1,7 +1,6 @@
-use std::collections::BTreeMap;
 use std::fmt::Formatter;
 
-use crate::value::Value;
+use crate::value::{ObjectMap, Value};
 use bytes::Bytes;
 use fancy_regex::{Captures, Regex};
 use nom::combinator::eof;
@@ -168,7 +167,7 @@ impl KeyValueFilter {
     pub fn apply_filter(&self, value: &Value) -> Result<Value, InternalError> {
         match value {
             Value::Bytes(bytes) => {
-                let mut result = Value::Object(BTreeMap::default());
+                let mut result = Value::Object(ObjectMap::new());
                 let value = String::from_utf8_lossy(bytes);
                 self.re_pattern.captures_iter(value.as_ref()).for_each(|c| {
                     self.parse_key_value_capture(&mut result, c);
@@ -1,2 +1,5 @@
 pub mod array;
+pub mod csv;
 pub mod keyvalue;
+pub mod url;
+pub mod useragent;
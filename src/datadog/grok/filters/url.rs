@@ -0,0 +1,34 @@
+use url::Url;
+
+use crate::parsing::url::url_to_value;
+use crate::value::{ObjectMap, Value};
+
+/// Splits a URL into the components Datadog reports as `url_details`: `scheme`, `host`, `port`,
+/// `path` and `queryString`. Components that are not present in the URL are omitted.
+pub fn parse(url: &str) -> Option<Value> {
+    let url = Url::parse(url).ok()?;
+
+    let Value::Object(mut components) = url_to_value(&url, false) else {
+        return None;
+    };
+
+    let mut details = ObjectMap::new();
+    for (from, to) in [
+        ("scheme", "scheme"),
+        ("host", "host"),
+        ("port", "port"),
+        ("path", "path"),
+        ("query", "queryString"),
+    ] {
+        match components.remove(from) {
+            None | Some(Value::Null) => {}
+            Some(Value::Object(query)) if query.is_empty() => {}
+            Some(Value::Bytes(bytes)) if bytes.is_empty() => {}
+            Some(value) => {
+                details.insert(to.into(), value);
+            }
+        }
+    }
+
+    Some(details.into())
+}
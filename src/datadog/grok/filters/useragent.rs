@@ -0,0 +1,82 @@
+use percent_encoding::percent_decode;
+use woothee::parser::Parser as WootheeParser;
+
+use crate::parsing::user_agent::{Parser, UA_EXTRACTOR, UserAgent};
+use crate::value::{ObjectMap, Value};
+
+use super::super::{
+    ast::{Function, FunctionArgument},
+    grok_filter::GrokFilter,
+    parse_grok_rules::Error as GrokStaticError,
+};
+
+pub fn filter_from_function(f: &Function) -> Result<GrokFilter, GrokStaticError> {
+    match f.args.as_deref() {
+        None | Some([]) => Ok(GrokFilter::UserAgent(false)),
+        Some([FunctionArgument::Arg(Value::Boolean(decode))]) => Ok(GrokFilter::UserAgent(*decode)),
+        Some(_) => Err(GrokStaticError::InvalidFunctionArguments(f.name.clone())),
+    }
+}
+
+/// Extracts the `browser`, `os` and `device` of a user agent in the shape of Datadog's
+/// `useragent` filter, with the input optionally URI-decoded first. Attributes that can't be
+/// determined are omitted.
+pub fn parse(user_agent: &[u8], decode_uri_component: bool) -> Value {
+    let user_agent = if decode_uri_component {
+        percent_decode(user_agent).decode_utf8_lossy()
+    } else {
+        String::from_utf8_lossy(user_agent)
+    };
+
+    let UserAgent {
+        browser,
+        os,
+        device,
+    } = UA_EXTRACTOR
+        .parse_user_agent(&user_agent)
+        .or(WootheeParser::new().parse_user_agent(&user_agent));
+
+    let category = match device.category.as_deref() {
+        Some("pc") => "Desktop",
+        Some("smartphone" | "mobilephone") => "Mobile",
+        Some("crawler") => "Bot",
+        _ => "Other",
+    };
+
+    ObjectMap::from([
+        (
+            "browser".into(),
+            into_value([
+                ("family", browser.family),
+                ("major", browser.major),
+                ("minor", browser.minor),
+                ("patch", browser.patch),
+            ]),
+        ),
+        (
+            "os".into(),
+            into_value([
+                ("family", os.family),
+                ("major", os.major),
+                ("minor", os.minor),
+                ("patch", os.patch),
+                ("patch_minor", os.patch_minor),
+            ]),
+        ),
+        (
+            "device".into(),
+            into_value([
+                ("family", device.family),
+                ("category", Some(category.to_owned())),
+            ]),
+        ),
+    ])
+    .into()
+}
+
+fn into_value<'a>(iter: impl IntoIterator<Item = (&'a str, Option<String>)>) -> Value {
+    iter.into_iter()
+        .filter_map(|(name, value)| Some((name.into(), value?.into())))
+        .collect::<ObjectMap>()
+        .into()
+}
@@ -38,6 +38,11 @@ impl<'a> Matches<'a> {
         Matches { captures, names }
     }
 
+    /// Returns the part of the text matched by the whole pattern.
+    pub fn matched(&self) -> &str {
+        self.captures.at(0).unwrap_or("")
+    }
+
     /// Returns a tuple of key/value with all the matches found.
     ///
     /// Note that if no match is found, the value is empty.
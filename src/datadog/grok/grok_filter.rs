@@ -10,7 +10,7 @@ use percent_encoding::percent_decode;
 
 use super::{
     ast::{Function, FunctionArgument},
-    filters::{array, keyvalue, keyvalue::KeyValueFilter},
+    filters::{array, csv, csv::CsvFilter, keyvalue, keyvalue::KeyValueFilter, url, useragent},
     matchers::date::{DateFilter, apply_date_filter},
     parse_grok::InternalError,
     parse_grok_rules::Error as GrokStaticError,
@@ -41,6 +41,10 @@ pub enum GrokFilter {
         Box<Option<GrokFilter>>,
     ),
     KeyValue(KeyValueFilter),
+    Csv(CsvFilter),
+    Url,
+    // whether to URI-decode the value before parsing it
+    UserAgent(bool),
 }
 
 impl fmt::Display for GrokFilter {
@@ -63,6 +67,9 @@ impl fmt::Display for GrokFilter {
             GrokFilter::Xml => f.pad("Xml"),
             GrokFilter::Array(..) => f.pad("Array(..)"),
             GrokFilter::KeyValue(..) => f.pad("KeyValue(..)"),
+            GrokFilter::Csv(..) => f.pad("Csv(..)"),
+            GrokFilter::Url => f.pad("Url"),
+            GrokFilter::UserAgent(..) => f.pad("UserAgent(..)"),
         }
     }
 }
@@ -112,6 +119,9 @@ impl TryFrom<&Function> for GrokFilter {
                 .ok_or_else(|| GrokStaticError::InvalidFunctionArguments(f.name.clone())),
             "array" => array::filter_from_function(f),
             "keyvalue" => keyvalue::filter_from_function(f),
+            "csv" => csv::filter_from_function(f),
+            "url" => Ok(GrokFilter::Url),
+            "useragent" => useragent::filter_from_function(f),
             _ => Err(GrokStaticError::UnknownFilter(f.name.clone())),
         }
     }
@@ -240,6 +250,21 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, Interna
         },
         GrokFilter::Date(date_filter) => apply_date_filter(value, date_filter),
         GrokFilter::KeyValue(keyvalue_filter) => keyvalue_filter.apply_filter(value),
+        GrokFilter::Csv(csv_filter) => csv_filter.apply_filter(value),
+        GrokFilter::Url => match value {
+            Value::Bytes(bytes) => {
+                url::parse(String::from_utf8_lossy(bytes).as_ref()).ok_or_else(|| {
+                    InternalError::FailedToApplyFilter(filter.to_string(), value.to_string())
+                })
+            }
+            _ => Err(InternalError::FailedToApplyFilter(
+                filter.to_string(),
+                value.to_string(),
+            )),
+        },
+        GrokFilter::UserAgent(decode_uri_component) => parse_value(value, filter, |b| {
+            useragent::parse(b, *decode_uri_component)
+        }),
         GrokFilter::Array(brackets, delimiter, value_filter) => match value {
             Value::Bytes(bytes) => array::parse(
                 String::from_utf8_lossy(bytes).as_ref(),
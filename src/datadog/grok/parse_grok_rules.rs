@@ -48,6 +48,20 @@ pub struct GrokRuleParseContext {
     pub aliases: BTreeMap<KeyString, String>,
     /// used to detect cycles in alias definitions
     pub alias_stack: Vec<String>,
+    /// the top-level segments of the rule, with aliases expanded
+    pub segments: Vec<GrokSegment>,
+}
+
+/// A piece of a grok rule as written: either literal regex text or a single `%{...}` pattern.
+/// Rules are split into segments to find out how much of a rule matches a value when debugging.
+#[derive(Debug, Clone)]
+pub struct GrokSegment {
+    /// the text of the segment in the rule
+    pub source: String,
+    /// the destination field of the pattern, if any
+    pub field: Option<String>,
+    /// the length of the rule's regular expression up to and including this segment
+    pub regex_end: usize,
 }
 
 impl GrokRuleParseContext {
@@ -68,12 +82,22 @@ impl GrokRuleParseContext {
             .and_modify(|v| v.filters.insert(0, filter));
     }
 
+    /// marks the end of a segment of the rule
+    fn push_segment(&mut self, source: &str, field: Option<String>) {
+        self.segments.push(GrokSegment {
+            source: source.to_string(),
+            field,
+            regex_end: self.regex.len(),
+        });
+    }
+
     fn new(aliases: BTreeMap<KeyString, String>) -> Self {
         Self {
             regex: String::new(),
             fields: HashMap::new(),
             aliases,
             alias_stack: vec![],
+            segments: vec![],
         }
     }
 
@@ -175,16 +199,31 @@ fn parse_pattern(
     grok: &mut Grok,
 ) -> Result<GrokRule, Error> {
     parse_grok_rule(pattern, context)?;
+    compile_rule(&context.regex, &context.fields, grok, true)
+}
+
+/// Compiles the regular expression of a rule, anchored at the beginning of the value and, if
+/// `whole` is set, at its end.
+///
+/// # Arguments
+///
+/// - `regex` - the regular expression built from the rule
+/// - `fields` - the fields extracted by the rule
+/// - `grok` - an instance of Grok parser
+/// - `whole` - whether the rule must match the whole value
+pub(super) fn compile_rule(
+    regex: &str,
+    fields: &HashMap<String, GrokField>,
+    grok: &mut Grok,
+    whole: bool,
+) -> Result<GrokRule, Error> {
     let pattern = [
         // In Oniguruma the (?m) modifier is used to enable the DOTALL mode(dot includes newlines),
         // as opposed to the (?s) modifier in other regex flavors.
         // \A, \z - parses from the beginning to the end of string, not line(until \n)
         r"(?m)\A", // (?m) enables the DOTALL mode by default
-        &context
-            .regex
-            .replace("(?s)", "(?m)")
-            .replace("(?-s)", "(?-m)"),
-        r"\z",
+        &regex.replace("(?s)", "(?m)").replace("(?-s)", "(?-m)"),
+        if whole { r"\z" } else { "" },
     ]
     .concat();
 
@@ -195,10 +234,22 @@ fn parse_pattern(
 
     Ok(GrokRule {
         pattern,
-        fields: context.fields.clone(),
+        fields: fields.clone(),
     })
 }
 
+/// Parses a single pattern into its regular expression and segments, without compiling it.
+/// Used to find the longest prefix of the pattern matching a value.
+pub(super) fn parse_grok_rule_segments(
+    pattern: &str,
+    aliases: BTreeMap<KeyString, String>,
+) -> Result<GrokRuleParseContext, Error> {
+    let mut context = GrokRuleParseContext::new(aliases);
+    parse_grok_rule(pattern, &mut context)?;
+
+    Ok(context)
+}
+
 /// Parses a given rule to a pure grok pattern with a set of post-processing filters.
 ///
 /// # Arguments
@@ -209,17 +260,36 @@ fn parse_pattern(
 fn parse_grok_rule(rule: &str, context: &mut GrokRuleParseContext) -> Result<(), Error> {
     let mut regex_i = 0;
     for (start, end) in GROK_PATTERN_RE.find_iter(rule) {
-        context.append_regex(&rule[regex_i..start]);
+        append_literal(&rule[regex_i..start], context);
         regex_i = end;
         let pattern = parse_grok_pattern(&rule[start..end])
             .map_err(|e| Error::InvalidGrokExpression(rule[start..end].to_string(), e))?;
+
+        let segments = context.segments.len();
         resolve_grok_pattern(&pattern, context)?;
+        // references to aliases without a destination keep the segments of the alias definition
+        if pattern.destination.is_some() || context.segments.len() == segments {
+            context.segments.truncate(segments);
+            let field = pattern
+                .destination
+                .as_ref()
+                .map(|destination| destination.path.to_string());
+            context.push_segment(&rule[start..end], field);
+        }
     }
-    context.append_regex(&rule[regex_i..]);
+    append_literal(&rule[regex_i..], context);
 
     Ok(())
 }
 
+/// Appends literal regex text of a rule as its own segment.
+fn append_literal(literal: &str, context: &mut GrokRuleParseContext) {
+    if !literal.is_empty() {
+        context.append_regex(literal);
+        context.push_segment(literal, None);
+    }
+}
+
 /// Converts each rule to a pure grok rule:
 ///  - strips filters and collects them to apply later
 ///  - replaces references to aliases with their definitions
@@ -1,8 +1,39 @@
 #[cfg(feature = "datadog_filter")]
 pub mod filter;
 
+#[cfg(feature = "datadog_pipeline")]
+pub mod pipeline;
+
 #[cfg(all(feature = "datadog_grok", not(target_arch = "wasm32")))]
 pub mod grok;
 
 #[cfg(feature = "datadog_search")]
 pub mod search;
+
+/// Quote a string as a VRL string literal, escaping template delimiters.
+#[cfg(any(feature = "datadog_filter", feature = "datadog_pipeline"))]
+pub(crate) fn string_literal(string: &str) -> String {
+    let escaped = string
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+        .replace('\r', r"\r")
+        .replace('\t', r"\t")
+        .replace("{{", r"\{{")
+        .replace("}}", r"\}}");
+
+    format!("\"{escaped}\"")
+}
+
+#[cfg(all(test, any(feature = "datadog_filter", feature = "datadog_pipeline")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_literals() {
+        assert_eq!(
+            string_literal(r#"%{notSpace:a} "\d+" {{b}}"#),
+            r#""%{notSpace:a} \"\\d+\" \{{b\}}""#
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use serde::Deserialize;
+
+/// A Datadog log pipeline, as exported from the Datadog UI or API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Pipeline {
+    #[serde(flatten)]
+    pub common: Common,
+
+    /// Only logs matching this filter are processed by the pipeline.
+    #[serde(default)]
+    pub filter: Option<Filter>,
+
+    #[serde(default)]
+    pub processors: Vec<Processor>,
+}
+
+/// The fields shared by pipelines and all processors.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Common {
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default = "enabled")]
+    pub is_enabled: bool,
+}
+
+const fn enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Filter {
+    #[serde(default)]
+    pub query: String,
+}
+
+/// A processor of a pipeline. Processors that can't be deserialized into one of the supported
+/// processors are kept as [`Processor::Unsupported`], so that they can be reported.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Processor {
+    Supported(SupportedProcessor),
+    Unsupported(UnsupportedProcessor),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum SupportedProcessor {
+    GrokParser(GrokParser),
+    AttributeRemapper(AttributeRemapper),
+    CategoryProcessor(CategoryProcessor),
+    DateRemapper(Remapper),
+    StatusRemapper(Remapper),
+    ServiceRemapper(Remapper),
+    MessageRemapper(Remapper),
+    Pipeline(Pipeline),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UnsupportedProcessor {
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    #[serde(flatten)]
+    pub common: Common,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GrokParser {
+    #[serde(flatten)]
+    pub common: Common,
+
+    /// The attribute to parse.
+    #[serde(default = "message")]
+    pub source: String,
+
+    pub grok: Grok,
+}
+
+fn message() -> String {
+    "message".to_owned()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Grok {
+    /// Newline separated `name pattern` rules, tried in order.
+    pub match_rules: String,
+
+    /// Newline separated `name pattern` rules that can be referenced by the match rules.
+    #[serde(default)]
+    pub support_rules: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AttributeRemapper {
+    #[serde(flatten)]
+    pub common: Common,
+
+    pub sources: Vec<String>,
+
+    #[serde(default)]
+    pub source_type: AttributeType,
+
+    pub target: String,
+
+    #[serde(default)]
+    pub target_type: AttributeType,
+
+    #[serde(default)]
+    pub target_format: Option<TargetFormat>,
+
+    #[serde(default)]
+    pub preserve_source: bool,
+
+    #[serde(default)]
+    pub override_on_conflict: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttributeType {
+    #[default]
+    Attribute,
+    Tag,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetFormat {
+    Auto,
+    String,
+    Integer,
+    Double,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CategoryProcessor {
+    #[serde(flatten)]
+    pub common: Common,
+
+    pub target: String,
+
+    pub categories: Vec<Category>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Category {
+    pub filter: Filter,
+    pub name: String,
+}
+
+/// A processor that remaps the first existing source attribute onto a reserved attribute, such
+/// as the date or status of a log.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Remapper {
+    #[serde(flatten)]
+    pub common: Common,
+
+    pub sources: Vec<String>,
+}
@@ -0,0 +1,9 @@
+#![deny(warnings, clippy::pedantic)]
+
+//! Converts exported Datadog log pipeline definitions into equivalent VRL programs.
+
+mod definition;
+mod translate;
+
+pub use definition::*;
+pub use translate::{Error, import, to_vrl};
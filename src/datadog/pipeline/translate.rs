@@ -0,0 +1,420 @@
+use std::fmt::Write;
+
+use serde::Deserialize;
+
+use super::definition::{
+    AttributeRemapper, AttributeType, CategoryProcessor, Common, GrokParser, Pipeline, Processor,
+    Remapper, SupportedProcessor, TargetFormat, UnsupportedProcessor,
+};
+use crate::datadog::string_literal;
+use crate::path::{OwnedTargetPath, OwnedValuePath};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid pipeline definition: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// The exported JSON is either a single pipeline or a list of pipelines.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Export {
+    Pipelines(Vec<Pipeline>),
+    Pipeline(Pipeline),
+}
+
+/// Translate an exported Datadog pipeline definition (JSON) into VRL source.
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid pipeline definition.
+pub fn import(json: &str) -> Result<String, Error> {
+    let pipelines = match serde_json::from_str(json)? {
+        Export::Pipelines(pipelines) => pipelines,
+        Export::Pipeline(pipeline) => vec![pipeline],
+    };
+
+    let mut writer = Writer::default();
+    for (i, pipeline) in pipelines.iter().enumerate() {
+        if i > 0 {
+            writer.blank();
+        }
+        writer.pipeline(pipeline);
+    }
+
+    Ok(writer.output)
+}
+
+/// Translate a Datadog pipeline into VRL source.
+///
+/// Processors that have no VRL equivalent are left as comments in the program.
+#[must_use]
+pub fn to_vrl(pipeline: &Pipeline) -> String {
+    let mut writer = Writer::default();
+    writer.pipeline(pipeline);
+    writer.output
+}
+
+#[derive(Default)]
+struct Writer {
+    output: String,
+    indent: usize,
+}
+
+impl Writer {
+    fn line(&mut self, line: &str) {
+        writeln!(self.output, "{:width$}{line}", "", width = self.indent * 2)
+            .expect("writing to a string can't fail");
+    }
+
+    fn blank(&mut self) {
+        self.output.push('\n');
+    }
+
+    fn block(&mut self, header: &str, body: impl FnOnce(&mut Self)) {
+        self.line(&format!("{header} {{"));
+        self.indent += 1;
+        body(self);
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    /// Write the comment describing a processor, returning `false` if the processor is disabled
+    /// and should be skipped.
+    fn header(&mut self, kind: &str, common: &Common) -> bool {
+        let name = common
+            .name
+            .as_deref()
+            .map(|name| format!(": {name}"))
+            .unwrap_or_default();
+
+        if common.is_enabled {
+            self.line(&format!("# {kind}{name}"));
+        } else {
+            self.line(&format!("# disabled {kind} skipped{name}"));
+        }
+
+        common.is_enabled
+    }
+
+    fn pipeline(&mut self, pipeline: &Pipeline) {
+        if !self.header("pipeline", &pipeline.common) {
+            return;
+        }
+
+        let processors = |writer: &mut Self| {
+            for (i, processor) in pipeline.processors.iter().enumerate() {
+                if i > 0 {
+                    writer.blank();
+                }
+                writer.processor(processor);
+            }
+        };
+
+        match pipeline.filter.as_ref().map(|filter| filter.query.trim()) {
+            Some(query) if !query.is_empty() && query != "*" => self.block(
+                &format!("if match_datadog_query(., {})", string_literal(query)),
+                processors,
+            ),
+            _ => processors(self),
+        }
+    }
+
+    fn processor(&mut self, processor: &Processor) {
+        match processor {
+            Processor::Supported(processor) => match processor {
+                SupportedProcessor::GrokParser(parser) => self.grok_parser(parser),
+                SupportedProcessor::AttributeRemapper(remapper) => {
+                    self.attribute_remapper(remapper);
+                }
+                SupportedProcessor::CategoryProcessor(processor) => {
+                    self.category_processor(processor);
+                }
+                SupportedProcessor::DateRemapper(remapper) => self.date_remapper(remapper),
+                SupportedProcessor::StatusRemapper(remapper) => {
+                    self.reserved_remapper("status remapper", remapper, "status");
+                }
+                SupportedProcessor::ServiceRemapper(remapper) => {
+                    self.reserved_remapper("service remapper", remapper, "service");
+                }
+                SupportedProcessor::MessageRemapper(remapper) => {
+                    self.reserved_remapper("message remapper", remapper, "message");
+                }
+                SupportedProcessor::Pipeline(pipeline) => self.pipeline(pipeline),
+            },
+            Processor::Unsupported(UnsupportedProcessor { kind, common }) => {
+                let name = common
+                    .name
+                    .as_deref()
+                    .map(|name| format!(": {name}"))
+                    .unwrap_or_default();
+                self.line(&format!("# unsupported processor `{kind}`{name}"));
+            }
+        }
+    }
+
+    fn grok_parser(&mut self, parser: &GrokParser) {
+        if !self.header("grok parser", &parser.common) {
+            return;
+        }
+
+        let patterns = rules(&parser.grok.match_rules)
+            .map(|(_, pattern)| pattern)
+            .collect::<Vec<_>>();
+        let aliases = rules(&parser.grok.support_rules).collect::<Vec<_>>();
+
+        self.line("parsed, err = parse_groks(");
+        self.indent += 1;
+        self.line(&format!("string!({}),", path(&parser.source)));
+        self.line("patterns: [");
+        self.indent += 1;
+        for pattern in patterns {
+            self.line(&format!("{},", string_literal(pattern)));
+        }
+        self.indent -= 1;
+        if aliases.is_empty() {
+            self.line("]");
+        } else {
+            self.line("],");
+            self.line("aliases: {");
+            self.indent += 1;
+            for (name, pattern) in aliases {
+                self.line(&format!(
+                    "{}: {},",
+                    string_literal(name),
+                    string_literal(pattern)
+                ));
+            }
+            self.indent -= 1;
+            self.line("}");
+        }
+        self.indent -= 1;
+        self.line(")");
+        self.block("if err == null", |writer| {
+            writer.line(". = merge(., parsed, deep: true)");
+        });
+    }
+
+    fn attribute_remapper(&mut self, remapper: &AttributeRemapper) {
+        if !self.header("attribute remapper", &remapper.common) {
+            return;
+        }
+
+        if remapper.source_type == AttributeType::Tag || remapper.target_type == AttributeType::Tag
+        {
+            self.line("# remapping tags is not supported");
+            return;
+        }
+
+        let target = path(&remapper.target);
+        let condition = |source: &String| {
+            if remapper.override_on_conflict {
+                format!("exists({})", path(source))
+            } else {
+                format!("exists({}) && !exists({target})", path(source))
+            }
+        };
+
+        self.chain(&remapper.sources, condition, |writer, source| {
+            let source = path(source);
+            let value = if remapper.preserve_source {
+                source
+            } else {
+                format!("del({source})")
+            };
+
+            match remapper.target_format.and_then(conversion) {
+                Some(function) => {
+                    writer.line(&format!("value = {value}"));
+                    writer.line(&format!("{target} = {function}(value) ?? value"));
+                }
+                None => writer.line(&format!("{target} = {value}")),
+            }
+        });
+    }
+
+    fn category_processor(&mut self, processor: &CategoryProcessor) {
+        if !self.header("category processor", &processor.common) {
+            return;
+        }
+
+        let target = path(&processor.target);
+        self.chain(
+            &processor.categories,
+            |category| {
+                format!(
+                    "match_datadog_query(., {})",
+                    string_literal(&category.filter.query)
+                )
+            },
+            |writer, category| {
+                writer.line(&format!("{target} = {}", string_literal(&category.name)));
+            },
+        );
+    }
+
+    fn date_remapper(&mut self, remapper: &Remapper) {
+        if !self.header("date remapper", &remapper.common) {
+            return;
+        }
+
+        self.first_existing(&remapper.sources, |writer, source| {
+            writer.line(&format!(
+                "date, err = parse_timestamp(string!({source}), \"%+\")"
+            ));
+            writer.block("if err != null", |writer| {
+                writer.line(&format!(
+                    "date, err = from_unix_timestamp(to_int!({source}), unit: \"milliseconds\")"
+                ));
+            });
+            writer.block("if err == null", |writer| {
+                writer.line(".timestamp = date");
+            });
+        });
+    }
+
+    fn reserved_remapper(&mut self, kind: &str, remapper: &Remapper, target: &str) {
+        if !self.header(kind, &remapper.common) {
+            return;
+        }
+
+        let target = path(target);
+        self.first_existing(&remapper.sources, |writer, source| {
+            writer.line(&format!("{target} = {source}"));
+        });
+    }
+
+    /// Write an `if` chain that runs `body` for the first of the `sources` that exists.
+    fn first_existing(&mut self, sources: &[String], mut body: impl FnMut(&mut Self, &str)) {
+        self.chain(
+            sources,
+            |source| format!("exists({})", path(source)),
+            |writer, source| body(writer, &path(source)),
+        );
+    }
+
+    /// Write an `if`/`else if` chain that runs `body` for the first item whose `condition` holds.
+    fn chain<T>(
+        &mut self,
+        items: &[T],
+        condition: impl Fn(&T) -> String,
+        mut body: impl FnMut(&mut Self, &T),
+    ) {
+        for (i, item) in items.iter().enumerate() {
+            let keyword = if i == 0 { "if" } else { "} else if" };
+
+            self.line(&format!("{keyword} {} {{", condition(item)));
+            self.indent += 1;
+            body(self, item);
+            self.indent -= 1;
+        }
+
+        if !items.is_empty() {
+            self.line("}");
+        }
+    }
+}
+
+/// The VRL function converting a value to the given format, if any.
+fn conversion(format: TargetFormat) -> Option<&'static str> {
+    match format {
+        TargetFormat::Auto => None,
+        TargetFormat::String => Some("to_string"),
+        TargetFormat::Integer => Some("to_int"),
+        TargetFormat::Double => Some("to_float"),
+    }
+}
+
+/// Parse newline separated `name pattern` grok rules.
+fn rules(rules: &str) -> impl Iterator<Item = (&str, &str)> {
+    rules.lines().filter_map(|line| {
+        let (name, pattern) = line.trim().split_once(char::is_whitespace)?;
+        Some((name, pattern.trim_start()))
+    })
+}
+
+/// Convert a Datadog attribute name, e.g. `http.status_code`, into a VRL event path.
+fn path(attribute: &str) -> String {
+    let mut path = OwnedValuePath::root();
+    for field in attribute.trim_start_matches('@').split('.') {
+        path.push_field(field);
+    }
+
+    OwnedTargetPath::event(path).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn attribute_paths() {
+        assert_eq!(path("http.status_code"), ".http.status_code");
+        assert_eq!(path("@network.client.ip"), ".network.client.ip");
+        assert_eq!(path("user-agent"), r#"."user-agent""#);
+    }
+
+    #[test]
+    fn unsupported_and_disabled_processors() {
+        let source = import(
+            r#"{
+                "type": "pipeline",
+                "name": "test",
+                "filter": { "query": "*" },
+                "processors": [
+                    { "type": "geo-ip-parser", "name": "geo", "sources": ["ip"], "target": "geo" },
+                    { "type": "status-remapper", "name": "status", "is_enabled": false, "sources": ["level"] }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            source,
+            indoc! {"
+                # pipeline: test
+                # unsupported processor `geo-ip-parser`: geo
+
+                # disabled status remapper skipped: status
+            "}
+        );
+    }
+
+    #[test]
+    fn attribute_remapper() {
+        let source = import(
+            r#"{
+                "processors": [{
+                    "type": "attribute-remapper",
+                    "sources": ["status_code", "code"],
+                    "target": "http.status_code",
+                    "target_format": "integer",
+                    "preserve_source": true
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            source,
+            indoc! {"
+                # pipeline
+                # attribute remapper
+                if exists(.status_code) && !exists(.http.status_code) {
+                  value = .status_code
+                  .http.status_code = to_int(value) ?? value
+                } else if exists(.code) && !exists(.http.status_code) {
+                  value = .code
+                  .http.status_code = to_int(value) ?? value
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn invalid_definition() {
+        assert!(matches!(import("[1]"), Err(Error::Json(_))));
+    }
+}
@@ -9,6 +9,8 @@ use super::node::{Comparison, ComparisonValue, QueryNode, Range};
 
 #[derive(Debug, Parser)]
 #[grammar = "src/datadog/search/grammar.pest"]
+#[grammar = "src/datadog/search/lucene.pest"]
+#[grammar = "src/datadog/search/kql.pest"]
 pub struct EventPlatformQuery;
 
 pub const DEFAULT_FIELD: &str = "_default_";
@@ -25,15 +27,19 @@ impl QueryVisitor {
     pub fn visit_queryroot(token: Pair<Rule>, default_field: &str) -> QueryNode {
         let contents = token.into_inner().next().unwrap();
         match contents.as_rule() {
-            Rule::query => Self::visit_query(contents, default_field),
+            Rule::query | Rule::lucene_query => Self::visit_query(contents, default_field),
             // A queryroot will only ever contain a query
             _ => unreachable!(),
         }
     }
 
     fn visit_query(token: Pair<Rule>, default_field: &str) -> QueryNode {
+        // Lucene combines clauses without an explicit conjunction with OR, whereas Datadog
+        // combines them with AND.
+        let implicit_or = token.as_rule() == Rule::lucene_query;
         let contents = token.into_inner();
         let mut is_not: bool = false;
+        let mut conjunction: bool = false;
 
         //  AND takes precedence over OR.
         // We will combine each consecutive clause in an AND group,
@@ -48,6 +54,7 @@ impl QueryVisitor {
             let query_node: Option<QueryNode> = match node.as_rule() {
                 Rule::multiterm => Some(Self::visit_multiterm(node, default_field)),
                 Rule::conjunction => {
+                    conjunction = true;
                     let inner = node.into_inner().next().unwrap();
                     match inner.as_rule() {
                         Rule::AND => (),
@@ -71,11 +78,17 @@ impl QueryVisitor {
                     };
                     None
                 }
-                Rule::clause => Some(Self::visit_clause(node, default_field)),
+                Rule::clause | Rule::lucene_clause => Some(Self::visit_clause(node, default_field)),
                 _ => unreachable!(),
             };
             // If we found a clause to add to our list, add it
             if let Some(mut n) = query_node {
+                if implicit_or && !conjunction && !and_group.is_empty() {
+                    and_groups.push(QueryNode::new_boolean(BooleanType::And, and_group));
+                    and_group = Vec::new();
+                }
+                conjunction = false;
+
                 if is_not {
                     is_not = false;
 
@@ -126,9 +139,10 @@ impl QueryVisitor {
                 Rule::field => {
                     field = Some(Self::visit_field(item));
                 }
-                Rule::value => {
+                Rule::value | Rule::lucene_value => {
                     // As per the parser, value can only ever be one of:
-                    // STAR, PHRASE, TERM, TERM_PREFIX, TERM_GLOB, range, comparison.
+                    // STAR, PHRASE, TERM, TERM_PREFIX, TERM_GLOB, range, comparison, or for
+                    // Lucene queries, also REGEX and fuzzy.
                     let value_contents = item.into_inner().next().unwrap();
                     match ((field.unwrap_or(default_field)), value_contents.as_rule()) {
                         (EXISTS_FIELD, Rule::TERM) => {
@@ -170,18 +184,38 @@ impl QueryVisitor {
                                 phrase: Self::visit_phrase(value_contents),
                             };
                         }
-                        (f, Rule::TERM_PREFIX) => {
+                        (f, Rule::TERM_PREFIX | Rule::LUCENE_TERM_PREFIX) => {
                             return QueryNode::AttributePrefix {
                                 attr: unescape(f),
                                 prefix: Self::visit_prefix(value_contents),
                             };
                         }
-                        (f, Rule::TERM_GLOB) => {
+                        (f, Rule::TERM_GLOB | Rule::LUCENE_TERM_GLOB) => {
                             return QueryNode::AttributeWildcard {
                                 attr: unescape(f),
                                 wildcard: Self::visit_wildcard(value_contents),
                             };
                         }
+                        (f, Rule::REGEX) => {
+                            return QueryNode::AttributeRegex {
+                                attr: unescape(f),
+                                regex: Self::visit_regex(value_contents),
+                            };
+                        }
+                        (f, Rule::fuzzy) => {
+                            let mut fuzzy = value_contents.into_inner();
+                            let value = Self::visit_term(fuzzy.next().unwrap());
+                            // Lucene's default (and maximum) edit distance is 2.
+                            let distance = fuzzy
+                                .next()
+                                .map_or(2, |distance| distance.as_str().parse().unwrap_or(2));
+
+                            return QueryNode::AttributeFuzzy {
+                                attr: unescape(f),
+                                value,
+                                distance,
+                            };
+                        }
                         (f, Rule::range) => {
                             let range_values = value_contents.into_inner();
 
@@ -197,11 +231,7 @@ impl QueryVisitor {
                                         Range::Value(lv),
                                         Range::Value(rv),
                                         Range::Comparison(rc),
-                                    ) => match (lc, rc) {
-                                        (Comparison::Gte, Comparison::Lte) => (true, lv, rv, true),
-                                        (Comparison::Gt, Comparison::Lt) => (false, lv, rv, false),
-                                        _ => panic!("invalid range comparison"),
-                                    },
+                                    ) => (lc == Comparison::Gte, lv, rv, rc == Comparison::Lte),
                                     _ => panic!("invalid range value"),
                                 };
 
@@ -239,7 +269,9 @@ impl QueryVisitor {
                         _ => unreachable!(),
                     }
                 }
-                Rule::query => return Self::visit_query(item, field.unwrap_or(default_field)),
+                Rule::query | Rule::lucene_query => {
+                    return Self::visit_query(item, field.unwrap_or(default_field));
+                }
                 // We've covered all the cases, so this should never happen
                 _ => unreachable!(),
             }
@@ -283,6 +315,13 @@ impl QueryVisitor {
         unescape(token.as_str())
     }
 
+    fn visit_regex(token: Pair<Rule>) -> String {
+        // Strip the delimiting slashes. Only escaped slashes are unescaped, since everything
+        // else is regular expression syntax.
+        let regex = token.as_str();
+        regex[1..regex.len() - 1].replace("\\/", "/")
+    }
+
     fn visit_phrase(token: Pair<Rule>) -> String {
         let quoted_string = token.as_str();
         unescape(&quoted_string[1..quoted_string.len() - 1])
@@ -0,0 +1,206 @@
+use itertools::Itertools;
+use pest::iterators::Pair;
+
+use super::grammar::{DEFAULT_FIELD, Rule, unescape};
+use super::node::{BooleanType, Comparison, ComparisonValue, QueryNode};
+
+/// The KqlVisitor converts the output of the parser for Kibana Query Language queries into
+/// QueryNodes, in the same manner as the `QueryVisitor` does for Lucene based queries.
+pub struct KqlVisitor;
+
+impl KqlVisitor {
+    pub fn visit_queryroot(token: Pair<Rule>) -> QueryNode {
+        let contents = token
+            .into_inner()
+            .find(|item| item.as_rule() == Rule::kql_or)
+            // A queryroot will always contain a query
+            .unwrap();
+        Self::visit_or(contents)
+    }
+
+    fn visit_or(token: Pair<Rule>) -> QueryNode {
+        let nodes = token
+            .into_inner()
+            .filter(|item| item.as_rule() == Rule::kql_and)
+            .map(Self::visit_and)
+            .collect();
+        QueryNode::new_boolean(BooleanType::Or, nodes)
+    }
+
+    fn visit_and(token: Pair<Rule>) -> QueryNode {
+        let nodes = token
+            .into_inner()
+            .filter(|item| item.as_rule() == Rule::kql_not)
+            .map(Self::visit_not)
+            .collect();
+        QueryNode::new_boolean(BooleanType::And, nodes)
+    }
+
+    fn visit_not(token: Pair<Rule>) -> QueryNode {
+        let mut is_not = false;
+        let mut node = QueryNode::MatchAllDocs;
+        for item in token.into_inner() {
+            match item.as_rule() {
+                Rule::KQL_NOT => is_not = true,
+                Rule::kql_sub => node = Self::visit_sub(item),
+                _ => unreachable!(),
+            }
+        }
+        negate(node, is_not)
+    }
+
+    fn visit_sub(token: Pair<Rule>) -> QueryNode {
+        // As per the parser, a sub-query will only ever contain one of:
+        // a grouped query, a nested query, a range, a field value or an unfielded value.
+        let item = token.into_inner().next().unwrap();
+        match item.as_rule() {
+            Rule::kql_or => Self::visit_or(item),
+            Rule::kql_nested => {
+                let mut contents = item.into_inner();
+                let attr = Self::visit_field(contents.next().unwrap());
+                QueryNode::NestedQuery {
+                    attr,
+                    node: Box::new(Self::visit_or(contents.next().unwrap())),
+                }
+            }
+            Rule::kql_range => {
+                let (field, operator, value) = item.into_inner().collect_tuple().unwrap();
+                let comparator = match operator.into_inner().next().unwrap().as_rule() {
+                    Rule::GT => Comparison::Gt,
+                    Rule::GT_EQ => Comparison::Gte,
+                    Rule::LT => Comparison::Lt,
+                    Rule::LT_EQ => Comparison::Lte,
+                    _ => unreachable!(),
+                };
+                let value = match value.as_rule() {
+                    Rule::KQL_QUOTED => ComparisonValue::String(visit_quoted(value.as_str())),
+                    _ => value.as_str().into(),
+                };
+                QueryNode::AttributeComparison {
+                    attr: Self::visit_field(field),
+                    comparator,
+                    value,
+                }
+            }
+            Rule::kql_field_value => {
+                let (field, values) = item.into_inner().collect_tuple().unwrap();
+                Self::visit_value_list(&Self::visit_field(field), values)
+            }
+            Rule::kql_value => Self::visit_value(DEFAULT_FIELD, item),
+            _ => unreachable!(),
+        }
+    }
+
+    fn visit_value_list(attr: &str, token: Pair<Rule>) -> QueryNode {
+        let item = token.into_inner().next().unwrap();
+        match item.as_rule() {
+            Rule::kql_value_or => {
+                let nodes = item
+                    .into_inner()
+                    .filter(|item| item.as_rule() == Rule::kql_value_and)
+                    .map(|and| {
+                        let nodes = and
+                            .into_inner()
+                            .filter(|item| item.as_rule() == Rule::kql_value_not)
+                            .map(|not| {
+                                let mut is_not = false;
+                                let mut node = QueryNode::MatchAllDocs;
+                                for item in not.into_inner() {
+                                    match item.as_rule() {
+                                        Rule::KQL_NOT => is_not = true,
+                                        Rule::kql_value_list => {
+                                            node = Self::visit_value_list(attr, item);
+                                        }
+                                        _ => unreachable!(),
+                                    }
+                                }
+                                negate(node, is_not)
+                            })
+                            .collect();
+                        QueryNode::new_boolean(BooleanType::And, nodes)
+                    })
+                    .collect();
+                QueryNode::new_boolean(BooleanType::Or, nodes)
+            }
+            Rule::kql_value => Self::visit_value(attr, item),
+            _ => unreachable!(),
+        }
+    }
+
+    fn visit_value(attr: &str, token: Pair<Rule>) -> QueryNode {
+        let attr = attr.to_owned();
+        let item = token.into_inner().next().unwrap();
+        match item.as_rule() {
+            Rule::KQL_QUOTED => QueryNode::QuotedAttribute {
+                attr,
+                phrase: visit_quoted(item.as_str()),
+            },
+            Rule::KQL_LITERAL => {
+                // Normalize the whitespace between the words of the literal.
+                let literal = item.as_str().split_whitespace().join(" ");
+                let stars = unescaped_stars(&literal);
+                match stars.as_slice() {
+                    // A bare wildcard matches everything, or any value of a field.
+                    [_] if literal == "*" => {
+                        if attr == DEFAULT_FIELD {
+                            QueryNode::MatchAllDocs
+                        } else {
+                            QueryNode::AttributeExists { attr }
+                        }
+                    }
+                    [] => QueryNode::AttributeTerm {
+                        attr,
+                        value: unescape(&literal),
+                    },
+                    [index] if *index == literal.len() - 1 => QueryNode::AttributePrefix {
+                        attr,
+                        prefix: unescape(&literal[..*index]),
+                    },
+                    _ => QueryNode::AttributeWildcard {
+                        attr,
+                        wildcard: unescape(&literal),
+                    },
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn visit_field(token: Pair<Rule>) -> String {
+        let item = token.into_inner().next().unwrap();
+        match item.as_rule() {
+            Rule::KQL_QUOTED => visit_quoted(item.as_str()),
+            _ => unescape(item.as_str()),
+        }
+    }
+}
+
+fn negate(node: QueryNode, is_not: bool) -> QueryNode {
+    match (node, is_not) {
+        (node, false) => node,
+        (QueryNode::MatchAllDocs, true) => QueryNode::MatchNoDocs,
+        (node, true) => QueryNode::NegatedNode {
+            node: Box::new(node),
+        },
+    }
+}
+
+fn visit_quoted(quoted: &str) -> String {
+    unescape(&quoted[1..quoted.len() - 1])
+}
+
+/// Returns the byte offsets of the `*` wildcards in a literal which aren't escaped.
+fn unescaped_stars(literal: &str) -> Vec<usize> {
+    let mut stars = Vec::new();
+    let mut escape_sequence = false;
+    for (index, c) in literal.char_indices() {
+        if escape_sequence {
+            escape_sequence = false;
+        } else if c == '\\' {
+            escape_sequence = true;
+        } else if c == '*' {
+            stars.push(index);
+        }
+    }
+    stars
+}
@@ -7,9 +7,11 @@
 
 mod field;
 mod grammar;
+mod kql;
 mod node;
 mod parser;
 
 pub use field::{Field, normalize_fields};
+pub use grammar::DEFAULT_FIELD;
 pub use node::{BooleanType, Comparison, ComparisonValue, QueryNode};
-pub use parser::Error as ParseError;
+pub use parser::{Dialect, Error as ParseError, parse};
@@ -127,6 +127,17 @@ pub enum QueryNode {
     AttributePrefix { attr: String, prefix: String },
     /// Search for an attribute that matches a wildcard or glob string.
     AttributeWildcard { attr: String, wildcard: String },
+    /// Search for an attribute within an edit distance of a specific term.
+    AttributeFuzzy {
+        attr: String,
+        value: String,
+        distance: u8,
+    },
+    /// Search for an attribute that matches a regular expression.
+    AttributeRegex { attr: String, regex: String },
+    /// Match the QueryNode within against the object(s) at an attribute, with its attributes
+    /// relative to them.
+    NestedQuery { attr: String, node: Box<QueryNode> },
     /// Container node denoting negation of the QueryNode within.
     NegatedNode { node: Box<QueryNode> },
     /// Container node for compound Boolean operations.
@@ -183,6 +194,18 @@ impl QueryNode {
             QueryNode::AttributeWildcard { attr, wildcard } => {
                 Self::is_default_attr(attr) + wildcard
             }
+            QueryNode::AttributeFuzzy {
+                attr,
+                value,
+                distance,
+            } => {
+                Self::is_default_attr(attr)
+                    + &format!("{}~{}", &Self::lucene_escape(value), distance)
+            }
+            QueryNode::AttributeRegex { attr, regex } => {
+                Self::is_default_attr(attr) + &format!("/{}/", regex.replace('/', "\\/"))
+            }
+            QueryNode::NestedQuery { attr, node } => format!("{attr}:{{{}}}", node.to_lucene()),
             QueryNode::NegatedNode { node } => {
                 if matches!(
                     **node,
@@ -3,28 +3,101 @@ use std::str::FromStr;
 use pest::Parser;
 
 use super::{
-    grammar::{DEFAULT_FIELD, EventPlatformQuery, QueryVisitor},
+    grammar::{DEFAULT_FIELD, EventPlatformQuery, QueryVisitor, Rule},
+    kql::KqlVisitor,
     node::QueryNode,
 };
 
 pub type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 
-/// Quick wrapper parse function to convert query strings into our AST
-impl FromStr for QueryNode {
+/// The query languages which can be parsed into a `QueryNode`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Dialect {
+    /// Datadog Search Syntax, where consecutive terms are combined with AND.
+    #[default]
+    Datadog,
+    /// Lucene query syntax, adding regular expression and fuzzy terms, and boosts (which are
+    /// ignored). Consecutive terms are combined with OR.
+    Lucene,
+    /// Kibana Query Language.
+    Kql,
+}
+
+impl FromStr for Dialect {
     type Err = Error;
 
-    fn from_str(query: &str) -> Result<Self, Self::Err> {
-        // Clean up our query string
-        let clean_query = query.trim();
-        // If we have an empty query, we presume we're matching everything
-        Ok(if clean_query.is_empty() {
-            Self::MatchAllDocs
-        } else {
-            // Otherwise parse and interpret the query
-            let mut ast = EventPlatformQuery::parse(super::grammar::Rule::queryroot, query)?;
+    fn from_str(dialect: &str) -> Result<Self, Self::Err> {
+        match dialect {
+            "datadog" => Ok(Self::Datadog),
+            "lucene" => Ok(Self::Lucene),
+            "kql" => Ok(Self::Kql),
+            _ => Err(format!("unknown query dialect: {dialect:?}").into()),
+        }
+    }
+}
+
+/// Parse a query string of the given dialect into our AST.
+///
+/// # Errors
+///
+/// Will return `Err` if the query isn't valid syntax for the dialect, or contains an invalid
+/// regular expression.
+pub fn parse(query: &str, dialect: Dialect) -> Result<QueryNode, Error> {
+    // Clean up our query string
+    let clean_query = query.trim();
+    // If we have an empty query, we presume we're matching everything
+    if clean_query.is_empty() {
+        return Ok(QueryNode::MatchAllDocs);
+    }
+
+    // Otherwise parse and interpret the query
+    let node = match dialect {
+        Dialect::Datadog | Dialect::Lucene => {
+            let rule = if dialect == Dialect::Lucene {
+                Rule::lucene_queryroot
+            } else {
+                Rule::queryroot
+            };
+            let mut ast = EventPlatformQuery::parse(rule, query)?;
             let rootquery = ast.next().ok_or("Unable to find root query")?;
             QueryVisitor::visit_queryroot(rootquery, DEFAULT_FIELD)
-        })
+        }
+        Dialect::Kql => {
+            let mut ast = EventPlatformQuery::parse(Rule::kql_queryroot, query)?;
+            let rootquery = ast.next().ok_or("Unable to find root query")?;
+            KqlVisitor::visit_queryroot(rootquery)
+        }
+    };
+
+    validate_regexes(&node)?;
+    Ok(node)
+}
+
+/// Check that the regular expressions in a query compile, so matching needn't fail later.
+fn validate_regexes(node: &QueryNode) -> Result<(), Error> {
+    match node {
+        QueryNode::AttributeRegex { regex, .. } => {
+            regex::Regex::new(regex)?;
+        }
+        QueryNode::NestedQuery { node, .. } | QueryNode::NegatedNode { node } => {
+            validate_regexes(node)?;
+        }
+        QueryNode::Boolean { nodes, .. } => {
+            for node in nodes {
+                validate_regexes(node)?;
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Quick wrapper parse function to convert Datadog query strings into our AST
+impl FromStr for QueryNode {
+    type Err = Error;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        parse(query, Dialect::Datadog)
     }
 }
 
@@ -636,4 +709,163 @@ mod tests {
             panic!("Unable to properly parse '{query:?}' - got {res:?}")
         }
     }
+
+    fn parse_dialect(s: &str, dialect: Dialect) -> QueryNode {
+        super::parse(s, dialect).unwrap_or_else(|error| panic!("Unable to parse {s:?}: {error}."))
+    }
+
+    fn term(attr: &str, value: &str) -> QueryNode {
+        QueryNode::AttributeTerm {
+            attr: attr.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+
+    #[test]
+    fn parses_mixed_range_brackets() {
+        assert_eq!(
+            parse("foo:{1 TO 5]"),
+            QueryNode::AttributeRange {
+                attr: "foo".to_owned(),
+                lower: ComparisonValue::Integer(1),
+                lower_inclusive: false,
+                upper: ComparisonValue::Integer(5),
+                upper_inclusive: true,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_lucene_implicit_or() {
+        assert_eq!(
+            parse_dialect("foo bar AND baz", Dialect::Lucene),
+            QueryNode::new_boolean(
+                BooleanType::Or,
+                vec![
+                    term(DEFAULT_FIELD, "foo"),
+                    QueryNode::new_boolean(
+                        BooleanType::And,
+                        vec![term(DEFAULT_FIELD, "bar"), term(DEFAULT_FIELD, "baz")]
+                    ),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn parses_lucene_fuzzy_regex_and_boost() {
+        assert_eq!(
+            parse_dialect("name:jane~1^2", Dialect::Lucene),
+            QueryNode::AttributeFuzzy {
+                attr: "name".to_owned(),
+                value: "jane".to_owned(),
+                distance: 1,
+            }
+        );
+        assert_eq!(
+            parse_dialect("name:jane~", Dialect::Lucene),
+            QueryNode::AttributeFuzzy {
+                attr: "name".to_owned(),
+                value: "jane".to_owned(),
+                distance: 2,
+            }
+        );
+        assert_eq!(
+            parse_dialect(r"path:/\/api\/.*/", Dialect::Lucene),
+            QueryNode::AttributeRegex {
+                attr: "path".to_owned(),
+                regex: "/api/.*".to_owned(),
+            }
+        );
+        assert_eq!(
+            parse_dialect("foo:(bar)^0.5", Dialect::Lucene),
+            term("foo", "bar")
+        );
+        assert!(super::parse("foo:/(/", Dialect::Lucene).is_err());
+        // Datadog queries have no regular expressions.
+        assert_eq!(parse("foo:/bar/"), term("foo", "/bar/"));
+    }
+
+    #[test]
+    fn parses_kql() {
+        assert_eq!(
+            parse_dialect("a:1 and NOT b:two words or c >= 3", Dialect::Kql),
+            QueryNode::new_boolean(
+                BooleanType::Or,
+                vec![
+                    QueryNode::new_boolean(
+                        BooleanType::And,
+                        vec![
+                            term("a", "1"),
+                            QueryNode::NegatedNode {
+                                node: Box::new(term("b", "two words")),
+                            },
+                        ]
+                    ),
+                    QueryNode::AttributeComparison {
+                        attr: "c".to_owned(),
+                        comparator: Comparison::Gte,
+                        value: ComparisonValue::Integer(3),
+                    },
+                ]
+            )
+        );
+        assert_eq!(
+            parse_dialect("method:(GET or HEAD)", Dialect::Kql),
+            QueryNode::new_boolean(
+                BooleanType::Or,
+                vec![term("method", "GET"), term("method", "HEAD")]
+            )
+        );
+        assert_eq!(
+            parse_dialect(r#""a.b":"c d" and e:*"#, Dialect::Kql),
+            QueryNode::new_boolean(
+                BooleanType::And,
+                vec![
+                    QueryNode::QuotedAttribute {
+                        attr: "a.b".to_owned(),
+                        phrase: "c d".to_owned(),
+                    },
+                    QueryNode::AttributeExists {
+                        attr: "e".to_owned(),
+                    },
+                ]
+            )
+        );
+        assert_eq!(
+            parse_dialect(r"a:fo* and b:f*o and c:f\*o", Dialect::Kql),
+            QueryNode::new_boolean(
+                BooleanType::And,
+                vec![
+                    QueryNode::AttributePrefix {
+                        attr: "a".to_owned(),
+                        prefix: "fo".to_owned(),
+                    },
+                    QueryNode::AttributeWildcard {
+                        attr: "b".to_owned(),
+                        wildcard: "f*o".to_owned(),
+                    },
+                    term("c", "f*o"),
+                ]
+            )
+        );
+        assert_eq!(
+            parse_dialect("items:{ name:b and count > 1 }", Dialect::Kql),
+            QueryNode::NestedQuery {
+                attr: "items".to_owned(),
+                node: Box::new(QueryNode::new_boolean(
+                    BooleanType::And,
+                    vec![
+                        term("name", "b"),
+                        QueryNode::AttributeComparison {
+                            attr: "count".to_owned(),
+                            comparator: Comparison::Gt,
+                            value: ComparisonValue::Integer(1),
+                        },
+                    ]
+                )),
+            }
+        );
+        assert!(super::parse("a:1 and", Dialect::Kql).is_err());
+    }
 }
@@ -130,6 +130,7 @@ pub fn build_function_doc(func: &dyn Function) -> FunctionDoc {
                 description,
                 default,
                 enum_variants,
+                ..
             } = param;
 
             let name = keyword.trim().to_string();
@@ -228,6 +229,9 @@ fn kind_to_types(kind_bits: u16) -> Vec<String> {
     if (kind_bits & kind::FLOAT) == kind::FLOAT {
         types.push("float".to_string());
     }
+    if (kind_bits & kind::DECIMAL) == kind::DECIMAL {
+        types.push("decimal".to_string());
+    }
     if (kind_bits & kind::BOOLEAN) == kind::BOOLEAN {
         types.push("boolean".to_string());
     }
@@ -240,6 +244,9 @@ fn kind_to_types(kind_bits: u16) -> Vec<String> {
     if (kind_bits & kind::TIMESTAMP) == kind::TIMESTAMP {
         types.push("timestamp".to_string());
     }
+    if (kind_bits & kind::DURATION) == kind::DURATION {
+        types.push("duration".to_string());
+    }
     if (kind_bits & kind::REGEX) == kind::REGEX {
         types.push("regex".to_string());
     }
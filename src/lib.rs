@@ -26,6 +26,9 @@ pub mod core;
 #[cfg(feature = "stdlib-base")]
 pub mod stdlib;
 
+#[cfg(feature = "stdlib-base")]
+pub mod avro;
+
 #[cfg(feature = "stdlib-base")]
 pub mod protobuf;
 
@@ -35,6 +38,9 @@ pub mod docs;
 #[cfg(feature = "cli")]
 pub mod cli;
 
+#[cfg(feature = "python")]
+pub mod python;
+
 #[cfg(feature = "test_framework")]
 pub mod test;
 
@@ -49,5 +55,8 @@ pub use datadog::filter as datadog_filter;
 #[cfg(all(feature = "datadog_grok", not(target_arch = "wasm32")))]
 pub use datadog::grok as datadog_grok;
 
+#[cfg(feature = "datadog_pipeline")]
+pub use datadog::pipeline as datadog_pipeline;
+
 #[cfg(feature = "datadog_search")]
 pub use datadog::search as datadog_search_syntax;
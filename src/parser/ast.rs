@@ -347,12 +347,15 @@ pub enum Literal {
     Boolean(bool),
     Regex(String),
     Timestamp(String),
+    Duration(String),
     Null,
 }
 
 impl fmt::Display for Literal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Literal::{Boolean, Float, Integer, Null, RawString, Regex, String, Timestamp};
+        use Literal::{
+            Boolean, Duration, Float, Integer, Null, RawString, Regex, String, Timestamp,
+        };
 
         match self {
             String(v) => write!(f, r#""{v}""#),
@@ -362,6 +365,7 @@ impl fmt::Display for Literal {
             Boolean(v) => v.fmt(f),
             Regex(v) => write!(f, "r'{v}'"),
             Timestamp(v) => write!(f, "t'{v}'"),
+            Duration(v) => write!(f, "d'{v}'"),
             Null => f.write_str("null"),
         }
     }
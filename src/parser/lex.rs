@@ -352,6 +352,7 @@ impl<'input> Lexer<'input> {
                     'r' if self.test_peek(|ch| ch == '\'') => Some(self.regex_literal(start)),
                     's' if self.test_peek(|ch| ch == '\'') => Some(self.raw_string_literal(start)),
                     't' if self.test_peek(|ch| ch == '\'') => Some(self.timestamp_literal(start)),
+                    'd' if self.test_peek(|ch| ch == '\'') => Some(self.duration_literal(start)),
 
                     ch if is_ident_start(ch) => Some(Ok(self.identifier_or_function_call(start))),
                     ch if is_digit(ch) || (ch == '-' && self.test_peek(is_digit)) => {
@@ -394,6 +395,7 @@ pub enum Token<S> {
     FloatLiteral(NotNan<f64>),
     RegexLiteral(S),
     TimestampLiteral(S),
+    DurationLiteral(S),
 
     // Reserved for future use.
     ReservedIdentifier(S),
@@ -469,12 +471,12 @@ pub enum Token<S> {
 impl<S> Token<S> {
     pub(crate) fn map<R>(self, f: impl Fn(S) -> R) -> Token<R> {
         use self::Token::{
-            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
-            FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
-            ReservedIdentifier, Return, SemiColon, StringLiteral, TimestampLiteral, True,
-            Underscore,
+            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, DurationLiteral, Else, Equals,
+            Escape, False, FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral,
+            InvalidToken, LBrace, LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator,
+            PathField, Percent, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral,
+            RegexLiteral, ReservedIdentifier, Return, SemiColon, StringLiteral, TimestampLiteral,
+            True, Underscore,
         };
 
         match self {
@@ -492,6 +494,7 @@ impl<S> Token<S> {
             FloatLiteral(s) => FloatLiteral(s),
             RegexLiteral(s) => RegexLiteral(f(s)),
             TimestampLiteral(s) => TimestampLiteral(f(s)),
+            DurationLiteral(s) => DurationLiteral(f(s)),
 
             ReservedIdentifier(s) => ReservedIdentifier(f(s)),
 
@@ -540,12 +543,12 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Token::{
-            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
-            FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
-            ReservedIdentifier, Return, SemiColon, StringLiteral, TimestampLiteral, True,
-            Underscore,
+            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, DurationLiteral, Else, Equals,
+            Escape, False, FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral,
+            InvalidToken, LBrace, LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator,
+            PathField, Percent, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral,
+            RegexLiteral, ReservedIdentifier, Return, SemiColon, StringLiteral, TimestampLiteral,
+            True, Underscore,
         };
 
         let s = match *self {
@@ -559,6 +562,7 @@ where
             FloatLiteral(_) => "FloatLiteral",
             RegexLiteral(_) => "RegexLiteral",
             TimestampLiteral(_) => "TimestampLiteral",
+            DurationLiteral(_) => "DurationLiteral",
             ReservedIdentifier(_) => "ReservedIdentifier",
             InvalidToken(_) => "InvalidToken",
 
@@ -942,6 +946,13 @@ impl<'input> Lexer<'input> {
                         Err(_) => break,
                     }
                 }
+                'd' if chars.peek().map(|(_, ch)| ch) == Some(&'\'') => {
+                    let result = Lexer::new(&self.input[pos + 1..]).duration_literal(0);
+                    match take_until_end(result, &mut last_char, &mut end, &mut chars) {
+                        Ok(()) => continue,
+                        Err(_) => break,
+                    }
+                }
 
                 '}' if braces == 0 => break,
                 '}' => braces -= 1,
@@ -1056,6 +1067,20 @@ impl<'input> Lexer<'input> {
                                     }
                                 }
                             }
+                            s if s.starts_with("d'") => {
+                                let r = Lexer::new(&self.input[pos + 1..])
+                                    .duration_literal(0)
+                                    .map_err(|e| e.offset_by(pos + 1))?;
+                                match literal_check(r, &mut chars) {
+                                    Ok(ch) => ch,
+                                    Err(()) => {
+                                        return Err(Error::UnexpectedParseError(
+                                            "Expected characters at end of duration literal."
+                                                .to_string(),
+                                        ));
+                                    }
+                                }
+                            }
                             _ => *ch,
                         };
 
@@ -1090,6 +1115,9 @@ impl<'input> Lexer<'input> {
                     }
                 }
 
+                // wildcard path segment
+                '*' if last_char == Some('.') => {}
+
                 // function-call-abort
                 '!' => {}
 
@@ -1163,6 +1191,10 @@ impl<'input> Lexer<'input> {
         self.quoted_literal(start, Token::TimestampLiteral)
     }
 
+    fn duration_literal(&mut self, start: usize) -> SpannedResult<'input, usize> {
+        self.quoted_literal(start, Token::DurationLiteral)
+    }
+
     fn numeric_literal_or_identifier(&mut self, start: usize) -> SpannedResult<'input, usize> {
         let (end, int) = self.take_while(start, |ch| is_digit(ch) || ch == '_');
 
@@ -1415,10 +1447,10 @@ mod test {
     #![allow(clippy::print_stdout)] // tests
 
     use super::super::lex::Token::{
-        Arrow, Bang, Colon, Comma, Dot, Else, Equals, FloatLiteral, FunctionCall, Identifier, If,
-        IntegerLiteral, LBrace, LBracket, LParen, LQuery, Newline, Operator, PathField, Percent,
-        RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral, StringLiteral,
-        TimestampLiteral, True,
+        Arrow, Bang, Colon, Comma, Dot, DurationLiteral, Else, Equals, FloatLiteral, FunctionCall,
+        Identifier, If, IntegerLiteral, LBrace, LBracket, LParen, LQuery, Newline, Operator,
+        PathField, Percent, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
+        StringLiteral, TimestampLiteral, True,
     };
     use super::*;
 
@@ -1662,6 +1694,19 @@ mod test {
         );
     }
 
+    #[test]
+    #[rustfmt::skip]
+    fn duration_literals() {
+        test(
+            data("d'1h30m' + dur"),
+            vec![
+                ("~~~~~~~~      ", DurationLiteral("1h30m")),
+                ("         ~    ", Operator("+")),
+                ("           ~~~", Identifier("dur")),
+            ],
+        );
+    }
+
     #[test]
     #[rustfmt::skip]
     fn raw_string_literals() {
@@ -1826,6 +1871,58 @@ mod test {
         );
     }
 
+    #[test]
+    fn wildcard_queries() {
+        test(
+            data(".a[*].b .c.* * 2"),
+            vec![
+                ("~               ", LQuery),
+                ("~               ", Dot),
+                (" ~              ", Identifier("a")),
+                ("  ~             ", LBracket),
+                ("   ~            ", Operator("*")),
+                ("    ~           ", RBracket),
+                ("     ~          ", Dot),
+                ("      ~         ", Identifier("b")),
+                ("      ~         ", RQuery),
+                ("        ~       ", LQuery),
+                ("        ~       ", Dot),
+                ("         ~      ", Identifier("c")),
+                ("          ~     ", Dot),
+                ("           ~    ", Operator("*")),
+                ("           ~    ", RQuery),
+                ("             ~  ", Operator("*")),
+                ("               ~", IntegerLiteral(2)),
+            ],
+        );
+    }
+
+    #[test]
+    fn slice_queries() {
+        test(
+            data(".a[1:-1] .b[:2]"),
+            vec![
+                ("~              ", LQuery),
+                ("~              ", Dot),
+                (" ~             ", Identifier("a")),
+                ("  ~            ", LBracket),
+                ("   ~           ", IntegerLiteral(1)),
+                ("    ~          ", Colon),
+                ("     ~~        ", IntegerLiteral(-1)),
+                ("       ~       ", RBracket),
+                ("       ~       ", RQuery),
+                ("         ~     ", LQuery),
+                ("         ~     ", Dot),
+                ("          ~    ", Identifier("b")),
+                ("           ~   ", LBracket),
+                ("            ~  ", Colon),
+                ("             ~ ", IntegerLiteral(2)),
+                ("              ~", RBracket),
+                ("              ~", RQuery),
+            ],
+        );
+    }
+
     #[test]
     #[rustfmt::skip]
     fn nested_queries() {
@@ -0,0 +1,18 @@
+use bytes::Bytes;
+use csv::ReaderBuilder;
+
+/// Parses the first record of a CSV formatted input into its fields. Empty input results in no
+/// fields.
+pub fn parse_csv_record(input: &[u8], delimiter: u8, quote: u8) -> Result<Vec<Bytes>, csv::Error> {
+    let reader = ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(delimiter)
+        .quote(quote)
+        .from_reader(input);
+
+    reader.into_byte_records().next().transpose().map(|record| {
+        record
+            .map(|record| record.iter().map(Bytes::copy_from_slice).collect())
+            .unwrap_or_default()
+    })
+}
@@ -1,3 +1,6 @@
+pub mod csv;
 pub mod query_string;
 pub mod ruby_hash;
+pub mod url;
+pub mod user_agent;
 pub mod xml;
@@ -0,0 +1,290 @@
+use std::{borrow::Cow, sync::LazyLock};
+
+use woothee::parser::Parser as WootheeParser;
+
+use crate::value::Value;
+
+pub static UA_EXTRACTOR: LazyLock<ua_parser::Extractor> = LazyLock::new(|| {
+    let regexes = include!(concat!(env!("OUT_DIR"), "/user_agent_regexes.rs"));
+    ua_parser::Extractor::try_from(regexes).expect("Regex file is not valid.")
+});
+
+#[derive(Default)]
+pub struct UserAgent {
+    pub browser: Browser,
+    pub os: Os,
+    pub device: Device,
+}
+
+impl UserAgent {
+    pub fn partial_schema(self) -> Value {
+        let Self {
+            browser,
+            os,
+            device,
+        } = self;
+
+        IntoIterator::into_iter([
+            ("browser", browser.partial_schema()),
+            ("os", os.partial_schema()),
+            ("device", device.partial_schema()),
+        ])
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+    }
+
+    pub fn full_schema(self) -> Value {
+        let Self {
+            browser,
+            os,
+            device,
+        } = self;
+
+        IntoIterator::into_iter([
+            ("browser", browser.full_schema()),
+            ("os", os.full_schema()),
+            ("device", device.full_schema()),
+        ])
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+    }
+
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            browser: self.browser.or(other.browser),
+            os: self.os.or(other.os),
+            device: self.device.or(other.device),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Browser {
+    pub family: Option<String>,
+    pub version: Option<String>,
+    pub major: Option<String>,
+    pub minor: Option<String>,
+    pub patch: Option<String>,
+}
+
+impl Browser {
+    pub fn partial_schema(self) -> Value {
+        let Self {
+            family, version, ..
+        } = self;
+
+        into_value([("family", family), ("version", version)])
+    }
+
+    pub fn full_schema(self) -> Value {
+        let Self {
+            family,
+            version,
+            major,
+            minor,
+            patch,
+        } = self;
+
+        into_value([
+            ("family", family),
+            ("version", version),
+            ("major", major),
+            ("minor", minor),
+            ("patch", patch),
+        ])
+    }
+
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            family: self.family.or(other.family),
+            version: self.version.or(other.version),
+            major: self.major.or(other.major),
+            minor: self.minor.or(other.minor),
+            patch: self.patch.or(other.patch),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Os {
+    pub family: Option<String>,
+    pub version: Option<String>,
+    pub major: Option<String>,
+    pub minor: Option<String>,
+    pub patch: Option<String>,
+    pub patch_minor: Option<String>,
+}
+
+impl Os {
+    pub fn partial_schema(self) -> Value {
+        let Self {
+            family, version, ..
+        } = self;
+
+        into_value([("family", family), ("version", version)])
+    }
+
+    pub fn full_schema(self) -> Value {
+        let Self {
+            family,
+            version,
+            major,
+            minor,
+            patch,
+            patch_minor,
+        } = self;
+
+        into_value([
+            ("family", family),
+            ("version", version),
+            ("major", major),
+            ("minor", minor),
+            ("patch", patch),
+            ("patch_minor", patch_minor),
+        ])
+    }
+
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            family: self.family.or(other.family),
+            version: self.version.or(other.version),
+            major: self.major.or(other.major),
+            minor: self.minor.or(other.minor),
+            patch: self.patch.or(other.patch),
+            patch_minor: self.patch_minor.or(other.patch_minor),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Device {
+    pub family: Option<String>,
+    pub category: Option<String>,
+    pub brand: Option<String>,
+    pub model: Option<String>,
+}
+
+impl Device {
+    pub fn partial_schema(self) -> Value {
+        let Self { category, .. } = self;
+
+        into_value([("category", category)])
+    }
+
+    pub fn full_schema(self) -> Value {
+        let Self {
+            category,
+            family,
+            brand,
+            model,
+        } = self;
+
+        into_value([
+            ("category", category),
+            ("family", family),
+            ("brand", brand),
+            ("model", model),
+        ])
+    }
+
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            category: self.category.or(other.category),
+            family: self.family.or(other.family),
+            brand: self.brand.or(other.brand),
+            model: self.model.or(other.model),
+        }
+    }
+}
+
+fn into_value<'a>(iter: impl IntoIterator<Item = (&'a str, Option<String>)>) -> Value {
+    iter.into_iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                value.map_or(Value::Null, std::convert::Into::into),
+            )
+        })
+        .collect()
+}
+
+pub trait Parser {
+    fn parse_user_agent(&self, user_agent: &str) -> UserAgent;
+}
+
+impl Parser for WootheeParser {
+    fn parse_user_agent(&self, user_agent: &str) -> UserAgent {
+        fn unknown_to_none<'a>(s: impl Into<Cow<'a, str>>) -> Option<String> {
+            let cow = s.into();
+            match cow.as_ref() {
+                "" | woothee::woothee::VALUE_UNKNOWN => None,
+                _ => Some(cow.into_owned()),
+            }
+        }
+
+        let ua = self.parse(user_agent).unwrap_or_default();
+
+        UserAgent {
+            browser: Browser {
+                family: unknown_to_none(ua.name),
+                version: unknown_to_none(ua.version),
+                ..Default::default()
+            },
+            os: Os {
+                family: unknown_to_none(ua.os),
+                version: unknown_to_none(ua.os_version),
+                ..Default::default()
+            },
+            device: Device {
+                category: unknown_to_none(ua.category),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Parser for ua_parser::Extractor<'_> {
+    fn parse_user_agent(&self, user_agent: &str) -> UserAgent {
+        let browser = self
+            .ua
+            .extract(user_agent)
+            .map(|ua| Browser {
+                family: Some(ua.family.into_owned()),
+                major: ua.major.map(Into::into),
+                minor: ua.minor.map(Into::into),
+                patch: ua.patch.map(Into::into),
+                ..Default::default()
+            })
+            .unwrap_or_default();
+
+        let os = self
+            .os
+            .extract(user_agent)
+            .map(|os| Os {
+                family: Some(os.os.into_owned()),
+                major: os.major.map(Cow::into_owned),
+                minor: os.minor.map(Cow::into_owned),
+                patch: os.patch.map(Cow::into_owned),
+                patch_minor: os.patch_minor.map(Cow::into_owned),
+                ..Default::default()
+            })
+            .unwrap_or_default();
+
+        let device = self
+            .dev
+            .extract(user_agent)
+            .map(|dev| Device {
+                family: Some(dev.device.into_owned()),
+                brand: dev.brand.map(Cow::into_owned),
+                model: dev.model.map(Cow::into_owned),
+                ..Default::default()
+            })
+            .unwrap_or_default();
+
+        UserAgent {
+            browser,
+            os,
+            device,
+        }
+    }
+}
@@ -35,6 +35,11 @@ impl<'a, 'b> BorrowedTargetPath<'a, 'b> {
 pub enum BorrowedSegment<'a> {
     Field(Cow<'a, str>),
     Index(isize),
+    Wildcard,
+    Slice {
+        start: Option<isize>,
+        end: Option<isize>,
+    },
     Invalid,
 }
 
@@ -51,6 +56,12 @@ impl BorrowedSegment<'_> {
     pub fn is_index(&self) -> bool {
         matches!(self, BorrowedSegment::Index(_))
     }
+    pub fn is_wildcard(&self) -> bool {
+        matches!(self, BorrowedSegment::Wildcard)
+    }
+    pub fn is_slice(&self) -> bool {
+        matches!(self, BorrowedSegment::Slice { .. })
+    }
     pub fn is_invalid(&self) -> bool {
         matches!(self, BorrowedSegment::Invalid)
     }
@@ -61,6 +72,11 @@ impl<'a> From<&'a OwnedSegment> for BorrowedSegment<'a> {
         match segment {
             OwnedSegment::Field(field) => Self::Field(field.as_str().into()),
             OwnedSegment::Index(i) => Self::Index(*i),
+            OwnedSegment::Wildcard => Self::Wildcard,
+            OwnedSegment::Slice { start, end } => Self::Slice {
+                start: *start,
+                end: *end,
+            },
         }
     }
 }
@@ -127,7 +143,9 @@ impl quickcheck::Arbitrary for BorrowedSegment<'static> {
 
     fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
         match self {
-            BorrowedSegment::Invalid => Box::new(std::iter::empty()),
+            BorrowedSegment::Invalid
+            | BorrowedSegment::Wildcard
+            | BorrowedSegment::Slice { .. } => Box::new(std::iter::empty()),
             BorrowedSegment::Index(index) => Box::new(index.shrink().map(BorrowedSegment::Index)),
             BorrowedSegment::Field(field) => Box::new(
                 field
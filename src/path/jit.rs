@@ -57,10 +57,27 @@ enum JitState {
     Continue,
     Dot,
     IndexStart,
-    NegativeIndex { value: isize },
-    Index { value: isize },
-    Field { start: usize },
-    Quote { start: usize },
+    NegativeIndex {
+        value: isize,
+    },
+    Index {
+        value: isize,
+    },
+    WildcardIndex,
+    /// After a `*` wildcard, which can only be followed by another segment starting with `.`
+    /// or `[`.
+    Wildcard,
+    SliceEnd {
+        start: Option<isize>,
+        end: Option<isize>,
+        negative: bool,
+    },
+    Field {
+        start: usize,
+    },
+    Quote {
+        start: usize,
+    },
     EscapedQuote,
     End,
 }
@@ -77,11 +94,16 @@ impl<'a> Iterator for JitValuePathIter<'a> {
                         | JitState::IndexStart
                         | JitState::Index { .. }
                         | JitState::NegativeIndex { .. }
+                        | JitState::WildcardIndex
+                        | JitState::SliceEnd { .. }
                         | JitState::Quote { .. }
                         | JitState::EscapedQuote
                         | JitState::Dot => Some(BorrowedSegment::Invalid),
 
-                        JitState::Continue | JitState::EventRoot | JitState::End => None,
+                        JitState::Continue
+                        | JitState::Wildcard
+                        | JitState::EventRoot
+                        | JitState::End => None,
 
                         JitState::Field { start } => {
                             Some(BorrowedSegment::Field(Cow::Borrowed(&self.path[start..])))
@@ -94,6 +116,7 @@ impl<'a> Iterator for JitValuePathIter<'a> {
                     let (result, state) = match self.state {
                         JitState::Start => match c {
                             '.' => (None, JitState::EventRoot),
+                            '*' => (Some(Some(BorrowedSegment::Wildcard)), JitState::Wildcard),
                             'A'..='Z' | 'a'..='z' | '_' | '0'..='9' | '@' | '-' => {
                                 (None, JitState::Field { start: index })
                             }
@@ -110,7 +133,13 @@ impl<'a> Iterator for JitValuePathIter<'a> {
                             '\"' => (None, JitState::Quote { start: index + 1 }),
                             _ => (Some(Some(BorrowedSegment::Invalid)), JitState::End),
                         },
+                        JitState::Wildcard => match c {
+                            '.' => (None, JitState::Dot),
+                            '[' => (None, JitState::IndexStart),
+                            _ => (Some(Some(BorrowedSegment::Invalid)), JitState::End),
+                        },
                         JitState::EventRoot => match c {
+                            '*' => (Some(Some(BorrowedSegment::Wildcard)), JitState::Wildcard),
                             'A'..='Z' | 'a'..='z' | '_' | '0'..='9' | '@' | '-' => {
                                 (None, JitState::Field { start: index })
                             }
@@ -119,6 +148,7 @@ impl<'a> Iterator for JitValuePathIter<'a> {
                             _ => (Some(Some(BorrowedSegment::Invalid)), JitState::End),
                         },
                         JitState::Dot => match c {
+                            '*' => (Some(Some(BorrowedSegment::Wildcard)), JitState::Wildcard),
                             'A'..='Z' | 'a'..='z' | '_' | '0'..='9' | '@' | '-' => {
                                 (None, JitState::Field { start: index })
                             }
@@ -190,6 +220,54 @@ impl<'a> Iterator for JitValuePathIter<'a> {
                                 },
                             ),
                             '-' => (None, JitState::NegativeIndex { value: 0 }),
+                            '*' => (None, JitState::WildcardIndex),
+                            ':' => (
+                                None,
+                                JitState::SliceEnd {
+                                    start: None,
+                                    end: None,
+                                    negative: false,
+                                },
+                            ),
+                            _ => (Some(Some(BorrowedSegment::Invalid)), JitState::End),
+                        },
+                        JitState::WildcardIndex => match c {
+                            ']' => (Some(Some(BorrowedSegment::Wildcard)), JitState::Continue),
+                            _ => (Some(Some(BorrowedSegment::Invalid)), JitState::End),
+                        },
+                        JitState::SliceEnd {
+                            start,
+                            end,
+                            negative,
+                        } => match c {
+                            '-' if end.is_none() && !negative => (
+                                None,
+                                JitState::SliceEnd {
+                                    start,
+                                    end,
+                                    negative: true,
+                                },
+                            ),
+                            '0'..='9' => {
+                                let new_digit = c as isize - '0' as isize;
+                                let value = end.unwrap_or(0) * 10;
+                                (
+                                    None,
+                                    JitState::SliceEnd {
+                                        start,
+                                        end: Some(if negative {
+                                            value - new_digit
+                                        } else {
+                                            value + new_digit
+                                        }),
+                                        negative,
+                                    },
+                                )
+                            }
+                            ']' if end.is_some() || !negative => (
+                                Some(Some(BorrowedSegment::Slice { start, end })),
+                                JitState::Continue,
+                            ),
                             _ => (Some(Some(BorrowedSegment::Invalid)), JitState::End),
                         },
                         JitState::Index { value } => match c {
@@ -206,6 +284,14 @@ impl<'a> Iterator for JitValuePathIter<'a> {
                                 Some(Some(BorrowedSegment::Index(value))),
                                 JitState::Continue,
                             ),
+                            ':' => (
+                                None,
+                                JitState::SliceEnd {
+                                    start: Some(value),
+                                    end: None,
+                                    negative: false,
+                                },
+                            ),
                             _ => (Some(Some(BorrowedSegment::Invalid)), JitState::End),
                         },
                         JitState::NegativeIndex { value } => match c {
@@ -222,6 +308,14 @@ impl<'a> Iterator for JitValuePathIter<'a> {
                                 Some(Some(BorrowedSegment::Index(value))),
                                 JitState::Continue,
                             ),
+                            ':' => (
+                                None,
+                                JitState::SliceEnd {
+                                    start: Some(value),
+                                    end: None,
+                                    negative: false,
+                                },
+                            ),
                             _ => (Some(Some(BorrowedSegment::Invalid)), JitState::End),
                         },
                         JitState::End => (Some(None), JitState::End),
@@ -365,6 +459,93 @@ mod test {
             ),
             (r#"."🤖""#, vec![BorrowedSegment::Field("🤖".into())]),
             ("(a)", vec![BorrowedSegment::Invalid]),
+            ("[*]", vec![BorrowedSegment::Wildcard]),
+            (".*", vec![BorrowedSegment::Wildcard]),
+            (
+                ".items[*].price",
+                vec![
+                    BorrowedSegment::Field("items".into()),
+                    BorrowedSegment::Wildcard,
+                    BorrowedSegment::Field("price".into()),
+                ],
+            ),
+            (
+                "headers.*",
+                vec![
+                    BorrowedSegment::Field("headers".into()),
+                    BorrowedSegment::Wildcard,
+                ],
+            ),
+            (
+                "headers.*[0]",
+                vec![
+                    BorrowedSegment::Field("headers".into()),
+                    BorrowedSegment::Wildcard,
+                    BorrowedSegment::Index(0),
+                ],
+            ),
+            ("foo*", vec![BorrowedSegment::Invalid]),
+            (
+                "foo[0]*",
+                vec![
+                    BorrowedSegment::Field("foo".into()),
+                    BorrowedSegment::Index(0),
+                    BorrowedSegment::Invalid,
+                ],
+            ),
+            (
+                "foo[*]*",
+                vec![
+                    BorrowedSegment::Field("foo".into()),
+                    BorrowedSegment::Wildcard,
+                    BorrowedSegment::Invalid,
+                ],
+            ),
+            (
+                ".**",
+                vec![BorrowedSegment::Wildcard, BorrowedSegment::Invalid],
+            ),
+            (
+                ".*foo",
+                vec![BorrowedSegment::Wildcard, BorrowedSegment::Invalid],
+            ),
+            (
+                "*\"foo\"",
+                vec![BorrowedSegment::Wildcard, BorrowedSegment::Invalid],
+            ),
+            ("[*", vec![BorrowedSegment::Invalid]),
+            ("[*1]", vec![BorrowedSegment::Invalid]),
+            (
+                "[1:3]",
+                vec![BorrowedSegment::Slice {
+                    start: Some(1),
+                    end: Some(3),
+                }],
+            ),
+            (
+                "[-2:]",
+                vec![BorrowedSegment::Slice {
+                    start: Some(-2),
+                    end: None,
+                }],
+            ),
+            (
+                "[:-1]",
+                vec![BorrowedSegment::Slice {
+                    start: None,
+                    end: Some(-1),
+                }],
+            ),
+            (
+                "[:]",
+                vec![BorrowedSegment::Slice {
+                    start: None,
+                    end: None,
+                }],
+            ),
+            ("[1:-]", vec![BorrowedSegment::Invalid]),
+            ("[1:2:3]", vec![BorrowedSegment::Invalid]),
+            ("[1:2", vec![BorrowedSegment::Invalid]),
         ];
 
         for (path, expected) in test_cases {
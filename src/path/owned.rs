@@ -88,7 +88,7 @@ impl OwnedValuePath {
                     components.push(field.as_str());
                 }
 
-                OwnedSegment::Index(_) => {
+                OwnedSegment::Index(_) | OwnedSegment::Wildcard | OwnedSegment::Slice { .. } => {
                     return None;
                 }
             }
@@ -100,6 +100,12 @@ impl OwnedValuePath {
     pub fn push(&mut self, segment: OwnedSegment) {
         self.segments.push(segment);
     }
+
+    /// Returns true if the path contains a wildcard or slice segment, so it can point to
+    /// more than one value.
+    pub fn matches_many(&self) -> bool {
+        self.segments.iter().any(OwnedSegment::matches_many)
+    }
 }
 
 // OwnedValuePath values must have at least one segment.
@@ -279,8 +285,10 @@ impl From<&OwnedValuePath> for String {
                 OwnedSegment::Field(field) => {
                     serialize_field(&mut output, field.as_ref(), (i != 0).then_some("."))
                 }
-                OwnedSegment::Index(index) => {
-                    write!(output, "[{index}]").expect("Could not write to string")
+                segment @ (OwnedSegment::Index(_)
+                | OwnedSegment::Wildcard
+                | OwnedSegment::Slice { .. }) => {
+                    write!(output, "{segment}").expect("Could not write to string")
                 }
             }
         }
@@ -327,6 +335,14 @@ impl From<Vec<OwnedSegment>> for OwnedValuePath {
 pub enum OwnedSegment {
     Field(KeyString),
     Index(isize),
+    /// Every element of an array, or every field of an object.
+    Wildcard,
+    /// The elements of an array from `start` (inclusive) up to `end` (exclusive). Negative
+    /// bounds count from the end of the array, and missing bounds extend to its edges.
+    Slice {
+        start: Option<isize>,
+        end: Option<isize>,
+    },
 }
 
 impl OwnedSegment {
@@ -343,13 +359,20 @@ impl OwnedSegment {
     pub fn is_index(&self) -> bool {
         matches!(self, OwnedSegment::Index(_))
     }
+    pub fn is_wildcard(&self) -> bool {
+        matches!(self, OwnedSegment::Wildcard)
+    }
+    pub fn is_slice(&self) -> bool {
+        matches!(self, OwnedSegment::Slice { .. })
+    }
+
+    /// Returns true if the segment can match more than one value.
+    pub fn matches_many(&self) -> bool {
+        self.is_wildcard() || self.is_slice()
+    }
 
     pub fn can_start_with(&self, prefix: &OwnedSegment) -> bool {
-        match (self, prefix) {
-            (OwnedSegment::Index(a), OwnedSegment::Index(b)) => a == b,
-            (OwnedSegment::Index(_), _) | (_, OwnedSegment::Index(_)) => false,
-            (OwnedSegment::Field(a), OwnedSegment::Field(b)) => a == b,
-        }
+        self == prefix
     }
 }
 
@@ -403,6 +426,8 @@ impl<'a> TryFrom<BorrowedSegment<'a>> for OwnedSegment {
             BorrowedSegment::Invalid => Err(()),
             BorrowedSegment::Index(i) => Ok(OwnedSegment::Index(i)),
             BorrowedSegment::Field(field) => Ok(OwnedSegment::Field(field.into())),
+            BorrowedSegment::Wildcard => Ok(OwnedSegment::Wildcard),
+            BorrowedSegment::Slice { start, end } => Ok(OwnedSegment::Slice { start, end }),
         }
     }
 }
@@ -427,6 +452,18 @@ impl Display for OwnedSegment {
         match self {
             OwnedSegment::Index(i) => write!(f, "[{i}]"),
             OwnedSegment::Field(field) => format_field(f, field),
+            OwnedSegment::Wildcard => write!(f, "[*]"),
+            OwnedSegment::Slice { start, end } => {
+                f.write_char('[')?;
+                if let Some(start) = start {
+                    write!(f, "{start}")?;
+                }
+                f.write_char(':')?;
+                if let Some(end) = end {
+                    write!(f, "{end}")?;
+                }
+                f.write_char(']')
+            }
         }
     }
 }
@@ -512,6 +549,13 @@ mod test {
             (r#"foo."a\"a"."b\\b".bar"#, Some(r#"foo."a\"a"."b\\b".bar"#)),
             ("<invalid>", None),
             (r#""🤖""#, Some(r#""🤖""#)),
+            ("items[*].price", Some("items[*].price")),
+            ("headers.*", Some("headers[*]")),
+            ("[1:3]", Some("[1:3]")),
+            ("[-2:]", Some("[-2:]")),
+            ("[:-1]", Some("[:-1]")),
+            ("[:]", Some("[:]")),
+            ("foo[*", None),
         ];
 
         for (path, expected) in test_cases {
@@ -1,5 +1,7 @@
 use prost_reflect::{DescriptorPool, MessageDescriptor};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use super::source::compile_proto_file;
 
 pub fn get_message_descriptor(
     descriptor_set_path: &Path,
@@ -17,6 +19,15 @@ pub fn get_message_descriptor(
     )
 }
 
+pub fn get_message_descriptor_from_source(
+    proto_path: &Path,
+    include_paths: &[PathBuf],
+    message_type: &str,
+) -> Result<MessageDescriptor, String> {
+    let pool = compile_proto_file(proto_path, include_paths)?;
+    resolve_message_descriptor(pool, message_type, &format!("'{}'", proto_path.display()))
+}
+
 pub fn get_message_descriptor_from_bytes(
     descriptor_bytes: &[u8],
     message_type: &str,
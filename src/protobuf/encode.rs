@@ -1,9 +1,11 @@
 use crate::compiler::prelude::*;
-use crate::value::value::simdutf_bytes_utf8_lossy;
-use chrono::Timelike;
-#[cfg(feature = "enable_system_functions")]
+use crate::value::value::{format_duration, simdutf_bytes_utf8_lossy};
+use chrono::{DateTime, SecondsFormat, Timelike, Utc};
 use prost::Message;
-use prost_reflect::{DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor};
+use prost_reflect::{
+    DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor, ReflectMessage,
+};
+use rust_decimal::prelude::ToPrimitive;
 use std::collections::HashMap;
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
@@ -19,6 +21,12 @@ fn convert_value_raw(
     kind: &Kind,
     options: &Options,
 ) -> Result<prost_reflect::Value, String> {
+    if let Kind::Message(descriptor) = kind
+        && let Some(message) = encode_well_known(&value, descriptor, options)?
+    {
+        return Ok(prost_reflect::Value::Message(message));
+    }
+
     let kind_str = value.kind_str().to_owned();
     match (value, kind) {
         (Value::Boolean(b), Kind::Bool) => Ok(prost_reflect::Value::Bool(b)),
@@ -68,6 +76,22 @@ fn convert_value_raw(
         (Value::Integer(i), Kind::Fixed64) => Ok(prost_reflect::Value::U64(i as u64)),
         (Value::Integer(i), Kind::Double) => Ok(prost_reflect::Value::F64(i as f64)),
         (Value::Integer(i), Kind::Enum(_)) => Ok(prost_reflect::Value::EnumNumber(i as i32)),
+        (Value::Decimal(d), Kind::Int64 | Kind::Sfixed64 | Kind::Sint64) => d
+            .to_i64()
+            .filter(|_| d.is_integer())
+            .map(prost_reflect::Value::I64)
+            .ok_or_else(|| format!("Can't convert '{d}' to i64")),
+        (Value::Decimal(d), Kind::Uint64 | Kind::Fixed64) => d
+            .to_u64()
+            .filter(|_| d.is_integer())
+            .map(prost_reflect::Value::U64)
+            .ok_or_else(|| format!("Can't convert '{d}' to u64")),
+        (Value::Decimal(d), Kind::Double) => {
+            Ok(prost_reflect::Value::F64(d.to_f64().unwrap_or_default()))
+        }
+        (Value::Decimal(d), Kind::Float) => {
+            Ok(prost_reflect::Value::F32(d.to_f32().unwrap_or_default()))
+        }
         (Value::Bytes(b), Kind::Int32 | Kind::Sfixed32 | Kind::Sint32) => {
             let string = simdutf_bytes_utf8_lossy(&b);
             let number: i32 = string
@@ -123,21 +147,10 @@ fn convert_value_raw(
         (Value::Regex(r), Kind::String) => Ok(prost_reflect::Value::String(r.as_str().to_owned())),
         (Value::Regex(r), Kind::Bytes) => Ok(prost_reflect::Value::Bytes(r.as_bytes())),
         (Value::Timestamp(t), Kind::Int64) => Ok(prost_reflect::Value::I64(t.timestamp_micros())),
-        (Value::Timestamp(t), Kind::Message(descriptor))
-            if descriptor.full_name() == "google.protobuf.Timestamp" =>
-        {
-            let mut message = DynamicMessage::new(descriptor.clone());
-            message
-                .try_set_field_by_name("seconds", prost_reflect::Value::I64(t.timestamp()))
-                .map_err(|e| format!("Error setting 'seconds' field: {e}"))?;
-            message
-                .try_set_field_by_name("nanos", prost_reflect::Value::I32(t.nanosecond() as i32))
-                .map_err(|e| format!("Error setting 'nanos' field: {e}"))?;
-            Ok(prost_reflect::Value::Message(message))
-        }
         (Value::Boolean(b), Kind::String) => Ok(prost_reflect::Value::String(b.to_string())),
         (Value::Integer(i), Kind::String) => Ok(prost_reflect::Value::String(i.to_string())),
         (Value::Float(f), Kind::String) => Ok(prost_reflect::Value::String(f.to_string())),
+        (Value::Decimal(d), Kind::String) => Ok(prost_reflect::Value::String(d.to_string())),
         (Value::Timestamp(t), Kind::String) => Ok(prost_reflect::Value::String(t.to_string())),
         _ => Err(format!(
             "Cannot encode `{kind_str}` into protobuf `{kind:?}`",
@@ -151,19 +164,259 @@ fn convert_value(
     value: Value,
     options: &Options,
 ) -> Result<prost_reflect::Value, String> {
-    if let Value::Array(a) = value {
-        if field_descriptor.cardinality() == prost_reflect::Cardinality::Repeated {
+    let kind = field_descriptor.kind();
+    match value {
+        Value::Array(a)
+            if field_descriptor.cardinality() == prost_reflect::Cardinality::Repeated =>
+        {
             let repeated: Result<Vec<prost_reflect::Value>, String> = a
                 .into_iter()
-                .map(|v| convert_value_raw(v, &field_descriptor.kind(), options))
+                .map(|v| convert_value_raw(v, &kind, options))
                 .collect();
             Ok(prost_reflect::Value::List(repeated?))
-        } else {
+        }
+        // Arrays can only be encoded into a single field as a `ListValue` or `Value`.
+        Value::Array(_)
+            if !kind.as_message().is_some_and(|descriptor| {
+                matches!(
+                    descriptor.full_name(),
+                    "google.protobuf.ListValue" | "google.protobuf.Value"
+                )
+            }) =>
+        {
             Err("Cannot encode array into a non-repeated protobuf field".into())
         }
+        value => convert_value_raw(value, &kind, options),
+    }
+}
+
+/// The well-known types which are mapped to a value other than an object, and so are placed
+/// in the `value` field of an `Any`.
+const WELL_KNOWN_TYPES: &[&str] = &[
+    "google.protobuf.Any",
+    "google.protobuf.BoolValue",
+    "google.protobuf.BytesValue",
+    "google.protobuf.DoubleValue",
+    "google.protobuf.Duration",
+    "google.protobuf.FieldMask",
+    "google.protobuf.FloatValue",
+    "google.protobuf.Int32Value",
+    "google.protobuf.Int64Value",
+    "google.protobuf.ListValue",
+    "google.protobuf.StringValue",
+    "google.protobuf.Struct",
+    "google.protobuf.Timestamp",
+    "google.protobuf.UInt32Value",
+    "google.protobuf.UInt64Value",
+    "google.protobuf.Value",
+];
+
+/// Encodes a value into one of the well-known types in the `google.protobuf` package, accepting
+/// the values which correspond to their proto3 JSON mapping. Returns `None` for any other
+/// message type, and for an `Any` without an `@type` field, which are encoded field by field.
+fn encode_well_known(
+    value: &Value,
+    descriptor: &MessageDescriptor,
+    options: &Options,
+) -> Result<Option<DynamicMessage>, String> {
+    let mut message = DynamicMessage::new(descriptor.clone());
+    match descriptor.full_name() {
+        "google.protobuf.Timestamp" => {
+            let timestamp = match value {
+                Value::Timestamp(t) => *t,
+                Value::Bytes(b) => {
+                    let string = simdutf_bytes_utf8_lossy(b);
+                    DateTime::parse_from_rfc3339(&string)
+                        .map_err(|e| format!("Cannot parse `{string}` as timestamp: {e}"))?
+                        .with_timezone(&Utc)
+                }
+                _ => return Err(cannot_encode(value, descriptor)),
+            };
+            set_field(
+                &mut message,
+                "seconds",
+                Value::from(timestamp.timestamp()),
+                options,
+            )?;
+            set_field(
+                &mut message,
+                "nanos",
+                Value::from(i64::from(timestamp.nanosecond())),
+                options,
+            )?;
+        }
+        "google.protobuf.Duration" => {
+            let (seconds, nanos) = match value {
+                Value::Integer(i) => (*i, 0),
+                Value::Float(f) => {
+                    let f = f.into_inner();
+                    (f.trunc() as i64, (f.fract() * 1e9).round() as i64)
+                }
+                Value::Bytes(b) => parse_duration(&simdutf_bytes_utf8_lossy(b))?,
+                Value::Duration(d) => (d.num_seconds(), i64::from(d.subsec_nanos())),
+                _ => return Err(cannot_encode(value, descriptor)),
+            };
+            set_field(&mut message, "seconds", Value::from(seconds), options)?;
+            set_field(&mut message, "nanos", Value::from(nanos), options)?;
+        }
+        "google.protobuf.Struct" => {
+            if !value.is_object() {
+                return Err(cannot_encode(value, descriptor));
+            }
+            set_field(&mut message, "fields", value.clone(), options)?;
+        }
+        "google.protobuf.ListValue" => {
+            if !value.is_array() {
+                return Err(cannot_encode(value, descriptor));
+            }
+            set_field(&mut message, "values", value.clone(), options)?;
+        }
+        "google.protobuf.Value" => {
+            let (field, value) = match value {
+                Value::Null => ("null_value", Value::from(0)),
+                Value::Boolean(_) => ("bool_value", value.clone()),
+                Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => {
+                    ("number_value", value.clone())
+                }
+                Value::Bytes(_) | Value::Regex(_) => ("string_value", value.clone()),
+                Value::Timestamp(t) => (
+                    "string_value",
+                    Value::from(t.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+                ),
+                Value::Duration(d) => ("string_value", Value::from(format_duration(*d))),
+                Value::Object(_) => ("struct_value", value.clone()),
+                Value::Array(_) => ("list_value", value.clone()),
+            };
+            set_field(&mut message, field, value, options)?;
+        }
+        "google.protobuf.DoubleValue"
+        | "google.protobuf.FloatValue"
+        | "google.protobuf.Int64Value"
+        | "google.protobuf.UInt64Value"
+        | "google.protobuf.Int32Value"
+        | "google.protobuf.UInt32Value"
+        | "google.protobuf.BoolValue"
+        | "google.protobuf.StringValue"
+        | "google.protobuf.BytesValue" => set_field(&mut message, "value", value.clone(), options)?,
+        "google.protobuf.FieldMask" => {
+            let Value::Bytes(b) = value else {
+                return Err(cannot_encode(value, descriptor));
+            };
+            let paths = simdutf_bytes_utf8_lossy(b)
+                .split(',')
+                .filter(|path| !path.is_empty())
+                .map(|path| Value::from(snake_case(path)))
+                .collect::<Vec<_>>();
+            set_field(&mut message, "paths", Value::from(paths), options)?;
+        }
+        "google.protobuf.Any" => {
+            let Some(type_url) = value.as_object().and_then(|o| o.get("@type")) else {
+                return Ok(None);
+            };
+            let type_url = type_url
+                .as_str()
+                .ok_or("The `@type` field of a protobuf `Any` must be a string")?
+                .into_owned();
+            let message_name = type_url.rsplit('/').next().unwrap_or_default();
+            let packed_descriptor = descriptor
+                .parent_pool()
+                .get_message_by_name(message_name)
+                .ok_or_else(|| {
+                    format!("The message type '{message_name}' could not be found for `Any`")
+                })?;
+
+            let mut object = value.as_object().cloned().unwrap_or_default();
+            object.remove("@type");
+            let packed = if WELL_KNOWN_TYPES.contains(&message_name) {
+                let value = object.remove("value").unwrap_or(Value::Null);
+                match encode_well_known(&value, &packed_descriptor, options)? {
+                    Some(packed) => packed,
+                    None => encode_message(&packed_descriptor, value, options)?,
+                }
+            } else {
+                encode_message(&packed_descriptor, Value::Object(object), options)?
+            };
+
+            set_field(&mut message, "type_url", Value::from(type_url), options)?;
+            set_field(
+                &mut message,
+                "value",
+                Value::from(Bytes::from(packed.encode_to_vec())),
+                options,
+            )?;
+        }
+        _ => return Ok(None),
+    }
+    Ok(Some(message))
+}
+
+fn set_field(
+    message: &mut DynamicMessage,
+    name: &str,
+    value: Value,
+    options: &Options,
+) -> Result<(), String> {
+    let field = message
+        .descriptor()
+        .get_field_by_name(name)
+        .ok_or_else(|| {
+            format!(
+                "Missing field '{name}' in '{}'",
+                message.descriptor().full_name()
+            )
+        })?;
+    let value = convert_value(&field, value, options)?;
+    message
+        .try_set_field(&field, value)
+        .map_err(|e| format!("Error setting '{name}' field: {e}"))
+}
+
+fn cannot_encode(value: &Value, descriptor: &MessageDescriptor) -> String {
+    format!(
+        "Cannot encode `{}` into protobuf `{}`",
+        value.kind_str(),
+        descriptor.full_name()
+    )
+}
+
+/// Parses a duration as in the proto3 JSON mapping, such as `"1.5s"`, into seconds and
+/// nanoseconds.
+fn parse_duration(string: &str) -> Result<(i64, i64), String> {
+    let invalid = || format!("Cannot parse `{string}` as duration");
+    let digits = string.strip_suffix('s').ok_or_else(invalid)?;
+    let (negative, digits) = match digits.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, digits),
+    };
+    let (seconds, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let seconds: i64 = seconds.parse().map_err(|_| invalid())?;
+    let nanos = if fraction.is_empty() {
+        0
     } else {
-        convert_value_raw(value, &field_descriptor.kind(), options)
+        format!("{fraction:0<9}").parse().map_err(|_| invalid())?
+    };
+    Ok(if negative {
+        (-seconds, -nanos)
+    } else {
+        (seconds, nanos)
+    })
+}
+
+/// Converts a lowerCamelCase field path to the snake_case field names in a `FieldMask`.
+fn snake_case(path: &str) -> String {
+    let mut output = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c.is_ascii_uppercase() {
+            output.push('_');
+            output.push(c.to_ascii_lowercase());
+        } else {
+            output.push(c);
+        }
     }
+    output
 }
 
 /// Converts a VRL [`Value`] into a protobuf [`DynamicMessage`].
@@ -233,7 +486,7 @@ pub(crate) fn encode_proto(descriptor: &MessageDescriptor, value: Value) -> Reso
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::protobuf::descriptor::get_message_descriptor;
+    use crate::protobuf::descriptor::{get_message_descriptor, get_message_descriptor_from_source};
     use crate::protobuf::parse::parse_proto;
     use crate::value;
     use bytes::Bytes;
@@ -259,6 +512,15 @@ mod tests {
         get_message_descriptor(&path, &format!("test.v1.{message_type}")).unwrap()
     }
 
+    fn test_well_known_descriptor(message_type: &str) -> MessageDescriptor {
+        get_message_descriptor_from_source(
+            &test_data_dir().join("test_well_known/v1/test_well_known.proto"),
+            &[test_data_dir()],
+            &format!("test_well_known.v1.{message_type}"),
+        )
+        .unwrap()
+    }
+
     fn test_protobuf3_descriptor() -> MessageDescriptor {
         let path = test_data_dir().join("test_protobuf3/v1/test_protobuf3.desc");
         get_message_descriptor(&path, "test_protobuf3.v1.Person").unwrap()
@@ -268,7 +530,7 @@ mod tests {
     fn test_encode_integers() {
         let message = encode_message(
             &test_message_descriptor("Integers"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("i32".into(), Value::Integer(-1234)),
                 ("i64".into(), Value::Integer(-9876)),
                 ("u32".into(), Value::Integer(1234)),
@@ -287,7 +549,7 @@ mod tests {
     fn test_encode_integers_from_bytes() {
         let message = encode_message(
             &test_message_descriptor("Integers"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("i32".into(), Value::Bytes(Bytes::from("-1234"))),
                 ("i64".into(), Value::Bytes(Bytes::from("-9876"))),
                 ("u32".into(), Value::Bytes(Bytes::from("1234"))),
@@ -306,7 +568,7 @@ mod tests {
     fn test_encode_floats() {
         let message = encode_message(
             &test_message_descriptor("Floats"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("d".into(), Value::Float(NotNan::new(11.0).unwrap())),
                 ("f".into(), Value::Float(NotNan::new(2.0).unwrap())),
             ])),
@@ -321,7 +583,7 @@ mod tests {
     fn test_encode_bytes_as_float() {
         let message = encode_message(
             &test_message_descriptor("Floats"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("d".into(), Value::Bytes(Bytes::from("11.0"))),
                 ("f".into(), Value::Bytes(Bytes::from("2.0"))),
             ])),
@@ -336,7 +598,7 @@ mod tests {
     fn test_encode_integer_as_double() {
         let message = encode_message(
             &test_message_descriptor("Floats"),
-            Value::Object(BTreeMap::from([("d".into(), Value::Integer(42))])),
+            Value::from(BTreeMap::from([("d".into(), Value::Integer(42))])),
             &Options::default(),
         )
         .unwrap();
@@ -348,7 +610,7 @@ mod tests {
         let bytes = Bytes::from(vec![0, 1, 2, 3]);
         let message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("text".into(), Value::Bytes(Bytes::from("vector"))),
                 ("binary".into(), Value::Bytes(bytes.clone())),
             ])),
@@ -363,19 +625,19 @@ mod tests {
     fn test_encode_map() {
         let message = encode_message(
             &test_message_descriptor("Map"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 (
                     "names".into(),
-                    Value::Object(BTreeMap::from([
+                    Value::from(BTreeMap::from([
                         ("forty-four".into(), Value::Integer(44)),
                         ("one".into(), Value::Integer(1)),
                     ])),
                 ),
                 (
                     "people".into(),
-                    Value::Object(BTreeMap::from([(
+                    Value::from(BTreeMap::from([(
                         "mark".into(),
-                        Value::Object(BTreeMap::from([
+                        Value::from(BTreeMap::from([
                             ("nickname".into(), Value::Bytes(Bytes::from("jeff"))),
                             ("age".into(), Value::Integer(22)),
                         ])),
@@ -421,7 +683,7 @@ mod tests {
     fn test_encode_enum() {
         let message = encode_message(
             &test_message_descriptor("Enum"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 (
                     "breakfast".into(),
                     Value::Bytes(Bytes::from("fruit_tomato")),
@@ -441,7 +703,7 @@ mod tests {
     fn test_encode_timestamp() {
         let message = encode_message(
             &test_message_descriptor("Timestamp"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "morning".into(),
                 Value::Timestamp(
                     DateTime::from_timestamp(8675, 309).expect("could not compute timestamp"),
@@ -459,7 +721,7 @@ mod tests {
     fn test_encode_repeated_primitive() {
         let message = encode_message(
             &test_message_descriptor("RepeatedPrimitive"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "numbers".into(),
                 Value::Array(vec![
                     Value::Integer(8),
@@ -481,15 +743,15 @@ mod tests {
     fn test_encode_repeated_message() {
         let message = encode_message(
             &test_message_descriptor("RepeatedMessage"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "messages".into(),
                 Value::Array(vec![
-                    Value::Object(BTreeMap::from([(
+                    Value::from(BTreeMap::from([(
                         "text".into(),
                         Value::Bytes(Bytes::from("vector")),
                     )])),
-                    Value::Object(BTreeMap::from([("index".into(), Value::Integer(4444))])),
-                    Value::Object(BTreeMap::from([
+                    Value::from(BTreeMap::from([("index".into(), Value::Integer(4444))])),
+                    Value::from(BTreeMap::from([
                         ("text".into(), Value::Bytes(Bytes::from("protobuf"))),
                         ("index".into(), Value::Integer(1)),
                     ])),
@@ -524,21 +786,21 @@ mod tests {
     fn test_encode_value_as_string() {
         let mut message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([("text".into(), Value::Boolean(true))])),
+            Value::from(BTreeMap::from([("text".into(), Value::Boolean(true))])),
             &Options::default(),
         )
         .unwrap();
         assert_eq!(Some("true"), mfield!(message, "text").as_str());
         message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([("text".into(), Value::Integer(123))])),
+            Value::from(BTreeMap::from([("text".into(), Value::Integer(123))])),
             &Options::default(),
         )
         .unwrap();
         assert_eq!(Some("123"), mfield!(message, "text").as_str());
         message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "text".into(),
                 Value::Float(NotNan::new(45.67).unwrap()),
             )])),
@@ -548,7 +810,7 @@ mod tests {
         assert_eq!(Some("45.67"), mfield!(message, "text").as_str());
         message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "text".into(),
                 Value::Timestamp(
                     DateTime::from_timestamp(8675, 309).expect("could not compute timestamp"),
@@ -659,4 +921,134 @@ mod tests {
             mfield!(message, "job_description").as_str()
         );
     }
+
+    #[test]
+    fn test_encode_well_known_types() {
+        let message = encode_message(
+            &test_well_known_descriptor("Event"),
+            value!({
+                time: "2024-01-02T03:04:05.5Z",
+                elapsed: "-1.25s",
+                attributes: { service: "web", tags: ["a", 1], nested: { ok: true, none: null } },
+                value: 12.5,
+                list: ["a", { b: 2 }],
+                mask: "user.fullName,age",
+                detail: { "@type": "type.googleapis.com/test_well_known.v1.User", name: "Someone", age: 42 },
+            }),
+            &Options::default(),
+        )
+        .unwrap();
+
+        let time = mfield!(message, "time").as_message().unwrap().clone();
+        assert_eq!(Some(1_704_164_645), mfield!(time, "seconds").as_i64());
+        assert_eq!(Some(500_000_000), mfield!(time, "nanos").as_i32());
+
+        let elapsed = mfield!(message, "elapsed").as_message().unwrap().clone();
+        assert_eq!(Some(-1), mfield!(elapsed, "seconds").as_i64());
+        assert_eq!(Some(-250_000_000), mfield!(elapsed, "nanos").as_i32());
+
+        let from_duration = encode_message(
+            &test_well_known_descriptor("Event"),
+            value!({ elapsed: (chrono::TimeDelta::milliseconds(-1250)) }),
+            &Options::default(),
+        )
+        .unwrap();
+        let elapsed = mfield!(from_duration, "elapsed")
+            .as_message()
+            .unwrap()
+            .clone();
+        assert_eq!(Some(-1), mfield!(elapsed, "seconds").as_i64());
+        assert_eq!(Some(-250_000_000), mfield!(elapsed, "nanos").as_i32());
+
+        let attributes = mfield!(message, "attributes").as_message().unwrap().clone();
+        let fields = mfield!(attributes, "fields");
+        let service = fields
+            .as_map()
+            .unwrap()
+            .get(&MapKey::String("service".into()))
+            .unwrap()
+            .as_message()
+            .unwrap()
+            .clone();
+        assert_eq!(Some("web"), mfield!(service, "string_value").as_str());
+
+        let value = mfield!(message, "value").as_message().unwrap().clone();
+        assert_eq!(Some(12.5), mfield!(value, "number_value").as_f64());
+
+        let list = mfield!(message, "list").as_message().unwrap().clone();
+        assert_eq!(2, mfield!(list, "values").as_list().unwrap().len());
+
+        let mask = mfield!(message, "mask").as_message().unwrap().clone();
+        assert_eq!(
+            vec![
+                prost_reflect::Value::String("user.full_name".into()),
+                prost_reflect::Value::String("age".into())
+            ],
+            mfield!(mask, "paths").as_list().unwrap()
+        );
+
+        let detail = mfield!(message, "detail").as_message().unwrap().clone();
+        assert_eq!(
+            Some("type.googleapis.com/test_well_known.v1.User"),
+            mfield!(detail, "type_url").as_str()
+        );
+        let user = DynamicMessage::decode(
+            test_well_known_descriptor("User"),
+            mfield!(detail, "value").as_bytes().unwrap().clone(),
+        )
+        .unwrap();
+        assert_eq!(Some("Someone"), mfield!(user, "name").as_str());
+        assert_eq!(Some(42), mfield!(user, "age").as_u32());
+    }
+
+    #[test]
+    fn test_encode_wrappers() {
+        let message = encode_message(
+            &test_well_known_descriptor("Wrappers"),
+            value!({
+                double_value: 1.5,
+                int64_value: "-7",
+                uint32_value: 7,
+                bool_value: false,
+                string_value: "text",
+            }),
+            &Options::default(),
+        )
+        .unwrap();
+
+        let wrapped = |field: &str| {
+            let wrapper = mfield!(message, field).as_message().unwrap().clone();
+            mfield!(wrapper, "value")
+        };
+        assert_eq!(Some(1.5), wrapped("double_value").as_f64());
+        assert_eq!(Some(-7), wrapped("int64_value").as_i64());
+        assert_eq!(Some(7), wrapped("uint32_value").as_u32());
+        assert_eq!(Some(false), wrapped("bool_value").as_bool());
+        assert_eq!(Some("text"), wrapped("string_value").as_str());
+        assert!(!message.has_field_by_name("float_value"));
+    }
+
+    #[test]
+    fn test_encode_well_known_errors() {
+        let descriptor = test_well_known_descriptor("Event");
+        for (value, error) in [
+            (
+                value!({ elapsed: "5m" }),
+                "Error converting elapsed field: Cannot parse `5m` as duration",
+            ),
+            (
+                value!({ attributes: "text" }),
+                "Error converting attributes field: Cannot encode `string` into protobuf `google.protobuf.Struct`",
+            ),
+            (
+                value!({ detail: { "@type": "type.googleapis.com/unknown.Message" } }),
+                "Error converting detail field: The message type 'unknown.Message' could not be found for `Any`",
+            ),
+        ] {
+            assert_eq!(
+                encode_message(&descriptor, value, &Options::default()).unwrap_err(),
+                error
+            );
+        }
+    }
 }
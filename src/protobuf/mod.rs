@@ -1,3 +1,5 @@
 pub mod descriptor;
 pub mod encode;
+pub mod otlp;
 pub mod parse;
+pub mod source;
@@ -0,0 +1,539 @@
+//! Decoding and encoding of OpenTelemetry protocol (OTLP) payloads. The resource, scope and
+//! record hierarchy of each signal is flattened into a list of records, each with its own copy of
+//! the `resource` and `scope` it belongs to, and attribute lists are converted to objects.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, Utc};
+use prost::Message;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor, ReflectMessage,
+};
+
+use super::encode::{Options as EncodeOptions, encode_message};
+use super::parse::{Options as ParseOptions, proto_to_value};
+use super::source::compile_proto_sources;
+use crate::value::value::format_duration;
+use crate::value::{ObjectMap, Value};
+
+const ANY_VALUE: &str = "opentelemetry.proto.common.v1.AnyValue";
+const KEY_VALUE: &str = "opentelemetry.proto.common.v1.KeyValue";
+
+type ScopeRecords = (Value, Vec<Value>);
+
+/// The OTLP protobuf definitions, from <https://github.com/open-telemetry/opentelemetry-proto>.
+static POOL: LazyLock<DescriptorPool> = LazyLock::new(|| {
+    macro_rules! proto {
+        ($path:literal) => {
+            ($path, include_str!(concat!("otlp/", $path)))
+        };
+    }
+
+    compile_proto_sources([
+        proto!("opentelemetry/proto/common/v1/common.proto"),
+        proto!("opentelemetry/proto/resource/v1/resource.proto"),
+        proto!("opentelemetry/proto/logs/v1/logs.proto"),
+        proto!("opentelemetry/proto/trace/v1/trace.proto"),
+        proto!("opentelemetry/proto/metrics/v1/metrics.proto"),
+    ])
+    .expect("OTLP protobuf definitions are valid")
+});
+
+/// An OpenTelemetry signal, which determines the message type of a payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Signal {
+    Logs,
+    Traces,
+    Metrics,
+}
+
+impl Signal {
+    /// The message type of a payload. Each has the same encoding as the corresponding
+    /// `Export*ServiceRequest` of the OTLP collector service.
+    fn data_message(self) -> &'static str {
+        match self {
+            Self::Logs => "opentelemetry.proto.logs.v1.LogsData",
+            Self::Traces => "opentelemetry.proto.trace.v1.TracesData",
+            Self::Metrics => "opentelemetry.proto.metrics.v1.MetricsData",
+        }
+    }
+
+    /// The names of the resource, scope and record list fields.
+    fn hierarchy(self) -> [&'static str; 3] {
+        match self {
+            Self::Logs => ["resource_logs", "scope_logs", "log_records"],
+            Self::Traces => ["resource_spans", "scope_spans", "spans"],
+            Self::Metrics => ["resource_metrics", "scope_metrics", "metrics"],
+        }
+    }
+
+    fn descriptor(self) -> MessageDescriptor {
+        POOL.get_message_by_name(self.data_message())
+            .expect("OTLP message types are defined")
+    }
+}
+
+impl FromStr for Signal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "logs" => Ok(Self::Logs),
+            "traces" => Ok(Self::Traces),
+            "metrics" => Ok(Self::Metrics),
+            _ => Err(format!("unknown OTLP signal '{s}'")),
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Logs => "logs",
+            Self::Traces => "traces",
+            Self::Metrics => "metrics",
+        })
+    }
+}
+
+/// Decodes an OTLP payload into an array of records.
+///
+/// Attribute lists become objects, `AnyValue`s become the value they hold, trace and span IDs
+/// become lowercase hex strings and `*_time_unix_nano` fields become timestamps. The
+/// `schema_url` of the resource and scope lists are placed in the `resource` and `scope` objects.
+///
+/// # Errors
+///
+/// Returns an error if `bytes` isn't a valid payload for the `signal`.
+pub fn decode_otlp(bytes: Bytes, signal: Signal) -> Result<Value, String> {
+    let data = DynamicMessage::decode(signal.descriptor(), bytes)
+        .map_err(|e| format!("Error parsing OTLP {signal}: {e}"))?;
+    let [resource_field, scope_field, records_field] = signal.hierarchy();
+
+    let mut records = Vec::new();
+    for resource_list in messages(&data, resource_field) {
+        let resource = parent_to_value(&resource_list, "resource")?;
+        for scope_list in messages(&resource_list, scope_field) {
+            let scope = parent_to_value(&scope_list, "scope")?;
+            for record in messages(&scope_list, records_field) {
+                let mut record = message_to_object(&record)?;
+                record.insert("resource".into(), resource.clone());
+                record.insert("scope".into(), scope.clone());
+                records.push(Value::Object(record));
+            }
+        }
+    }
+
+    Ok(Value::Array(records))
+}
+
+/// Encodes an array of records, in the form returned by [`decode_otlp`], into an OTLP payload.
+/// Records with equal `resource` and `scope` objects are grouped together.
+///
+/// # Errors
+///
+/// Returns an error if a record isn't an object, or one of its fields can't be encoded.
+pub fn encode_otlp(records: Value, signal: Signal) -> Result<Bytes, String> {
+    let Value::Array(records) = records else {
+        return Err("OTLP records must be an array".into());
+    };
+
+    // Each resource with its scopes, and each scope with its records.
+    let mut resources: Vec<(Value, Vec<ScopeRecords>)> = Vec::new();
+    for record in records {
+        let Value::Object(mut record) = record else {
+            return Err("OTLP records must be objects".into());
+        };
+        let resource = record.remove("resource").unwrap_or(Value::Null);
+        let scope = record.remove("scope").unwrap_or(Value::Null);
+
+        let scopes = match resources.iter().position(|(r, _)| *r == resource) {
+            Some(index) => &mut resources[index].1,
+            None => {
+                resources.push((resource, Vec::new()));
+                &mut resources.last_mut().expect("just pushed").1
+            }
+        };
+        match scopes.iter_mut().find(|(s, _)| *s == scope) {
+            Some((_, records)) => records.push(Value::Object(record)),
+            None => scopes.push((scope, vec![Value::Object(record)])),
+        }
+    }
+
+    let [resource_field, scope_field, records_field] = signal.hierarchy();
+    let resources = resources
+        .into_iter()
+        .map(|(resource, scopes)| {
+            let scopes = scopes
+                .into_iter()
+                .map(|(scope, records)| {
+                    let mut scope_list = parent_from_value(scope, "scope");
+                    scope_list.insert(records_field.into(), Value::Array(records));
+                    Value::Object(scope_list)
+                })
+                .collect();
+            let mut resource_list = parent_from_value(resource, "resource");
+            resource_list.insert(scope_field.into(), Value::Array(scopes));
+            Value::Object(resource_list)
+        })
+        .collect();
+    let data = ObjectMap::from([(resource_field.into(), Value::Array(resources))]);
+
+    let descriptor = signal.descriptor();
+    let data = to_proto_shape(Value::Object(data), &descriptor)?;
+    let message = encode_message(&descriptor, data, &EncodeOptions::default())
+        .map_err(|e| format!("Error encoding OTLP {signal}: {e}"))?;
+    Ok(Bytes::from(message.encode_to_vec()))
+}
+
+fn messages(message: &DynamicMessage, field: &str) -> Vec<DynamicMessage> {
+    message
+        .get_field_by_name(field)
+        .and_then(|list| {
+            list.as_list().map(|list| {
+                list.iter()
+                    .filter_map(|message| message.as_message().cloned())
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Converts the `resource` or `scope` of a resource or scope list, along with the list's
+/// `schema_url`, into an object.
+fn parent_to_value(list: &DynamicMessage, field: &str) -> Result<Value, String> {
+    let mut object = match list.get_field_by_name(field) {
+        Some(parent) if list.has_field_by_name(field) => message_to_object(
+            parent
+                .as_message()
+                .expect("resource and scope are messages"),
+        )?,
+        _ => ObjectMap::new(),
+    };
+    if let Some(schema_url) = list.get_field_by_name("schema_url")
+        && let Some(schema_url) = schema_url.as_str()
+        && !schema_url.is_empty()
+    {
+        object.insert("schema_url".into(), Value::from(schema_url));
+    }
+    Ok(Value::Object(object))
+}
+
+/// The inverse of [`parent_to_value`], returning the fields of the resource or scope list.
+fn parent_from_value(parent: Value, field: &str) -> ObjectMap {
+    let mut list = ObjectMap::new();
+    if let Value::Object(mut parent) = parent {
+        if let Some(schema_url) = parent.remove("schema_url") {
+            list.insert("schema_url".into(), schema_url);
+        }
+        list.insert(field.into(), Value::Object(parent));
+    }
+    list
+}
+
+fn message_to_object(message: &DynamicMessage) -> Result<ObjectMap, String> {
+    let mut object = ObjectMap::new();
+    for field in message.descriptor().fields() {
+        if message.has_field(&field) {
+            let value = message.get_field(&field);
+            object.insert(field.name().into(), field_to_value(&field, &value)?);
+        }
+    }
+    Ok(object)
+}
+
+fn field_to_value(field: &FieldDescriptor, value: &prost_reflect::Value) -> Result<Value, String> {
+    match value {
+        prost_reflect::Value::List(list) if is_message(field, KEY_VALUE) => {
+            attributes_to_object(list).map(Value::Object)
+        }
+        prost_reflect::Value::List(list) => list
+            .iter()
+            .map(|value| field_to_value(field, value))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        prost_reflect::Value::Message(message) if is_message(field, ANY_VALUE) => {
+            any_value_to_value(message)
+        }
+        prost_reflect::Value::Message(message) => message_to_object(message).map(Value::Object),
+        prost_reflect::Value::Bytes(bytes) if is_id(field) => {
+            Ok(Value::from(base16::encode_lower(bytes)))
+        }
+        prost_reflect::Value::U64(nanos) if is_time(field) => i64::try_from(*nanos)
+            .map(|nanos| Value::from(DateTime::from_timestamp_nanos(nanos)))
+            .map_err(|_| format!("Invalid timestamp in '{}'", field.name())),
+        value => proto_to_value(value, Some(field), &ParseOptions::default()),
+    }
+}
+
+fn attributes_to_object(list: &[prost_reflect::Value]) -> Result<ObjectMap, String> {
+    let mut object = ObjectMap::new();
+    for key_value in list.iter().filter_map(prost_reflect::Value::as_message) {
+        let key = key_value
+            .get_field_by_name("key")
+            .and_then(|key| key.as_str().map(ToOwned::to_owned))
+            .unwrap_or_default();
+        let value = match key_value.get_field_by_name("value") {
+            Some(value) if key_value.has_field_by_name("value") => {
+                any_value_to_value(value.as_message().expect("AnyValue is a message"))?
+            }
+            _ => Value::Null,
+        };
+        object.insert(key.into(), value);
+    }
+    Ok(object)
+}
+
+fn any_value_to_value(any_value: &DynamicMessage) -> Result<Value, String> {
+    let Some(field) = any_value
+        .descriptor()
+        .fields()
+        .find(|field| any_value.has_field(field))
+    else {
+        return Ok(Value::Null);
+    };
+
+    let value = any_value.get_field(&field);
+    let values = || {
+        value
+            .as_message()
+            .and_then(|message| message.get_field_by_name("values"))
+            .and_then(|values| values.as_list().map(<[_]>::to_vec))
+            .unwrap_or_default()
+    };
+    match field.name() {
+        "array_value" => values()
+            .iter()
+            .filter_map(prost_reflect::Value::as_message)
+            .map(any_value_to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        "kvlist_value" => attributes_to_object(&values()).map(Value::Object),
+        _ => proto_to_value(&value, Some(&field), &ParseOptions::default()),
+    }
+}
+
+/// Converts a value in the form returned by [`decode_otlp`] into the shape of the message
+/// `descriptor`, so that it can be encoded with [`encode_message`].
+fn to_proto_shape(value: Value, descriptor: &MessageDescriptor) -> Result<Value, String> {
+    if descriptor.full_name() == ANY_VALUE {
+        return Ok(value_to_any_value(value));
+    }
+    let Value::Object(object) = value else {
+        return Ok(value);
+    };
+
+    let mut shaped = ObjectMap::new();
+    for (key, value) in object {
+        let Some(field) = descriptor.get_field_by_name(&key) else {
+            continue;
+        };
+        let value = match (value, field.kind()) {
+            (Value::Object(attributes), _) if is_message(&field, KEY_VALUE) => {
+                Value::Array(attributes_from_object(attributes))
+            }
+            (Value::Array(values), Kind::Message(message)) => values
+                .into_iter()
+                .map(|value| to_proto_shape(value, &message))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)?,
+            (value, Kind::Message(message)) => to_proto_shape(value, &message)?,
+            (Value::Bytes(hex), Kind::Bytes) if is_id(&field) => base16::decode(&hex)
+                .map(|bytes| Value::Bytes(bytes.into()))
+                .map_err(|e| format!("Invalid hex in '{key}': {e}"))?,
+            (Value::Timestamp(timestamp), Kind::Fixed64) if is_time(&field) => timestamp
+                .timestamp_nanos_opt()
+                .map(Value::from)
+                .ok_or_else(|| format!("Timestamp in '{key}' is out of range"))?,
+            (value, _) => value,
+        };
+        shaped.insert(key, value);
+    }
+    Ok(Value::Object(shaped))
+}
+
+fn attributes_from_object(attributes: ObjectMap) -> Vec<Value> {
+    attributes
+        .into_iter()
+        .map(|(key, value)| {
+            Value::Object(ObjectMap::from([
+                ("key".into(), Value::from(key.as_str())),
+                ("value".into(), value_to_any_value(value)),
+            ]))
+        })
+        .collect()
+}
+
+fn value_to_any_value(value: Value) -> Value {
+    let (field, value) = match value {
+        Value::Null => return Value::Object(ObjectMap::new()),
+        Value::Boolean(_) => ("bool_value", value),
+        Value::Integer(_) => ("int_value", value),
+        Value::Float(_) | Value::Decimal(_) => ("double_value", value),
+        Value::Bytes(bytes) if std::str::from_utf8(&bytes).is_ok() => {
+            ("string_value", Value::Bytes(bytes))
+        }
+        Value::Bytes(_) => ("bytes_value", value),
+        Value::Regex(regex) => ("string_value", Value::from(regex.as_str())),
+        Value::Timestamp(timestamp) => ("string_value", Value::from(rfc3339(timestamp))),
+        Value::Duration(duration) => ("string_value", Value::from(format_duration(duration))),
+        Value::Array(values) => (
+            "array_value",
+            Value::Object(ObjectMap::from([(
+                "values".into(),
+                Value::Array(values.into_iter().map(value_to_any_value).collect()),
+            )])),
+        ),
+        Value::Object(attributes) => (
+            "kvlist_value",
+            Value::Object(ObjectMap::from([(
+                "values".into(),
+                Value::Array(attributes_from_object(attributes)),
+            )])),
+        ),
+    };
+    Value::Object(ObjectMap::from([(field.into(), value)]))
+}
+
+fn rfc3339(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn is_message(field: &FieldDescriptor, name: &str) -> bool {
+    field
+        .kind()
+        .as_message()
+        .is_some_and(|message| message.full_name() == name)
+}
+
+fn is_id(field: &FieldDescriptor) -> bool {
+    field.name().ends_with("_id")
+}
+
+fn is_time(field: &FieldDescriptor) -> bool {
+    field.name().ends_with("time_unix_nano")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    fn log_records() -> Value {
+        value!([
+            {
+                resource: { attributes: { "service.name": "checkout", "host.cores": 8 }, schema_url: "https://opentelemetry.io/schemas/1.24.0" },
+                scope: { name: "logger", version: "1.0.0" },
+                time_unix_nano: (DateTime::from_timestamp_nanos(1_700_000_000_123_456_789)),
+                severity_number: "SEVERITY_NUMBER_INFO",
+                severity_text: "INFO",
+                body: "user logged in",
+                attributes: { user: { id: 42, roles: ["admin", true, 1.5] }, empty: null },
+                trace_id: "5b8efff798038103d269b633813fc60c",
+                span_id: "eee19b7ec3c1b174",
+            },
+            {
+                resource: { attributes: { "service.name": "checkout", "host.cores": 8 }, schema_url: "https://opentelemetry.io/schemas/1.24.0" },
+                scope: { name: "logger", version: "1.0.0" },
+                severity_number: "SEVERITY_NUMBER_ERROR",
+                body: { message: "failed", code: 500 },
+            },
+            {
+                resource: { attributes: { "service.name": "cart" } },
+                scope: {},
+                body: "from another service",
+            },
+        ])
+    }
+
+    #[test]
+    fn roundtrips_logs() {
+        let bytes = encode_otlp(log_records(), Signal::Logs).unwrap();
+        assert_eq!(decode_otlp(bytes, Signal::Logs).unwrap(), log_records());
+    }
+
+    #[test]
+    fn groups_records_by_resource_and_scope() {
+        let bytes = encode_otlp(log_records(), Signal::Logs).unwrap();
+        let data = DynamicMessage::decode(Signal::Logs.descriptor(), bytes).unwrap();
+
+        let resources = messages(&data, "resource_logs");
+        assert_eq!(resources.len(), 2);
+        let scopes = messages(&resources[0], "scope_logs");
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(messages(&scopes[0], "log_records").len(), 2);
+        assert_eq!(
+            resources[0]
+                .get_field_by_name("schema_url")
+                .unwrap()
+                .as_str(),
+            Some("https://opentelemetry.io/schemas/1.24.0")
+        );
+    }
+
+    #[test]
+    fn roundtrips_traces() {
+        let spans = value!([{
+            resource: { attributes: { "service.name": "api" } },
+            scope: { name: "tracer" },
+            trace_id: "5b8efff798038103d269b633813fc60c",
+            span_id: "eee19b7ec3c1b174",
+            parent_span_id: "eee19b7ec3c1b173",
+            name: "GET /users",
+            kind: "SPAN_KIND_SERVER",
+            start_time_unix_nano: (DateTime::from_timestamp_nanos(1_700_000_000_000_000_000)),
+            end_time_unix_nano: (DateTime::from_timestamp_nanos(1_700_000_000_250_000_000)),
+            attributes: { "http.status_code": 200 },
+            events: [{ name: "cache miss", time_unix_nano: (DateTime::from_timestamp_nanos(1_700_000_000_100_000_000)) }],
+            links: [{ trace_id: "00000000000000000000000000000001", span_id: "0000000000000001", attributes: { reason: "retry" } }],
+            status: { code: "STATUS_CODE_ERROR", message: "timeout" },
+        }]);
+
+        let bytes = encode_otlp(spans.clone(), Signal::Traces).unwrap();
+        assert_eq!(decode_otlp(bytes, Signal::Traces).unwrap(), spans);
+    }
+
+    #[test]
+    fn roundtrips_metrics() {
+        let metrics = value!([
+            {
+                resource: {},
+                scope: { name: "meter" },
+                name: "requests",
+                unit: "1",
+                sum: {
+                    aggregation_temporality: "AGGREGATION_TEMPORALITY_CUMULATIVE",
+                    is_monotonic: true,
+                    data_points: [{ as_int: 10, attributes: { route: "/users" }, time_unix_nano: (DateTime::from_timestamp_nanos(1_700_000_000_000_000_000)) }],
+                },
+            },
+            {
+                resource: {},
+                scope: { name: "meter" },
+                name: "latency",
+                histogram: {
+                    data_points: [{ count: 3, sum: 1.5, bucket_counts: [1, 2], explicit_bounds: [0.5] }],
+                },
+            },
+        ]);
+
+        let bytes = encode_otlp(metrics.clone(), Signal::Metrics).unwrap();
+        assert_eq!(decode_otlp(bytes, Signal::Metrics).unwrap(), metrics);
+    }
+
+    #[test]
+    fn rejects_invalid_payloads() {
+        assert!(
+            decode_otlp(Bytes::from_static(b"\xff\xff"), Signal::Logs)
+                .unwrap_err()
+                .starts_with("Error parsing OTLP logs")
+        );
+        assert_eq!(
+            encode_otlp(value!([{ trace_id: "xyz" }]), Signal::Traces).unwrap_err(),
+            "Invalid hex in 'trace_id': Base16 data cannot have length 3 (must be even)"
+        );
+    }
+}
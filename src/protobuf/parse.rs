@@ -1,7 +1,10 @@
 use crate::compiler::prelude::*;
-use prost_reflect::ReflectMessage;
+use chrono::{DateTime, TimeDelta};
 #[cfg(any(feature = "enable_system_functions", test))]
-use prost_reflect::{DynamicMessage, MessageDescriptor};
+use prost_reflect::MessageDescriptor;
+use prost_reflect::{DynamicMessage, FieldDescriptor, ReflectMessage};
+
+use super::source::json_name;
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct Options {
@@ -35,6 +38,9 @@ pub fn proto_to_value(
                         "Internal error while parsing protobuf enum. Field descriptor: {field_descriptor:?}"
                     )
                 })?;
+                if enum_desc.full_name() == "google.protobuf.NullValue" {
+                    return Ok(Value::Null);
+                }
                 Value::from(
                     enum_desc
                         .get_value(*v)
@@ -47,22 +53,10 @@ pub fn proto_to_value(
                 Err("Expected valid field descriptor")?
             }
         }
-        prost_reflect::Value::Message(v) => {
-            let mut obj_map = ObjectMap::new();
-            for field_desc in v.descriptor().fields() {
-                if v.has_field(&field_desc) {
-                    let field_value = v.get_field(&field_desc);
-                    let out = proto_to_value(field_value.as_ref(), Some(&field_desc), options)?;
-                    let field_key = if options.use_json_names {
-                        field_desc.json_name()
-                    } else {
-                        field_desc.name()
-                    };
-                    obj_map.insert(field_key.into(), out);
-                }
-            }
-            Value::from(obj_map)
-        }
+        prost_reflect::Value::Message(v) => match well_known_to_value(v, options)? {
+            Some(value) => value,
+            None => Value::from(message_to_object(v, options)?),
+        },
         prost_reflect::Value::List(v) => {
             let vec = v
                 .iter()
@@ -102,6 +96,139 @@ pub fn proto_to_value(
     Ok(vrl_value)
 }
 
+fn message_to_object(message: &DynamicMessage, options: &Options) -> Result<ObjectMap, String> {
+    let mut obj_map = ObjectMap::new();
+    for field_desc in message.descriptor().fields() {
+        if message.has_field(&field_desc) {
+            let field_value = message.get_field(&field_desc);
+            let out = proto_to_value(field_value.as_ref(), Some(&field_desc), options)?;
+            let field_key = if options.use_json_names {
+                field_desc.json_name()
+            } else {
+                field_desc.name()
+            };
+            obj_map.insert(field_key.into(), out);
+        }
+    }
+    Ok(obj_map)
+}
+
+/// Converts the well-known types in the `google.protobuf` package to the VRL values which
+/// correspond to their proto3 JSON mapping, such as a `Timestamp` to a timestamp and a wrapper
+/// to the value it wraps. Returns `None` for any other message type.
+fn well_known_to_value(
+    message: &DynamicMessage,
+    options: &Options,
+) -> Result<Option<Value>, String> {
+    let value = match message.descriptor().full_name() {
+        "google.protobuf.Timestamp" => {
+            let (seconds, nanos) = seconds_and_nanos(message)?;
+            let timestamp = u32::try_from(nanos)
+                .ok()
+                .and_then(|nanos| DateTime::from_timestamp(seconds, nanos))
+                .ok_or_else(|| format!("Invalid timestamp of {seconds}s and {nanos}ns"))?;
+            Value::from(timestamp)
+        }
+        "google.protobuf.Duration" => {
+            let (seconds, nanos) = seconds_and_nanos(message)?;
+            // The nanoseconds have the same sign as the seconds.
+            let duration = TimeDelta::try_seconds(seconds)
+                .and_then(|duration| {
+                    duration.checked_add(&TimeDelta::nanoseconds(i64::from(nanos)))
+                })
+                .ok_or_else(|| format!("Invalid duration of {seconds}s and {nanos}ns"))?;
+            Value::Duration(duration)
+        }
+        "google.protobuf.Struct" | "google.protobuf.ListValue" => {
+            let field = message.descriptor().fields().next().ok_or_else(|| {
+                format!("Missing field in '{}'", message.descriptor().full_name())
+            })?;
+            proto_to_value(message.get_field(&field).as_ref(), Some(&field), options)?
+        }
+        "google.protobuf.Value" => match message
+            .descriptor()
+            .fields()
+            .find(|field| message.has_field(field))
+        {
+            Some(field) => {
+                proto_to_value(message.get_field(&field).as_ref(), Some(&field), options)?
+            }
+            None => Value::Null,
+        },
+        "google.protobuf.DoubleValue"
+        | "google.protobuf.FloatValue"
+        | "google.protobuf.Int64Value"
+        | "google.protobuf.UInt64Value"
+        | "google.protobuf.Int32Value"
+        | "google.protobuf.UInt32Value"
+        | "google.protobuf.BoolValue"
+        | "google.protobuf.StringValue"
+        | "google.protobuf.BytesValue" => {
+            let field = get_field(message, "value")?;
+            proto_to_value(message.get_field(&field).as_ref(), Some(&field), options)?
+        }
+        "google.protobuf.FieldMask" => {
+            let paths = message.get_field(&get_field(message, "paths")?);
+            let paths = paths
+                .as_list()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|path| path.as_str().map(json_name))
+                .collect::<Vec<_>>();
+            Value::from(paths.join(","))
+        }
+        "google.protobuf.Any" => return any_to_value(message, options),
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// Decodes the message packed in an `Any` into an object, with the type URL in the `@type`
+/// field. Messages which don't map to an object, such as the well-known types, are placed in
+/// the `value` field. Returns `None` if the message type isn't in the descriptor pool.
+fn any_to_value(message: &DynamicMessage, options: &Options) -> Result<Option<Value>, String> {
+    let type_url = message.get_field(&get_field(message, "type_url")?);
+    let type_url = type_url.as_str().unwrap_or_default();
+    let bytes = message.get_field(&get_field(message, "value")?);
+    let bytes = bytes.as_bytes().cloned().unwrap_or_default();
+
+    let message_name = type_url.rsplit('/').next().unwrap_or_default();
+    let Some(descriptor) = message
+        .descriptor()
+        .parent_pool()
+        .get_message_by_name(message_name)
+    else {
+        return Ok(None);
+    };
+
+    let packed = DynamicMessage::decode(descriptor, bytes)
+        .map_err(|error| format!("Error parsing protobuf `Any` of type '{type_url}': {error:?}"))?;
+    let mut object = match well_known_to_value(&packed, options)? {
+        Some(value) => ObjectMap::from([("value".into(), value)]),
+        None => message_to_object(&packed, options)?,
+    };
+    object.insert("@type".into(), Value::from(type_url));
+    Ok(Some(Value::from(object)))
+}
+
+fn get_field(message: &DynamicMessage, name: &str) -> Result<FieldDescriptor, String> {
+    message.descriptor().get_field_by_name(name).ok_or_else(|| {
+        format!(
+            "Missing field '{name}' in '{}'",
+            message.descriptor().full_name()
+        )
+    })
+}
+
+fn seconds_and_nanos(message: &DynamicMessage) -> Result<(i64, i32), String> {
+    let seconds = message.get_field(&get_field(message, "seconds")?);
+    let nanos = message.get_field(&get_field(message, "nanos")?);
+    Ok((
+        seconds.as_i64().unwrap_or_default(),
+        nanos.as_i32().unwrap_or_default(),
+    ))
+}
+
 #[cfg(feature = "enable_system_functions")]
 pub(crate) fn parse_proto(descriptor: &MessageDescriptor, value: Value) -> Resolved {
     let bytes = value.try_bytes()?;
@@ -118,8 +245,10 @@ pub(crate) fn parse_proto(descriptor: &MessageDescriptor, value: Value) -> Resol
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::protobuf::descriptor::get_message_descriptor;
+    use crate::protobuf::descriptor::{get_message_descriptor, get_message_descriptor_from_source};
+    use crate::protobuf::encode::encode_message;
     use crate::{owned_value_path, value};
+    use prost::Message;
     use std::path::PathBuf;
     use std::{env, fs};
 
@@ -187,4 +316,115 @@ mod tests {
             Some(&Value::from("some job"))
         );
     }
+
+    fn roundtrip(message_type: &str, value: Value) -> Value {
+        let descriptor = get_message_descriptor_from_source(
+            &test_data_dir().join("test_well_known/v1/test_well_known.proto"),
+            &[test_data_dir()],
+            &format!("test_well_known.v1.{message_type}"),
+        )
+        .unwrap();
+        let message = encode_message(&descriptor, value, &Default::default()).unwrap();
+        parse_proto(
+            &descriptor,
+            Value::from(Bytes::from(message.encode_to_vec())),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_well_known_types() {
+        let parsed = roundtrip(
+            "Event",
+            value!({
+                time: "2024-01-02T03:04:05.5Z",
+                elapsed: 90.25,
+                attributes: { service: "web", tags: ["a", 1], nested: { ok: true, none: null } },
+                value: "text",
+                list: [null, { b: 2 }],
+                mask: "user.fullName,age",
+                detail: { "@type": "type.googleapis.com/test_well_known.v1.User", name: "Someone" },
+                details: [
+                    { "@type": "type.googleapis.com/google.protobuf.Duration", value: "0.000001s" },
+                    { "@type": "type.googleapis.com/google.protobuf.Struct", value: { a: "b" } },
+                ],
+            }),
+        );
+
+        assert_eq!(
+            parsed,
+            value!({
+                time: (DateTime::from_timestamp(1_704_164_645, 500_000_000).unwrap()),
+                elapsed: (TimeDelta::milliseconds(90_250)),
+                attributes: { service: "web", tags: ["a", 1.0], nested: { ok: true, none: null } },
+                value: "text",
+                list: [null, { b: 2.0 }],
+                mask: "user.fullName,age",
+                detail: { "@type": "type.googleapis.com/test_well_known.v1.User", name: "Someone" },
+                details: [
+                    { "@type": "type.googleapis.com/google.protobuf.Duration", value: (TimeDelta::microseconds(1)) },
+                    { "@type": "type.googleapis.com/google.protobuf.Struct", value: { a: "b" } },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_durations() {
+        for duration in [
+            TimeDelta::milliseconds(-1500),
+            TimeDelta::nanoseconds(-1),
+            TimeDelta::zero(),
+            TimeDelta::seconds(315_576_000_000),
+        ] {
+            let parsed = roundtrip("Event", value!({ elapsed: (duration) }));
+            assert_eq!(parsed, value!({ elapsed: (duration) }));
+        }
+    }
+
+    #[test]
+    fn test_parse_wrappers() {
+        let parsed = roundtrip(
+            "Wrappers",
+            value!({
+                float_value: 0.5,
+                uint64_value: 0,
+                int32_value: (-3),
+                bool_value: true,
+                bytes_value: "raw",
+            }),
+        );
+
+        assert_eq!(
+            parsed,
+            value!({
+                float_value: 0.5,
+                uint64_value: 0,
+                int32_value: (-3),
+                bool_value: true,
+                bytes_value: "raw",
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_uint64_above_i64_max() {
+        let parsed = roundtrip("Wrappers", value!({uint64_value: (u64::MAX)}));
+
+        assert_eq!(parsed, value!({uint64_value: (u64::MAX)}));
+        assert!(parsed.get("uint64_value").unwrap().is_decimal());
+    }
+
+    #[test]
+    fn test_parse_any_of_unknown_type() {
+        let parsed = roundtrip(
+            "Event",
+            value!({ detail: { type_url: "type.googleapis.com/unknown.Message", value: "raw" } }),
+        );
+
+        assert_eq!(
+            parsed,
+            value!({ detail: { type_url: "type.googleapis.com/unknown.Message", value: "raw" } })
+        );
+    }
 }
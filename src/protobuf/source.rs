@@ -0,0 +1,864 @@
+//! Compiles `.proto` source files into a `DescriptorPool` in-process, so that protobuf
+//! functions can be used without running `protoc` to produce a descriptor set first.
+
+#![allow(clippy::upper_case_acronyms)]
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use pest::Parser;
+use pest::iterators::Pair;
+use pest_derive::Parser;
+use prost_reflect::DescriptorPool;
+use prost_reflect::prost_types::{
+    DescriptorProto, EnumDescriptorProto, EnumOptions, EnumValueDescriptorProto,
+    FieldDescriptorProto, FieldOptions, FileDescriptorProto, MessageOptions, MethodDescriptorProto,
+    OneofDescriptorProto, ServiceDescriptorProto,
+    descriptor_proto::{ExtensionRange, ReservedRange},
+    enum_descriptor_proto::EnumReservedRange,
+    field_descriptor_proto::{Label, Type},
+};
+
+#[derive(Parser)]
+#[grammar = "src/protobuf/proto.pest"]
+struct ProtoParser;
+
+/// The largest field number, used for `max` in message ranges.
+const MAX_FIELD_NUMBER: i32 = 536_870_911;
+
+/// Parse the `.proto` file at `path` and all of its imports into a `DescriptorPool`. Imports
+/// are resolved against `include_paths`, and the file's own name in the pool is its path
+/// relative to the first include path containing it. The well-known types in
+/// `google/protobuf/` are always available to import.
+///
+/// # Errors
+///
+/// Returns an error if a file can't be read or parsed, an import can't be found, or a file
+/// references a type which isn't defined.
+pub fn compile_proto_file(
+    path: &Path,
+    include_paths: &[PathBuf],
+) -> Result<DescriptorPool, String> {
+    let name = include_paths
+        .iter()
+        .find_map(|include_path| path.strip_prefix(include_path).ok())
+        .map(file_name)
+        .ok_or_else(|| {
+            format!(
+                "The protobuf source file '{}' is not within the include paths {}",
+                path.display(),
+                display_paths(include_paths)
+            )
+        })?;
+
+    let mut compiler = Compiler {
+        pool: DescriptorPool::global(),
+        include_paths,
+        files: Vec::new(),
+        loading: HashSet::new(),
+    };
+    compiler.load(&name, None)?;
+
+    add_files(compiler.pool, compiler.files)
+}
+
+/// Parse `.proto` sources held in memory, given as pairs of file name and source, into a
+/// `DescriptorPool`. Imports must be either one of the sources or a well-known type.
+///
+/// # Errors
+///
+/// Returns an error if a source can't be parsed, or references a file or type which isn't
+/// defined.
+pub fn compile_proto_sources<'a>(
+    sources: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<DescriptorPool, String> {
+    let files = sources
+        .into_iter()
+        .map(|(name, source)| parse_proto_source(name, source))
+        .collect::<Result<Vec<_>, _>>()?;
+    add_files(DescriptorPool::global(), files)
+}
+
+fn add_files(
+    mut pool: DescriptorPool,
+    files: Vec<FileDescriptorProto>,
+) -> Result<DescriptorPool, String> {
+    pool.add_file_descriptor_protos(files)
+        .map_err(|e| match e.file() {
+            Some(file) => format!("Failed to compile protobuf source file '{file}': {e}"),
+            None => format!("Failed to compile protobuf source files: {e}"),
+        })?;
+    Ok(pool)
+}
+
+struct Compiler<'a> {
+    pool: DescriptorPool,
+    include_paths: &'a [PathBuf],
+    /// The parsed files, in the order they were loaded.
+    files: Vec<FileDescriptorProto>,
+    /// The names of the files being loaded, to detect import cycles.
+    loading: HashSet<String>,
+}
+
+impl Compiler<'_> {
+    fn load(&mut self, name: &str, imported_by: Option<&str>) -> Result<(), String> {
+        if self.pool.get_file_by_name(name).is_some()
+            || self.files.iter().any(|file| file.name() == name)
+        {
+            return Ok(());
+        }
+        if !self.loading.insert(name.to_owned()) {
+            return Err(format!("The protobuf source file '{name}' imports itself"));
+        }
+
+        let Some(path) = self
+            .include_paths
+            .iter()
+            .map(|include_path| include_path.join(name))
+            .find(|path| path.is_file())
+        else {
+            return Err(match imported_by {
+                Some(imported_by) => format!(
+                    "The import '{name}' in '{imported_by}' was not found in the include paths {}",
+                    display_paths(self.include_paths)
+                ),
+                None => format!("The protobuf source file '{name}' was not found"),
+            });
+        };
+
+        let source = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to open protobuf source file '{path:?}': {e}"))?;
+        let file = parse_proto_source(name, &source)?;
+        for dependency in &file.dependency {
+            self.load(dependency, Some(name))?;
+        }
+
+        self.loading.remove(name);
+        self.files.push(file);
+        Ok(())
+    }
+}
+
+/// Parse the source of a single `.proto` file named `name` into a `FileDescriptorProto`. Type
+/// names are left as written, and are resolved when the file is added to a `DescriptorPool`.
+///
+/// # Errors
+///
+/// Returns an error if the source isn't valid, pointing at the location of the problem.
+pub fn parse_proto_source(name: &str, source: &str) -> Result<FileDescriptorProto, String> {
+    let root = ProtoParser::parse(Rule::proto, source)
+        .map_err(|e| {
+            format!(
+                "Failed to parse protobuf source file:\n{}",
+                e.with_path(name)
+            )
+        })?
+        .next()
+        .expect("proto root");
+
+    let mut file = FileDescriptorProto {
+        name: Some(name.to_owned()),
+        ..Default::default()
+    };
+    let mut proto3 = false;
+
+    for item in root.into_inner() {
+        match item.as_rule() {
+            Rule::syntax => {
+                let syntax = string_value(item.into_inner().next().unwrap());
+                match syntax.as_str() {
+                    "proto2" => (),
+                    "proto3" => proto3 = true,
+                    _ => return Err(format!("{name}: unsupported protobuf syntax '{syntax}'")),
+                }
+                file.syntax = Some(syntax);
+            }
+            Rule::import => {
+                let mut kind = None;
+                for part in item.into_inner() {
+                    match part.as_rule() {
+                        Rule::import_kind => kind = Some(part.as_str().to_owned()),
+                        _ => file.dependency.push(string_value(part)),
+                    }
+                }
+                let index = i32::try_from(file.dependency.len() - 1).expect("too many imports");
+                match kind.as_deref() {
+                    Some("public") => file.public_dependency.push(index),
+                    Some("weak") => file.weak_dependency.push(index),
+                    _ => (),
+                }
+            }
+            Rule::package => {
+                file.package = Some(item.into_inner().next().unwrap().as_str().to_owned());
+            }
+            Rule::message => file.message_type.push(message(item, proto3, name)?),
+            Rule::enum_def => file.enum_type.push(enumeration(item)?),
+            Rule::service => file.service.push(service(item)),
+            Rule::extend => file.extension.extend(extend(item, proto3, name)?),
+            // File options don't affect parsing or encoding.
+            Rule::option | Rule::EOI => (),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(file)
+}
+
+fn message(pair: Pair<Rule>, proto3: bool, file: &str) -> Result<DescriptorProto, String> {
+    let mut inner = pair.into_inner();
+    let mut message = DescriptorProto {
+        name: Some(inner.next().unwrap().as_str().to_owned()),
+        ..Default::default()
+    };
+    message_body(&mut message, inner.next().unwrap(), proto3, file)?;
+    Ok(message)
+}
+
+fn message_body(
+    message: &mut DescriptorProto,
+    body: Pair<Rule>,
+    proto3: bool,
+    file: &str,
+) -> Result<(), String> {
+    // proto3 `optional` fields are each placed within a synthetic oneof, which must follow the
+    // declared oneofs.
+    let mut synthetic_oneofs = Vec::new();
+
+    for item in body.into_inner() {
+        match item.as_rule() {
+            Rule::field => {
+                let field = field(item, proto3)?;
+                if field.proto3_optional() {
+                    synthetic_oneofs.push(message.field.len());
+                }
+                message.field.push(field);
+            }
+            Rule::map_field => {
+                let (field, entry) = map_field(item)?;
+                message.field.push(field);
+                message.nested_type.push(entry);
+            }
+            Rule::oneof => {
+                let index = i32::try_from(message.oneof_decl.len()).expect("too many oneofs");
+                let mut inner = item.into_inner();
+                message.oneof_decl.push(OneofDescriptorProto {
+                    name: Some(inner.next().unwrap().as_str().to_owned()),
+                    ..Default::default()
+                });
+                for item in inner {
+                    match item.as_rule() {
+                        Rule::field => {
+                            let mut field = field(item, false)?;
+                            field.oneof_index = Some(index);
+                            message.field.push(field);
+                        }
+                        Rule::group => return Err(unsupported_group(file, &item)),
+                        _ => (),
+                    }
+                }
+            }
+            Rule::message => message.nested_type.push(self::message(item, proto3, file)?),
+            Rule::enum_def => message.enum_type.push(enumeration(item)?),
+            Rule::extend => message.extension.extend(extend(item, proto3, file)?),
+            Rule::extensions => {
+                for (start, end) in ranges(item.into_inner().next().unwrap(), MAX_FIELD_NUMBER)? {
+                    message.extension_range.push(ExtensionRange {
+                        start: Some(start),
+                        end: Some(end + 1),
+                        ..Default::default()
+                    });
+                }
+            }
+            Rule::reserved => {
+                let reserved = item.into_inner().next().unwrap();
+                if reserved.as_rule() == Rule::ranges {
+                    for (start, end) in ranges(reserved, MAX_FIELD_NUMBER)? {
+                        message.reserved_range.push(ReservedRange {
+                            start: Some(start),
+                            end: Some(end + 1),
+                        });
+                    }
+                } else {
+                    message.reserved_name.extend(reserved_names(reserved));
+                }
+            }
+            Rule::group => return Err(unsupported_group(file, &item)),
+            Rule::option => {
+                let (name, value) = option(item);
+                if name == "deprecated" {
+                    message
+                        .options
+                        .get_or_insert_with(MessageOptions::default)
+                        .deprecated = Some(value == "true");
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    for field_index in synthetic_oneofs {
+        let field = &mut message.field[field_index];
+        field.oneof_index = Some(i32::try_from(message.oneof_decl.len()).expect("too many oneofs"));
+        message.oneof_decl.push(OneofDescriptorProto {
+            name: Some(format!("_{}", field.name())),
+            ..Default::default()
+        });
+    }
+
+    Ok(())
+}
+
+fn field(pair: Pair<Rule>, proto3: bool) -> Result<FieldDescriptorProto, String> {
+    let mut field = FieldDescriptorProto {
+        label: Some(Label::Optional as i32),
+        ..Default::default()
+    };
+
+    for item in pair.into_inner() {
+        match item.as_rule() {
+            Rule::label => match item.as_str() {
+                "required" => field.label = Some(Label::Required as i32),
+                "repeated" => field.label = Some(Label::Repeated as i32),
+                _ => field.proto3_optional = proto3.then_some(true),
+            },
+            Rule::type_name => set_type(&mut field, item.as_str()),
+            Rule::ident => {
+                field.json_name = Some(json_name(item.as_str()));
+                field.name = Some(item.as_str().to_owned());
+            }
+            Rule::int_lit => field.number = Some(int_value(&item)?),
+            Rule::field_options => field_options(&mut field, item),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(field)
+}
+
+/// Returns the field for a map, and the nested entry message type it's a repeated field of.
+fn map_field(pair: Pair<Rule>) -> Result<(FieldDescriptorProto, DescriptorProto), String> {
+    let mut inner = pair.into_inner();
+    let mut key = FieldDescriptorProto {
+        name: Some("key".to_owned()),
+        json_name: Some("key".to_owned()),
+        number: Some(1),
+        label: Some(Label::Optional as i32),
+        ..Default::default()
+    };
+    set_type(&mut key, inner.next().unwrap().as_str());
+    let mut value = FieldDescriptorProto {
+        name: Some("value".to_owned()),
+        json_name: Some("value".to_owned()),
+        number: Some(2),
+        label: Some(Label::Optional as i32),
+        ..Default::default()
+    };
+    set_type(&mut value, inner.next().unwrap().as_str());
+
+    let name = inner.next().unwrap().as_str();
+    let entry_name = format!("{}Entry", camel_case(name, true));
+    let mut field = FieldDescriptorProto {
+        name: Some(name.to_owned()),
+        json_name: Some(json_name(name)),
+        number: Some(int_value(&inner.next().unwrap())?),
+        label: Some(Label::Repeated as i32),
+        type_name: Some(entry_name.clone()),
+        r#type: Some(Type::Message as i32),
+        ..Default::default()
+    };
+    if let Some(options) = inner.next() {
+        field_options(&mut field, options);
+    }
+
+    let entry = DescriptorProto {
+        name: Some(entry_name),
+        field: vec![key, value],
+        options: Some(MessageOptions {
+            map_entry: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    Ok((field, entry))
+}
+
+fn enumeration(pair: Pair<Rule>) -> Result<EnumDescriptorProto, String> {
+    let mut inner = pair.into_inner();
+    let mut enumeration = EnumDescriptorProto {
+        name: Some(inner.next().unwrap().as_str().to_owned()),
+        ..Default::default()
+    };
+
+    for item in inner {
+        match item.as_rule() {
+            Rule::enum_value => {
+                let mut inner = item.into_inner();
+                enumeration.value.push(EnumValueDescriptorProto {
+                    name: Some(inner.next().unwrap().as_str().to_owned()),
+                    number: Some(int_value(&inner.next().unwrap())?),
+                    ..Default::default()
+                });
+            }
+            Rule::reserved => {
+                let reserved = item.into_inner().next().unwrap();
+                if reserved.as_rule() == Rule::ranges {
+                    for (start, end) in ranges(reserved, i32::MAX)? {
+                        enumeration.reserved_range.push(EnumReservedRange {
+                            start: Some(start),
+                            end: Some(end),
+                        });
+                    }
+                } else {
+                    enumeration.reserved_name.extend(reserved_names(reserved));
+                }
+            }
+            Rule::option => {
+                let (name, value) = option(item);
+                if name == "allow_alias" {
+                    enumeration
+                        .options
+                        .get_or_insert_with(EnumOptions::default)
+                        .allow_alias = Some(value == "true");
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(enumeration)
+}
+
+fn service(pair: Pair<Rule>) -> ServiceDescriptorProto {
+    let mut inner = pair.into_inner();
+    let mut service = ServiceDescriptorProto {
+        name: Some(inner.next().unwrap().as_str().to_owned()),
+        ..Default::default()
+    };
+
+    for rpc in inner.filter(|item| item.as_rule() == Rule::rpc) {
+        let mut method = MethodDescriptorProto::default();
+        let mut stream = false;
+        for item in rpc.into_inner() {
+            match item.as_rule() {
+                Rule::ident => method.name = Some(item.as_str().to_owned()),
+                Rule::stream => stream = true,
+                Rule::type_name if method.input_type.is_none() => {
+                    method.input_type = Some(item.as_str().to_owned());
+                    method.client_streaming = Some(std::mem::take(&mut stream));
+                }
+                Rule::type_name => {
+                    method.output_type = Some(item.as_str().to_owned());
+                    method.server_streaming = Some(std::mem::take(&mut stream));
+                }
+                _ => (),
+            }
+        }
+        service.method.push(method);
+    }
+
+    service
+}
+
+fn extend(pair: Pair<Rule>, proto3: bool, file: &str) -> Result<Vec<FieldDescriptorProto>, String> {
+    let mut inner = pair.into_inner();
+    let extendee = inner.next().unwrap().as_str().to_owned();
+
+    inner
+        .map(|item| match item.as_rule() {
+            Rule::field => {
+                let mut field = field(item, proto3)?;
+                field.extendee = Some(extendee.clone());
+                // Extensions never have a synthetic oneof.
+                field.proto3_optional = None;
+                Ok(field)
+            }
+            _ => Err(unsupported_group(file, &item)),
+        })
+        .collect()
+}
+
+fn field_options(field: &mut FieldDescriptorProto, pair: Pair<Rule>) {
+    for option in pair.into_inner() {
+        let mut inner = option.into_inner();
+        let name = inner.next().unwrap().as_str();
+        let value = inner.next().unwrap();
+        match name {
+            "default" => field.default_value = Some(default_value(value)),
+            "json_name" => field.json_name = Some(string_value(value.into_inner().next().unwrap())),
+            "packed" => {
+                field
+                    .options
+                    .get_or_insert_with(FieldOptions::default)
+                    .packed = Some(value.as_str() == "true");
+            }
+            "deprecated" => {
+                field
+                    .options
+                    .get_or_insert_with(FieldOptions::default)
+                    .deprecated = Some(value.as_str() == "true");
+            }
+            // Other options, including custom options, don't affect parsing or encoding.
+            _ => (),
+        }
+    }
+}
+
+/// Returns the name and the (unparsed) value of an option statement.
+fn option(pair: Pair<Rule>) -> (String, String) {
+    let mut inner = pair.into_inner();
+    let name = inner.next().unwrap().as_str().to_owned();
+    let value = inner.next().unwrap().as_str().to_owned();
+    (name, value)
+}
+
+/// Sets the type of a field, either a scalar type or the (possibly relative) name of a message
+/// or enum type.
+fn set_type(field: &mut FieldDescriptorProto, type_name: &str) {
+    let scalar = match type_name {
+        "double" => Type::Double,
+        "float" => Type::Float,
+        "int64" => Type::Int64,
+        "uint64" => Type::Uint64,
+        "int32" => Type::Int32,
+        "fixed64" => Type::Fixed64,
+        "fixed32" => Type::Fixed32,
+        "bool" => Type::Bool,
+        "string" => Type::String,
+        "bytes" => Type::Bytes,
+        "uint32" => Type::Uint32,
+        "sfixed32" => Type::Sfixed32,
+        "sfixed64" => Type::Sfixed64,
+        "sint32" => Type::Sint32,
+        "sint64" => Type::Sint64,
+        _ => {
+            field.type_name = Some(type_name.to_owned());
+            return;
+        }
+    };
+    field.r#type = Some(scalar as i32);
+}
+
+/// Formats a constant as a field default value, which is the unescaped text of strings and
+/// the literal text of everything else.
+fn default_value(constant: Pair<Rule>) -> String {
+    let text = constant.as_str().split_whitespace().collect::<String>();
+    match constant
+        .into_inner()
+        .find(|item| item.as_rule() != Rule::sign)
+    {
+        Some(item) if item.as_rule() == Rule::string_lit => string_value(item),
+        Some(item) if item.as_rule() == Rule::int_lit => {
+            let sign = if text.starts_with('-') { "-" } else { "" };
+            int_literal(item.as_str()).map_or(text, |value| format!("{sign}{value}"))
+        }
+        _ => text,
+    }
+}
+
+/// Returns the inclusive ranges of a reserved or extensions statement.
+fn ranges(pair: Pair<Rule>, max: i32) -> Result<Vec<(i32, i32)>, String> {
+    pair.into_inner()
+        .map(|range| {
+            let mut inner = range.into_inner();
+            let start = int_value(&inner.next().unwrap())?;
+            let end = match inner.next() {
+                Some(end) if end.as_rule() == Rule::max => max,
+                Some(end) => int_value(&end)?,
+                None => start,
+            };
+            Ok((start, end))
+        })
+        .collect()
+}
+
+fn reserved_names(pair: Pair<Rule>) -> Vec<String> {
+    pair.into_inner()
+        .map(|name| match name.as_rule() {
+            Rule::string_lit => string_value(name),
+            _ => name.as_str().to_owned(),
+        })
+        .collect()
+}
+
+fn int_value(pair: &Pair<Rule>) -> Result<i32, String> {
+    let text = pair.as_str();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    int_literal(digits)
+        .and_then(|value| i64::try_from(value).ok())
+        .map(|value| if negative { -value } else { value })
+        .and_then(|value| i32::try_from(value).ok())
+        .ok_or_else(|| {
+            let (line, column) = pair.line_col();
+            format!("{line}:{column}: invalid number '{text}'")
+        })
+}
+
+/// Parses a decimal, hexadecimal (`0x`) or octal (leading `0`) integer literal.
+fn int_literal(text: &str) -> Option<u64> {
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16).ok()
+    } else if text.len() > 1 && text.starts_with('0') {
+        u64::from_str_radix(&text[1..], 8).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+/// Returns the unescaped, concatenated contents of a string literal.
+fn string_value(pair: Pair<Rule>) -> String {
+    let mut value = String::new();
+    for part in pair.into_inner() {
+        let quoted = part.as_str();
+        unescape(&quoted[1..quoted.len() - 1], &mut value);
+    }
+    value
+}
+
+fn unescape(escaped: &str, output: &mut String) {
+    let mut chars = escaped.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        let Some(c) = chars.next() else { break };
+        match c {
+            'n' => output.push('\n'),
+            'r' => output.push('\r'),
+            't' => output.push('\t'),
+            'a' => output.push('\x07'),
+            'b' => output.push('\x08'),
+            'f' => output.push('\x0c'),
+            'v' => output.push('\x0b'),
+            'x' | 'X' => {
+                let mut code = 0;
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(16)) {
+                        Some(digit) => {
+                            code = code * 16 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                output.extend(char::from_u32(code));
+            }
+            '0'..='7' => {
+                let mut code = c.to_digit(8).unwrap_or_default();
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            code = code * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                output.extend(char::from_u32(code));
+            }
+            c => output.push(c),
+        }
+    }
+}
+
+/// The JSON name of a field, as `protoc` derives it: underscores are removed, capitalizing the
+/// letter which follows them.
+pub(super) fn json_name(name: &str) -> String {
+    camel_case(name, false)
+}
+
+fn camel_case(name: &str, capitalize_first: bool) -> String {
+    let mut output = String::with_capacity(name.len());
+    let mut capitalize = capitalize_first;
+    for c in name.chars() {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            output.extend(c.to_uppercase());
+            capitalize = false;
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+fn unsupported_group(file: &str, pair: &Pair<Rule>) -> String {
+    let (line, column) = pair.line_col();
+    format!("{file}:{line}:{column}: groups are not supported")
+}
+
+/// Formats a path relative to an include path as a protobuf file name, which always uses `/`.
+fn file_name(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    let paths = paths
+        .iter()
+        .map(|path| format!("'{}'", path.display()))
+        .collect::<Vec<_>>();
+    format!("[{}]", paths.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn test_data_dir() -> PathBuf {
+        PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("tests/data/protobuf")
+    }
+
+    #[test]
+    fn compiles_like_protoc() {
+        for (source, descriptor_set) in [
+            ("test/v1/test.proto", "test/v1/test.desc"),
+            (
+                "test_protobuf/v1/test_protobuf.proto",
+                "test_protobuf/v1/test_protobuf.desc",
+            ),
+            (
+                "test_protobuf3/v1/test_protobuf3.proto",
+                "test_protobuf3/v1/test_protobuf3.desc",
+            ),
+        ] {
+            let pool = compile_proto_file(&test_data_dir().join(source), &[test_data_dir()])
+                .unwrap_or_else(|e| panic!("{source}: {e}"));
+            let expected = DescriptorPool::decode(
+                std::fs::read(test_data_dir().join(descriptor_set))
+                    .unwrap()
+                    .as_slice(),
+            )
+            .unwrap();
+
+            for expected in expected.all_messages() {
+                let message = pool
+                    .get_message_by_name(expected.full_name())
+                    .unwrap_or_else(|| panic!("{source}: missing {}", expected.full_name()));
+                assert_eq!(
+                    format!("{:?}", message.descriptor_proto()),
+                    format!("{:?}", expected.descriptor_proto()),
+                    "{source}: {}",
+                    expected.full_name()
+                );
+            }
+            for expected in expected.all_enums() {
+                let enumeration = pool.get_enum_by_name(expected.full_name()).unwrap();
+                assert_eq!(
+                    enumeration.enum_descriptor_proto(),
+                    expected.enum_descriptor_proto()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parses_proto2_features() {
+        let file = parse_proto_source(
+            "a.proto",
+            r#"
+            syntax = "proto2";
+            package a;
+            import public "b.proto";
+            option java_package = "com.example";
+
+            /* A message. */
+            message M {
+              option deprecated = true;
+              required int32 id = 0x1 [(custom.opt) = { a: "}" }];
+              optional string s = 2 [default = "a\tb\x41"];
+              optional E e = 3 [default = B];
+              repeated sint64 values = 4 [packed = true];
+              optional double d = 5 [default = -inf];
+              oneof choice {
+                bytes raw = 6;
+                M nested = 7;
+              }
+              extensions 100 to max;
+              reserved 8, 10 to 12;
+              reserved "old";
+            }
+
+            enum E {
+              option allow_alias = true;
+              A = 0;
+              B = 1;
+              C = 1;
+              NEGATIVE = -1;
+            }
+
+            extend M { optional int32 ext = 100; }
+
+            service S {
+              rpc Call(M) returns (stream .a.M);
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(file.dependency, ["b.proto"]);
+        assert_eq!(file.public_dependency, [0]);
+        let message = &file.message_type[0];
+        let fields = &message.field;
+        assert_eq!(fields[0].number(), 1);
+        assert_eq!(fields[0].label(), Label::Required);
+        assert_eq!(fields[1].default_value(), "a\tbA");
+        assert_eq!(fields[2].type_name(), "E");
+        assert_eq!(fields[2].default_value(), "B");
+        assert_eq!(fields[3].r#type(), Type::Sint64);
+        assert_eq!(fields[3].options.as_ref().unwrap().packed, Some(true));
+        assert_eq!(fields[4].default_value(), "-inf");
+        assert_eq!(fields[5].oneof_index, Some(0));
+        assert_eq!(fields[6].oneof_index, Some(0));
+        assert_eq!(message.extension_range[0].end, Some(MAX_FIELD_NUMBER + 1));
+        assert_eq!(message.reserved_range.len(), 2);
+        assert_eq!(message.reserved_range[1].end, Some(13));
+        assert_eq!(message.reserved_name, ["old"]);
+        assert_eq!(file.enum_type[0].value[3].number(), -1);
+        assert_eq!(file.extension[0].extendee(), "M");
+        let method = &file.service[0].method[0];
+        assert!(!method.client_streaming() && method.server_streaming());
+        assert_eq!(method.output_type(), ".a.M");
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        let error = parse_proto_source("a.proto", "message M {\n  int32 id = ;\n}").unwrap_err();
+        assert!(error.contains("a.proto:2:"), "{error}");
+    }
+
+    #[test]
+    fn reports_missing_imports_and_types() {
+        let dir = env::temp_dir().join("vrl_protobuf_source_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("imports.proto"),
+            "syntax = \"proto3\";\nimport \"missing.proto\";\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("types.proto"),
+            "syntax = \"proto3\";\nmessage M { Missing m = 1; }\n",
+        )
+        .unwrap();
+
+        let error =
+            compile_proto_file(&dir.join("imports.proto"), std::slice::from_ref(&dir)).unwrap_err();
+        assert!(
+            error.contains("The import 'missing.proto' in 'imports.proto' was not found"),
+            "{error}"
+        );
+        let error = compile_proto_file(&dir.join("types.proto"), &[dir]).unwrap_err();
+        assert!(
+            error.contains("types.proto") && error.contains("Missing"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Python bindings for compiling and running VRL programs.
+//!
+//! The `vrl` Python module is built from this crate with the `python` feature enabled, and
+//! packaged as an extension module with maturin (`maturin build`, see `pyproject.toml`):
+//!
+//! ```python
+//! import vrl
+//!
+//! program = vrl.compile(".message = upcase!(.message); %seen = true")
+//! program.run({"message": "hello"})  # ({"message": "HELLO"}, {"seen": True})
+//! ```
+//!
+//! To embed the module in a Rust program running Python instead, register [`vrl`] with
+//! `pyo3::append_to_inittab!` before initializing the interpreter.
+//!
+//! Python values are converted to and from [`Value`] as described by its `IntoPyObject` and
+//! `FromPyObject` implementations.
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::compiler::runtime::{Runtime, Terminate};
+use crate::compiler::state::RuntimeState;
+use crate::compiler::{
+    CompilationResult, CompileConfig, TargetValue, TimeZone, compile_with_state,
+};
+use crate::diagnostic::Formatter;
+use crate::prelude::state::TypeState;
+use crate::value::{Secrets, Value};
+
+create_exception!(
+    vrl,
+    CompileError,
+    PyValueError,
+    "Raised when a VRL program fails to compile."
+);
+create_exception!(
+    vrl,
+    TerminateError,
+    PyRuntimeError,
+    "Raised when a VRL program errors or aborts while processing an event."
+);
+
+/// A compiled VRL program.
+#[pyclass(frozen, module = "vrl")]
+pub struct Program {
+    program: crate::compiler::Program,
+    warnings: Option<String>,
+}
+
+#[pymethods]
+impl Program {
+    /// Runs the program on the event and its metadata, returning them as modified by the
+    /// program, as an `(event, metadata)` tuple.
+    ///
+    /// Raises `TerminateError` if the program errors or aborts.
+    #[pyo3(signature = (event, metadata=None))]
+    fn run(
+        &self,
+        py: Python<'_>,
+        event: Value,
+        metadata: Option<Value>,
+    ) -> PyResult<(Value, Value)> {
+        let mut target = TargetValue {
+            value: event,
+            metadata: metadata.unwrap_or_else(|| Value::Object(Default::default())),
+            secrets: Secrets::default(),
+        };
+
+        py.allow_threads(|| {
+            let mut runtime = Runtime::new(RuntimeState::default());
+            runtime.resolve(&mut target, &self.program, &TimeZone::default())
+        })
+        .map_err(|terminate| match terminate {
+            Terminate::Abort(error) | Terminate::Error(error) => {
+                TerminateError::new_err(error.to_string())
+            }
+        })?;
+
+        Ok((target.value, target.metadata))
+    }
+
+    /// The warnings reported when compiling the program, or `None` if there were none.
+    #[getter]
+    fn warnings(&self) -> Option<&str> {
+        self.warnings.as_deref()
+    }
+}
+
+/// Compiles a VRL program.
+///
+/// The program can call the functions of the standard library, or only the named `functions`
+/// if given. Raises `CompileError` with the rendered diagnostics if the program is invalid.
+#[pyfunction]
+#[pyo3(signature = (source, functions=None))]
+fn compile(source: &str, functions: Option<Vec<String>>) -> PyResult<Program> {
+    let mut fns = crate::stdlib::all();
+    if let Some(functions) = functions {
+        if let Some(unknown) = functions.iter().find(|name| {
+            !fns.iter()
+                .any(|function| function.identifier() == name.as_str())
+        }) {
+            return Err(PyValueError::new_err(format!(
+                "unknown function `{unknown}`"
+            )));
+        }
+        fns.retain(|function| functions.iter().any(|name| name == function.identifier()));
+    }
+
+    let CompilationResult {
+        program,
+        warnings,
+        config: _,
+    } = compile_with_state(
+        source,
+        &fns,
+        &TypeState::default(),
+        CompileConfig::default(),
+    )
+    .map_err(|diagnostics| {
+        CompileError::new_err(Formatter::new(source, diagnostics).to_string())
+    })?;
+
+    Ok(Program {
+        program,
+        warnings: (!warnings.is_empty()).then(|| Formatter::new(source, warnings).to_string()),
+    })
+}
+
+/// The `vrl` Python module.
+#[pymodule]
+pub fn vrl(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(compile, m)?)?;
+    m.add_class::<Program>()?;
+    m.add("CompileError", m.py().get_type::<CompileError>())?;
+    m.add("TerminateError", m.py().get_type::<TerminateError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::ffi::c_str;
+    use pyo3::types::PyDict;
+
+    use super::*;
+
+    /// Runs the Python statements with the `vrl` module imported, returning the value of
+    /// `result`.
+    fn run(source: &std::ffi::CStr) -> PyResult<Value> {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "vrl")?;
+            vrl(&module)?;
+            let locals = PyDict::new(py);
+            locals.set_item("vrl", module)?;
+            py.run(source, None, Some(&locals))?;
+            locals.get_item("result")?.expect("result is set").extract()
+        })
+    }
+
+    #[test]
+    fn compiles_and_runs() {
+        let result = run(c_str!(
+            r#"
+import datetime
+program = vrl.compile('.message = upcase!(.message); .at = to_unix_timestamp!(.at); .host = del(%host); %seen = true')
+event, metadata = program.run(
+    {"message": "hello", "at": datetime.datetime(2021, 1, 1, tzinfo=datetime.timezone.utc)},
+    metadata={"host": "localhost"},
+)
+result = [event, metadata]
+"#
+        ));
+
+        assert_eq!(
+            result.unwrap(),
+            crate::value!([
+                { message: "HELLO", at: 1_609_459_200, host: "localhost" },
+                { seen: true },
+            ])
+        );
+    }
+
+    #[test]
+    fn reports_compile_errors() {
+        let result = run(c_str!(
+            r#"
+try:
+    vrl.compile('.message = upcase(.message')
+except vrl.CompileError as error:
+    result = str(error)
+"#
+        ));
+
+        let error = result.unwrap();
+        assert!(
+            error.to_string().contains("error[E204]: syntax error"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn restricts_functions() {
+        let result = run(c_str!(
+            r#"
+errors = []
+for functions in (["downcase"], ["missing"]):
+    try:
+        vrl.compile('upcase("a")', functions=functions)
+    except ValueError as error:
+        errors.append(type(error).__name__)
+result = errors
+"#
+        ));
+
+        assert_eq!(
+            result.unwrap(),
+            crate::value!(["CompileError", "ValueError"])
+        );
+    }
+
+    #[test]
+    fn reports_warnings_and_termination() {
+        let result = run(c_str!(
+            r#"
+program = vrl.compile('"unused"; abort')
+try:
+    program.run({})
+except vrl.TerminateError as error:
+    result = [program.warnings is not None, str(error)]
+"#
+        ));
+
+        assert_eq!(result.unwrap(), crate::value!([true, "aborted"]));
+    }
+}
@@ -266,13 +266,13 @@ mod tests {
         }
 
         tcp_source_port_too_large {
-            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: u64::MAX, destination_port: 80],
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: i64::MAX, destination_port: 80],
             want: Err("invalid argument"),
             tdef: TypeDef::bytes().fallible(),
         }
 
         tcp_destination_port_too_large {
-            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: 80 , destination_port: u64::MAX],
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: 80 , destination_port: i64::MAX],
             want: Err("invalid argument"),
             tdef: TypeDef::bytes().fallible(),
         }
@@ -314,7 +314,7 @@ mod tests {
         }
 
         seed_too_large {
-            args: func_args![seed: u64::MAX,source_ip: "5.6.7.8", destination_ip: "1.2.3.4", protocol: 6, source_port: 3344, destination_port: 1122],
+            args: func_args![seed: i64::MAX,source_ip: "5.6.7.8", destination_ip: "1.2.3.4", protocol: 6, source_port: 3344, destination_port: 1122],
             want: Err("invalid argument"),
             tdef: TypeDef::bytes().fallible(),
         }
@@ -272,6 +272,7 @@ fn compact_array(array: Vec<Value>, options: &CompactOptions) -> Vec<Value> {
 mod test {
     use super::*;
     use crate::btreemap;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_compacted_array() {
@@ -365,7 +366,7 @@ mod test {
                 CompactOptions::default(),
             ),
             (
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -373,7 +374,7 @@ mod test {
                     ),
                     (KeyString::from("key3"), Value::from(2)),
                 ]),
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -388,7 +389,7 @@ mod test {
                 CompactOptions::default(),
             ),
             (
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -396,7 +397,7 @@ mod test {
                     ),
                     (KeyString::from("key3"), Value::from(2)),
                 ]),
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -410,11 +411,11 @@ mod test {
                 },
             ),
             (
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (KeyString::from("key3"), Value::from(2)),
                 ]),
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -440,7 +441,8 @@ mod test {
         ];
 
         for (expected, original, options) in cases {
-            assert_eq!(expected, compact_object(original, &options));
+            let compacted = compact_object(original.into_iter().collect(), &options);
+            assert_eq!(Value::from(expected), Value::Object(compacted));
         }
     }
 
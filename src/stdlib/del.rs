@@ -31,7 +31,7 @@ fn del(query: &expression::Query, compact: bool, ctx: &mut Context) -> Resolved
     } else if let Some(ident) = query.variable_ident() {
         match ctx.state_mut().variable_mut(ident) {
             Some(value) => {
-                let new_value = value.get(path).cloned();
+                let new_value = value.project(path);
                 value.remove(path, compact);
                 Ok(new_value.unwrap_or(Value::Null))
             }
@@ -42,7 +42,7 @@ fn del(query: &expression::Query, compact: bool, ctx: &mut Context) -> Resolved
 
         // No need to do the actual deletion, as the expression is only
         // available as an argument to the function.
-        Ok(value.get(path).cloned().unwrap_or(Value::Null))
+        Ok(value.project(path).unwrap_or(Value::Null))
     } else {
         Ok(Value::Null)
     }
@@ -161,7 +161,7 @@ impl Function for Del {
         ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
-        let query = arguments.required_query("target")?;
+        let query = arguments.required_wildcard_query("target")?;
         let compact = arguments.optional("compact");
 
         if let Some(target_path) = query.external_path()
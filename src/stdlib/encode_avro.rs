@@ -0,0 +1,199 @@
+use super::parse_avro::{WIRE_FORMAT_ENUM, schema, wire_format};
+use crate::avro::schema::AvroSchema;
+use crate::avro::{WireFormat, encode_avro};
+use crate::compiler::prelude::*;
+use std::sync::LazyLock;
+
+static DEFAULT_WIRE_FORMAT: LazyLock<Value> = LazyLock::new(|| Value::from("raw"));
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required(
+            "value",
+            kind::ANY,
+            "The value to encode. For the `object_container` wire format, an array of records.",
+        ),
+        Parameter::required(
+            "schema",
+            kind::BYTES,
+            "The Avro schema to encode the value with, in its JSON representation. Must be a literal string.",
+        ),
+        Parameter::optional("wire_format", kind::BYTES, "How the Avro data is framed.")
+            .default(&DEFAULT_WIRE_FORMAT)
+            .enum_variants(WIRE_FORMAT_ENUM),
+        Parameter::optional(
+            "schema_id",
+            kind::INTEGER,
+            "The ID of the schema in the schema registry. Required for the `confluent` wire format.",
+        ),
+    ]
+});
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeAvro;
+
+impl Function for EncodeAvro {
+    fn identifier(&self) -> &'static str {
+        "encode_avro"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Encodes the `value` as [Avro](https://avro.apache.org/) data with the given `schema`.
+            The schema is parsed and validated when the program is compiled. This is the inverse
+            of `parse_avro`.
+
+            Missing record fields are encoded as null, and a union value is encoded with the
+            first branch of the union it matches. Timestamps can be encoded as the `date` and
+            `timestamp-*` logical types, and decimals, floats, integers or decimal strings as
+            `decimal`.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Codec.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &[
+            "`value` does not match the `schema`.",
+            "`schema_id` is not between 0 and 4294967295.",
+        ]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::BYTES
+    }
+
+    fn notices(&self) -> &'static [&'static str] {
+        &["Object container files are written uncompressed, with a random sync marker."]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Encode an Avro record",
+                source: r#"encode_base64(encode_avro!({"message": "hello", "timestamp": t'2023-11-14T22:13:20Z'}, s'{"type": "record", "name": "Log", "fields": [{"name": "message", "type": "string"}, {"name": "timestamp", "type": {"type": "long", "logicalType": "timestamp-millis"}}]}'))"#,
+                result: Ok("CmhlbGxvgKCr/vli"),
+            },
+            example! {
+                title: "Encode a Confluent Kafka message",
+                source: r#"encode_base64(encode_avro!({"action": "login", "success": true}, s'{"type": "record", "name": "Event", "fields": [{"name": "action", "type": ["null", "string"]}, {"name": "success", "type": "boolean"}]}', wire_format: "confluent", schema_id: 42))"#,
+                result: Ok("AAAAACoCCmxvZ2luAQ=="),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let schema = schema(&arguments, state)?;
+        let wire_format = wire_format(&arguments, state)?;
+        let schema_id = arguments.optional("schema_id");
+
+        if wire_format == WireFormat::Confluent && schema_id.is_none() {
+            return Err(Box::new(ExpressionError::from(
+                "the `confluent` wire format requires a `schema_id`",
+            )));
+        }
+
+        Ok(EncodeAvroFn {
+            value,
+            schema,
+            wire_format,
+            schema_id,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodeAvroFn {
+    value: Box<dyn Expression>,
+    schema: AvroSchema,
+    wire_format: WireFormat,
+    schema_id: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for EncodeAvroFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let schema_id = match &self.schema_id {
+            Some(schema_id) => {
+                let schema_id = schema_id.resolve(ctx)?.try_integer()?;
+                Some(u32::try_from(schema_id).map_err(|_| {
+                    format!("schema ID `{schema_id}` is not between 0 and 4294967295")
+                })?)
+            }
+            None => None,
+        };
+
+        let bytes = encode_avro(&self.schema, &value, self.wire_format, schema_id)
+            .map_err(|e| format!("unable to encode Avro: {e}"))?;
+        Ok(Value::Bytes(bytes))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    const SCHEMA: &str = r#"{"type": "record", "name": "Metric", "fields": [
+        {"name": "name", "type": "string"},
+        {"name": "value", "type": "double"},
+        {"name": "host", "type": ["null", "string"]}
+    ]}"#;
+
+    test_function![
+        encode_avro => EncodeAvro;
+
+        raw {
+            args: func_args![value: value!({ name: "up", value: 1.0 }), schema: SCHEMA],
+            want: Ok(b"\x04up\0\0\0\0\0\0\xf0\x3f\0".as_slice()),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        confluent {
+            args: func_args![value: value!({ name: "up", value: 1, host: "a" }), schema: SCHEMA, wire_format: "confluent", schema_id: 258],
+            want: Ok(b"\0\0\0\x01\x02\x04up\0\0\0\0\0\0\xf0\x3f\x02\x02a".as_slice()),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        missing_schema_id {
+            args: func_args![value: value!({}), schema: SCHEMA, wire_format: "confluent"],
+            want: Err("the `confluent` wire format requires a `schema_id`"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_schema_id {
+            args: func_args![value: value!({ name: "up", value: 1.0 }), schema: SCHEMA, wire_format: "confluent", schema_id: -1],
+            want: Err("schema ID `-1` is not between 0 and 4294967295"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        mismatch {
+            args: func_args![value: value!({ name: "up", value: "one" }), schema: SCHEMA],
+            want: Err("unable to encode Avro: cannot encode string as `double` in field `value` of `Metric`"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        not_records {
+            args: func_args![value: value!({}), schema: SCHEMA, wire_format: "object_container"],
+            want: Err("unable to encode Avro: expected an array of records, found map"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
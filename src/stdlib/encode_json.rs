@@ -151,6 +151,18 @@ mod tests {
             tdef: TypeDef::bytes().infallible(),
         }
 
+        decimal {
+            args: func_args![value: Value::Decimal("19.99".parse().unwrap())],
+            want: Ok("19.99"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        precise_decimal {
+            args: func_args![value: Value::Decimal("12345678901234567.89".parse().unwrap())],
+            want: Ok(r#""12345678901234567.89""#),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
         boolean {
             args: func_args![value: false],
             want: Ok("false"),
@@ -0,0 +1,124 @@
+use super::parse_otlp::{SIGNAL_ENUM, signal};
+use crate::compiler::prelude::*;
+use crate::protobuf::otlp::{Signal, encode_otlp};
+use std::sync::LazyLock;
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required(
+            "value",
+            kind::ARRAY,
+            "The records to encode, in the form returned by `parse_otlp`.",
+        ),
+        Parameter::required(
+            "signal",
+            kind::BYTES,
+            "The OpenTelemetry signal the records belong to.",
+        )
+        .enum_variants(SIGNAL_ENUM),
+    ]
+});
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeOtlp;
+
+impl Function for EncodeOtlp {
+    fn identifier(&self) -> &'static str {
+        "encode_otlp"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Encodes the `value` into an [OpenTelemetry protocol](https://opentelemetry.io/docs/specs/otlp/)
+            (OTLP) protobuf payload. This is the inverse of `parse_otlp`: records with equal
+            `resource` and `scope` objects are grouped together, and attribute objects, hex IDs and
+            timestamps are converted back to their OTLP representation.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Codec.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &[
+            "A record in `value` is not an object.",
+            "A field of a record cannot be encoded as its OTLP type.",
+        ]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::BYTES
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[example! {
+            title: "Encode OTLP logs",
+            source: r#"encode_base64(encode_otlp!([{"resource": {"attributes": {"service.name": "checkout-service"}}, "scope": {"name": "logger"}, "severity_number": "SEVERITY_NUMBER_INFO", "body": "user logged in", "attributes": {"user": 42}}], "logs"))"#,
+            result: Ok(
+                "ClQKJAoiCgxzZXJ2aWNlLm5hbWUSEgoQY2hlY2tvdXQtc2VydmljZRIsCggKBmxvZ2dlchIgEAkqEAoOdXNlciBsb2dnZWQgaW4yCgoEdXNlchICGCo=",
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let signal = signal(&arguments, state)?;
+
+        Ok(EncodeOtlpFn { value, signal }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodeOtlpFn {
+    value: Box<dyn Expression>,
+    signal: Signal,
+}
+
+impl FunctionExpression for EncodeOtlpFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        Ok(Value::Bytes(encode_otlp(value, self.signal)?))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        encode_otlp => EncodeOtlp;
+
+        empty {
+            args: func_args![value: value!([]), signal: "traces"],
+            want: Ok(""),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        metric {
+            args: func_args![value: value!([{ name: "up", gauge: { data_points: [{ as_int: 1 }] } }]), signal: "metrics"],
+            want: Ok(b"\n\x15\x12\x13\x12\x11\n\x02up*\x0b\n\t1\x01\0\0\0\0\0\0\0".as_slice()),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        not_an_object {
+            args: func_args![value: value!(["log"]), signal: "logs"],
+            want: Err("OTLP records must be objects"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
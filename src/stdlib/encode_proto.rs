@@ -1,11 +1,8 @@
 use super::util::example_path_or_basename;
 use crate::compiler::prelude::*;
-use crate::protobuf::descriptor::get_message_descriptor;
+use super::parse_proto::message_descriptor;
 use crate::protobuf::encode::encode_proto;
 use prost_reflect::MessageDescriptor;
-use std::ffi::OsString;
-use std::path::Path;
-use std::path::PathBuf;
 use std::sync::LazyLock;
 
 #[derive(Clone, Copy, Debug)]
@@ -60,6 +57,15 @@ impl Function for EncodeProto {
         kind::BYTES
     }
 
+    fn notices(&self) -> &'static [&'static str] {
+        &[indoc! {"
+            The Google well-known types are encoded from the values in the proto3 JSON mapping:
+            a `google.protobuf.Timestamp` from a timestamp or an RFC 3339 string, a `Struct` from
+            an object, a wrapper such as `Int64Value` from the value it wraps, and an `Any` from
+            an object with the packed message's fields and its type URL in the `@type` field.
+        "}]
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         const PARAMETERS: &[Parameter] = &[
             Parameter::required(
@@ -70,9 +76,10 @@ impl Function for EncodeProto {
             Parameter::required(
                 "desc_file",
                 kind::BYTES,
-                "The path to the protobuf descriptor set file. Must be a literal string.
+                "The path to the protobuf descriptor set file, or to a `.proto` source file. Must be a literal string.
 
-This file is the output of protoc -o <path> ...",
+A descriptor set file is the output of protoc -o <path> .... A `.proto` source file is parsed when
+the program is compiled, along with the files it imports.",
             ),
             Parameter::required(
                 "message_type",
@@ -81,6 +88,15 @@ This file is the output of protoc -o <path> ...",
 
 Must be a literal string.",
             ),
+            Parameter::optional(
+                "include_paths",
+                kind::ARRAY,
+                "The directories to search for the files imported by a `.proto` source file, and
+which `desc_file` is relative to. Must be a literal array of strings.
+
+Defaults to the directory containing `desc_file`. The Google well-known types, such as
+`google/protobuf/timestamp.proto`, can always be imported.",
+            ),
         ];
         PARAMETERS
     }
@@ -96,19 +112,7 @@ Must be a literal string.",
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        let desc_file = arguments.required_literal("desc_file", state)?;
-        let desc_file_str = desc_file
-            .try_bytes_utf8_lossy()
-            .expect("descriptor file must be a string");
-        let message_type = arguments.required_literal("message_type", state)?;
-        let message_type_str = message_type
-            .try_bytes_utf8_lossy()
-            .expect("message_type must be a string");
-        let os_string: OsString = desc_file_str.into_owned().into();
-        let path_buf = PathBuf::from(os_string);
-        let path = Path::new(&path_buf);
-        let descriptor =
-            get_message_descriptor(path, &message_type_str).expect("message type not found");
+        let descriptor = message_descriptor(state, &arguments)?;
 
         Ok(EncodeProtoFn { descriptor, value }.as_expr())
     }
@@ -135,6 +139,7 @@ impl FunctionExpression for EncodeProtoFn {
 mod tests {
     use super::*;
     use crate::value;
+    use std::path::PathBuf;
     use std::{env, fs};
 
     fn test_data_dir() -> PathBuf {
@@ -164,5 +169,15 @@ mod tests {
             want: Ok(value!(read_pb_file("test_protobuf3/v1/input/person_someone.pb"))),
             tdef: TypeDef::bytes().fallible(),
         }
+
+        encodes_from_proto_source {
+            args: func_args![
+                value: value!({ name: "Someone", phones: [{number: "123-456", type: "PHONE_TYPE_MOBILE"}] }),
+                desc_file: test_data_dir().join("test_protobuf3/v1/test_protobuf3.proto").to_str().unwrap().to_owned(),
+                message_type: "test_protobuf3.v1.Person",
+                include_paths: Value::Array(vec![test_data_dir().to_str().unwrap().into()])],
+            want: Ok(value!(read_pb_file("test_protobuf3/v1/input/person_someone.pb"))),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
 }
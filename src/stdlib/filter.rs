@@ -170,14 +170,20 @@ impl FunctionExpression for FilterFn {
     fn type_def(&self, ctx: &state::TypeState) -> TypeDef {
         let mut type_def = self.value.type_def(ctx);
 
-        // Erase any type information from the array or object, as we can't know
-        // which elements are removed at runtime.
-        if type_def.contains_array() {
-            type_def.kind_mut().add_array(Collection::any());
+        // We can't know which elements are removed at runtime, but the kinds of
+        // the remaining elements are preserved.
+        //
+        // Object fields keep their key, so every known field becomes optional.
+        if let Some(object) = type_def.kind_mut().as_object_mut() {
+            for kind in object.known_mut().values_mut() {
+                *kind = kind.clone().or_undefined();
+            }
         }
 
-        if type_def.contains_object() {
-            type_def.kind_mut().add_object(Collection::any());
+        // Array elements shift when preceding elements are removed, so only the
+        // reduced kind of all elements is known.
+        if let Some(array) = type_def.kind_mut().as_array_mut() {
+            *array = Collection::from_unknown(array.reduced_kind());
         }
 
         type_def
@@ -1,8 +1,8 @@
 use std::collections::HashSet;
-use std::collections::btree_map;
 
 use crate::compiler::expression::Expr;
 use crate::compiler::prelude::*;
+use crate::value::object_map;
 use std::sync::LazyLock;
 
 static DEFAULT_SEPARATOR: LazyLock<Value> = LazyLock::new(|| Value::Bytes(Bytes::from(".")));
@@ -190,7 +190,7 @@ impl FunctionExpression for FlattenFn {
 
 /// An iterator to walk over maps allowing us to flatten nested maps to a single level.
 struct MapFlatten<'a> {
-    values: btree_map::Iter<'a, KeyString, Value>,
+    values: object_map::Iter<'a>,
     separator: &'a str,
     inner: Option<Box<MapFlatten<'a>>>,
     parent: Option<KeyString>,
@@ -199,7 +199,7 @@ struct MapFlatten<'a> {
 
 impl<'a> MapFlatten<'a> {
     fn new(
-        values: btree_map::Iter<'a, KeyString, Value>,
+        values: object_map::Iter<'a>,
         separator: &'a str,
         except: &'a HashSet<KeyString>,
     ) -> Self {
@@ -214,7 +214,7 @@ impl<'a> MapFlatten<'a> {
 
     fn new_from_parent(
         parent: KeyString,
-        values: btree_map::Iter<'a, KeyString, Value>,
+        values: object_map::Iter<'a>,
         separator: &'a str,
         except: &'a HashSet<KeyString>,
     ) -> Self {
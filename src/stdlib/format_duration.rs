@@ -0,0 +1,165 @@
+use crate::compiler::prelude::*;
+use crate::value::value::{to_nanoseconds, unit_nanoseconds};
+use chrono::TimeDelta;
+use rust_decimal::Decimal;
+
+use super::parse_duration::UNIT_ENUM;
+
+static PARAMETERS: &[Parameter] = &[
+    Parameter::required("value", kind::DURATION, "The duration to format."),
+    Parameter::optional(
+        "unit",
+        kind::BYTES,
+        "The unit to format the duration in. If omitted, the duration is formatted with the largest units first, such as `1h30m`.",
+    )
+    .enum_variants(UNIT_ENUM),
+];
+
+fn format_duration(duration: TimeDelta, unit: Option<&Value>) -> Resolved {
+    let Some(unit) = unit else {
+        return Ok(crate::value::value::format_duration(duration).into());
+    };
+
+    let unit = unit.try_bytes_utf8_lossy()?;
+    let unit_nanos = unit_nanoseconds(&unit).ok_or(format!("unknown unit format: '{unit}'"))?;
+
+    let nanos = Decimal::try_from_i128_with_scale(to_nanoseconds(duration), 0)
+        .ok()
+        .and_then(|nanos| nanos.checked_div(Decimal::from(unit_nanos)))
+        .ok_or(format!("unable to convert duration to '{unit}'"))?;
+
+    Ok(format!("{}{unit}", nanos.normalize()).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FormatDuration;
+
+impl Function for FormatDuration {
+    fn identifier(&self) -> &'static str {
+        "format_duration"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Formats the `value` duration as a string, such as `1h30m`, which `parse_duration` reads back.
+
+            If `unit` is set, the duration is formatted as a number of that unit instead, such as `1.5h`.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Timestamp.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &["`unit` is not a supported duration unit."]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::BYTES
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Format a duration",
+                source: "format_duration(d'90m')",
+                result: Ok("1h30m"),
+            },
+            example! {
+                title: "Format the time between two timestamps",
+                source: "format_duration(t'2024-01-02T00:00:00Z' - t'2024-01-01T12:00:00.5Z')",
+                result: Ok("11h59m59s500ms"),
+            },
+            example! {
+                title: "Format a duration in a unit",
+                source: r#"format_duration!(d'90m', unit: "h")"#,
+                result: Ok("1.5h"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let unit = arguments.optional("unit");
+
+        Ok(FormatDurationFn { value, unit }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FormatDurationFn {
+    value: Box<dyn Expression>,
+    unit: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for FormatDurationFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let duration = self.value.resolve(ctx)?.try_duration()?;
+        let unit = self
+            .unit
+            .as_ref()
+            .map(|unit| unit.resolve(ctx))
+            .transpose()?;
+
+        format_duration(duration, unit.as_ref())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().maybe_fallible(self.unit.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        format_duration => FormatDuration;
+
+        compact {
+            args: func_args![value: TimeDelta::milliseconds(5_400_005)],
+            want: Ok("1h30m5ms"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        negative {
+            args: func_args![value: TimeDelta::milliseconds(-1500)],
+            want: Ok("-1s500ms"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        zero {
+            args: func_args![value: TimeDelta::zero()],
+            want: Ok("0s"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        unit {
+            args: func_args![value: TimeDelta::minutes(90), unit: "h"],
+            want: Ok("1.5h"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        unit_fraction {
+            args: func_args![value: TimeDelta::nanoseconds(1500), unit: "us"],
+            want: Ok("1.5us"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        unknown_unit {
+            args: func_args![value: TimeDelta::minutes(90), unit: "y"],
+            want: Err("unknown unit format: 'y'"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -62,14 +62,12 @@ impl Function for FromEntries {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::ARRAY,
-            required: true,
-            description: "The array of key/value objects to convert.",
-            default: None,
-            enum_variants: None,
-        }]
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "value",
+            kind::ARRAY,
+            "The array of key/value objects to convert.",
+        )];
+        PARAMETERS
     }
 
     fn examples(&self) -> &'static [Example] {
@@ -0,0 +1,1350 @@
+//! [JMESPath](https://jmespath.org/specification.html) expressions, which transform a document
+//! with projections, filters and functions, such as `people[?age > `20`].name | sort(@)`.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use ordered_float::NotNan;
+
+use super::{SyntaxError, as_f64, compare_numbers, is_number, json_eq};
+use crate::value::{KeyString, Kind, ObjectMap, Value, kind::Collection};
+
+/// A parsed `JMESPath` expression.
+#[derive(Debug, Clone)]
+pub(crate) struct JmesPath {
+    ast: Ast,
+}
+
+impl JmesPath {
+    pub(crate) fn parse(input: &str) -> Result<Self, SyntaxError> {
+        let tokens = lex(input)?;
+        let mut parser = Parser { tokens: &tokens };
+        let ast = parser.expression(0)?;
+        match parser.current() {
+            Token::Eof => Ok(Self { ast }),
+            _ => Err(parser.error("unexpected token")),
+        }
+    }
+
+    /// The kind of value the expression evaluates to.
+    ///
+    /// Projections, filters, flattens, slices and multi-select lists evaluate to an array, even
+    /// for a single match, or to null if what they apply to has no elements. Other expressions,
+    /// such as `items[0]`, evaluate to the value they select as is.
+    pub(crate) fn kind(&self) -> Kind {
+        self.ast.kind()
+    }
+
+    /// Evaluates the expression against the document.
+    pub(crate) fn search(&self, value: &Value) -> Result<Value, String> {
+        self.ast.evaluate(Cow::Borrowed(value)).map(Cow::into_owned)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Ast {
+    /// The current node, written as `@` or implied at the start of an expression.
+    Identity,
+    Field(KeyString),
+    Index(i64),
+    Slice {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+    },
+    /// Evaluates the right side against the result of the left, unless that is null.
+    Subexpression(Box<Ast>, Box<Ast>),
+    /// Evaluates the right side against the result of the left.
+    IndexExpression(Box<Ast>, Box<Ast>),
+    /// Evaluates the right side against each element of the array on the left.
+    Projection(Box<Ast>, Box<Ast>),
+    /// Evaluates the right side against each value of the object on the left.
+    ValueProjection(Box<Ast>, Box<Ast>),
+    FilterProjection {
+        left: Box<Ast>,
+        right: Box<Ast>,
+        condition: Box<Ast>,
+    },
+    Flatten(Box<Ast>),
+    MultiSelectList(Vec<Ast>),
+    MultiSelectHash(Vec<(KeyString, Ast)>),
+    Literal(Value),
+    Comparison(Comparison, Box<Ast>, Box<Ast>),
+    Or(Box<Ast>, Box<Ast>),
+    And(Box<Ast>, Box<Ast>),
+    Not(Box<Ast>),
+    Pipe(Box<Ast>, Box<Ast>),
+    Function(Function, Vec<Ast>),
+    ExpressionReference(Box<Ast>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(KeyString),
+    QuotedIdentifier(KeyString),
+    Number(i64),
+    Literal(Value),
+    Dot,
+    Star,
+    Flatten,
+    Filter,
+    LeftBracket,
+    RightBracket,
+    LeftBrace,
+    RightBrace,
+    LeftParen,
+    RightParen,
+    Comma,
+    Colon,
+    At,
+    Ampersand,
+    And,
+    Or,
+    Pipe,
+    Not,
+    Comparison(ComparisonToken),
+    Eof,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComparisonToken {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Token {
+    /// How tightly the token binds to the expression on its left.
+    fn binding_power(&self) -> u8 {
+        match self {
+            Self::Pipe => 1,
+            Self::Or => 2,
+            Self::And => 3,
+            Self::Comparison(_) => 5,
+            Self::Flatten => 9,
+            Self::Star => 20,
+            Self::Filter => 21,
+            Self::Dot => 40,
+            Self::Not => 45,
+            Self::LeftBrace => 50,
+            Self::LeftBracket => 55,
+            Self::LeftParen => 60,
+            _ => 0,
+        }
+    }
+}
+
+/// Projections stop at tokens that bind less tightly than this, such as pipes.
+const PROJECTION_STOP: u8 = 10;
+
+fn lex(input: &str) -> Result<Vec<(usize, Token)>, SyntaxError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            ' ' | '\t' | '\n' | '\r' => continue,
+            '.' => Token::Dot,
+            '*' => Token::Star,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            '@' => Token::At,
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ']' => Token::RightBracket,
+            '[' if chars.next_if(|(_, c)| *c == ']').is_some() => Token::Flatten,
+            '[' if chars.next_if(|(_, c)| *c == '?').is_some() => Token::Filter,
+            '[' => Token::LeftBracket,
+            '&' if chars.next_if(|(_, c)| *c == '&').is_some() => Token::And,
+            '&' => Token::Ampersand,
+            '|' if chars.next_if(|(_, c)| *c == '|').is_some() => Token::Or,
+            '|' => Token::Pipe,
+            '!' if chars.next_if(|(_, c)| *c == '=').is_some() => {
+                Token::Comparison(ComparisonToken::Ne)
+            }
+            '!' => Token::Not,
+            '=' if chars.next_if(|(_, c)| *c == '=').is_some() => {
+                Token::Comparison(ComparisonToken::Eq)
+            }
+            '<' if chars.next_if(|(_, c)| *c == '=').is_some() => {
+                Token::Comparison(ComparisonToken::Le)
+            }
+            '<' => Token::Comparison(ComparisonToken::Lt),
+            '>' if chars.next_if(|(_, c)| *c == '=').is_some() => {
+                Token::Comparison(ComparisonToken::Ge)
+            }
+            '>' => Token::Comparison(ComparisonToken::Gt),
+            '-' | '0'..='9' => {
+                let end = consume_while(&mut chars, |c| c.is_ascii_digit());
+                input
+                    .get(position..end.unwrap_or(input.len()))
+                    .and_then(|number| number.parse().ok())
+                    .map(Token::Number)
+                    .ok_or_else(|| SyntaxError::new("invalid number", position))?
+            }
+            'a'..='z' | 'A'..='Z' | '_' => {
+                let end = consume_while(&mut chars, |c| c.is_ascii_alphanumeric() || c == '_');
+                Token::Identifier(input[position..end.unwrap_or(input.len())].into())
+            }
+            '"' => {
+                let text = delimited(input, &mut chars, position, '"')?;
+                let identifier = serde_json::from_str::<String>(text)
+                    .map_err(|_| SyntaxError::new("invalid quoted identifier", position))?;
+                Token::QuotedIdentifier(identifier.into())
+            }
+            '\'' => {
+                let text = delimited(input, &mut chars, position, '\'')?;
+                let string = text[1..text.len().saturating_sub(1)].replace("\\'", "'");
+                Token::Literal(string.into())
+            }
+            '`' => {
+                let text = delimited(input, &mut chars, position, '`')?;
+                let json = text[1..text.len().saturating_sub(1)].replace("\\`", "`");
+                let value = serde_json::from_str::<Value>(&json)
+                    .map_err(|_| SyntaxError::new("invalid JSON literal", position))?;
+                Token::Literal(value)
+            }
+            _ => return Err(SyntaxError::new("unexpected character", position)),
+        };
+        tokens.push((position, token));
+    }
+    tokens.push((input.len(), Token::Eof));
+    Ok(tokens)
+}
+
+/// Consumes the characters that match, returning the position of the first one that doesn't.
+fn consume_while(
+    chars: &mut Peekable<CharIndices<'_>>,
+    predicate: impl Fn(char) -> bool,
+) -> Option<usize> {
+    while chars.next_if(|(_, c)| predicate(*c)).is_some() {}
+    chars.peek().map(|(position, _)| *position)
+}
+
+/// Consumes the text up to the closing delimiter, skipping escaped ones, and returns the text
+/// including both delimiters.
+fn delimited<'a>(
+    input: &'a str,
+    chars: &mut Peekable<CharIndices<'_>>,
+    start: usize,
+    delimiter: char,
+) -> Result<&'a str, SyntaxError> {
+    let mut escaped = false;
+    for (position, c) in chars.by_ref() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == delimiter => return Ok(&input[start..=position]),
+            _ => {}
+        }
+    }
+    Err(SyntaxError::new("unterminated string", start))
+}
+
+struct Parser<'a> {
+    /// The remaining tokens, which always end with `Token::Eof`.
+    tokens: &'a [(usize, Token)],
+}
+
+impl Parser<'_> {
+    fn current(&self) -> &Token {
+        self.tokens.first().map_or(&Token::Eof, |(_, token)| token)
+    }
+
+    fn lookahead(&self) -> &Token {
+        self.tokens.get(1).map_or(&Token::Eof, |(_, token)| token)
+    }
+
+    fn error(&self, message: &str) -> SyntaxError {
+        SyntaxError::new(
+            message,
+            self.tokens.first().map_or(0, |(position, _)| *position),
+        )
+    }
+
+    fn advance(&mut self) -> Token {
+        match self.tokens.split_first() {
+            Some(((_, token), rest)) if !rest.is_empty() => {
+                self.tokens = rest;
+                token.clone()
+            }
+            _ => Token::Eof,
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), SyntaxError> {
+        if self.current() == token {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.error("unexpected token"))
+        }
+    }
+
+    fn expression(&mut self, binding_power: u8) -> Result<Ast, SyntaxError> {
+        let position = self.tokens.first().map_or(0, |(position, _)| *position);
+        let token = self.advance();
+        let mut left = self.nud(token, position)?;
+        while binding_power < self.current().binding_power() {
+            let token = self.advance();
+            left = self.led(&token, left)?;
+        }
+        Ok(left)
+    }
+
+    /// Parses the expression that starts with the token.
+    fn nud(&mut self, token: Token, position: usize) -> Result<Ast, SyntaxError> {
+        Ok(match token {
+            Token::Literal(value) => Ast::Literal(value),
+            Token::Identifier(name) => Ast::Field(name),
+            Token::QuotedIdentifier(name) => {
+                if self.current() == &Token::LeftParen {
+                    return Err(SyntaxError::new("function names can't be quoted", position));
+                }
+                Ast::Field(name)
+            }
+            Token::Star => {
+                let right = if self.current() == &Token::RightBracket {
+                    Ast::Identity
+                } else {
+                    self.projection_rhs(Token::Star.binding_power())?
+                };
+                Ast::ValueProjection(Box::new(Ast::Identity), Box::new(right))
+            }
+            Token::Filter => self.filter(Ast::Identity)?,
+            Token::LeftBrace => self.multi_select_hash()?,
+            Token::LeftParen => {
+                let expression = self.expression(0)?;
+                self.expect(&Token::RightParen)?;
+                expression
+            }
+            Token::Flatten => {
+                let right = self.projection_rhs(Token::Flatten.binding_power())?;
+                Ast::Projection(
+                    Box::new(Ast::Flatten(Box::new(Ast::Identity))),
+                    Box::new(right),
+                )
+            }
+            Token::Not => Ast::Not(Box::new(self.expression(Token::Not.binding_power())?)),
+            Token::LeftBracket => match (self.current(), self.lookahead()) {
+                (Token::Number(_) | Token::Colon, _) => {
+                    let index = self.index_expression()?;
+                    self.project_if_slice(Ast::Identity, index)?
+                }
+                (Token::Star, Token::RightBracket) => {
+                    self.advance();
+                    self.advance();
+                    let right = self.projection_rhs(Token::Star.binding_power())?;
+                    Ast::Projection(Box::new(Ast::Identity), Box::new(right))
+                }
+                _ => self.multi_select_list()?,
+            },
+            Token::At => Ast::Identity,
+            Token::Ampersand => Ast::ExpressionReference(Box::new(self.expression(0)?)),
+            _ => return Err(SyntaxError::new("unexpected token", position)),
+        })
+    }
+
+    /// Parses the expression that continues the one on its left with the token.
+    fn led(&mut self, token: &Token, left: Ast) -> Result<Ast, SyntaxError> {
+        Ok(match token {
+            Token::Dot => {
+                if self.current() == &Token::Star {
+                    self.advance();
+                    let right = self.projection_rhs(Token::Dot.binding_power())?;
+                    Ast::ValueProjection(Box::new(left), Box::new(right))
+                } else {
+                    let right = self.dot_rhs(Token::Dot.binding_power())?;
+                    Ast::Subexpression(Box::new(left), Box::new(right))
+                }
+            }
+            Token::Pipe => Ast::Pipe(
+                Box::new(left),
+                Box::new(self.expression(Token::Pipe.binding_power())?),
+            ),
+            Token::Or => Ast::Or(
+                Box::new(left),
+                Box::new(self.expression(Token::Or.binding_power())?),
+            ),
+            Token::And => Ast::And(
+                Box::new(left),
+                Box::new(self.expression(Token::And.binding_power())?),
+            ),
+            Token::LeftParen => {
+                let Ast::Field(name) = left else {
+                    return Err(self.error("invalid function name"));
+                };
+                self.function(&name)?
+            }
+            Token::Filter => self.filter(left)?,
+            Token::Comparison(comparison) => {
+                let right = self.expression(token.binding_power())?;
+                let comparison = match comparison {
+                    ComparisonToken::Eq => Comparison::Eq,
+                    ComparisonToken::Ne => Comparison::Ne,
+                    ComparisonToken::Lt => Comparison::Lt,
+                    ComparisonToken::Le => Comparison::Le,
+                    ComparisonToken::Gt => Comparison::Gt,
+                    ComparisonToken::Ge => Comparison::Ge,
+                };
+                Ast::Comparison(comparison, Box::new(left), Box::new(right))
+            }
+            Token::Flatten => {
+                let right = self.projection_rhs(Token::Flatten.binding_power())?;
+                Ast::Projection(Box::new(Ast::Flatten(Box::new(left))), Box::new(right))
+            }
+            Token::LeftBracket => {
+                if matches!(self.current(), Token::Number(_) | Token::Colon) {
+                    let index = self.index_expression()?;
+                    self.project_if_slice(left, index)?
+                } else {
+                    self.expect(&Token::Star)?;
+                    self.expect(&Token::RightBracket)?;
+                    let right = self.projection_rhs(Token::Star.binding_power())?;
+                    Ast::Projection(Box::new(left), Box::new(right))
+                }
+            }
+            _ => return Err(self.error("unexpected token")),
+        })
+    }
+
+    /// Parses an index or a slice, after the opening bracket.
+    fn index_expression(&mut self) -> Result<Ast, SyntaxError> {
+        if self.current() == &Token::Colon || self.lookahead() == &Token::Colon {
+            let mut parts = [None; 3];
+            let mut part = parts.iter_mut();
+            let mut current = part.next();
+            loop {
+                match self.advance() {
+                    Token::RightBracket => break,
+                    Token::Colon => {
+                        current = part.next();
+                        if current.is_none() {
+                            return Err(self.error("too many colons in slice"));
+                        }
+                    }
+                    Token::Number(number) => match current.as_deref_mut() {
+                        Some(slot @ None) => *slot = Some(number),
+                        _ => return Err(self.error("unexpected number in slice")),
+                    },
+                    _ => return Err(self.error("unexpected token in slice")),
+                }
+            }
+            let [start, end, step] = parts;
+            if step == Some(0) {
+                return Err(self.error("slice step can't be 0"));
+            }
+            Ok(Ast::Slice { start, end, step })
+        } else {
+            let Token::Number(index) = self.advance() else {
+                return Err(self.error("expected an index"));
+            };
+            self.expect(&Token::RightBracket)?;
+            Ok(Ast::Index(index))
+        }
+    }
+
+    fn project_if_slice(&mut self, left: Ast, index: Ast) -> Result<Ast, SyntaxError> {
+        let is_slice = matches!(index, Ast::Slice { .. });
+        let expression = Ast::IndexExpression(Box::new(left), Box::new(index));
+        if is_slice {
+            let right = self.projection_rhs(Token::Star.binding_power())?;
+            Ok(Ast::Projection(Box::new(expression), Box::new(right)))
+        } else {
+            Ok(expression)
+        }
+    }
+
+    fn filter(&mut self, left: Ast) -> Result<Ast, SyntaxError> {
+        let condition = self.expression(0)?;
+        self.expect(&Token::RightBracket)?;
+        let right = if self.current() == &Token::Flatten {
+            Ast::Identity
+        } else {
+            self.projection_rhs(Token::Filter.binding_power())?
+        };
+        Ok(Ast::FilterProjection {
+            left: Box::new(left),
+            right: Box::new(right),
+            condition: Box::new(condition),
+        })
+    }
+
+    fn multi_select_list(&mut self) -> Result<Ast, SyntaxError> {
+        let mut expressions = Vec::new();
+        loop {
+            expressions.push(self.expression(0)?);
+            if self.current() == &Token::RightBracket {
+                self.advance();
+                return Ok(Ast::MultiSelectList(expressions));
+            }
+            self.expect(&Token::Comma)?;
+        }
+    }
+
+    fn multi_select_hash(&mut self) -> Result<Ast, SyntaxError> {
+        let mut pairs = Vec::new();
+        loop {
+            let (Token::Identifier(key) | Token::QuotedIdentifier(key)) = self.advance() else {
+                return Err(self.error("expected a key"));
+            };
+            self.expect(&Token::Colon)?;
+            pairs.push((key, self.expression(0)?));
+            match self.advance() {
+                Token::Comma => {}
+                Token::RightBrace => return Ok(Ast::MultiSelectHash(pairs)),
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    /// Parses what a projection applies to each of its elements.
+    fn projection_rhs(&mut self, binding_power: u8) -> Result<Ast, SyntaxError> {
+        match self.current() {
+            token if token.binding_power() < PROJECTION_STOP => Ok(Ast::Identity),
+            Token::LeftBracket | Token::Filter => self.expression(binding_power),
+            Token::Dot => {
+                self.advance();
+                self.dot_rhs(binding_power)
+            }
+            _ => Err(self.error("unexpected token after projection")),
+        }
+    }
+
+    fn dot_rhs(&mut self, binding_power: u8) -> Result<Ast, SyntaxError> {
+        match self.current() {
+            Token::Identifier(_) | Token::QuotedIdentifier(_) | Token::Star => {
+                self.expression(binding_power)
+            }
+            Token::LeftBracket => {
+                self.advance();
+                self.multi_select_list()
+            }
+            Token::LeftBrace => {
+                self.advance();
+                self.multi_select_hash()
+            }
+            _ => Err(self.error("expected an identifier, `*`, `[` or `{` after `.`")),
+        }
+    }
+
+    fn function(&mut self, name: &str) -> Result<Ast, SyntaxError> {
+        let function = Function::from_name(name).ok_or_else(|| self.error("unknown function"))?;
+        let mut arguments = Vec::new();
+        while self.current() != &Token::RightParen {
+            arguments.push(self.expression(0)?);
+            if self.current() == &Token::Comma {
+                self.advance();
+            }
+        }
+        let (min, variadic) = function.arity();
+        if arguments.len() < min || (!variadic && arguments.len() > min) {
+            return Err(self.error(&format!(
+                "wrong number of arguments to `{}`",
+                function.name()
+            )));
+        }
+        self.advance();
+        Ok(Ast::Function(function, arguments))
+    }
+}
+
+/// Returns false for the values `JMESPath` considers false: empty arrays, objects and strings,
+/// `false` and `null`.
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Array(array) => !array.is_empty(),
+        Value::Object(object) => !object.is_empty(),
+        Value::Bytes(bytes) => !bytes.is_empty(),
+        Value::Boolean(boolean) => *boolean,
+        Value::Null => false,
+        _ => true,
+    }
+}
+
+/// Returns the elements of an array, borrowing them if the array is borrowed.
+fn elements(value: Cow<'_, Value>) -> Option<Vec<Cow<'_, Value>>> {
+    match value {
+        Cow::Borrowed(Value::Array(array)) => Some(array.iter().map(Cow::Borrowed).collect()),
+        Cow::Owned(Value::Array(array)) => Some(array.into_iter().map(Cow::Owned).collect()),
+        _ => None,
+    }
+}
+
+/// Returns the values of an object, borrowing them if the object is borrowed.
+fn object_values(value: Cow<'_, Value>) -> Option<Vec<Cow<'_, Value>>> {
+    match value {
+        Cow::Borrowed(Value::Object(object)) => Some(object.values().map(Cow::Borrowed).collect()),
+        Cow::Owned(Value::Object(object)) => Some(object.into_values().map(Cow::Owned).collect()),
+        _ => None,
+    }
+}
+
+/// Returns the part of the value that `select` picks, or null.
+fn child<'a>(
+    value: Cow<'a, Value>,
+    select: impl for<'b> Fn(&'b Value) -> Option<&'b Value>,
+) -> Cow<'a, Value> {
+    match value {
+        Cow::Borrowed(value) => select(value).map_or(Cow::Owned(Value::Null), Cow::Borrowed),
+        Cow::Owned(value) => Cow::Owned(select(&value).cloned().unwrap_or(Value::Null)),
+    }
+}
+
+/// Evaluates the expression against each element, dropping null results.
+fn project<'a>(
+    expression: &'a Ast,
+    elements: Vec<Cow<'a, Value>>,
+) -> Result<Cow<'a, Value>, String> {
+    let mut projected = Vec::with_capacity(elements.len());
+    for element in elements {
+        let value = expression.evaluate(element)?;
+        if !matches!(value.as_ref(), Value::Null) {
+            projected.push(value.into_owned());
+        }
+    }
+    Ok(Cow::Owned(Value::Array(projected)))
+}
+
+impl Ast {
+    fn kind(&self) -> Kind {
+        match self {
+            Self::Slice { .. }
+            | Self::Projection(..)
+            | Self::ValueProjection(..)
+            | Self::FilterProjection { .. }
+            | Self::Flatten(_)
+            | Self::MultiSelectList(_) => Kind::array(Collection::any()).or_null(),
+            Self::MultiSelectHash(_) => Kind::object(Collection::any()).or_null(),
+            Self::Literal(value) => value.kind(),
+            Self::IndexExpression(_, right) | Self::Pipe(_, right) => right.kind(),
+            Self::Subexpression(_, right) => right.kind().or_null(),
+            _ => Kind::any(),
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn evaluate<'a>(&'a self, value: Cow<'a, Value>) -> Result<Cow<'a, Value>, String> {
+        const NULL: Cow<'_, Value> = Cow::Owned(Value::Null);
+
+        Ok(match self {
+            Self::Identity => value,
+            Self::Field(name) => child(value, |value| value.as_object()?.get(name.as_str())),
+            Self::Index(index) => child(value, |value| {
+                let array = value.as_array()?;
+                let offset = usize::try_from(index.unsigned_abs()).ok()?;
+                let index = if *index < 0 {
+                    array.len().checked_sub(offset)?
+                } else {
+                    offset
+                };
+                array.get(index)
+            }),
+            Self::Slice { start, end, step } => match elements(value) {
+                Some(elements) => Cow::Owned(Value::Array(slice(&elements, *start, *end, *step))),
+                None => NULL,
+            },
+            Self::Subexpression(left, right) => match left.evaluate(value)? {
+                Cow::Owned(Value::Null) | Cow::Borrowed(Value::Null) => NULL,
+                left => right.evaluate(left)?,
+            },
+            Self::IndexExpression(left, right) | Self::Pipe(left, right) => {
+                right.evaluate(left.evaluate(value)?)?
+            }
+            Self::Projection(left, right) => match elements(left.evaluate(value)?) {
+                Some(elements) => project(right, elements)?,
+                None => NULL,
+            },
+            Self::ValueProjection(left, right) => match object_values(left.evaluate(value)?) {
+                Some(values) => project(right, values)?,
+                None => NULL,
+            },
+            Self::FilterProjection {
+                left,
+                right,
+                condition,
+            } => match elements(left.evaluate(value)?) {
+                Some(elements) => {
+                    let mut matched = Vec::new();
+                    for element in elements {
+                        if is_truthy(condition.evaluate(element.clone())?.as_ref()) {
+                            matched.push(element);
+                        }
+                    }
+                    project(right, matched)?
+                }
+                None => NULL,
+            },
+            Self::Flatten(inner) => match elements(inner.evaluate(value)?) {
+                Some(elements) => {
+                    let mut flattened = Vec::new();
+                    for element in elements {
+                        match element.into_owned() {
+                            Value::Array(array) => flattened.extend(array),
+                            element => flattened.push(element),
+                        }
+                    }
+                    Cow::Owned(Value::Array(flattened))
+                }
+                None => NULL,
+            },
+            Self::MultiSelectList(expressions) => {
+                if matches!(value.as_ref(), Value::Null) {
+                    return Ok(NULL);
+                }
+                let mut selected = Vec::with_capacity(expressions.len());
+                for expression in expressions {
+                    selected.push(expression.evaluate(value.clone())?.into_owned());
+                }
+                Cow::Owned(Value::Array(selected))
+            }
+            Self::MultiSelectHash(pairs) => {
+                if matches!(value.as_ref(), Value::Null) {
+                    return Ok(NULL);
+                }
+                let mut selected = ObjectMap::new();
+                for (key, expression) in pairs {
+                    let value = expression.evaluate(value.clone())?.into_owned();
+                    selected.insert(key.clone(), value);
+                }
+                Cow::Owned(Value::Object(selected))
+            }
+            Self::Literal(literal) => Cow::Borrowed(literal),
+            Self::Comparison(comparison, left, right) => {
+                let left = left.evaluate(value.clone())?;
+                let right = right.evaluate(value)?;
+                Cow::Owned(comparison.evaluate(&left, &right))
+            }
+            Self::Or(left, right) => {
+                let left = left.evaluate(value.clone())?;
+                if is_truthy(left.as_ref()) {
+                    left
+                } else {
+                    right.evaluate(value)?
+                }
+            }
+            Self::And(left, right) => {
+                let left = left.evaluate(value.clone())?;
+                if is_truthy(left.as_ref()) {
+                    right.evaluate(value)?
+                } else {
+                    left
+                }
+            }
+            Self::Not(inner) => {
+                Cow::Owned(Value::Boolean(!is_truthy(inner.evaluate(value)?.as_ref())))
+            }
+            Self::Function(function, arguments) => {
+                let mut resolved = Vec::with_capacity(arguments.len());
+                for argument in arguments {
+                    resolved.push(match argument {
+                        Self::ExpressionReference(expression) => Argument::Expression(expression),
+                        argument => Argument::Value(argument.evaluate(value.clone())?),
+                    });
+                }
+                function.call(resolved)?
+            }
+            // Expression references are only meaningful as function arguments.
+            Self::ExpressionReference(_) => NULL,
+        })
+    }
+}
+
+/// Returns the elements that a slice selects, following Python's slicing rules.
+fn slice(
+    elements: &[Cow<'_, Value>],
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+) -> Vec<Value> {
+    let len = i64::try_from(elements.len()).unwrap_or(i64::MAX);
+    let step = step.unwrap_or(1);
+    let bound = |index: i64, low: i64, high: i64| {
+        if index < 0 {
+            len.saturating_add(index).max(low)
+        } else {
+            index.min(high)
+        }
+    };
+
+    let (mut index, end) = if step > 0 {
+        (
+            start.map_or(0, |start| bound(start, 0, len)),
+            end.map_or(len, |end| bound(end, 0, len)),
+        )
+    } else {
+        (
+            start.map_or(len.saturating_sub(1), |start| {
+                bound(start, -1, len.saturating_sub(1))
+            }),
+            end.map_or(-1, |end| bound(end, -1, len.saturating_sub(1))),
+        )
+    };
+
+    let mut sliced = Vec::new();
+    while (step > 0 && index < end) || (step < 0 && index > end) {
+        if let Some(element) = usize::try_from(index).ok().and_then(|i| elements.get(i)) {
+            sliced.push(element.as_ref().clone());
+        }
+        index = index.saturating_add(step);
+    }
+    sliced
+}
+
+impl Comparison {
+    /// Compares the values. Only numbers can be ordered, so ordering anything else is null.
+    fn evaluate(self, left: &Value, right: &Value) -> Value {
+        let ordering = compare_numbers(left, right);
+        match self {
+            Self::Eq => json_eq(left, right).into(),
+            Self::Ne => (!json_eq(left, right)).into(),
+            Self::Lt => ordering.map_or(Value::Null, |o| o.is_lt().into()),
+            Self::Le => ordering.map_or(Value::Null, |o| o.is_le().into()),
+            Self::Gt => ordering.map_or(Value::Null, |o| o.is_gt().into()),
+            Self::Ge => ordering.map_or(Value::Null, |o| o.is_ge().into()),
+        }
+    }
+}
+
+/// Returns the `JMESPath` name of the value's type.
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Bytes(_) => "string",
+        Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => "number",
+        Value::Boolean(_) => "boolean",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+        Value::Null => "null",
+        Value::Regex(_) => "regex",
+        Value::Timestamp(_) => "timestamp",
+        Value::Duration(_) => "duration",
+    }
+}
+
+fn float(float: f64) -> Value {
+    NotNan::new(float).map_or(Value::Null, Value::Float)
+}
+
+fn integer(len: usize) -> Value {
+    Value::Integer(i64::try_from(len).unwrap_or(i64::MAX))
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Function {
+    Abs,
+    Avg,
+    Ceil,
+    Contains,
+    EndsWith,
+    Floor,
+    Join,
+    Keys,
+    Length,
+    Map,
+    Max,
+    MaxBy,
+    Merge,
+    Min,
+    MinBy,
+    NotNull,
+    Reverse,
+    Sort,
+    SortBy,
+    StartsWith,
+    Sum,
+    ToArray,
+    ToNumber,
+    ToString,
+    Type,
+    Values,
+}
+
+/// An evaluated function argument.
+enum Argument<'a> {
+    Value(Cow<'a, Value>),
+    Expression(&'a Ast),
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "abs" => Self::Abs,
+            "avg" => Self::Avg,
+            "ceil" => Self::Ceil,
+            "contains" => Self::Contains,
+            "ends_with" => Self::EndsWith,
+            "floor" => Self::Floor,
+            "join" => Self::Join,
+            "keys" => Self::Keys,
+            "length" => Self::Length,
+            "map" => Self::Map,
+            "max" => Self::Max,
+            "max_by" => Self::MaxBy,
+            "merge" => Self::Merge,
+            "min" => Self::Min,
+            "min_by" => Self::MinBy,
+            "not_null" => Self::NotNull,
+            "reverse" => Self::Reverse,
+            "sort" => Self::Sort,
+            "sort_by" => Self::SortBy,
+            "starts_with" => Self::StartsWith,
+            "sum" => Self::Sum,
+            "to_array" => Self::ToArray,
+            "to_number" => Self::ToNumber,
+            "to_string" => Self::ToString,
+            "type" => Self::Type,
+            "values" => Self::Values,
+            _ => return None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Abs => "abs",
+            Self::Avg => "avg",
+            Self::Ceil => "ceil",
+            Self::Contains => "contains",
+            Self::EndsWith => "ends_with",
+            Self::Floor => "floor",
+            Self::Join => "join",
+            Self::Keys => "keys",
+            Self::Length => "length",
+            Self::Map => "map",
+            Self::Max => "max",
+            Self::MaxBy => "max_by",
+            Self::Merge => "merge",
+            Self::Min => "min",
+            Self::MinBy => "min_by",
+            Self::NotNull => "not_null",
+            Self::Reverse => "reverse",
+            Self::Sort => "sort",
+            Self::SortBy => "sort_by",
+            Self::StartsWith => "starts_with",
+            Self::Sum => "sum",
+            Self::ToArray => "to_array",
+            Self::ToNumber => "to_number",
+            Self::ToString => "to_string",
+            Self::Type => "type",
+            Self::Values => "values",
+        }
+    }
+
+    /// Returns the number of arguments the function takes, and whether it takes more.
+    fn arity(self) -> (usize, bool) {
+        match self {
+            Self::Merge | Self::NotNull => (1, true),
+            Self::Contains
+            | Self::EndsWith
+            | Self::Join
+            | Self::Map
+            | Self::MaxBy
+            | Self::MinBy
+            | Self::SortBy
+            | Self::StartsWith => (2, false),
+            _ => (1, false),
+        }
+    }
+
+    fn invalid_type(self, expected: &str, got: &Value) -> String {
+        format!(
+            "invalid type for `{}`: expected {expected}, got {}",
+            self.name(),
+            type_name(got)
+        )
+    }
+
+    #[allow(clippy::too_many_lines, clippy::cast_precision_loss)]
+    fn call(self, arguments: Vec<Argument<'_>>) -> Result<Cow<'_, Value>, String> {
+        let mut arguments = Arguments {
+            function: self,
+            arguments: arguments.into_iter(),
+        };
+
+        Ok(Cow::Owned(match self {
+            Self::Abs => match arguments.number()?.as_ref() {
+                Value::Integer(i) => i.checked_abs().map_or_else(
+                    || float(as_f64(&Value::Integer(*i)).unwrap_or_default().abs()),
+                    Value::Integer,
+                ),
+                Value::Decimal(d) => Value::Decimal(d.abs()),
+                number => float(as_f64(number).unwrap_or_default().abs()),
+            },
+            Self::Avg => {
+                let numbers = arguments.numbers()?;
+                if numbers.is_empty() {
+                    return Ok(Cow::Owned(Value::Null));
+                }
+                let sum = numbers.iter().filter_map(|n| as_f64(n)).sum::<f64>();
+                float(sum / numbers.len() as f64)
+            }
+            Self::Ceil | Self::Floor => {
+                let ceil = matches!(self, Self::Ceil);
+                match arguments.number()?.into_owned() {
+                    Value::Float(f) if ceil => float(f.ceil()),
+                    Value::Float(f) => float(f.floor()),
+                    Value::Decimal(d) if ceil => Value::Decimal(d.ceil()),
+                    Value::Decimal(d) => Value::Decimal(d.floor()),
+                    number => number,
+                }
+            }
+            Self::Contains => {
+                let subject = arguments.value()?;
+                let search = arguments.value()?;
+                match (subject.as_ref(), search.as_ref()) {
+                    (Value::Array(array), search) => {
+                        array.iter().any(|element| json_eq(element, search)).into()
+                    }
+                    (Value::Bytes(subject), Value::Bytes(search)) => {
+                        String::from_utf8_lossy(subject)
+                            .contains(String::from_utf8_lossy(search).as_ref())
+                            .into()
+                    }
+                    (Value::Bytes(_), _) => false.into(),
+                    (subject, _) => return Err(self.invalid_type("array or string", subject)),
+                }
+            }
+            Self::EndsWith | Self::StartsWith => {
+                let subject = arguments.string()?;
+                let affix = arguments.string()?;
+                if matches!(self, Self::EndsWith) {
+                    subject.ends_with(&affix).into()
+                } else {
+                    subject.starts_with(&affix).into()
+                }
+            }
+            Self::Join => {
+                let glue = arguments.string()?;
+                let strings = arguments
+                    .array()?
+                    .iter()
+                    .map(|element| match element.as_ref() {
+                        Value::Bytes(bytes) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+                        element => Err(self.invalid_type("array[string]", element)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                strings.join(&glue).into()
+            }
+            Self::Keys => arguments
+                .object()?
+                .keys()
+                .map(|key| Value::from(key.as_str()))
+                .collect::<Vec<_>>()
+                .into(),
+            Self::Values => arguments
+                .object()?
+                .values()
+                .cloned()
+                .collect::<Vec<_>>()
+                .into(),
+            Self::Length => match arguments.value()?.as_ref() {
+                Value::Bytes(bytes) => integer(String::from_utf8_lossy(bytes).chars().count()),
+                Value::Array(array) => integer(array.len()),
+                Value::Object(object) => integer(object.len()),
+                value => return Err(self.invalid_type("string, array or object", value)),
+            },
+            Self::Map => {
+                let expression = arguments.expression()?;
+                let mut mapped = Vec::new();
+                for element in arguments.array()? {
+                    mapped.push(expression.evaluate(element)?.into_owned());
+                }
+                mapped.into()
+            }
+            Self::Max | Self::Min => {
+                let elements = arguments.array()?;
+                let keys = elements.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+                self.check_sortable(&keys)?;
+                extreme(&keys, matches!(self, Self::Max)).map_or(Value::Null, Clone::clone)
+            }
+            Self::MaxBy | Self::MinBy => {
+                let elements = arguments.array()?;
+                let expression = arguments.expression()?;
+                let keys = self.keys(expression, &elements)?;
+                let keys = keys.iter().collect::<Vec<_>>();
+                let max = matches!(self, Self::MaxBy);
+                extreme(&keys, max)
+                    .and_then(|key| keys.iter().position(|k| std::ptr::eq(*k, key)))
+                    .map_or(Value::Null, |index| elements[index].as_ref().clone())
+            }
+            Self::Merge => {
+                let mut merged = ObjectMap::new();
+                while let Some(object) = arguments.next_object()? {
+                    merged.extend(object);
+                }
+                Value::Object(merged)
+            }
+            Self::NotNull => {
+                for argument in arguments.arguments.by_ref() {
+                    if let Argument::Value(value) = argument
+                        && !matches!(value.as_ref(), Value::Null)
+                    {
+                        return Ok(value);
+                    }
+                }
+                Value::Null
+            }
+            Self::Reverse => match arguments.value()?.into_owned() {
+                Value::Bytes(bytes) => String::from_utf8_lossy(&bytes)
+                    .chars()
+                    .rev()
+                    .collect::<String>()
+                    .into(),
+                Value::Array(mut array) => {
+                    array.reverse();
+                    array.into()
+                }
+                value => return Err(self.invalid_type("string or array", &value)),
+            },
+            Self::Sort => {
+                let mut elements = arguments
+                    .array()?
+                    .into_iter()
+                    .map(Cow::into_owned)
+                    .collect::<Vec<_>>();
+                self.check_sortable(&elements.iter().collect::<Vec<_>>())?;
+                elements.sort_by(order);
+                elements.into()
+            }
+            Self::SortBy => {
+                let elements = arguments.array()?;
+                let expression = arguments.expression()?;
+                let keys = self.keys(expression, &elements)?;
+                let mut sorted = elements.into_iter().zip(keys).collect::<Vec<_>>();
+                sorted.sort_by(|(_, a), (_, b)| order(a, b));
+                sorted
+                    .into_iter()
+                    .map(|(element, _)| element.into_owned())
+                    .collect::<Vec<_>>()
+                    .into()
+            }
+            Self::Sum => {
+                let numbers = arguments.numbers()?;
+                let integers = numbers
+                    .iter()
+                    .try_fold(0_i64, |sum, number| sum.checked_add(number.as_integer()?));
+                integers.map_or_else(
+                    || float(numbers.iter().filter_map(|n| as_f64(n)).sum()),
+                    Value::Integer,
+                )
+            }
+            Self::ToArray => match arguments.value()?.into_owned() {
+                array @ Value::Array(_) => array,
+                value => vec![value].into(),
+            },
+            Self::ToNumber => match arguments.value()?.into_owned() {
+                number if is_number(&number) => number,
+                Value::Bytes(bytes) => {
+                    let string = String::from_utf8_lossy(&bytes);
+                    string.trim().parse::<i64>().map_or_else(
+                        |_| {
+                            string
+                                .trim()
+                                .parse::<f64>()
+                                .ok()
+                                .filter(|f| f.is_finite())
+                                .map_or(Value::Null, float)
+                        },
+                        Value::Integer,
+                    )
+                }
+                _ => Value::Null,
+            },
+            Self::ToString => match arguments.value()? {
+                string @ Cow::Borrowed(Value::Bytes(_)) => return Ok(string),
+                Cow::Owned(string @ Value::Bytes(_)) => string,
+                value => serde_json::to_string(value.as_ref())
+                    .map_err(|err| err.to_string())?
+                    .into(),
+            },
+            Self::Type => type_name(arguments.value()?.as_ref()).into(),
+        }))
+    }
+
+    /// Evaluates the key expression of `sort_by`, `max_by` or `min_by` against the elements.
+    fn keys<'a>(
+        self,
+        expression: &'a Ast,
+        elements: &[Cow<'a, Value>],
+    ) -> Result<Vec<Value>, String> {
+        let mut keys = Vec::with_capacity(elements.len());
+        for element in elements {
+            keys.push(expression.evaluate(element.clone())?.into_owned());
+        }
+        self.check_sortable(&keys.iter().collect::<Vec<_>>())?;
+        Ok(keys)
+    }
+
+    /// Checks that the values are all numbers or all strings, so that they can be ordered.
+    fn check_sortable(self, values: &[&Value]) -> Result<(), String> {
+        let mut expected = None;
+        for value in values {
+            let kind = match value {
+                Value::Bytes(_) => "string",
+                value if is_number(value) => "number",
+                value => return Err(self.invalid_type("numbers or strings", value)),
+            };
+            match expected {
+                Some(expected) if expected != kind => {
+                    return Err(self.invalid_type(&format!("only {expected}s"), value));
+                }
+                _ => expected = Some(kind),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Orders two numbers or two strings.
+fn order(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
+        _ => compare_numbers(a, b).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// Returns the first of the largest or smallest values.
+fn extreme<'a>(values: &[&'a Value], max: bool) -> Option<&'a Value> {
+    let wanted = if max {
+        Ordering::Greater
+    } else {
+        Ordering::Less
+    };
+    values.iter().copied().reduce(|best, value| {
+        if order(value, best) == wanted {
+            value
+        } else {
+            best
+        }
+    })
+}
+
+/// The arguments of a function call, which the parser has checked the number of.
+struct Arguments<'a> {
+    function: Function,
+    arguments: std::vec::IntoIter<Argument<'a>>,
+}
+
+impl<'a> Arguments<'a> {
+    fn value(&mut self) -> Result<Cow<'a, Value>, String> {
+        match self.arguments.next() {
+            Some(Argument::Value(value)) => Ok(value),
+            Some(Argument::Expression(_)) => Err(format!(
+                "invalid type for `{}`: expected a value, got an expression",
+                self.function.name()
+            )),
+            None => Ok(Cow::Owned(Value::Null)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<&'a Ast, String> {
+        match self.arguments.next() {
+            Some(Argument::Expression(expression)) => Ok(expression),
+            Some(Argument::Value(value)) => Err(self.function.invalid_type("expression", &value)),
+            None => Ok(&Ast::Identity),
+        }
+    }
+
+    fn number(&mut self) -> Result<Cow<'a, Value>, String> {
+        let value = self.value()?;
+        if is_number(&value) {
+            Ok(value)
+        } else {
+            Err(self.function.invalid_type("number", &value))
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        match self.value()?.as_ref() {
+            Value::Bytes(bytes) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+            value => Err(self.function.invalid_type("string", value)),
+        }
+    }
+
+    fn array(&mut self) -> Result<Vec<Cow<'a, Value>>, String> {
+        let value = self.value()?;
+        let kind = type_name(&value);
+        elements(value).ok_or_else(|| {
+            format!(
+                "invalid type for `{}`: expected array, got {kind}",
+                self.function.name()
+            )
+        })
+    }
+
+    fn numbers(&mut self) -> Result<Vec<Cow<'a, Value>>, String> {
+        let numbers = self.array()?;
+        match numbers.iter().find(|number| !is_number(number)) {
+            Some(value) => Err(self.function.invalid_type("array[number]", value)),
+            None => Ok(numbers),
+        }
+    }
+
+    fn object(&mut self) -> Result<ObjectMap, String> {
+        match self.value()?.into_owned() {
+            Value::Object(object) => Ok(object),
+            value => Err(self.function.invalid_type("object", &value)),
+        }
+    }
+
+    /// Returns the next object of a variadic argument list, or `None` if there are no more.
+    fn next_object(&mut self) -> Result<Option<ObjectMap>, String> {
+        if self.arguments.len() == 0 {
+            return Ok(None);
+        }
+        self.object().map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stdlib::json_query::tests::fixtures;
+
+    #[test]
+    fn conformance() {
+        for (file, given, case) in fixtures("jmespath") {
+            let expression = case.get("expression").unwrap().as_str().unwrap();
+            let path = JmesPath::parse(&expression).map_err(|err| err.to_string());
+            let result = path.clone().and_then(|path| path.search(&given));
+            match case.get("result") {
+                Some(expected) => {
+                    let actual = result.unwrap_or_else(|e| panic!("{file}: {expression}: {e}"));
+                    assert!(
+                        json_eq(&actual, expected),
+                        "{file}: {expression}: expected {expected:?}, got {actual:?}"
+                    );
+                    let kind = path.unwrap().kind();
+                    assert!(
+                        kind.is_superset(&actual.kind()).is_ok(),
+                        "{file}: {expression}: {actual:?} is not {kind}"
+                    );
+                }
+                None => assert!(result.is_err(), "{file}: {expression}: expected an error"),
+            }
+        }
+    }
+
+    #[test]
+    fn syntax_error_position() {
+        assert_eq!(
+            JmesPath::parse("foo[?bar ==]").unwrap_err().to_string(),
+            "unexpected token at position 11"
+        );
+    }
+}
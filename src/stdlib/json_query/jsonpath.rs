@@ -0,0 +1,953 @@
+//! [JSONPath](https://www.rfc-editor.org/rfc/rfc9535) queries, which select the nodes of a
+//! document that match a path, such as `$.store.book[?@.price < 10].title`.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use ordered_float::NotNan;
+use regex::Regex;
+
+use super::{SyntaxError, compare_numbers, json_eq};
+use crate::value::{KeyString, Value};
+
+/// The largest integer that indexes and slices may use, as `JSONPath` follows I-JSON.
+const MAX_INTEGER: i64 = 9_007_199_254_740_991;
+
+/// A parsed `JSONPath` query.
+#[derive(Debug, Clone)]
+pub(crate) struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    pub(crate) fn parse(input: &str) -> Result<Self, SyntaxError> {
+        let mut parser = Parser::new(input);
+        let query = parser.query()?;
+        if query.relative {
+            return Err(SyntaxError::new("a query must start with `$`", 0));
+        }
+        if parser.peek().is_some() {
+            return Err(parser.error("unexpected character"));
+        }
+        Ok(Self {
+            segments: query.segments,
+        })
+    }
+
+    /// Returns the nodes of the document that the query selects, in document order.
+    pub(crate) fn query<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        select(&self.segments, root, root)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Segment {
+    descendant: bool,
+    selectors: Vec<Selector>,
+}
+
+#[derive(Debug, Clone)]
+enum Selector {
+    Name(KeyString),
+    Wildcard,
+    Index(i64),
+    Slice {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+    },
+    Filter(LogicalExpr),
+}
+
+/// A query inside a filter, relative to the current node (`@`) or to the root (`$`).
+#[derive(Debug, Clone)]
+struct Query {
+    relative: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum LogicalExpr {
+    Or(Vec<LogicalExpr>),
+    And(Vec<LogicalExpr>),
+    Not(Box<LogicalExpr>),
+    Comparison(Comparable, Comparison, Comparable),
+    Exists(Query),
+    Regex(RegexFunction),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// An expression that produces a single value, or nothing.
+#[derive(Debug, Clone)]
+enum Comparable {
+    Literal(Value),
+    /// A query that selects at most one node.
+    Query(Query),
+    Length(Box<Comparable>),
+    Count(Query),
+    Value(Query),
+}
+
+/// The `match` and `search` functions, which test a string against an I-Regexp pattern.
+#[derive(Debug, Clone)]
+struct RegexFunction {
+    /// Whether the whole string must match, rather than a substring.
+    full: bool,
+    value: Box<Comparable>,
+    pattern: Pattern,
+}
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    /// A literal pattern, compiled once. Invalid patterns match nothing.
+    Literal(Option<Regex>),
+    Dynamic(Box<Comparable>),
+}
+
+/// An argument to a function, before it is checked against the parameter's type.
+enum Argument {
+    Literal(Value),
+    Query(Query),
+    Logical(LogicalExpr),
+    Value(Comparable),
+}
+
+fn select<'a>(segments: &[Segment], current: &'a Value, root: &'a Value) -> Vec<&'a Value> {
+    let mut nodes = vec![current];
+    for segment in segments {
+        let mut selected = Vec::new();
+        for node in nodes {
+            segment.select(node, root, &mut selected);
+        }
+        nodes = selected;
+    }
+    nodes
+}
+
+impl Segment {
+    fn select<'a>(&self, node: &'a Value, root: &'a Value, selected: &mut Vec<&'a Value>) {
+        if self.descendant {
+            for selector in &self.selectors {
+                selector.select(node, root, selected);
+            }
+            for child in children(node) {
+                self.select(child, root, selected);
+            }
+        } else {
+            for selector in &self.selectors {
+                selector.select(node, root, selected);
+            }
+        }
+    }
+
+    /// Returns true if the segment selects at most one node.
+    fn is_singular(&self) -> bool {
+        !self.descendant
+            && matches!(
+                self.selectors.as_slice(),
+                [Selector::Name(_) | Selector::Index(_)]
+            )
+    }
+}
+
+fn children(node: &Value) -> Box<dyn Iterator<Item = &Value> + '_> {
+    match node {
+        Value::Array(array) => Box::new(array.iter()),
+        Value::Object(object) => Box::new(object.values()),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+impl Selector {
+    fn select<'a>(&self, node: &'a Value, root: &'a Value, selected: &mut Vec<&'a Value>) {
+        match (self, node) {
+            (Self::Name(name), Value::Object(object)) => {
+                selected.extend(object.get(name.as_str()));
+            }
+            (Self::Wildcard, _) => selected.extend(children(node)),
+            (Self::Index(index), Value::Array(array)) => {
+                selected.extend(normalize(*index, array.len()).and_then(|index| array.get(index)));
+            }
+            (Self::Slice { start, end, step }, Value::Array(array)) => {
+                selected.extend(
+                    slice_indices(*start, *end, *step, array.len()).map(|index| &array[index]),
+                );
+            }
+            (Self::Filter(filter), _) => {
+                selected.extend(children(node).filter(|child| filter.test(child, root)));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Converts an index, which counts from the end if it is negative, to an offset in the array.
+fn normalize(index: i64, len: usize) -> Option<usize> {
+    let offset = usize::try_from(index.unsigned_abs()).ok()?;
+    if index < 0 {
+        len.checked_sub(offset)
+    } else {
+        Some(offset)
+    }
+}
+
+/// Returns the indices of the array that a slice selects, in the order the slice's step goes.
+fn slice_indices(
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+    len: usize,
+) -> impl Iterator<Item = usize> {
+    let len = i64::try_from(len).unwrap_or(i64::MAX);
+    let step = step.unwrap_or(1);
+    let bound = |index: i64| {
+        if index < 0 {
+            len.saturating_add(index)
+        } else {
+            index
+        }
+    };
+
+    let (mut index, last) = if step >= 0 {
+        (
+            start.map_or(0, bound).clamp(0, len),
+            end.map_or(len, bound).clamp(0, len),
+        )
+    } else {
+        (
+            start.map_or(len, bound).clamp(-1, len.saturating_sub(1)),
+            end.map_or(-1, bound).clamp(-1, len.saturating_sub(1)),
+        )
+    };
+
+    std::iter::from_fn(move || {
+        let in_range = match step {
+            0 => false,
+            1.. => index < last,
+            _ => index > last,
+        };
+        let current = usize::try_from(index).ok().filter(|_| in_range)?;
+        index = index.saturating_add(step);
+        Some(current)
+    })
+}
+
+impl Query {
+    fn select<'a>(&self, current: &'a Value, root: &'a Value) -> Vec<&'a Value> {
+        select(
+            &self.segments,
+            if self.relative { current } else { root },
+            root,
+        )
+    }
+
+    fn is_singular(&self) -> bool {
+        self.segments.iter().all(Segment::is_singular)
+    }
+}
+
+impl LogicalExpr {
+    fn test(&self, current: &Value, root: &Value) -> bool {
+        match self {
+            Self::Or(exprs) => exprs.iter().any(|expr| expr.test(current, root)),
+            Self::And(exprs) => exprs.iter().all(|expr| expr.test(current, root)),
+            Self::Not(expr) => !expr.test(current, root),
+            Self::Comparison(left, comparison, right) => {
+                let left = left.evaluate(current, root);
+                let right = right.evaluate(current, root);
+                comparison.test(left.as_deref(), right.as_deref())
+            }
+            Self::Exists(query) => !query.select(current, root).is_empty(),
+            Self::Regex(function) => function.test(current, root),
+        }
+    }
+}
+
+impl Comparison {
+    fn test(self, left: Option<&Value>, right: Option<&Value>) -> bool {
+        let equal = || match (left, right) {
+            (None, None) => true,
+            (Some(left), Some(right)) => json_eq(left, right),
+            _ => false,
+        };
+        let less = |left: Option<&Value>, right: Option<&Value>| match (left, right) {
+            (Some(Value::Bytes(left)), Some(Value::Bytes(right))) => left < right,
+            (Some(left), Some(right)) => compare_numbers(left, right).is_some_and(Ordering::is_lt),
+            _ => false,
+        };
+
+        match self {
+            Self::Eq => equal(),
+            Self::Ne => !equal(),
+            Self::Lt => less(left, right),
+            Self::Le => less(left, right) || equal(),
+            Self::Gt => less(right, left),
+            Self::Ge => less(right, left) || equal(),
+        }
+    }
+}
+
+impl Comparable {
+    fn evaluate<'a>(&'a self, current: &'a Value, root: &'a Value) -> Option<Cow<'a, Value>> {
+        match self {
+            Self::Literal(value) => Some(Cow::Borrowed(value)),
+            Self::Query(query) | Self::Value(query) => match query.select(current, root)[..] {
+                [node] => Some(Cow::Borrowed(node)),
+                _ => None,
+            },
+            Self::Length(value) => {
+                let len = match value.evaluate(current, root)?.as_ref() {
+                    Value::Bytes(bytes) => String::from_utf8_lossy(bytes).chars().count(),
+                    Value::Array(array) => array.len(),
+                    Value::Object(object) => object.len(),
+                    _ => return None,
+                };
+                Some(Cow::Owned(len_value(len)))
+            }
+            Self::Count(query) => Some(Cow::Owned(len_value(query.select(current, root).len()))),
+        }
+    }
+
+    /// Returns true if the comparable is a query that may select more than one node, which
+    /// can't be used where a single value is expected.
+    fn is_non_singular_query(&self) -> bool {
+        matches!(self, Self::Query(query) if !query.is_singular())
+    }
+}
+
+fn len_value(len: usize) -> Value {
+    Value::Integer(i64::try_from(len).unwrap_or(i64::MAX))
+}
+
+impl RegexFunction {
+    fn test(&self, current: &Value, root: &Value) -> bool {
+        let value = self.value.evaluate(current, root);
+        let Some(Value::Bytes(value)) = value.as_deref() else {
+            return false;
+        };
+        let value = String::from_utf8_lossy(value);
+
+        match &self.pattern {
+            Pattern::Literal(regex) => regex.as_ref().is_some_and(|regex| regex.is_match(&value)),
+            Pattern::Dynamic(pattern) => match pattern.evaluate(current, root).as_deref() {
+                Some(Value::Bytes(pattern)) => {
+                    compile_pattern(&String::from_utf8_lossy(pattern), self.full)
+                        .is_some_and(|regex| regex.is_match(&value))
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Compiles an I-Regexp pattern, in which `.` doesn't match line breaks.
+fn compile_pattern(pattern: &str, full: bool) -> Option<Regex> {
+    let mut translated = String::with_capacity(pattern.len());
+    let mut escaped = false;
+    let mut in_class = false;
+    for c in pattern.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '.' if !in_class => {
+                translated.push_str(r"[^\n\r]");
+                continue;
+            }
+            _ => {}
+        }
+        translated.push(c);
+    }
+
+    if full {
+        Regex::new(&format!(r"\A(?:{translated})\z")).ok()
+    } else {
+        Regex::new(&translated).ok()
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            chars: input.char_indices().peekable(),
+        }
+    }
+
+    fn position(&mut self) -> usize {
+        self.chars.peek().map_or(self.input.len(), |(i, _)| *i)
+    }
+
+    fn error(&mut self, message: &str) -> SyntaxError {
+        SyntaxError::new(message, self.position())
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.chars.clone().nth(1).map(|(_, c)| c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.chars.next_if(|(_, next)| *next == c).is_some()
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), SyntaxError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{c}`")))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .chars
+            .next_if(|(_, c)| matches!(c, ' ' | '\t' | '\n' | '\r'))
+            .is_some()
+        {}
+    }
+
+    /// Parses a query starting with `$` or `@`.
+    fn query(&mut self) -> Result<Query, SyntaxError> {
+        let relative = match self.peek() {
+            Some('$') => false,
+            Some('@') => true,
+            _ => return Err(self.error("expected `$` or `@`")),
+        };
+        self.chars.next();
+        Ok(Query {
+            relative,
+            segments: self.segments()?,
+        })
+    }
+
+    fn segments(&mut self) -> Result<Vec<Segment>, SyntaxError> {
+        let mut segments = Vec::new();
+        loop {
+            // Whitespace may separate segments, but isn't part of the query if none follows.
+            let before = self.chars.clone();
+            self.skip_whitespace();
+            let segment = match self.peek() {
+                Some('.') if self.peek_second() == Some('.') => {
+                    self.chars.next();
+                    self.chars.next();
+                    let selectors = if self.peek() == Some('[') {
+                        self.bracketed_selection()?
+                    } else {
+                        vec![self.dot_selector()?]
+                    };
+                    Segment {
+                        descendant: true,
+                        selectors,
+                    }
+                }
+                Some('.') => {
+                    self.chars.next();
+                    Segment {
+                        descendant: false,
+                        selectors: vec![self.dot_selector()?],
+                    }
+                }
+                Some('[') => Segment {
+                    descendant: false,
+                    selectors: self.bracketed_selection()?,
+                },
+                _ => {
+                    self.chars = before;
+                    return Ok(segments);
+                }
+            };
+            segments.push(segment);
+        }
+    }
+
+    /// Parses the wildcard or member name that follows a `.`.
+    fn dot_selector(&mut self) -> Result<Selector, SyntaxError> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let start = self.position();
+        if self.chars.next_if(|(_, c)| is_name_first(*c)).is_none() {
+            return Err(self.error("expected a member name or `*`"));
+        }
+        while self
+            .chars
+            .next_if(|(_, c)| is_name_first(*c) || c.is_ascii_digit())
+            .is_some()
+        {}
+        let end = self.position();
+        Ok(Selector::Name(self.input[start..end].into()))
+    }
+
+    fn bracketed_selection(&mut self) -> Result<Vec<Selector>, SyntaxError> {
+        self.expect('[')?;
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_whitespace();
+            selectors.push(self.selector()?);
+            self.skip_whitespace();
+            if !self.eat(',') {
+                break;
+            }
+        }
+        self.expect(']')?;
+        Ok(selectors)
+    }
+
+    fn selector(&mut self) -> Result<Selector, SyntaxError> {
+        match self.peek() {
+            Some(quote @ ('\'' | '"')) => Ok(Selector::Name(self.string(quote)?.into())),
+            Some('*') => {
+                self.chars.next();
+                Ok(Selector::Wildcard)
+            }
+            Some('?') => {
+                self.chars.next();
+                self.skip_whitespace();
+                Ok(Selector::Filter(self.logical_or()?))
+            }
+            Some(':' | '-' | '0'..='9') => {
+                let start = self.optional_integer()?;
+                self.skip_whitespace();
+                if !self.eat(':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("expected an index"));
+                }
+                self.skip_whitespace();
+                let end = self.optional_integer()?;
+                self.skip_whitespace();
+                let step = if self.eat(':') {
+                    self.skip_whitespace();
+                    self.optional_integer()?
+                } else {
+                    None
+                };
+                Ok(Selector::Slice { start, end, step })
+            }
+            _ => Err(self.error("expected a selector")),
+        }
+    }
+
+    fn optional_integer(&mut self) -> Result<Option<i64>, SyntaxError> {
+        match self.peek() {
+            Some('-' | '0'..='9') => self.integer().map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn integer(&mut self) -> Result<i64, SyntaxError> {
+        let start = self.position();
+        let negative = self.eat('-');
+        let digits = self.position();
+        while self.chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+        let end = self.position();
+
+        let text = &self.input[digits..end];
+        let valid = match text.as_bytes() {
+            [b'0'] => !negative,
+            [] | [b'0', ..] => false,
+            _ => true,
+        };
+        self.input[start..end]
+            .parse::<i64>()
+            .ok()
+            .filter(|i| valid && (-MAX_INTEGER..=MAX_INTEGER).contains(i))
+            .ok_or_else(|| SyntaxError::new("invalid integer", start))
+    }
+
+    /// Parses a string literal in single or double quotes.
+    fn string(&mut self, quote: char) -> Result<String, SyntaxError> {
+        self.expect(quote)?;
+        let mut string = String::new();
+        loop {
+            let position = self.position();
+            match self.chars.next() {
+                None => return Err(self.error("unterminated string")),
+                Some((_, c)) if c == quote => return Ok(string),
+                Some((_, '\\')) => {
+                    let c = match self.chars.next().map(|(_, c)| c) {
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some(c @ ('/' | '\\')) => c,
+                        Some(c) if c == quote => c,
+                        Some('u') => self.unicode_escape(position)?,
+                        _ => return Err(SyntaxError::new("invalid escape", position)),
+                    };
+                    string.push(c);
+                }
+                Some((_, c)) if c < '\u{20}' => {
+                    return Err(SyntaxError::new("unescaped control character", position));
+                }
+                Some((_, c)) => string.push(c),
+            }
+        }
+    }
+
+    /// Parses the hex digits of a `\u` escape, and the low surrogate that follows a high one.
+    fn unicode_escape(&mut self, position: usize) -> Result<char, SyntaxError> {
+        let error = || SyntaxError::new("invalid unicode escape", position);
+        let mut units = vec![self.hex_unit().ok_or_else(error)?];
+        if (0xD800..0xDC00).contains(&units[0]) {
+            if !(self.eat('\\') && self.eat('u')) {
+                return Err(error());
+            }
+            units.push(self.hex_unit().ok_or_else(error)?);
+        }
+        match char::decode_utf16(units).collect::<Vec<_>>()[..] {
+            [Ok(c)] => Ok(c),
+            _ => Err(error()),
+        }
+    }
+
+    fn hex_unit(&mut self) -> Option<u16> {
+        let hex = (0..4)
+            .map(|_| self.chars.next_if(|(_, c)| c.is_ascii_hexdigit()))
+            .map(|next| next.map(|(_, c)| c))
+            .collect::<Option<String>>()?;
+        u16::from_str_radix(&hex, 16).ok()
+    }
+
+    fn logical_or(&mut self) -> Result<LogicalExpr, SyntaxError> {
+        let mut exprs = vec![self.logical_and()?];
+        loop {
+            let before = self.chars.clone();
+            self.skip_whitespace();
+            if !(self.eat('|') && self.eat('|')) {
+                self.chars = before;
+                break;
+            }
+            self.skip_whitespace();
+            exprs.push(self.logical_and()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            LogicalExpr::Or(exprs)
+        })
+    }
+
+    fn logical_and(&mut self) -> Result<LogicalExpr, SyntaxError> {
+        let mut exprs = vec![self.basic_expr()?];
+        loop {
+            let before = self.chars.clone();
+            self.skip_whitespace();
+            if !(self.eat('&') && self.eat('&')) {
+                self.chars = before;
+                break;
+            }
+            self.skip_whitespace();
+            exprs.push(self.basic_expr()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            LogicalExpr::And(exprs)
+        })
+    }
+
+    fn basic_expr(&mut self) -> Result<LogicalExpr, SyntaxError> {
+        if self.eat('!') {
+            self.skip_whitespace();
+            let expr = if self.peek() == Some('(') {
+                self.paren_expr()?
+            } else {
+                let position = self.position();
+                match self.argument()? {
+                    Argument::Query(query) => LogicalExpr::Exists(query),
+                    Argument::Logical(expr @ LogicalExpr::Regex(_)) => expr,
+                    _ => return Err(SyntaxError::new("expected a test expression", position)),
+                }
+            };
+            return Ok(LogicalExpr::Not(Box::new(expr)));
+        }
+        if self.peek() == Some('(') {
+            return self.paren_expr();
+        }
+
+        let position = self.position();
+        let left = self.argument()?;
+        let before = self.chars.clone();
+        self.skip_whitespace();
+        let Some(comparison) = self.comparison() else {
+            self.chars = before;
+            return match left {
+                Argument::Query(query) => Ok(LogicalExpr::Exists(query)),
+                Argument::Logical(expr) => Ok(expr),
+                _ => Err(SyntaxError::new("expected a test expression", position)),
+            };
+        };
+        let left = comparable(left, position)?;
+        self.skip_whitespace();
+        let position = self.position();
+        let right = self.argument()?;
+        let right = comparable(right, position)?;
+        Ok(LogicalExpr::Comparison(left, comparison, right))
+    }
+
+    fn paren_expr(&mut self) -> Result<LogicalExpr, SyntaxError> {
+        self.expect('(')?;
+        self.skip_whitespace();
+        let expr = self.logical_or()?;
+        self.skip_whitespace();
+        self.expect(')')?;
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Option<Comparison> {
+        let comparison = match (self.peek()?, self.peek_second()) {
+            ('=', Some('=')) => Comparison::Eq,
+            ('!', Some('=')) => Comparison::Ne,
+            ('<', Some('=')) => Comparison::Le,
+            ('>', Some('=')) => Comparison::Ge,
+            ('<', _) => Comparison::Lt,
+            ('>', _) => Comparison::Gt,
+            _ => return None,
+        };
+        self.chars.next();
+        if matches!(
+            comparison,
+            Comparison::Eq | Comparison::Ne | Comparison::Le | Comparison::Ge
+        ) {
+            self.chars.next();
+        }
+        Some(comparison)
+    }
+
+    /// Parses a literal, query or function call, which are the operands of comparisons and
+    /// the arguments of functions.
+    fn argument(&mut self) -> Result<Argument, SyntaxError> {
+        match self.peek() {
+            Some('$' | '@') => return Ok(Argument::Query(self.query()?)),
+            Some(quote @ ('\'' | '"')) => {
+                return Ok(Argument::Literal(self.string(quote)?.into()));
+            }
+            Some('-' | '0'..='9') => return Ok(Argument::Literal(self.number()?)),
+            _ => {}
+        }
+
+        let position = self.position();
+        while self
+            .chars
+            .next_if(|(_, c)| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '_')
+            .is_some()
+        {}
+        let end = self.position();
+        let name = &self.input[position..end];
+        if self.peek() == Some('(') {
+            return self.function(name, position);
+        }
+        match name {
+            "true" => Ok(Argument::Literal(true.into())),
+            "false" => Ok(Argument::Literal(false.into())),
+            "null" => Ok(Argument::Literal(Value::Null)),
+            _ => Err(SyntaxError::new("expected an expression", position)),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, SyntaxError> {
+        let start = self.position();
+        self.eat('-');
+        let digits = self.position();
+        while self.chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+        let integer_end = self.position();
+        if self.eat('.') {
+            if self.chars.next_if(|(_, c)| c.is_ascii_digit()).is_none() {
+                return Err(self.error("expected a digit"));
+            }
+            while self.chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+        }
+        if self
+            .chars
+            .next_if(|(_, c)| matches!(c, 'e' | 'E'))
+            .is_some()
+        {
+            self.chars.next_if(|(_, c)| matches!(c, '+' | '-'));
+            if self.chars.next_if(|(_, c)| c.is_ascii_digit()).is_none() {
+                return Err(self.error("expected a digit"));
+            }
+            while self.chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {}
+        }
+        let end = self.position();
+
+        let integer = &self.input[digits..integer_end];
+        if integer.is_empty() || (integer.len() > 1 && integer.starts_with('0')) {
+            return Err(SyntaxError::new("invalid number", start));
+        }
+        let text = &self.input[start..end];
+        if let Ok(integer) = text.parse::<i64>() {
+            return Ok(integer.into());
+        }
+        text.parse::<f64>()
+            .ok()
+            .and_then(|float| NotNan::new(float).ok())
+            .map(Value::Float)
+            .ok_or_else(|| SyntaxError::new("invalid number", start))
+    }
+
+    fn function(&mut self, name: &str, position: usize) -> Result<Argument, SyntaxError> {
+        self.expect('(')?;
+        self.skip_whitespace();
+        let mut arguments = Vec::new();
+        if !self.eat(')') {
+            loop {
+                let position = self.position();
+                arguments.push((self.function_argument()?, position));
+                self.skip_whitespace();
+                if self.eat(')') {
+                    break;
+                }
+                self.expect(',')?;
+                self.skip_whitespace();
+            }
+        }
+
+        let arity_error =
+            || SyntaxError::new(format!("wrong number of arguments to `{name}`"), position);
+        let mut arguments = arguments.into_iter();
+        let mut next = || arguments.next().ok_or_else(arity_error);
+        let function = match name {
+            "length" => {
+                let (argument, position) = next()?;
+                Argument::Value(Comparable::Length(Box::new(comparable(
+                    argument, position,
+                )?)))
+            }
+            "count" | "value" => {
+                let (argument, position) = next()?;
+                let Argument::Query(query) = argument else {
+                    return Err(SyntaxError::new("expected a query", position));
+                };
+                Argument::Value(if name == "count" {
+                    Comparable::Count(query)
+                } else {
+                    Comparable::Value(query)
+                })
+            }
+            "match" | "search" => {
+                let full = name == "match";
+                let (value, value_position) = next()?;
+                let (pattern, pattern_position) = next()?;
+                let pattern = match pattern {
+                    Argument::Literal(Value::Bytes(pattern)) => {
+                        Pattern::Literal(compile_pattern(&String::from_utf8_lossy(&pattern), full))
+                    }
+                    pattern => Pattern::Dynamic(Box::new(comparable(pattern, pattern_position)?)),
+                };
+                Argument::Logical(LogicalExpr::Regex(RegexFunction {
+                    full,
+                    value: Box::new(comparable(value, value_position)?),
+                    pattern,
+                }))
+            }
+            _ => {
+                return Err(SyntaxError::new(
+                    format!("unknown function `{name}`"),
+                    position,
+                ));
+            }
+        };
+        if next().is_ok() {
+            return Err(arity_error());
+        }
+        Ok(function)
+    }
+
+    /// Parses a function argument, which may also be a logical expression.
+    fn function_argument(&mut self) -> Result<Argument, SyntaxError> {
+        let before = self.chars.clone();
+        if let Ok(argument) = self.argument() {
+            let after = self.chars.clone();
+            self.skip_whitespace();
+            if matches!(self.peek(), Some(',' | ')')) {
+                self.chars = after;
+                return Ok(argument);
+            }
+        }
+        self.chars = before;
+        Ok(Argument::Logical(self.logical_or()?))
+    }
+}
+
+/// Checks that an argument produces a single value, as comparisons and most function
+/// parameters need.
+fn comparable(argument: Argument, position: usize) -> Result<Comparable, SyntaxError> {
+    let comparable = match argument {
+        Argument::Literal(value) => Comparable::Literal(value),
+        Argument::Query(query) => Comparable::Query(query),
+        Argument::Value(comparable) => comparable,
+        Argument::Logical(_) => {
+            return Err(SyntaxError::new("expected a value, not a test", position));
+        }
+    };
+    if comparable.is_non_singular_query() {
+        return Err(SyntaxError::new(
+            "expected a query that selects at most one node",
+            position,
+        ));
+    }
+    Ok(comparable)
+}
+
+fn is_name_first(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stdlib::json_query::tests::fixtures;
+
+    #[test]
+    fn conformance() {
+        for (file, given, case) in fixtures("jsonpath") {
+            let expression = case.get("expression").unwrap().as_str().unwrap();
+            let path = JsonPath::parse(&expression);
+            match case.get("result") {
+                Some(Value::Array(expected)) => {
+                    let path = path.unwrap_or_else(|e| panic!("{file}: {expression}: {e}"));
+                    let actual = path.query(&given).into_iter().cloned().collect::<Vec<_>>();
+                    assert!(
+                        json_eq(
+                            &Value::Array(actual.clone()),
+                            &Value::Array(expected.clone())
+                        ),
+                        "{file}: {expression}: expected {expected:?}, got {actual:?}"
+                    );
+                }
+                _ => assert!(path.is_err(), "{file}: {expression}: expected an error"),
+            }
+        }
+    }
+
+    #[test]
+    fn syntax_error_position() {
+        assert_eq!(
+            JsonPath::parse("$.a[?@.b == ]").unwrap_err().to_string(),
+            "expected an expression at position 12"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! The `JSONPath` and `JMESPath` query languages, evaluated directly against `Value` using JSON's
+//! data model, in which integers, floats and decimals are all numbers.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::value::Value;
+
+pub(crate) mod jmespath;
+pub(crate) mod jsonpath;
+
+/// An error in the syntax of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SyntaxError {
+    message: String,
+    position: usize,
+}
+
+impl SyntaxError {
+    pub(crate) fn new(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+/// Returns true if the value is a JSON number.
+pub(crate) fn is_number(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Integer(_) | Value::Float(_) | Value::Decimal(_)
+    )
+}
+
+/// Converts a number to a float, or returns `None` if the value isn't a number.
+#[allow(clippy::cast_precision_loss)] // JSON numbers are doubles
+pub(crate) fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Float(f) => Some(f.into_inner()),
+        Value::Decimal(d) => d.to_f64(),
+        _ => None,
+    }
+}
+
+/// Compares two numbers, or returns `None` if either of the values isn't a number.
+pub(crate) fn compare_numbers(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+        (Value::Decimal(a), Value::Decimal(b)) => Some(a.cmp(b)),
+        (Value::Integer(a), Value::Decimal(b)) => Some(Decimal::from(*a).cmp(b)),
+        (Value::Decimal(a), Value::Integer(b)) => Some(a.cmp(&Decimal::from(*b))),
+        _ => as_f64(a)?.partial_cmp(&as_f64(b)?),
+    }
+}
+
+/// Compares two values for equality the way JSON does, so that `1` equals `1.0`.
+pub(crate) fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key.as_str()).is_some_and(|b| json_eq(a, b)))
+        }
+        _ => compare_numbers(a, b).map_or_else(|| a == b, Ordering::is_eq),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::value::Value;
+
+    /// Reads the conformance fixtures in the directory, each of which is an array of suites in
+    /// the format of the `JMESPath` compliance tests: a `given` document and the `cases` to
+    /// evaluate against it, each with an `expression` and either the expected `result` or the
+    /// expected `error`.
+    pub(crate) fn fixtures(directory: &str) -> Vec<(String, Value, Value)> {
+        let mut fixtures = Vec::new();
+        let mut paths = std::fs::read_dir(Path::new("tests/data").join(directory))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        for path in paths {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let suites: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+            for suite in suites.as_array().unwrap() {
+                let given = suite.get("given").unwrap().clone();
+                for case in suite.get("cases").unwrap().as_array().unwrap() {
+                    fixtures.push((name.clone(), given.clone(), case.clone()));
+                }
+            }
+        }
+        fixtures
+    }
+}
@@ -200,15 +200,27 @@ fn recursive_type_def(from: &mut Kind, to: Kind, root: bool) {
         for v in object.known_mut().values_mut() {
             recursive_type_def(v, to.clone(), false);
         }
+
+        map_unknown_type_def(object, &to);
     }
 
     if let Some(array) = from.as_array_mut() {
         for v in array.known_mut().values_mut() {
             recursive_type_def(v, to.clone(), false);
         }
+
+        map_unknown_type_def(array, &to);
     }
 
     if !root {
         *from = to;
     }
 }
+
+/// Any element not known at compile-time is also mapped by the closure, so
+/// the unknown kind of the collection becomes the closure's return kind.
+fn map_unknown_type_def<T: Ord + Clone>(collection: &mut Collection<T>, to: &Kind) {
+    if collection.unknown_kind().contains_any_defined() {
+        collection.set_unknown(to.clone());
+    }
+}
@@ -0,0 +1,247 @@
+use crate::compiler::prelude::*;
+use crate::datadog_filter::{MatcherSet, build_matcher_set};
+use crate::datadog_search_syntax::{ParseError, QueryNode};
+use crate::prelude::function::Error::InvalidArgument;
+
+use super::match_datadog_query::VrlFilter;
+
+const QUERIES_KEYWORD: &str = "queries";
+
+#[derive(Clone, Copy, Debug)]
+pub struct MatchDatadogQueries;
+
+impl Function for MatchDatadogQueries {
+    fn identifier(&self) -> &'static str {
+        "match_datadog_queries"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Matches an object against a set of named [Datadog Search Syntax](https://docs.datadoghq.com/logs/explorer/search_syntax/)
+            queries, returning the names of the queries which match.
+
+            The queries are compiled together, so conditions shared between them (such as the same
+            attribute matching the same value) are only evaluated once. This is faster than
+            calling `match_datadog_query` once for each query.
+
+            The names are returned in the order the queries are given. Object keys are ordered by
+            name, so give the queries as an array of `[name, query]` pairs to choose the order.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Object.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ARRAY
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Route by query",
+                source: r#"match_datadog_queries({"service": "web", "status": "error"}, {"errors": "status:error", "web": "service:web", "warnings": "status:warn"})"#,
+                result: Ok(r#"["errors", "web"]"#),
+            },
+            example! {
+                title: "Shared conditions",
+                source: r#"match_datadog_queries({"name": "foobar", "count": 4}, {"a": "@name:foo* AND @count:>2", "b": "@name:foo* AND NOT @count:>2"})"#,
+                result: Ok(r#"["a"]"#),
+            },
+            example! {
+                title: "Queries in order",
+                source: r#"match_datadog_queries({"service": "web", "status": "error"}, [["web", "service:web"], ["errors", "status:error"]])"#,
+                result: Ok(r#"["web", "errors"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let queries_value = arguments.required_literal(QUERIES_KEYWORD, state)?;
+
+        let invalid_queries = |error| {
+            Box::new(InvalidArgument {
+                keyword: QUERIES_KEYWORD,
+                value: queries_value.clone(),
+                error,
+            }) as Box<dyn DiagnosticMessage>
+        };
+
+        // Queries are given as an object keyed by name, or as `[name, query]` pairs to keep
+        // the names in order.
+        let named_queries = match &queries_value {
+            Value::Object(queries) => queries
+                .iter()
+                .map(|(name, query)| (Value::from(name.as_str()), query))
+                .collect(),
+            Value::Array(pairs) => pairs
+                .iter()
+                .map(|pair| match pair.as_array() {
+                    Some([name, query]) => Ok((name.clone(), query)),
+                    _ => Err(invalid_queries("expected a [name, query] pair")),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => return Err(invalid_queries("expected an object or array of queries")),
+        };
+
+        let queries = named_queries
+            .into_iter()
+            .map(|(name, query)| {
+                let name = name
+                    .try_bytes_utf8_lossy()
+                    .map_err(|_| invalid_queries("query name should be a string"))?
+                    .into_owned();
+                let query = query
+                    .try_bytes_utf8_lossy()
+                    .map_err(|_| invalid_queries("datadog search query should be a string"))?;
+
+                // Compile each Datadog search query to AST.
+                let node: QueryNode = query.parse().map_err(|e: ParseError| {
+                    Box::new(ExpressionError::from(format!("query {name}: {e}")))
+                        as Box<dyn DiagnosticMessage>
+                })?;
+
+                Ok((name, node))
+            })
+            .collect::<Result<Vec<_>, Box<dyn DiagnosticMessage>>>()?;
+
+        let matchers = build_matcher_set(queries, &VrlFilter)
+            .map_err(|_| invalid_queries("failed to build matcher"))?;
+
+        Ok(MatchDatadogQueriesFn { value, matchers }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[
+            Parameter::required("value", kind::OBJECT, "The object."),
+            Parameter::required(
+                "queries",
+                kind::OBJECT | kind::ARRAY,
+                "The Datadog Search Syntax queries, keyed by name or as `[name, query]` pairs.",
+            ),
+        ];
+        PARAMETERS
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MatchDatadogQueriesFn {
+    value: Box<dyn Expression>,
+    matchers: MatcherSet<Value>,
+}
+
+impl FunctionExpression for MatchDatadogQueriesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(self
+            .matchers
+            .matches(&value)
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def()
+    }
+}
+
+fn type_def() -> TypeDef {
+    TypeDef::array(Collection::from_unknown(Kind::bytes())).infallible()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::value;
+
+    use super::*;
+
+    test_function![
+        match_datadog_queries => MatchDatadogQueries;
+
+        none_match {
+            args: func_args![value: value!({"message": "test message"}), queries: value!({"a": "_exists_:@a", "b": "-_exists_:message"})],
+            want: Ok(value!([])),
+            tdef: type_def(),
+        }
+
+        names_in_order {
+            args: func_args![value: value!({"message": "test message", "tags": ["env:prod"]}), queries: value!([["c", "env:prod"], ["a", "test"], ["b", "env:dev"]])],
+            want: Ok(value!(["c", "a"])),
+            tdef: type_def(),
+        }
+
+        object_names_in_key_order {
+            args: func_args![value: value!({"message": "test message", "tags": ["env:prod"]}), queries: value!({"c": "env:prod", "a": "test", "b": "env:dev"})],
+            want: Ok(value!(["a", "c"])),
+            tdef: type_def(),
+        }
+
+        indexed_terms {
+            args: func_args![value: value!({"status": "warn", "tags": ["env:prod", "team:a"]}), queries: value!([["error", "@status:error"], ["warn", "@status:warn OR @status:error"], ["prod", "env:prod"], ["team", "team:b"], ["tag", "tags:team\\:a"]])],
+            want: Ok(value!(["warn", "prod", "tag"])),
+            tdef: type_def(),
+        }
+
+        invalid_pair {
+            args: func_args![value: value!({}), queries: value!([["a"]])],
+            want: Err("invalid argument"),
+            tdef: type_def(),
+        }
+
+        shared_conditions {
+            args: func_args![value: value!({"a": 1, "b": "x"}), queries: value!({"both": "@a:1 AND @b:x", "either": "@a:2 OR @b:x", "not": "-@a:1"})],
+            want: Ok(value!(["both", "either"])),
+            tdef: type_def(),
+        }
+
+        match_all {
+            args: func_args![value: value!({}), queries: value!({"all": "*", "none": "-*:*"})],
+            want: Ok(value!(["all"])),
+            tdef: type_def(),
+        }
+    ];
+
+    #[test]
+    fn shares_conditions() {
+        let queries = [
+            ("a", "@a:1 AND @b:x"),
+            ("b", "@a:1 OR @b:y"),
+            ("c", "-@b:x AND (@a:1 OR service:web)"),
+        ]
+        .map(|(name, query)| (name, query.parse::<QueryNode>().unwrap()));
+        let matchers = build_matcher_set(queries, &VrlFilter).unwrap();
+
+        assert_eq!(matchers.conditions(), 4);
+        assert_eq!(matchers.indexes(), 3);
+        assert_eq!(
+            matchers.matches(&value!({"a": 1, "b": "y", "service": "web"})),
+            ["b", "c"]
+        );
+    }
+
+    #[test]
+    fn indexes_terms_by_field() {
+        let queries = (0..100)
+            .rev()
+            .map(|i| (format!("q{i}"), format!("@id:{i} OR env:prod{i}")))
+            .map(|(name, query)| (name, query.parse::<QueryNode>().unwrap()));
+        let matchers = build_matcher_set(queries, &VrlFilter).unwrap();
+
+        assert_eq!(matchers.conditions(), 200);
+        assert_eq!(matchers.indexes(), 2);
+        assert_eq!(
+            matchers.matches(&value!({"id": 7, "tags": ["env:prod42", "env:staging"]})),
+            ["q42", "q7"]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::compiler::prelude::*;
 use crate::datadog_filter::{
-    Filter, Matcher, Resolver, Run, build_matcher,
+    ExactValues, Filter, Matcher, Resolver, Run, build_matcher,
     regex::{wildcard_regex, word_regex},
 };
 use crate::datadog_search_syntax::{Comparison, ComparisonValue, Field, ParseError, QueryNode};
@@ -123,7 +123,7 @@ fn type_def() -> TypeDef {
 }
 
 #[derive(Default, Clone)]
-struct VrlFilter;
+pub(crate) struct VrlFilter;
 
 /// Implements `Resolver`, which translates Datadog Search Syntax literal names into
 /// fields.
@@ -224,6 +224,31 @@ impl Filter<Value> for VrlFilter {
         })
     }
 
+    fn exact_values(
+        &self,
+        field: &Field,
+    ) -> Result<Option<Box<dyn ExactValues<Value>>>, PathParseError> {
+        let buf = lookup_field(field)?;
+
+        Ok(match field {
+            // Default fields are compared by word boundary.
+            Field::Default(_) => None,
+            // A literal "tags" field is compared by element, and individual tags by element value.
+            Field::Reserved(f) if f == "tags" => Some(Box::new(FieldValues {
+                buf,
+                tag_prefix: Some(String::new()),
+            })),
+            Field::Tag(tag) => Some(Box::new(FieldValues {
+                buf,
+                tag_prefix: Some(format!("{tag}:")),
+            })),
+            _ => Some(Box::new(FieldValues {
+                buf,
+                tag_prefix: None,
+            })),
+        })
+    }
+
     fn prefix(
         &self,
         field: Field,
@@ -420,6 +445,38 @@ impl Filter<Value> for VrlFilter {
     }
 }
 
+/// The values of a field which `VrlFilter::equals` compares exactly.
+#[derive(Clone, Debug)]
+struct FieldValues {
+    buf: OwnedValuePath,
+    /// For tags, the prefix of the elements to strip to find their values.
+    tag_prefix: Option<String>,
+}
+
+impl ExactValues<Value> for FieldValues {
+    fn for_each(&self, value: &Value, f: &mut dyn FnMut(&str)) {
+        let Some(value) = value.get(&self.buf) else {
+            return;
+        };
+
+        match (&self.tag_prefix, value) {
+            (Some(prefix), Value::Array(tags)) => {
+                for tag in tags {
+                    if let Value::Bytes(tag) = tag
+                        && let Some(value) = std::str::from_utf8(tag)
+                            .ok()
+                            .and_then(|tag| tag.strip_prefix(prefix.as_str()))
+                    {
+                        f(value);
+                    }
+                }
+            }
+            (Some(_), _) => (),
+            (None, value) => f(&string_value(value)),
+        }
+    }
+}
+
 fn resolve_value(
     buf: OwnedValuePath,
     match_fn: Box<dyn Matcher<Value>>,
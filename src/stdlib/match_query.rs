@@ -0,0 +1,550 @@
+use crate::compiler::function::EnumVariant;
+use crate::compiler::prelude::*;
+use crate::datadog_filter::{
+    Filter, Matcher, Resolver, Run, build_matcher,
+    regex::{wildcard_regex, word_regex},
+};
+use crate::datadog_search_syntax::{
+    Comparison, ComparisonValue, DEFAULT_FIELD, Dialect, Field, QueryNode,
+};
+use crate::path::{OwnedValuePath, PathParseError, parse_value_path};
+use crate::prelude::function::Error::InvalidArgument;
+use crate::value;
+use regex::Regex;
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+const QUERY_KEYWORD: &str = "query";
+
+static DEFAULT_DIALECT: LazyLock<Value> = LazyLock::new(|| Value::Bytes(Bytes::from("lucene")));
+
+static DIALECT_ENUM: &[EnumVariant] = &[
+    EnumVariant {
+        value: "lucene",
+        description: "Lucene query syntax. Terms without an operator between them are combined with `OR`.",
+    },
+    EnumVariant {
+        value: "kql",
+        description: "Kibana Query Language.",
+    },
+];
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required("value", kind::OBJECT, "The object."),
+        Parameter::required("query", kind::BYTES, "The query."),
+        Parameter::optional("dialect", kind::BYTES, "The query language of `query`.")
+            .default(&DEFAULT_DIALECT)
+            .enum_variants(DIALECT_ENUM),
+    ]
+});
+
+fn dialects() -> Vec<Value> {
+    vec![value!("lucene"), value!("kql")]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MatchQuery;
+
+impl Function for MatchQuery {
+    fn identifier(&self) -> &'static str {
+        "match_query"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Matches an object against a [Lucene](https://lucene.apache.org/core/2_9_4/queryparsersyntax.html)
+            or [Kibana Query Language](https://www.elastic.co/guide/en/kibana/current/kuery-query.html) query.
+
+            Unlike `match_datadog_query`, fields are paths into the object. Queries without a field
+            match words in any value of the object. Array values match if any of their elements
+            match. Boosts are ignored.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Object.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::BOOLEAN
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Lucene query",
+                source: r#"match_query({"user": {"name": "jane"}, "status": 404}, "user.name:jane AND status:[400 TO 499]")"#,
+                result: Ok("true"),
+            },
+            example! {
+                title: "Fuzzy and regular expression terms",
+                source: r#"match_query({"user": "jnae", "host": "web-01"}, "user:jane~ AND host:/web-[0-9]+/")"#,
+                result: Ok("true"),
+            },
+            example! {
+                title: "KQL query",
+                source: r#"match_query({"method": "GET", "message": "request failed"}, "method:(GET or HEAD) and not request succeeded", dialect: "kql")"#,
+                result: Ok("true"),
+            },
+            example! {
+                title: "KQL nested query",
+                source: r#"match_query({"items": [{"name": "a", "count": 1}, {"name": "b", "count": 5}]}, "items:{ name:b and count >= 5 }", dialect: "kql")"#,
+                result: Ok("true"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let query_value = arguments.required_literal(QUERY_KEYWORD, state)?;
+        let dialect = match arguments
+            .optional_enum("dialect", &dialects(), state)?
+            .unwrap_or_else(|| DEFAULT_DIALECT.clone())
+            .try_bytes()
+            .expect("dialect not bytes")
+            .as_ref()
+        {
+            b"lucene" => Dialect::Lucene,
+            b"kql" => Dialect::Kql,
+            _ => unreachable!("enum invariant"),
+        };
+
+        // Query should always be a string.
+        let query = query_value
+            .try_bytes_utf8_lossy()
+            .expect("query should be a UTF8 string");
+
+        // Compile the query to AST.
+        let node = crate::datadog_search_syntax::parse(&query, dialect).map_err(|e| {
+            Box::new(ExpressionError::from(e.to_string())) as Box<dyn DiagnosticMessage>
+        })?;
+
+        // Regular expressions are validated by the parser, but may still fail to compile once
+        // anchored to match a whole value.
+        validate_anchored_regexes(&node).map_err(|e| {
+            Box::new(ExpressionError::from(e.to_string())) as Box<dyn DiagnosticMessage>
+        })?;
+
+        // Build the matcher function that accepts a VRL value, once, at boot-time.
+        let filter = build_matcher(&node, &QueryFilter).map_err(|_| {
+            Box::new(InvalidArgument {
+                keyword: QUERY_KEYWORD,
+                value: query_value,
+                error: "failed to build matcher",
+            }) as Box<dyn DiagnosticMessage>
+        })?;
+
+        Ok(MatchQueryFn { value, filter }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MatchQueryFn {
+    value: Box<dyn Expression>,
+    filter: Box<dyn Matcher<Value>>,
+}
+
+impl FunctionExpression for MatchQueryFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(self.filter.run(&value).into())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def()
+    }
+}
+
+fn type_def() -> TypeDef {
+    TypeDef::boolean().infallible()
+}
+
+/// A `Filter` for generic Lucene and KQL queries. Every field is a path into the value, and
+/// the default field searches the words of all the values within it.
+#[derive(Default, Clone)]
+struct QueryFilter;
+
+impl Resolver for QueryFilter {
+    fn build_fields(&self, attr: &str) -> Vec<Field> {
+        if attr == DEFAULT_FIELD {
+            vec![Field::Default(attr.to_owned())]
+        } else {
+            vec![Field::Attribute(attr.to_owned())]
+        }
+    }
+}
+
+impl Filter<Value> for QueryFilter {
+    fn exists(&self, field: Field) -> Result<Box<dyn Matcher<Value>>, PathParseError> {
+        match_any(&field, |value| !value.is_null())
+    }
+
+    fn equals(
+        &self,
+        field: Field,
+        to_match: &str,
+    ) -> Result<Box<dyn Matcher<Value>>, PathParseError> {
+        // The default field is compared by word boundary.
+        if let Field::Default(_) = field {
+            let re = word_regex(to_match);
+            match_any(&field, move |value| re.is_match(&string_value(value)))
+        } else {
+            let to_match = to_match.to_owned();
+            match_any(&field, move |value| string_value(value) == to_match)
+        }
+    }
+
+    fn prefix(
+        &self,
+        field: Field,
+        prefix: &str,
+    ) -> Result<Box<dyn Matcher<Value>>, PathParseError> {
+        if let Field::Default(_) = field {
+            let re = word_regex(&format!("{prefix}*"));
+            match_any(&field, move |value| re.is_match(&string_value(value)))
+        } else {
+            let prefix = prefix.to_owned();
+            match_any(&field, move |value| {
+                string_value(value).starts_with(&prefix)
+            })
+        }
+    }
+
+    fn wildcard(
+        &self,
+        field: Field,
+        wildcard: &str,
+    ) -> Result<Box<dyn Matcher<Value>>, PathParseError> {
+        let re = if let Field::Default(_) = field {
+            word_regex(wildcard)
+        } else {
+            wildcard_regex(wildcard)
+        };
+        match_any(&field, move |value| re.is_match(&string_value(value)))
+    }
+
+    fn fuzzy(
+        &self,
+        field: Field,
+        value: &str,
+        distance: u8,
+    ) -> Result<Box<dyn Matcher<Value>>, PathParseError> {
+        let to_match = value.to_owned();
+        let distance = usize::from(distance);
+
+        // The default field is compared word by word.
+        if let Field::Default(_) = field {
+            match_any(&field, move |value| {
+                words(&string_value(value)).any(|word| edit_distance(word, &to_match) <= distance)
+            })
+        } else {
+            match_any(&field, move |value| {
+                edit_distance(&string_value(value), &to_match) <= distance
+            })
+        }
+    }
+
+    fn regex(&self, field: Field, regex: &str) -> Result<Box<dyn Matcher<Value>>, PathParseError> {
+        // Regular expressions must match a whole value (or word, for the default field).
+        let re = anchored_regex(regex).expect("regex validated at compile time");
+
+        if let Field::Default(_) = field {
+            match_any(&field, move |value| {
+                words(&string_value(value)).any(|word| re.is_match(word))
+            })
+        } else {
+            match_any(&field, move |value| re.is_match(&string_value(value)))
+        }
+    }
+
+    fn nested(
+        &self,
+        field: Field,
+        matcher: Box<dyn Matcher<Value>>,
+    ) -> Result<Box<dyn Matcher<Value>>, PathParseError> {
+        // There are no objects to nest within the words of all values.
+        if let Field::Default(_) = field {
+            Ok(Box::new(false))
+        } else {
+            match_any(&field, move |value| value.is_object() && matcher.run(value))
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn compare(
+        &self,
+        field: Field,
+        comparator: Comparison,
+        comparison_value: ComparisonValue,
+    ) -> Result<Box<dyn Matcher<Value>>, PathParseError> {
+        match_any(&field, move |value| {
+            let ordering = match (value, &comparison_value) {
+                (Value::Integer(lhs), ComparisonValue::Integer(rhs)) => lhs.partial_cmp(rhs),
+                (Value::Integer(lhs), ComparisonValue::Float(rhs)) => {
+                    (*lhs as f64).partial_cmp(rhs)
+                }
+                (Value::Float(lhs), ComparisonValue::Float(rhs)) => {
+                    lhs.into_inner().partial_cmp(rhs)
+                }
+                (Value::Float(lhs), ComparisonValue::Integer(rhs)) => {
+                    lhs.into_inner().partial_cmp(&(*rhs as f64))
+                }
+                // Numeric bounds never match values which aren't numeric.
+                (_, ComparisonValue::Integer(_) | ComparisonValue::Float(_)) => None,
+                // Otherwise, compare as strings.
+                _ => Some(
+                    string_value(value)
+                        .as_ref()
+                        .cmp(&comparison_value.to_string()),
+                ),
+            };
+
+            ordering.is_some_and(|ordering| match comparator {
+                Comparison::Lt => ordering.is_lt(),
+                Comparison::Lte => ordering.is_le(),
+                Comparison::Gt => ordering.is_gt(),
+                Comparison::Gte => ordering.is_ge(),
+            })
+        })
+    }
+}
+
+/// Compiles `regex` so that it only matches a whole string.
+fn anchored_regex(regex: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{regex})$"))
+}
+
+/// Checks that every regular expression within `node` compiles with [`anchored_regex`].
+fn validate_anchored_regexes(node: &QueryNode) -> Result<(), regex::Error> {
+    match node {
+        QueryNode::AttributeRegex { regex, .. } => {
+            anchored_regex(regex)?;
+        }
+        QueryNode::NestedQuery { node, .. } | QueryNode::NegatedNode { node } => {
+            validate_anchored_regexes(node)?;
+        }
+        QueryNode::Boolean { nodes, .. } => {
+            for node in nodes {
+                validate_anchored_regexes(node)?;
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Returns a matcher which is true if `match_fn` is true for any of the values of `field`. An
+/// array holds multiple values, and the default field holds every value within the object.
+fn match_any<F>(field: &Field, match_fn: F) -> Result<Box<dyn Matcher<Value>>, PathParseError>
+where
+    F: Fn(&Value) -> bool + Send + Sync + Clone + 'static,
+{
+    if let Field::Default(_) = field {
+        return Ok(Run::boxed(move |obj: &Value| any_leaf(obj, &match_fn)));
+    }
+
+    let path: OwnedValuePath = parse_value_path(field.as_str())?;
+
+    Ok(Run::boxed(move |obj: &Value| match obj.get(&path) {
+        Some(Value::Array(values)) => values.iter().any(&match_fn),
+        Some(value) => match_fn(value),
+        None => false,
+    }))
+}
+
+/// Returns true if `match_fn` is true for any value nested within `value` which isn't an object
+/// or an array.
+fn any_leaf<F: Fn(&Value) -> bool>(value: &Value, match_fn: &F) -> bool {
+    match value {
+        Value::Object(map) => map.values().any(|value| any_leaf(value, match_fn)),
+        Value::Array(values) => values.iter().any(|value| any_leaf(value, match_fn)),
+        value => match_fn(value),
+    }
+}
+
+/// Returns a string value from a VRL `Value`, treating Bytes values as their UTF8
+/// representation.
+fn string_value(value: &Value) -> Cow<'_, str> {
+    match value {
+        Value::Bytes(val) => String::from_utf8_lossy(val),
+        _ => Cow::from(value.to_string()),
+    }
+}
+
+fn words(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+}
+
+/// The Levenshtein distance between two strings, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    test_function![
+        match_query => MatchQuery;
+
+        lucene_field_path {
+            args: func_args![value: value!({"user": {"name": "jane"}}), query: "user.name:jane"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        lucene_reserved_names_are_paths {
+            args: func_args![value: value!({"host": "a", "tags": ["env:prod"]}), query: "host:a AND tags:env\\:prod"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        lucene_implicit_or {
+            args: func_args![value: value!({"a": "x"}), query: "a:y a:x"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        lucene_default_field_words {
+            args: func_args![value: value!({"message": "the request failed", "a": {"b": ["timeout"]}}), query: "failed AND timeout"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        lucene_array_values {
+            args: func_args![value: value!({"ids": [1, 2, 3]}), query: "ids:2"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        lucene_boost_ignored {
+            args: func_args![value: value!({"a": "x"}), query: "a:x^2 AND a:(x OR y)^0.5"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        lucene_fuzzy {
+            args: func_args![value: value!({"name": "jnae"}), query: "name:jane~"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        lucene_fuzzy_distance {
+            args: func_args![value: value!({"name": "jnae"}), query: "name:jane~1"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        lucene_regex {
+            args: func_args![value: value!({"path": "/api/v1/users"}), query: "path:/\\/api\\/v[0-9]+\\/.*/"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        lucene_regex_whole_value {
+            args: func_args![value: value!({"host": "web-01.example"}), query: "host:/web-[0-9]+/"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        lucene_range {
+            args: func_args![value: value!({"status": 404}), query: "status:[400 TO 500} AND -status:{403 TO *]"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        lucene_string_comparison {
+            args: func_args![value: value!({"version": "beta"}), query: "version:>alpha"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        lucene_wildcard {
+            args: func_args![value: value!({"name": "foobar"}), query: "name:f*b*"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        kql_keywords {
+            args: func_args![value: value!({"a": 1, "b": "x"}), query: "a:1 AND NOT b:y or c:z", dialect: "kql"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        kql_value_list {
+            args: func_args![value: value!({"method": "HEAD"}), query: "method:(GET or HEAD)", dialect: "kql"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        kql_multi_word_value {
+            args: func_args![value: value!({"message": "request failed"}), query: "message:request failed", dialect: "kql"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        kql_range {
+            args: func_args![value: value!({"bytes": 2048}), query: "bytes >= 1024 and bytes < 4096", dialect: "kql"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        kql_exists {
+            args: func_args![value: value!({"a": null, "b": 1}), query: "a:* or not b:*", dialect: "kql"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        kql_nested {
+            args: func_args![value: value!({"items": [{"name": "a", "count": 5}, {"name": "b", "count": 1}]}), query: "items:{ name:b and count >= 5 }", dialect: "kql"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        kql_nested_object {
+            args: func_args![value: value!({"user": {"name": "jane", "age": 30}}), query: "user:{ name:j* and age > 18 }", dialect: "kql"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        kql_quoted_field {
+            args: func_args![value: value!({"user": {"name": "jane doe"}}), query: r#""user.name":"jane doe""#, dialect: "kql"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+    ];
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("jane", "jnae"), 2);
+        assert_eq!(edit_distance("jane", "jane"), 0);
+    }
+}
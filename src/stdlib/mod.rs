@@ -40,6 +40,7 @@ cfg_if::cfg_if! {
         mod del;
         mod dirname;
         mod downcase;
+        mod encode_avro;
         mod encode_base16;
         mod encode_base64;
         mod encode_charset;
@@ -49,6 +50,7 @@ cfg_if::cfg_if! {
         mod encode_key_value;
         mod encode_logfmt;
         mod encode_lz4;
+        mod encode_otlp;
         mod encode_percent;
         mod encode_punycode;
         mod encode_snappy;
@@ -62,6 +64,7 @@ cfg_if::cfg_if! {
         mod float;
         mod floor;
         mod for_each;
+        mod format_duration;
         mod format_int;
         mod format_number;
         mod format_timestamp;
@@ -94,6 +97,7 @@ cfg_if::cfg_if! {
         mod is_string;
         mod is_timestamp;
         mod join;
+        mod json_query;
         mod keys;
         mod length;
         mod log;
@@ -103,13 +107,16 @@ cfg_if::cfg_if! {
         mod r#match;
         mod match_any;
         mod match_array;
+        mod match_datadog_queries;
         mod match_datadog_query;
+        mod match_query;
         mod merge;
         mod mod_func;
         mod now;
         mod object;
         mod object_from_array;
         mod parse_apache_log;
+        mod parse_avro;
         mod parse_aws_alb_log;
         mod parse_aws_cloudwatch_log_subscription_message;
         mod parse_aws_vpc_flow_log;
@@ -131,6 +138,7 @@ cfg_if::cfg_if! {
         mod parse_linux_authorization;
         mod parse_logfmt;
         mod parse_nginx_log;
+        mod parse_otlp;
         mod parse_query_string;
         mod parse_regex;
         mod parse_regex_all;
@@ -144,6 +152,8 @@ cfg_if::cfg_if! {
         mod parse_yaml;
         mod pop;
         mod push;
+        mod query_jmespath;
+        mod query_jsonpath;
         mod random_bool;
         mod random_bytes;
         mod random_float;
@@ -169,6 +179,7 @@ cfg_if::cfg_if! {
         mod tally_value;
         mod timestamp;
         mod to_bool;
+        mod to_decimal;
         mod to_entries;
         mod to_float;
         mod to_int;
@@ -300,6 +311,7 @@ cfg_if::cfg_if! {
             casing::pascalcase::Pascalcase,
             casing::screamingsnakecase::ScreamingSnakecase,
             casing::snakecase::Snakecase,
+            encode_avro::EncodeAvro,
             encode_base16::EncodeBase16,
             encode_base64::EncodeBase64,
             encode_charset::EncodeCharset,
@@ -309,6 +321,7 @@ cfg_if::cfg_if! {
             encode_key_value::EncodeKeyValue,
             encode_logfmt::EncodeLogfmt,
             encode_lz4::EncodeLz4,
+            encode_otlp::EncodeOtlp,
             encode_percent::EncodePercent,
             encode_punycode::EncodePunycode,
             encode_snappy::EncodeSnappy,
@@ -322,6 +335,7 @@ cfg_if::cfg_if! {
             float::Float,
             floor::Floor,
             for_each::ForEach,
+            format_duration::FormatDuration,
             format_int::FormatInt,
             format_number::FormatNumber,
             format_timestamp::FormatTimestamp,
@@ -362,13 +376,16 @@ cfg_if::cfg_if! {
             map_values::MapValues,
             match_any::MatchAny,
             match_array::MatchArray,
+            match_datadog_queries::MatchDatadogQueries,
             match_datadog_query::MatchDatadogQuery,
+            match_query::MatchQuery,
             merge::Merge,
             mod_func::Mod,
             now::Now,
             object::Object,
             object_from_array::ObjectFromArray,
             parse_apache_log::ParseApacheLog,
+            parse_avro::ParseAvro,
             parse_aws_alb_log::ParseAwsAlbLog,
             parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage,
             parse_aws_vpc_flow_log::ParseAwsVpcFlowLog,
@@ -390,6 +407,7 @@ cfg_if::cfg_if! {
             parse_linux_authorization::ParseLinuxAuthorization,
             parse_logfmt::ParseLogFmt,
             parse_nginx_log::ParseNginxLog,
+            parse_otlp::ParseOtlp,
             parse_query_string::ParseQueryString,
             parse_regex::ParseRegex,
             parse_regex_all::ParseRegexAll,
@@ -403,6 +421,8 @@ cfg_if::cfg_if! {
             parse_yaml::ParseYaml,
             pop::Pop,
             push::Push,
+            query_jmespath::QueryJmespath,
+            query_jsonpath::QueryJsonpath,
             r#match::Match,
             random_bool::RandomBool,
             random_bytes::RandomBytes,
@@ -429,6 +449,7 @@ cfg_if::cfg_if! {
             tally_value::TallyValue,
             timestamp::Timestamp,
             to_bool::ToBool,
+            to_decimal::ToDecimal,
             to_entries::ToEntries,
             to_float::ToFloat,
             to_int::ToInt,
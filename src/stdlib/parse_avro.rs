@@ -0,0 +1,268 @@
+use crate::avro::schema::AvroSchema;
+use crate::avro::{WireFormat, decode_avro};
+use crate::compiler::function::EnumVariant;
+use crate::compiler::prelude::*;
+use crate::value;
+use std::sync::LazyLock;
+
+pub(super) static WIRE_FORMAT_ENUM: &[EnumVariant] = &[
+    EnumVariant {
+        value: "raw",
+        description: "A single Avro binary encoded value.",
+    },
+    EnumVariant {
+        value: "confluent",
+        description: "A single value prefixed with a zero byte and a four byte schema ID, as written by Confluent serializers.",
+    },
+    EnumVariant {
+        value: "object_container",
+        description: "An Avro object container file, holding an array of records.",
+    },
+];
+
+static DEFAULT_WIRE_FORMAT: LazyLock<Value> = LazyLock::new(|| Value::from("raw"));
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required("value", kind::BYTES, "The Avro data to parse."),
+        Parameter::required(
+            "schema",
+            kind::BYTES,
+            "The Avro schema of the data, in its JSON representation. Must be a literal string.",
+        ),
+        Parameter::optional("wire_format", kind::BYTES, "How the Avro data is framed.")
+            .default(&DEFAULT_WIRE_FORMAT)
+            .enum_variants(WIRE_FORMAT_ENUM),
+    ]
+});
+
+pub(super) fn wire_format_variants() -> Vec<Value> {
+    vec![
+        value!("raw"),
+        value!("confluent"),
+        value!("object_container"),
+    ]
+}
+
+/// Returns the wire format given as the `wire_format` argument.
+pub(super) fn wire_format(
+    arguments: &ArgumentList,
+    state: &state::TypeState,
+) -> Result<WireFormat, Box<dyn DiagnosticMessage>> {
+    let wire_format = arguments
+        .optional_enum("wire_format", &wire_format_variants(), state)?
+        .unwrap_or_else(|| DEFAULT_WIRE_FORMAT.clone());
+    Ok(wire_format
+        .try_bytes_utf8_lossy()
+        .expect("wire format not bytes")
+        .parse()
+        .expect("wire format is a valid variant"))
+}
+
+/// Parses the Avro schema given as the `schema` argument.
+pub(super) fn schema(
+    arguments: &ArgumentList,
+    state: &state::TypeState,
+) -> Result<AvroSchema, Box<dyn DiagnosticMessage>> {
+    let schema = arguments.required_literal("schema", state)?;
+    let json = schema.try_bytes_utf8_lossy().map_err(|_| {
+        Box::new(function::Error::InvalidArgument {
+            keyword: "schema",
+            value: schema.clone(),
+            error: "schema must be a string",
+        }) as Box<dyn DiagnosticMessage>
+    })?;
+
+    AvroSchema::parse(&json).map_err(|e| {
+        Box::new(ExpressionError::from(format!("invalid Avro schema: {e}")))
+            as Box<dyn DiagnosticMessage>
+    })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseAvro;
+
+impl Function for ParseAvro {
+    fn identifier(&self) -> &'static str {
+        "parse_avro"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Parses the `value` as [Avro](https://avro.apache.org/) data with the given `schema`.
+            The schema is parsed and validated when the program is compiled.
+
+            Records and maps are returned as objects, enums as their symbol, and the value of a
+            union as the value of its branch. The `timestamp-millis`, `timestamp-micros`,
+            `timestamp-nanos` and `date` logical types are returned as timestamps, `decimal` as a
+            decimal, and `uuid` as a string.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Parse.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &[
+            "`value` is not valid Avro data for the `schema` and `wire_format`.",
+            "`value` is an object container file written with a different schema.",
+        ]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ANY
+    }
+
+    fn notices(&self) -> &'static [&'static str] {
+        &[
+            "The schema ID of the Confluent wire format is not checked against the `schema`.",
+            indoc! {"
+                Object container files are not resolved against the `schema`, so they must have
+                been written with the same schema, ignoring documentation and defaults.
+            "},
+        ]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Parse an Avro record",
+                source: r#"parse_avro!(decode_base64!("CmhlbGxvgKCr/vli"), s'{"type": "record", "name": "Log", "fields": [{"name": "message", "type": "string"}, {"name": "timestamp", "type": {"type": "long", "logicalType": "timestamp-millis"}}]}')"#,
+                result: Ok(r#"{"message": "hello", "timestamp": "2023-11-14T22:13:20Z"}"#),
+            },
+            example! {
+                title: "Parse a Confluent Kafka message",
+                source: r#"parse_avro!(decode_base64!("AAAAACoCCmxvZ2luAQ=="), s'{"type": "record", "name": "Event", "fields": [{"name": "action", "type": ["null", "string"]}, {"name": "success", "type": "boolean"}]}', wire_format: "confluent")"#,
+                result: Ok(r#"{"action": "login", "success": true}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let schema = schema(&arguments, state)?;
+        let wire_format = wire_format(&arguments, state)?;
+
+        Ok(ParseAvroFn {
+            value,
+            schema,
+            wire_format,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseAvroFn {
+    value: Box<dyn Expression>,
+    schema: AvroSchema,
+    wire_format: WireFormat,
+}
+
+impl FunctionExpression for ParseAvroFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?.try_bytes()?;
+        decode_avro(&self.schema, &bytes, self.wire_format)
+            .map_err(|e| format!("unable to parse Avro: {e}").into())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def(&self.schema, self.wire_format)
+    }
+}
+
+fn type_def(schema: &AvroSchema, wire_format: WireFormat) -> TypeDef {
+    match wire_format {
+        WireFormat::Raw | WireFormat::Confluent => TypeDef::from(schema.kind()),
+        WireFormat::ObjectContainer => TypeDef::array(Collection::from_unknown(schema.kind())),
+    }
+    .fallible()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avro::encode_avro;
+    use chrono::DateTime;
+
+    const SCHEMA: &str = r#"{"type": "record", "name": "Log", "fields": [
+        {"name": "message", "type": "string"},
+        {"name": "level", "type": {"type": "enum", "name": "Level", "symbols": ["INFO", "ERROR"]}},
+        {"name": "timestamp", "type": {"type": "long", "logicalType": "timestamp-micros"}},
+        {"name": "duration", "type": ["null", {"type": "bytes", "logicalType": "decimal", "precision": 6, "scale": 3}]}
+    ]}"#;
+
+    fn schema() -> AvroSchema {
+        AvroSchema::parse(SCHEMA).unwrap()
+    }
+
+    fn log() -> Value {
+        value!({
+            message: "started",
+            level: "INFO",
+            timestamp: (DateTime::from_timestamp_micros(1_700_000_000_000_001).unwrap()),
+            duration: (rust_decimal::Decimal::new(1250, 3)),
+        })
+    }
+
+    fn encoded(value: &Value, wire_format: WireFormat) -> Value {
+        Value::Bytes(encode_avro(&schema(), value, wire_format, Some(7)).unwrap())
+    }
+
+    test_function![
+        parse_avro => ParseAvro;
+
+        raw {
+            args: func_args![value: encoded(&log(), WireFormat::Raw), schema: SCHEMA],
+            want: Ok(log()),
+            tdef: type_def(&schema(), WireFormat::Raw),
+        }
+
+        confluent {
+            args: func_args![value: encoded(&log(), WireFormat::Confluent), schema: SCHEMA, wire_format: "confluent"],
+            want: Ok(log()),
+            tdef: type_def(&schema(), WireFormat::Confluent),
+        }
+
+        object_container {
+            args: func_args![
+                value: encoded(&Value::Array(vec![log(), log()]), WireFormat::ObjectContainer),
+                schema: SCHEMA,
+                wire_format: "object_container",
+            ],
+            want: Ok(Value::Array(vec![log(), log()])),
+            tdef: type_def(&schema(), WireFormat::ObjectContainer),
+        }
+
+        truncated {
+            args: func_args![value: "\u{2}", schema: r#""string""#],
+            want: Err("unable to parse Avro: unexpected end of data"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        oversized_block {
+            args: func_args![
+                value: Value::Bytes(Bytes::from_static(b"\x80\x80\x80\x80\x80\x40")),
+                schema: r#"{"type": "array", "items": "null"}"#,
+            ],
+            want: Err("unable to parse Avro: invalid block count `1099511627776`"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::null())).fallible(),
+        }
+
+        invalid_schema {
+            args: func_args![value: "", schema: r#"{"type": "record", "name": "Log"}"#],
+            want: Err("invalid Avro schema: record has no `fields` attribute"),
+            tdef: TypeDef::any().fallible(),
+        }
+    ];
+}
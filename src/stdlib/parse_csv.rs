@@ -1,5 +1,7 @@
-use crate::{compiler::prelude::*, stdlib::csv_utils::parse_single_byte_delimiter};
-use csv::ReaderBuilder;
+use crate::{
+    compiler::prelude::*, parsing::csv::parse_csv_record,
+    stdlib::csv_utils::parse_single_byte_delimiter,
+};
 use std::sync::LazyLock;
 
 static DEFAULT_DELIMITER: LazyLock<Value> = LazyLock::new(|| Value::Bytes(Bytes::from(",")));
@@ -20,24 +22,13 @@ fn parse_csv(csv_string: Value, delimiter: Value) -> Resolved {
     let csv_string = csv_string.try_bytes()?;
     let delimiter = parse_single_byte_delimiter(delimiter)?;
 
-    let reader = ReaderBuilder::new()
-        .has_headers(false)
-        .delimiter(delimiter)
-        .from_reader(&*csv_string);
-    reader
-        .into_byte_records()
-        .next()
-        .transpose()
+    parse_csv_record(&csv_string, delimiter, b'"')
         .map_err(|err| format!("invalid csv record: {err}").into()) // shouldn't really happen
-        .map(|record| {
-            record
-                .map(|record| {
-                    record
-                        .iter()
-                        .map(|x| Bytes::copy_from_slice(x).into())
-                        .collect::<Vec<Value>>()
-                })
-                .unwrap_or_default()
+        .map(|fields| {
+            fields
+                .into_iter()
+                .map(Value::from)
+                .collect::<Vec<_>>()
                 .into()
         })
 }
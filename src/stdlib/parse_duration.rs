@@ -4,7 +4,7 @@ use regex::Regex;
 use rust_decimal::{Decimal, prelude::ToPrimitive};
 use std::{collections::HashMap, str::FromStr, sync::LazyLock};
 
-static UNIT_ENUM: &[EnumVariant] = &[
+pub(super) static UNIT_ENUM: &[EnumVariant] = &[
     EnumVariant {
         value: "ns",
         description: "Nanoseconds (1 billion nanoseconds in a second)",
@@ -49,11 +49,22 @@ static UNIT_ENUM: &[EnumVariant] = &[
 
 static PARAMETERS: &[Parameter] = &[
     Parameter::required("value", kind::BYTES, "The string of the duration."),
-    Parameter::required("unit", kind::BYTES, "The output units for the duration.")
-        .enum_variants(UNIT_ENUM),
+    Parameter::optional(
+        "unit",
+        kind::BYTES,
+        "The output units for the duration. If omitted, a duration is returned.",
+    )
+    .enum_variants(UNIT_ENUM),
 ];
 
-fn parse_duration(bytes: &Value, unit: &Value) -> Resolved {
+fn parse_duration(bytes: &Value, unit: Option<&Value>) -> Resolved {
+    let Some(unit) = unit else {
+        let value = bytes.try_bytes_utf8_lossy()?;
+        return crate::value::value::parse_duration(&value)
+            .map(Value::Duration)
+            .map_err(Into::into);
+    };
+
     let value = bytes.try_bytes_utf8_lossy()?;
     let mut value = &value[..];
     let conversion_factor = {
@@ -126,7 +137,11 @@ impl Function for ParseDuration {
     }
 
     fn usage(&self) -> &'static str {
-        "Parses the `value` into a human-readable duration format specified by `unit`."
+        indoc! {"
+            Parses the `value` into a duration, such as `1h30m` or `1.5s`.
+
+            If `unit` is set, the duration is returned as a float in that unit instead.
+        "}
     }
 
     fn category(&self) -> &'static str {
@@ -138,11 +153,16 @@ impl Function for ParseDuration {
     }
 
     fn return_kind(&self) -> u16 {
-        kind::FLOAT
+        kind::DURATION | kind::FLOAT
     }
 
     fn examples(&self) -> &'static [Example] {
         &[
+            example! {
+                title: "Parse duration",
+                source: r#"parse_duration!("1h 30m")"#,
+                result: Ok("d'1h30m'"),
+            },
             example! {
                 title: "Parse duration (milliseconds)",
                 source: r#"parse_duration!("1005ms", unit: "s")"#,
@@ -163,7 +183,7 @@ impl Function for ParseDuration {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        let unit = arguments.required("unit");
+        let unit = arguments.optional("unit");
 
         Ok(ParseDurationFn { value, unit }.as_expr())
     }
@@ -176,19 +196,27 @@ impl Function for ParseDuration {
 #[derive(Debug, Clone)]
 struct ParseDurationFn {
     value: Box<dyn Expression>,
-    unit: Box<dyn Expression>,
+    unit: Option<Box<dyn Expression>>,
 }
 
 impl FunctionExpression for ParseDurationFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let bytes = self.value.resolve(ctx)?;
-        let unit = self.unit.resolve(ctx)?;
+        let unit = self
+            .unit
+            .as_ref()
+            .map(|unit| unit.resolve(ctx))
+            .transpose()?;
 
-        parse_duration(&bytes, &unit)
+        parse_duration(&bytes, unit.as_ref())
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
-        TypeDef::float().fallible()
+        if self.unit.is_some() {
+            TypeDef::float().fallible()
+        } else {
+            TypeDef::duration().fallible()
+        }
     }
 }
 
@@ -196,10 +224,29 @@ impl FunctionExpression for ParseDurationFn {
 mod tests {
     use super::*;
     use crate::value;
+    use chrono::TimeDelta;
 
     test_function![
         parse_duration => ParseDuration;
 
+        duration {
+            args: func_args![value: "1h 30m"],
+            want: Ok(TimeDelta::minutes(90)),
+            tdef: TypeDef::duration().fallible(),
+        }
+
+        duration_fraction {
+            args: func_args![value: "1.5s"],
+            want: Ok(TimeDelta::milliseconds(1500)),
+            tdef: TypeDef::duration().fallible(),
+        }
+
+        duration_invalid {
+            args: func_args![value: "5y"],
+            want: Err("unknown duration unit: 'y'"),
+            tdef: TypeDef::duration().fallible(),
+        }
+
         s_m {
             args: func_args![value: "30s",
                              unit: "m"],
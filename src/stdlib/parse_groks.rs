@@ -70,8 +70,7 @@ use std::sync::LazyLock;
 #[cfg(not(target_arch = "wasm32"))]
 use std::{fs::File, io::BufReader, path::Path};
 
-static DEFAULT_ALIASES: LazyLock<Value> =
-    LazyLock::new(|| Value::Object(std::collections::BTreeMap::new()));
+static DEFAULT_ALIASES: LazyLock<Value> = LazyLock::new(|| Value::Object(ObjectMap::new()));
 static DEFAULT_ALIAS_SOURCES: LazyLock<Value> = LazyLock::new(|| Value::Array(vec![]));
 
 static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
@@ -468,7 +467,7 @@ mod test {
                     "_x_forwarded_for": r#"%{regex("[^\\\"]*"):http._x_forwarded_for:nullIf("-")}"#
                 })
             ],
-            want: Ok(Value::Object(btreemap! {
+            want: Ok(Value::from(btreemap! {
                 "date_access" => "13/Jul/2016:10:55:36",
                 "duration" => 202_000_000,
                 "http" => btreemap! {
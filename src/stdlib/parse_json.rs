@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use serde_json::{
     Error, Map,
     value::{RawValue, Value as JsonValue},
@@ -66,6 +64,12 @@ fn parse_json_with_depth(value: Value, max_depth: Value, lossy: Value) -> Resolv
     Ok(Value::from(res))
 }
 
+/// The fields of a JSON object, in the order of the document with the `preserve_order` feature.
+#[cfg(not(feature = "preserve_order"))]
+type RawObject<'a> = std::collections::HashMap<String, &'a RawValue>;
+#[cfg(feature = "preserve_order")]
+type RawObject<'a> = indexmap::IndexMap<String, &'a RawValue>;
+
 fn parse_layer(value: &RawValue, remaining_depth: u8) -> std::result::Result<JsonValue, Error> {
     let raw_value = value.get();
 
@@ -77,7 +81,7 @@ fn parse_layer(value: &RawValue, remaining_depth: u8) -> std::result::Result<Jso
             serde_json::value::to_value(raw_value)
         } else {
             // Parse each value of the object as a raw JSON value recursively with the same method.
-            let map: HashMap<String, &RawValue> = serde_json::from_str(raw_value)?;
+            let map: RawObject = serde_json::from_str(raw_value)?;
 
             let mut res_map: Map<String, JsonValue> = Map::with_capacity(map.len());
             for (k, v) in map {
@@ -350,9 +354,9 @@ mod tests {
             tdef: json_type_def(),
         }
 
-        lossy_float_conversion {
+        unsigned_64_bit_conversion {
             args: func_args![ value: r#"{"num": 9223372036854775808}"#],
-            want: Ok(value!({"num": 9.223_372_036_854_776e18})),
+            want: Ok(value!({"num": (1_u64 << 63)})),
             tdef: json_type_def(),
         }
 
@@ -414,4 +418,19 @@ mod tests {
             tdef: json_type_def(),
         }
     ];
+
+    #[test]
+    #[cfg(feature = "preserve_order")]
+    fn keeps_key_order() {
+        let json = r#"{"z":1,"a":{"y":true,"b":{"x":"raw"}}}"#;
+
+        let value = parse_json(json.into(), false.into()).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+
+        let value = parse_json_with_depth(json.into(), 2.into(), false.into()).unwrap();
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"z":1,"a":{"y":true,"b":"{\"x\":\"raw\"}"}}"#
+        );
+    }
 }
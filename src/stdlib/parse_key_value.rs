@@ -1,6 +1,7 @@
 use crate::compiler::function::EnumVariant;
 use crate::compiler::prelude::*;
 use crate::value;
+use crate::value::object_map::Entry;
 use nom::{
     self, IResult, Parser,
     branch::alt,
@@ -14,7 +15,6 @@ use nom::{
 use nom_language::error::VerboseError;
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, btree_map::Entry},
     iter::Peekable,
     str::{Chars, FromStr},
     sync::LazyLock,
@@ -73,7 +73,7 @@ pub fn parse_key_value(
 
     // Construct Value::Object by grouping values with the same key into an array.
     // This logic depends on values not being arrays which is true for this parser.
-    let mut map = BTreeMap::new();
+    let mut map = ObjectMap::new();
     for (key, value) in values {
         match map.entry(key) {
             Entry::Vacant(entry) => {
@@ -0,0 +1,180 @@
+use crate::compiler::function::EnumVariant;
+use crate::compiler::prelude::*;
+use crate::protobuf::otlp::{Signal, decode_otlp};
+use crate::value;
+use std::sync::LazyLock;
+
+pub(super) static SIGNAL_ENUM: &[EnumVariant] = &[
+    EnumVariant {
+        value: "logs",
+        description: "Log records, from a `LogsData` or `ExportLogsServiceRequest` message.",
+    },
+    EnumVariant {
+        value: "traces",
+        description: "Spans, from a `TracesData` or `ExportTraceServiceRequest` message.",
+    },
+    EnumVariant {
+        value: "metrics",
+        description: "Metrics, from a `MetricsData` or `ExportMetricsServiceRequest` message.",
+    },
+];
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required("value", kind::BYTES, "The OTLP protobuf payload to parse."),
+        Parameter::required(
+            "signal",
+            kind::BYTES,
+            "The OpenTelemetry signal the payload contains.",
+        )
+        .enum_variants(SIGNAL_ENUM),
+    ]
+});
+
+pub(super) fn signal_variants() -> Vec<Value> {
+    vec![value!("logs"), value!("traces"), value!("metrics")]
+}
+
+/// Returns the signal given as the `signal` argument.
+pub(super) fn signal(
+    arguments: &ArgumentList,
+    state: &state::TypeState,
+) -> Result<Signal, Box<dyn DiagnosticMessage>> {
+    let signal = arguments.required_enum("signal", &signal_variants(), state)?;
+    Ok(signal
+        .try_bytes_utf8_lossy()
+        .expect("signal not bytes")
+        .parse()
+        .expect("signal is a valid variant"))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseOtlp;
+
+impl Function for ParseOtlp {
+    fn identifier(&self) -> &'static str {
+        "parse_otlp"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Parses the `value` as an [OpenTelemetry protocol](https://opentelemetry.io/docs/specs/otlp/)
+            (OTLP) protobuf payload, flattening its resources and scopes into an array with one
+            object per log record, span or metric.
+
+            Each record has the `resource` and `scope` it belongs to, including their `schema_url`.
+            Attribute lists are returned as objects, trace and span IDs as hex strings, and
+            `*_time_unix_nano` fields as timestamps.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Parse.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &["`value` is not a valid OTLP payload for the `signal`."]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ARRAY
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[example! {
+            title: "Parse OTLP logs",
+            source: r#"parse_otlp!(decode_base64!("ClQKJAoiCgxzZXJ2aWNlLm5hbWUSEgoQY2hlY2tvdXQtc2VydmljZRIsCggKBmxvZ2dlchIgEAkqEAoOdXNlciBsb2dnZWQgaW4yCgoEdXNlchICGCo="), "logs")"#,
+            result: Ok(indoc! {r#"
+                [{
+                    "attributes": {"user": 42},
+                    "body": "user logged in",
+                    "resource": {"attributes": {"service.name": "checkout-service"}},
+                    "scope": {"name": "logger"},
+                    "severity_number": "SEVERITY_NUMBER_INFO"
+                }]
+            "#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let signal = signal(&arguments, state)?;
+
+        Ok(ParseOtlpFn { value, signal }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseOtlpFn {
+    value: Box<dyn Expression>,
+    signal: Signal,
+}
+
+impl FunctionExpression for ParseOtlpFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?.try_bytes()?;
+        Ok(decode_otlp(bytes, self.signal)?)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def()
+    }
+}
+
+fn type_def() -> TypeDef {
+    TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protobuf::otlp::encode_otlp;
+    use chrono::DateTime;
+
+    fn payload(signal: Signal, records: Value) -> Value {
+        Value::Bytes(encode_otlp(records, signal).unwrap())
+    }
+
+    test_function![
+        parse_otlp => ParseOtlp;
+
+        logs {
+            args: func_args![
+                value: payload(Signal::Logs, value!([{ resource: { attributes: { "service.name": "web" } }, scope: {}, body: { message: "hello" }, time_unix_nano: (DateTime::from_timestamp_nanos(1_000_000_000)) }])),
+                signal: "logs",
+            ],
+            want: Ok(value!([{ resource: { attributes: { "service.name": "web" } }, scope: {}, body: { message: "hello" }, time_unix_nano: (DateTime::from_timestamp_nanos(1_000_000_000)) }])),
+            tdef: type_def(),
+        }
+
+        spans {
+            args: func_args![
+                value: payload(Signal::Traces, value!([{ resource: {}, scope: { name: "tracer" }, name: "span", span_id: "0102030405060708" }])),
+                signal: "traces",
+            ],
+            want: Ok(value!([{ resource: {}, scope: { name: "tracer" }, name: "span", span_id: "0102030405060708" }])),
+            tdef: type_def(),
+        }
+
+        empty {
+            args: func_args![value: "", signal: "metrics"],
+            want: Ok(value!([])),
+            tdef: type_def(),
+        }
+
+        invalid {
+            args: func_args![value: "\u{ff}", signal: "logs"],
+            want: Err("Error parsing OTLP logs: failed to decode Protobuf message: invalid varint"),
+            tdef: type_def(),
+        }
+    ];
+}
@@ -1,12 +1,12 @@
 use super::util::example_path_or_basename;
 use crate::compiler::prelude::*;
-use crate::protobuf::descriptor::get_message_descriptor;
+use crate::protobuf::descriptor::{
+    get_message_descriptor, get_message_descriptor_from_source,
+};
 use crate::protobuf::parse::parse_proto;
 use crate::stdlib::json_utils::json_type_def::json_type_def;
 use prost_reflect::MessageDescriptor;
-use std::ffi::OsString;
-use std::path::Path;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 #[derive(Clone, Copy, Debug)]
@@ -63,7 +63,15 @@ impl Function for ParseProto {
     }
 
     fn notices(&self) -> &'static [&'static str] {
-        &["Only proto messages are parsed and returned."]
+        &[
+            "Only proto messages are parsed and returned.",
+            indoc! {"
+                The Google well-known types are returned as in the proto3 JSON mapping: a
+                `google.protobuf.Timestamp` as a timestamp, a `Struct` as an object, a wrapper such
+                as `Int64Value` as the value it wraps, and an `Any` as an object with the packed
+                message's fields and its type URL in the `@type` field.
+            "},
+        ]
     }
 
     fn parameters(&self) -> &'static [Parameter] {
@@ -76,9 +84,10 @@ impl Function for ParseProto {
             Parameter::required(
                 "desc_file",
                 kind::BYTES,
-                "The path to the protobuf descriptor set file. Must be a literal string.
+                "The path to the protobuf descriptor set file, or to a `.proto` source file. Must be a literal string.
 
-This file is the output of protoc -o <path> ...",
+A descriptor set file is the output of protoc -o <path> .... A `.proto` source file is parsed when
+the program is compiled, along with the files it imports.",
             ),
             Parameter::required(
                 "message_type",
@@ -87,6 +96,15 @@ This file is the output of protoc -o <path> ...",
 
 Must be a literal string.",
             ),
+            Parameter::optional(
+                "include_paths",
+                kind::ARRAY,
+                "The directories to search for the files imported by a `.proto` source file, and
+which `desc_file` is relative to. Must be a literal array of strings.
+
+Defaults to the directory containing `desc_file`. The Google well-known types, such as
+`google/protobuf/timestamp.proto`, can always be imported.",
+            ),
         ];
         PARAMETERS
     }
@@ -102,24 +120,72 @@ Must be a literal string.",
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        let desc_file = arguments.required_literal("desc_file", state)?;
-        let desc_file_str = desc_file
-            .try_bytes_utf8_lossy()
-            .expect("descriptor file must be a string");
-        let message_type = arguments.required_literal("message_type", state)?;
-        let message_type_str = message_type
-            .try_bytes_utf8_lossy()
-            .expect("message_type must be a string");
-        let os_string: OsString = desc_file_str.into_owned().into();
-        let path_buf = PathBuf::from(os_string);
-        let path = Path::new(&path_buf);
-        let descriptor =
-            get_message_descriptor(path, &message_type_str).expect("message type not found");
+        let descriptor = message_descriptor(state, &arguments)?;
 
         Ok(ParseProtoFn { descriptor, value }.as_expr())
     }
 }
 
+/// Loads the message descriptor named by the `message_type` argument from the `desc_file`
+/// argument, which is either a descriptor set or a `.proto` source file.
+pub(super) fn message_descriptor(
+    state: &state::TypeState,
+    arguments: &ArgumentList,
+) -> Result<MessageDescriptor, Box<dyn DiagnosticMessage>> {
+    let desc_file = arguments.required_literal("desc_file", state)?;
+    let desc_file = desc_file.try_bytes_utf8_lossy().map_err(|_| {
+        Box::new(function::Error::InvalidArgument {
+            keyword: "desc_file",
+            value: desc_file.clone(),
+            error: "descriptor file must be a string",
+        }) as Box<dyn DiagnosticMessage>
+    })?;
+    let path = PathBuf::from(desc_file.into_owned());
+
+    let message_type = arguments.required_literal("message_type", state)?;
+    let message_type = message_type.try_bytes_utf8_lossy().map_err(|_| {
+        Box::new(function::Error::InvalidArgument {
+            keyword: "message_type",
+            value: message_type.clone(),
+            error: "message type must be a string",
+        }) as Box<dyn DiagnosticMessage>
+    })?;
+
+    let include_paths = arguments.optional_literal("include_paths", state)?;
+    let descriptor = if path.extension().is_some_and(|extension| extension == "proto") {
+        let include_paths = match include_paths {
+            Some(include_paths) => include_paths_from_value(include_paths)?,
+            None => vec![path.parent().map(Path::to_path_buf).unwrap_or_default()],
+        };
+        get_message_descriptor_from_source(&path, &include_paths, &message_type)
+    } else {
+        get_message_descriptor(&path, &message_type)
+    };
+
+    descriptor.map_err(|e| Box::new(ExpressionError::from(e)) as Box<dyn DiagnosticMessage>)
+}
+
+fn include_paths_from_value(value: Value) -> Result<Vec<PathBuf>, Box<dyn DiagnosticMessage>> {
+    let invalid = |value: Value| {
+        Box::new(function::Error::InvalidArgument {
+            keyword: "include_paths",
+            value,
+            error: "include paths must be an array of strings",
+        }) as Box<dyn DiagnosticMessage>
+    };
+
+    let Value::Array(paths) = value else {
+        return Err(invalid(value));
+    };
+    paths
+        .iter()
+        .map(|path| match path {
+            Value::Bytes(bytes) => Ok(PathBuf::from(String::from_utf8_lossy(bytes).into_owned())),
+            _ => Err(invalid(Value::Array(paths.clone()))),
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 struct ParseProtoFn {
     descriptor: MessageDescriptor,
@@ -169,5 +235,39 @@ mod tests {
             want: Ok(value!({ name: "Someone", phones: [{number: "123-456", type: "PHONE_TYPE_MOBILE"}] })),
             tdef: json_type_def(),
         }
+
+        parses_from_proto_source {
+            args: func_args![ value: read_pb_file("test_protobuf/v1/input/person_someone.pb"),
+                desc_file: test_data_dir().join("test_protobuf/v1/test_protobuf.proto").to_str().unwrap().to_owned(),
+                message_type: "test_protobuf.v1.Person"],
+            want: Ok(value!({ name: "Someone", phones: [{number: "123-456"}] })),
+            tdef: json_type_def(),
+        }
     ];
+
+    #[test]
+    fn reports_missing_message_type() {
+        let desc_file = test_data_dir().join("test/v1/test.proto");
+        let arguments = func_args![
+            value: "",
+            desc_file: desc_file.to_str().unwrap().to_owned(),
+            message_type: "test.v1.Missing",
+            include_paths: Value::Array(vec![test_data_dir().to_str().unwrap().into()])
+        ];
+        let error = ParseProto
+            .compile(
+                &state::TypeState::default(),
+                &mut FunctionCompileContext::new(Span::new(0, 0), crate::compiler::CompileConfig::default()),
+                arguments.into(),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.message(),
+            format!(
+                "The message type 'test.v1.Missing' could not be found in '{}'",
+                desc_file.display()
+            )
+        );
+    }
 }
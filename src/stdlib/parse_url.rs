@@ -1,4 +1,5 @@
 use crate::compiler::prelude::*;
+use crate::parsing::url::url_to_value;
 use std::collections::BTreeMap;
 use std::sync::LazyLock;
 use url::Url;
@@ -130,42 +131,6 @@ impl FunctionExpression for ParseUrlFn {
     }
 }
 
-fn url_to_value(url: &Url, default_known_ports: bool) -> Value {
-    let mut map = BTreeMap::<&str, Value>::new();
-
-    map.insert("scheme", url.scheme().to_owned().into());
-    map.insert("username", url.username().to_owned().into());
-    map.insert(
-        "password",
-        url.password()
-            .map(ToOwned::to_owned)
-            .unwrap_or_default()
-            .into(),
-    );
-    map.insert("path", url.path().to_owned().into());
-    map.insert("host", url.host_str().map(ToOwned::to_owned).into());
-
-    let port = if default_known_ports {
-        url.port_or_known_default()
-    } else {
-        url.port()
-    };
-    map.insert("port", port.into());
-    map.insert("fragment", url.fragment().map(ToOwned::to_owned).into());
-    map.insert(
-        "query",
-        url.query_pairs()
-            .into_owned()
-            .map(|(k, v)| (k.into(), v.into()))
-            .collect::<ObjectMap>()
-            .into(),
-    );
-
-    map.into_iter()
-        .map(|(k, v)| (k.to_owned(), v))
-        .collect::<Value>()
-}
-
 fn inner_kind() -> BTreeMap<Field, Kind> {
     BTreeMap::from([
         ("scheme".into(), Kind::bytes()),
@@ -0,0 +1,205 @@
+use super::json_query::jmespath::JmesPath;
+use crate::compiler::prelude::*;
+
+fn compile_expression(expression: &Value) -> Result<JmesPath, ExpressionError> {
+    let expression = expression.try_bytes_utf8_lossy()?;
+    JmesPath::parse(&expression).map_err(|err| format!("invalid JMESPath: {err}").into())
+}
+
+fn query_jmespath(value: &Value, expression: &JmesPath) -> Resolved {
+    expression
+        .search(value)
+        .map_err(|err| format!("unable to evaluate JMESPath: {err}").into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct QueryJmespath;
+
+impl Function for QueryJmespath {
+    fn identifier(&self) -> &'static str {
+        "query_jmespath"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Evaluates the [JMESPath](https://jmespath.org/specification.html) `expression`
+            against `value`.
+
+            The expression is compiled once if it is a literal. Projections such as `items[*].name`
+            and filters such as `items[?price > `10`]` return arrays of their matches, even if only
+            one element matches, while expressions such as `items[0].name` return the value they
+            select as is. All of the JMESPath built-in functions are supported.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Object.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &[
+            "`expression` is not a valid JMESPath expression.",
+            "A function in `expression` is called with an argument of the wrong type.",
+        ]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ANY
+    }
+
+    fn return_rules(&self) -> &'static [&'static str] {
+        &[
+            "Returns `null` if the expression doesn't match anything.",
+            "Projections and filters return an array of their matches, which is empty if no element matches.",
+        ]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[
+            Parameter::required("value", kind::ANY, "The value to query."),
+            Parameter::required("expression", kind::BYTES, "The JMESPath expression."),
+        ];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Project a field of every element",
+                source: r#"query_jmespath!({"items": [{"name": "a"}, {"name": "b"}]}, "items[*].name")"#,
+                result: Ok(r#"["a", "b"]"#),
+            },
+            example! {
+                title: "Filter and sort",
+                source: r#"query_jmespath!({"people": [{"name": "b", "age": 30}, {"name": "a", "age": 40}, {"name": "c", "age": 20}]}, "people[?age > `25`].name | sort(@)")"#,
+                result: Ok(r#"["a", "b"]"#),
+            },
+            example! {
+                title: "Reshape an object",
+                source: r#"query_jmespath!({"user": {"id": 1, "tags": ["x", "y"]}}, "{id: user.id, tag_count: length(user.tags)}")"#,
+                result: Ok(r#"{"id": 1, "tag_count": 2}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let expression = arguments.required("expression");
+
+        let expression = match expression.resolve_constant(state) {
+            Some(expression) => JmesPathType::Constant(
+                compile_expression(&expression)
+                    .map_err(|e| Box::new(e) as Box<dyn DiagnosticMessage>)?,
+            ),
+            None => JmesPathType::Expression(expression),
+        };
+
+        Ok(QueryJmespathFn { value, expression }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum JmesPathType {
+    Constant(JmesPath),
+    Expression(Box<dyn Expression>),
+}
+
+#[derive(Debug, Clone)]
+struct QueryJmespathFn {
+    value: Box<dyn Expression>,
+    expression: JmesPathType,
+}
+
+impl FunctionExpression for QueryJmespathFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        match &self.expression {
+            JmesPathType::Constant(expression) => query_jmespath(&value, expression),
+            JmesPathType::Expression(expression) => {
+                let expression = compile_expression(&expression.resolve(ctx)?)?;
+                query_jmespath(&value, &expression)
+            }
+        }
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        match &self.expression {
+            JmesPathType::Constant(expression) => TypeDef::from(expression.kind()),
+            JmesPathType::Expression(_) => TypeDef::any(),
+        }
+        .fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        query_jmespath => QueryJmespath;
+
+        projection {
+            args: func_args![
+                value: value!({"items": [{"price": 5}, {"price": 12}, {"name": "c"}]}),
+                expression: "items[*].price",
+            ],
+            want: Ok(value!([5, 12])),
+            tdef: TypeDef::array(Collection::any()).or_null().fallible(),
+        }
+
+        single_match {
+            args: func_args![
+                value: value!({"items": [{"price": 5}, {"name": "c"}]}),
+                expression: "items[?price > `1`].price",
+            ],
+            want: Ok(value!([5])),
+            tdef: TypeDef::array(Collection::any()).or_null().fallible(),
+        }
+
+        index {
+            args: func_args![
+                value: value!({"items": [{"price": 5}, {"name": "c"}]}),
+                expression: "items[0].price",
+            ],
+            want: Ok(value!(5)),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        functions {
+            args: func_args![
+                value: value!({"items": [{"price": 5}, {"price": 12.5}]}),
+                expression: "{total: sum(items[].price), most: max_by(items, &price).price}",
+            ],
+            want: Ok(value!({"total": 17.5, "most": 12.5})),
+            tdef: TypeDef::object(Collection::any()).or_null().fallible(),
+        }
+
+        no_match {
+            args: func_args![value: value!({"a": 1}), expression: "b.c"],
+            want: Ok(value!(null)),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        invalid_type {
+            args: func_args![value: value!({"a": 1}), expression: "length(a)"],
+            want: Err("unable to evaluate JMESPath: invalid type for `length`: expected string, array or object, got number"),
+            tdef: TypeDef::any().fallible(),
+        }
+    ];
+
+    #[test]
+    fn invalid_literal_expression() {
+        let error = compile_expression(&value!("foo[")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid JMESPath: unexpected token at position 4"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use super::json_query::jsonpath::JsonPath;
+use crate::compiler::prelude::*;
+
+fn compile_path(path: &Value) -> Result<JsonPath, ExpressionError> {
+    let path = path.try_bytes_utf8_lossy()?;
+    JsonPath::parse(&path).map_err(|err| format!("invalid JSONPath: {err}").into())
+}
+
+fn query_jsonpath(value: &Value, path: &JsonPath) -> Value {
+    path.query(value)
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>()
+        .into()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct QueryJsonpath;
+
+impl Function for QueryJsonpath {
+    fn identifier(&self) -> &'static str {
+        "query_jsonpath"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Returns the values in `value` that the [JSONPath](https://www.rfc-editor.org/rfc/rfc9535)
+            query `path` selects, in document order.
+
+            The query is compiled once if `path` is a literal. Filters support the `length`,
+            `count`, `match`, `search` and `value` functions.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Object.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &["`path` is not a valid JSONPath query."]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ARRAY
+    }
+
+    fn return_rules(&self) -> &'static [&'static str] {
+        &["Returns an empty array if the query selects nothing."]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[
+            Parameter::required("value", kind::ANY, "The value to query."),
+            Parameter::required("path", kind::BYTES, "The JSONPath query."),
+        ];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Select a field of every element",
+                source: r#"query_jsonpath({"items": [{"price": 5}, {"price": 12}]}, "$.items[*].price")"#,
+                result: Ok("[5, 12]"),
+            },
+            example! {
+                title: "Filter elements",
+                source: r#"query_jsonpath({"items": [{"name": "a", "price": 5}, {"name": "b", "price": 12}]}, "$.items[?@.price > 10].name")"#,
+                result: Ok(r#"["b"]"#),
+            },
+            example! {
+                title: "Select fields at any depth",
+                source: r#"query_jsonpath({"user": {"id": 1, "manager": {"id": 2}}}, "$..id")"#,
+                result: Ok("[1, 2]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let path = arguments.required("path");
+
+        let path = match path.resolve_constant(state) {
+            Some(path) => JsonPathType::Constant(
+                compile_path(&path).map_err(|e| Box::new(e) as Box<dyn DiagnosticMessage>)?,
+            ),
+            None => JsonPathType::Expression(path),
+        };
+
+        Ok(QueryJsonpathFn { value, path }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum JsonPathType {
+    Constant(JsonPath),
+    Expression(Box<dyn Expression>),
+}
+
+#[derive(Debug, Clone)]
+struct QueryJsonpathFn {
+    value: Box<dyn Expression>,
+    path: JsonPathType,
+}
+
+impl FunctionExpression for QueryJsonpathFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        match &self.path {
+            JsonPathType::Constant(path) => Ok(query_jsonpath(&value, path)),
+            JsonPathType::Expression(path) => {
+                let path = compile_path(&path.resolve(ctx)?)?;
+                Ok(query_jsonpath(&value, &path))
+            }
+        }
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::any())
+            .maybe_fallible(matches!(self.path, JsonPathType::Expression(_)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        query_jsonpath => QueryJsonpath;
+
+        wildcard {
+            args: func_args![
+                value: value!({"items": [{"price": 5}, {"price": 12}, {"name": "c"}]}),
+                path: "$.items[*].price",
+            ],
+            want: Ok(value!([5, 12])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        slice {
+            args: func_args![value: value!([1, 2, 3, 4, 5]), path: "$[1:4:2]"],
+            want: Ok(value!([2, 4])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        filter_functions {
+            args: func_args![
+                value: value!({"hosts": [{"name": "web-01", "tags": ["a"]}, {"name": "db-01", "tags": []}]}),
+                path: "$.hosts[?match(@.name, 'web-.*') && length(@.tags) > 0].name",
+            ],
+            want: Ok(value!(["web-01"])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        no_match {
+            args: func_args![value: value!({"a": 1}), path: "$.b"],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+    ];
+
+    #[test]
+    fn invalid_literal_path() {
+        let error = compile_path(&value!("$.a[")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid JSONPath: expected a selector at position 4"
+        );
+    }
+}
@@ -227,7 +227,6 @@ impl FunctionExpression for ShannonEntropyFn {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
 
     use super::*;
     use crate::{stdlib::util::round_to_precision, value};
@@ -329,7 +328,7 @@ mod tests {
 
     fn prepare_function(function: &ShannonEntropyFn) -> Resolved {
         let tz = TimeZone::default();
-        let mut object: Value = Value::Object(BTreeMap::new());
+        let mut object: Value = Value::Object(ObjectMap::new());
         let mut runtime_state = state::RuntimeState::default();
         let mut ctx = Context::new(&mut object, &mut runtime_state, &tz);
         function.resolve(&mut ctx)
@@ -108,6 +108,7 @@ fn tag_type_externally(value: Value) -> Value {
         value @ Value::Bytes(_) => (Some("string"), value),
         value @ Value::Integer(_) => (Some("integer"), value),
         value @ Value::Float(_) => (Some("float"), value),
+        value @ Value::Decimal(_) => (Some("decimal"), value),
         value @ Value::Boolean(_) => (Some("boolean"), value),
         Value::Object(object) => (
             None,
@@ -126,6 +127,7 @@ fn tag_type_externally(value: Value) -> Value {
                 .into(),
         ),
         value @ Value::Timestamp(_) => (Some("timestamp"), value),
+        value @ Value::Duration(_) => (Some("duration"), value),
         value @ Value::Regex(_) => (Some("regex"), value),
         Value::Null => (None, Value::Null),
     };
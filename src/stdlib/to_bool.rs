@@ -2,12 +2,13 @@ use crate::compiler::conversion::Conversion;
 use crate::compiler::prelude::*;
 
 fn to_bool(value: Value) -> Resolved {
-    use Value::{Boolean, Bytes, Float, Integer, Null};
+    use Value::{Boolean, Bytes, Decimal, Float, Integer, Null};
 
     match value {
         Boolean(_) => Ok(value),
         Integer(v) => Ok(Boolean(v != 0)),
         Float(v) => Ok(Boolean(v != 0.0)),
+        Decimal(v) => Ok(Boolean(!v.is_zero())),
         Null => Ok(Boolean(false)),
         Bytes(v) => Conversion::Boolean
             .convert(v)
@@ -201,6 +202,7 @@ impl FunctionExpression for ToBoolFn {
         TypeDef::boolean().maybe_fallible(
             td.contains_bytes()
                 || td.contains_timestamp()
+                || td.contains_duration()
                 || td.contains_array()
                 || td.contains_object()
                 || td.contains_regex(),
@@ -0,0 +1,238 @@
+use crate::compiler::prelude::*;
+use rust_decimal::Decimal;
+
+fn to_decimal(value: Value) -> Resolved {
+    use Value::{Boolean, Bytes, Duration, Float, Integer, Null, Timestamp};
+
+    match value {
+        Value::Decimal(_) => Ok(value),
+        Integer(v) => Ok(Decimal::from(v).into()),
+        Float(v) => Decimal::try_from(v.into_inner())
+            .map(Into::into)
+            .map_err(|_| format!("float {v} is out of range for a decimal").into()),
+        Boolean(v) => Ok(Decimal::from(u8::from(v)).into()),
+        Null => Ok(Decimal::ZERO.into()),
+        Bytes(v) => {
+            let s = String::from_utf8_lossy(&v);
+            let number = s.trim();
+            if number.contains(['e', 'E']) {
+                Decimal::from_scientific(number)
+            } else {
+                Decimal::from_str_exact(number)
+            }
+            .map(Into::into)
+            .map_err(|e| format!(r#"Invalid decimal number "{s}": {e}"#).into())
+        }
+        Timestamp(v) => {
+            let seconds = Decimal::from(v.timestamp());
+            let fraction = Decimal::new(i64::from(v.timestamp_subsec_nanos()), 9);
+            #[allow(clippy::arithmetic_side_effects)] // an i64 and a fraction always fit
+            Ok((seconds + fraction).normalize().into())
+        }
+        Duration(v) => {
+            let seconds = Decimal::from(v.num_seconds());
+            let fraction = Decimal::new(i64::from(v.subsec_nanos()), 9);
+            #[allow(clippy::arithmetic_side_effects)] // an i64 and a fraction always fit
+            Ok((seconds + fraction).normalize().into())
+        }
+        v => Err(format!("unable to coerce {} into decimal", v.kind()).into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ToDecimal;
+
+impl Function for ToDecimal {
+    fn identifier(&self) -> &'static str {
+        "to_decimal"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Coerces the `value` into a decimal, an exact number with up to 28 significant digits.
+
+            Decimals hold numbers that do not fit an integer, such as unsigned 64-bit counters,
+            and fractions that would lose precision as a float. Arithmetic between decimals and
+            integers stays exact, while arithmetic with a float returns a float.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Coerce.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &[
+            "`value` is not a supported decimal representation.",
+            "`value` is a float outside of the range of a decimal.",
+        ]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::DECIMAL
+    }
+
+    fn return_rules(&self) -> &'static [&'static str] {
+        &[
+            "If `value` is a decimal, it will be returned as-is.",
+            "If `value` is an integer, it will be returned as a decimal.",
+            "If `value` is a float, the decimal closest to it is returned.",
+            "If `value` is a string, it must be the string representation of a number or else an error is raised.",
+            "If `value` is a boolean, `0` is returned for `false` and `1` is returned for `true`.",
+            "If `value` is a timestamp, a [Unix timestamp](https://en.wikipedia.org/wiki/Unix_time) with exact fractional seconds is returned.",
+            "If `value` is a duration, its exact length in seconds is returned.",
+        ]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "value",
+            kind::ANY,
+            "The value to convert to a decimal. Must be convertible to a decimal, otherwise an error is raised.",
+        )];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Coerce to a decimal",
+                source: r#"to_decimal!("18446744073709551615")"#,
+                result: Ok("18446744073709551615"),
+            },
+            example! {
+                title: "Exact arithmetic",
+                source: indoc! {r#"
+                    sum, _err = to_decimal!("0.1") + to_decimal!("0.2")
+                    sum
+                "#},
+                result: Ok("0.3"),
+            },
+            example! {
+                title: "Integer",
+                source: "to_decimal(5)",
+                result: Ok("5"),
+            },
+            example! {
+                title: "Timestamp",
+                source: "to_decimal(t'2020-12-30T22:20:53.824727Z')",
+                result: Ok("1609366853.824727"),
+            },
+            example! {
+                title: "Invalid string",
+                source: "to_decimal!(s'foobar')",
+                result: Err(
+                    r#"function call error for "to_decimal" at (0:22): Invalid decimal number "foobar": Invalid decimal: unknown character"#,
+                ),
+            },
+            example! {
+                title: "Array",
+                source: "to_decimal!([])",
+                result: Err(
+                    r#"function call error for "to_decimal" at (0:15): unable to coerce array into decimal"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ToDecimalFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ToDecimalFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ToDecimalFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        to_decimal(value)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let td = self.value.type_def(state);
+
+        TypeDef::decimal().maybe_fallible(
+            td.contains_bytes()
+                || td.contains_float()
+                || td.contains_array()
+                || td.contains_object()
+                || td.contains_regex(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, prelude::*};
+
+    use super::*;
+
+    test_function![
+        to_decimal => ToDecimal;
+
+        decimal {
+            args: func_args![value: Decimal::MAX],
+            want: Ok(Decimal::MAX),
+            tdef: TypeDef::decimal().infallible(),
+        }
+
+        integer {
+            args: func_args![value: -20],
+            want: Ok(Decimal::from(-20)),
+            tdef: TypeDef::decimal().infallible(),
+        }
+
+        unsigned_64_bit {
+            args: func_args![value: "18446744073709551615"],
+            want: Ok(Decimal::from(u64::MAX)),
+            tdef: TypeDef::decimal().fallible(),
+        }
+
+        fraction {
+            args: func_args![value: "0.10"],
+            want: Ok(Decimal::new(10, 2)),
+            tdef: TypeDef::decimal().fallible(),
+        }
+
+        scientific {
+            args: func_args![value: "1.5e3"],
+            want: Ok(Decimal::from(1500)),
+            tdef: TypeDef::decimal().fallible(),
+        }
+
+        float {
+            args: func_args![value: 20.5],
+            want: Ok(Decimal::new(205, 1)),
+            tdef: TypeDef::decimal().fallible(),
+        }
+
+        float_out_of_range {
+            args: func_args![value: 1e30],
+            want: Err("float 1000000000000000000000000000000 is out of range for a decimal"),
+            tdef: TypeDef::decimal().fallible(),
+        }
+
+        timestamp {
+            args: func_args![value: Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap().with_nanosecond(12_000_000).unwrap()],
+            want: Ok(Decimal::new(1_404_810_611_012, 3)),
+            tdef: TypeDef::decimal().infallible(),
+        }
+
+        duration {
+            args: func_args![value: TimeDelta::nanoseconds(-1_500_000_001)],
+            want: Ok(Decimal::new(-1_500_000_001, 9)),
+            tdef: TypeDef::decimal().infallible(),
+        }
+    ];
+}
@@ -64,14 +64,12 @@ impl Function for ToEntries {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::OBJECT | kind::ARRAY,
-            required: true,
-            description: "The object or array to manipulate.",
-            default: None,
-            enum_variants: None,
-        }]
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "value",
+            kind::OBJECT | kind::ARRAY,
+            "The object or array to manipulate.",
+        )];
+        PARAMETERS
     }
 
     fn examples(&self) -> &'static [Example] {
@@ -1,5 +1,6 @@
 use crate::compiler::conversion::Conversion;
 use crate::compiler::prelude::*;
+use rust_decimal::prelude::ToPrimitive;
 
 pub(crate) fn bytes_to_float(bytes: Bytes) -> Resolved {
     Conversion::Float
@@ -9,10 +10,11 @@ pub(crate) fn bytes_to_float(bytes: Bytes) -> Resolved {
 
 #[allow(clippy::cast_precision_loss)] //TODO evaluate removal options
 fn to_float(value: Value) -> Resolved {
-    use Value::{Boolean, Bytes, Float, Integer, Null, Timestamp};
+    use Value::{Boolean, Bytes, Decimal, Duration, Float, Integer, Null, Timestamp};
     match value {
         Float(_) => Ok(value),
         Integer(v) => Ok(Value::from_f64_or_zero(v as f64)),
+        Decimal(v) => Ok(Value::from_f64_or_zero(v.to_f64().unwrap_or_default())),
         Boolean(v) => Ok(NotNan::new(if v { 1.0 } else { 0.0 }).unwrap().into()),
         Null => Ok(NotNan::new(0.0).unwrap().into()),
         Timestamp(v) => {
@@ -22,6 +24,9 @@ fn to_float(value: Value) -> Resolved {
             };
             Ok(Value::from_f64_or_zero(nanoseconds / 1_000_000_000_f64))
         }
+        Duration(v) => Ok(Value::from_f64_or_zero(
+            v.num_seconds() as f64 + f64::from(v.subsec_nanos()) / 1_000_000_000_f64,
+        )),
         Bytes(v) => Conversion::Float
             .convert(v)
             .map_err(|e| e.to_string().into()),
@@ -57,9 +62,11 @@ impl Function for ToFloat {
         &[
             "If `value` is a float, it will be returned as-is.",
             "If `value` is an integer, it will be returned as as a float.",
+            "If `value` is a decimal, it will be returned as the nearest float.",
             "If `value` is a string, it must be the string representation of an float or else an error is raised.",
             "If `value` is a boolean, `0.0` is returned for `false` and `1.0` is returned for `true`.",
             "If `value` is a timestamp, a [Unix timestamp](https://en.wikipedia.org/wiki/Unix_time) with fractional seconds is returned.",
+            "If `value` is a duration, its length in seconds is returned.",
         ]
     }
 
@@ -178,7 +185,7 @@ impl FunctionExpression for ToFloatFn {
 
 #[cfg(test)]
 mod tests {
-    use chrono::prelude::*;
+    use chrono::{TimeDelta, prelude::*};
 
     use super::*;
 
@@ -203,5 +210,11 @@ mod tests {
              want: Ok(1_404_810_611.012),
              tdef: TypeDef::float().infallible(),
         }
+
+        duration {
+             args: func_args![value: TimeDelta::milliseconds(-1500)],
+             want: Ok(-1.5),
+             tdef: TypeDef::float().infallible(),
+        }
     ];
 }
@@ -1,19 +1,25 @@
 use crate::compiler::conversion::Conversion;
 use crate::compiler::prelude::*;
+use rust_decimal::prelude::ToPrimitive;
 
 fn to_int(value: Value) -> Resolved {
-    use Value::{Boolean, Bytes, Float, Integer, Null, Timestamp};
+    use Value::{Boolean, Bytes, Decimal, Duration, Float, Integer, Null, Timestamp};
 
     match value {
         Integer(_) => Ok(value),
         #[allow(clippy::cast_possible_truncation)] //TODO evaluate removal options
         Float(v) => Ok(Integer(v.into_inner() as i64)),
+        Decimal(v) => v
+            .to_i64()
+            .map(Integer)
+            .ok_or_else(|| format!("decimal {v} is out of range for an integer").into()),
         Boolean(v) => Ok(Integer(i64::from(v))),
         Null => Ok(0.into()),
         Bytes(v) => Conversion::Integer
             .convert(v)
             .map_err(|e| e.to_string().into()),
         Timestamp(v) => Ok(v.timestamp().into()),
+        Duration(v) => Ok(v.num_seconds().into()),
         v => Err(format!("unable to coerce {} into integer", v.kind()).into()),
     }
 }
@@ -49,9 +55,11 @@ impl Function for ToInt {
         &[
             "If `value` is an integer, it will be returned as-is.",
             "If `value` is a float, it will be truncated to its integer portion.",
+            "If `value` is a decimal, it will be truncated to its integer portion, or an error is raised if it does not fit an integer.",
             "If `value` is a string, it must be the string representation of an integer or else an error is raised.",
             "If `value` is a boolean, `0` is returned for `false` and `1` is returned for `true`.",
             "If `value` is a timestamp, a [Unix timestamp](https://en.wikipedia.org/wiki/Unix_time) (in seconds) is returned.",
+            "If `value` is a duration, its length in whole seconds is returned.",
             "If `value` is null, `0` is returned.",
         ]
     }
@@ -162,6 +170,7 @@ impl FunctionExpression for ToIntFn {
 
         TypeDef::integer().maybe_fallible(
             td.contains_bytes()
+                || td.contains_decimal()
                 || td.contains_array()
                 || td.contains_object()
                 || td.contains_regex(),
@@ -171,7 +180,7 @@ impl FunctionExpression for ToIntFn {
 
 #[cfg(test)]
 mod tests {
-    use chrono::{DateTime, Utc};
+    use chrono::{DateTime, TimeDelta, Utc};
 
     use super::*;
 
@@ -197,5 +206,11 @@ mod tests {
              want: Ok(1_571_227_200),
              tdef: TypeDef::integer().infallible(),
          }
+
+        duration {
+             args: func_args![value: TimeDelta::milliseconds(90_500)],
+             want: Ok(90),
+             tdef: TypeDef::integer().infallible(),
+        }
     ];
 }
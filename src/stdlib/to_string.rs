@@ -1,14 +1,16 @@
 use crate::compiler::prelude::*;
 
 fn to_string(value: Value) -> Resolved {
-    use Value::{Boolean, Bytes, Float, Integer, Null, Timestamp};
+    use Value::{Boolean, Bytes, Decimal, Duration, Float, Integer, Null, Timestamp};
     use chrono::SecondsFormat;
     let value = match value {
         v @ Bytes(_) => v,
         Integer(v) => v.to_string().into(),
         Float(v) => v.to_string().into(),
+        Decimal(v) => v.to_string().into(),
         Boolean(v) => v.to_string().into(),
         Timestamp(v) => v.to_rfc3339_opts(SecondsFormat::AutoSi, true).into(),
+        Duration(v) => crate::value::value::format_duration(v).into(),
         Null => "".into(),
         v => return Err(format!("unable to coerce {} into string", v.kind()).into()),
     };
@@ -32,7 +34,7 @@ impl Function for ToString {
     }
 
     fn internal_failure_reasons(&self) -> &'static [&'static str] {
-        &["`value` is not an integer, float, boolean, string, timestamp, or null."]
+        &["`value` is not an integer, float, boolean, string, timestamp, duration, or null."]
     }
 
     fn return_kind(&self) -> u16 {
@@ -41,9 +43,10 @@ impl Function for ToString {
 
     fn return_rules(&self) -> &'static [&'static str] {
         &[
-            "If `value` is an integer or float, returns the string representation.",
+            "If `value` is an integer, float or decimal, returns the string representation.",
             "If `value` is a boolean, returns `\"true\"` or `\"false\"`.",
             "If `value` is a timestamp, returns an [RFC 3339](\\(urls.rfc3339)) representation.",
+            "If `value` is a duration, returns it with the largest units first, such as `1h30m`.",
             "If `value` is a null, returns `\"\"`.",
         ]
     }
@@ -168,5 +171,11 @@ mod tests {
             want: Ok("20.5"),
             tdef: TypeDef::bytes(),
         }
+
+        duration {
+            args: func_args![value: chrono::TimeDelta::seconds(5400)],
+            want: Ok("1h30m"),
+            tdef: TypeDef::bytes(),
+        }
     ];
 }
@@ -63,7 +63,6 @@ impl FunctionExpression for UuidV4Fn {
 mod tests {
     use super::*;
     use crate::value::Value;
-    use std::collections::BTreeMap;
 
     test_type_def![default {
         expr: |_| { UuidV4Fn },
@@ -73,7 +72,7 @@ mod tests {
     #[test]
     fn uuid_v4() {
         let mut state = state::RuntimeState::default();
-        let mut object: Value = Value::Object(BTreeMap::new());
+        let mut object: Value = Value::Object(ObjectMap::new());
         let tz = TimeZone::default();
         let mut ctx = Context::new(&mut object, &mut state, &tz);
         let value = UuidV4Fn.resolve(&mut ctx).unwrap();
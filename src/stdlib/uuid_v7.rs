@@ -124,7 +124,6 @@ impl FunctionExpression for UuidV7Fn {
 mod tests {
     use super::*;
     use crate::value::Value;
-    use std::collections::BTreeMap;
 
     test_type_def![default {
         expr: |_| { UuidV7Fn { timestamp: None } },
@@ -134,7 +133,7 @@ mod tests {
     #[test]
     fn uuid_v7() {
         let mut state = state::RuntimeState::default();
-        let mut object: Value = Value::Object(BTreeMap::new());
+        let mut object: Value = Value::Object(ObjectMap::new());
         let tz = TimeZone::default();
         let mut ctx = Context::new(&mut object, &mut state, &tz);
         let value = UuidV7Fn { timestamp: None }.resolve(&mut ctx).unwrap();
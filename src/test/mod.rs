@@ -2,7 +2,7 @@
 #![allow(clippy::print_stderr)] // tests
 
 use std::path::{MAIN_SEPARATOR, PathBuf};
-use std::{collections::BTreeMap, env, str::FromStr, time::Instant};
+use std::{env, str::FromStr, time::Instant};
 
 use chrono::{DateTime, SecondsFormat, Utc};
 use nu_ansi_term::Color;
@@ -151,7 +151,7 @@ pub fn run_tests<T>(
                     let run_start = Instant::now();
 
                     finalize_config(config_metadata);
-                    let result = run_vrl(program, &mut test.object, cfg.timezone, cfg.runtime);
+                    let result = run_vrl(program, &mut test, cfg.timezone, cfg.runtime);
                     let run_end = run_start.elapsed();
 
                     let timings = {
@@ -220,6 +220,11 @@ fn process_result(
                     Ok(dt) => dt.to_rfc3339_opts(SecondsFormat::AutoSi, true).into(),
                     Err(_) => want.into(),
                 }
+            } else if want.starts_with("d'") && want.ends_with('\'') {
+                match crate::value::value::parse_duration(&want[2..want.len() - 1]) {
+                    Ok(duration) => crate::value::value::format_duration(duration).into(),
+                    Err(_) => want.into(),
+                }
             } else if want.starts_with("s'") && want.ends_with('\'') {
                 want[2..want.len() - 1].into()
             } else {
@@ -229,12 +234,10 @@ fn process_result(
                 })
             };
 
-            if match_mode.matches(&got_value, &want_value) {
-                print!(
-                    "{timings}{}",
-                    Color::Green.bold().paint(match_mode.ok_label())
-                );
-            } else {
+            let got_metadata = vrl_value_to_json_value(test.metadata.clone());
+            let want_metadata = test.result_metadata.as_ref();
+
+            if !match_mode.matches(&got_value, &want_value) {
                 print!("{}", Color::Red.bold().paint(match_mode.fail_label()));
 
                 if !config.no_diff {
@@ -246,6 +249,24 @@ fn process_result(
                 }
 
                 failed = true;
+            } else if let Some(want_metadata) = want_metadata.filter(|want| **want != got_metadata)
+            {
+                print!("{}", Color::Red.bold().paint("FAILED (metadata)"));
+
+                if !config.no_diff {
+                    let want = serde_json::to_string_pretty(want_metadata).unwrap();
+                    let got = serde_json::to_string_pretty(&got_metadata).unwrap();
+
+                    let diff = prettydiff::diff_lines(&want, &got);
+                    println!("  {diff}");
+                }
+
+                failed = true;
+            } else {
+                print!(
+                    "{timings}{}",
+                    Color::Green.bold().paint(match_mode.ok_label())
+                );
             }
             println!();
 
@@ -422,6 +443,9 @@ fn vrl_value_to_json_value(value: Value) -> serde_json::Value {
         v @ Value::Bytes(_) => String(v.try_bytes_utf8_lossy().unwrap().into_owned()),
         Value::Integer(v) => v.into(),
         Value::Float(v) => v.into_inner().into(),
+        Value::Decimal(v) => {
+            serde_json::to_value(Value::Decimal(v)).unwrap_or_else(|_| String(v.to_string()))
+        }
         Value::Boolean(v) => v.into(),
         Value::Object(v) => v
             .into_iter()
@@ -432,6 +456,7 @@ fn vrl_value_to_json_value(value: Value) -> serde_json::Value {
             .map(vrl_value_to_json_value)
             .collect::<serde_json::Value>(),
         Value::Timestamp(v) => v.to_rfc3339_opts(SecondsFormat::AutoSi, true).into(),
+        Value::Duration(v) => crate::value::value::format_duration(v).into(),
         Value::Regex(v) => v.to_string().into(),
         Value::Null => Null,
     }
@@ -467,21 +492,17 @@ impl MatchMode {
 
 fn run_vrl(
     program: Program,
-    test_object: &mut Value,
+    test: &mut Test,
     timezone: TimeZone,
     vrl_runtime: VrlRuntime,
 ) -> Result<Value, Terminate> {
-    let mut metadata = Value::from(BTreeMap::new());
+    let mut secrets = secrets(test);
     let mut target = TargetValueRef {
-        value: test_object,
-        metadata: &mut metadata,
-        secrets: &mut Secrets::new(),
+        value: &mut test.object,
+        metadata: &mut test.metadata,
+        secrets: &mut secrets,
     };
 
-    // Insert a dummy secret for examples to use
-    target.insert_secret("my_secret", "secret value");
-    target.insert_secret("datadog_api_key", "secret value");
-
     match vrl_runtime {
         VrlRuntime::Ast => {
             // test_enrichment.finish_load();
@@ -490,3 +511,63 @@ fn run_vrl(
         }
     }
 }
+
+/// The secrets a test runs with: dummy secrets for the examples, and those set by the test.
+fn secrets(test: &Test) -> Secrets {
+    let mut secrets = Secrets::new();
+    secrets.insert_secret("my_secret", "secret value");
+    secrets.insert_secret("datadog_api_key", "secret value");
+
+    for (key, value) in &test.secrets {
+        secrets.insert_secret(key, value);
+    }
+
+    secrets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test(content: &str) -> Test {
+        Test::from_content(
+            "secrets".to_owned(),
+            "test".to_owned(),
+            content,
+            "secrets.vrl".to_owned(),
+        )
+    }
+
+    #[test]
+    fn secrets_directive() {
+        let test = test(indoc::indoc! {r#"
+            # secrets: { "token": "s3cr3t", "my_secret": "overridden" }
+            # result: null
+
+            null
+        "#});
+        assert_eq!(test.error, None);
+
+        let secrets = secrets(&test);
+        assert_eq!(secrets.get_secret("token"), Some("s3cr3t"));
+        assert_eq!(secrets.get_secret("my_secret"), Some("overridden"));
+        assert_eq!(secrets.get_secret("datadog_api_key"), Some("secret value"));
+        assert_eq!(secrets.get_secret("missing"), None);
+    }
+
+    #[test]
+    fn invalid_secrets_directive() {
+        let test = test(indoc::indoc! {r#"
+            # secrets: { "token": 1 }
+            # result: null
+
+            null
+        "#});
+
+        assert!(
+            test.error
+                .unwrap()
+                .starts_with("unable to parse secrets as a JSON object of strings")
+        );
+    }
+}
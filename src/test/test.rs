@@ -4,7 +4,7 @@ use crate::compiler::function::Example;
 use crate::path::OwnedTargetPath;
 use crate::path::parse_value_path;
 use crate::test::{example_vrl_path, test_prefix};
-use crate::value::Value;
+use crate::value::{ObjectMap, Value};
 
 #[derive(Debug)]
 pub struct Test {
@@ -13,7 +13,10 @@ pub struct Test {
     pub error: Option<String>,
     pub source: String,
     pub object: Value,
+    pub metadata: Value,
+    pub secrets: BTreeMap<String, String>,
     pub result: String,
+    pub result_metadata: Option<serde_json::Value>,
     pub result_approx: bool,
     pub skip: bool,
     pub check_diagnostics: bool,
@@ -27,19 +30,38 @@ pub struct Test {
 enum CaptureMode {
     Result,
     Object,
+    Metadata,
+    Secrets,
+    ResultMetadata,
     None,
     Done,
 }
 
 impl Test {
     pub fn from_path(path: &Path) -> Self {
-        let name = test_name(path);
-        let category = test_category(path);
         let content = fs::read_to_string(path).expect("content");
 
+        Self::from_content(
+            test_name(path),
+            test_category(path),
+            &content,
+            path.to_string_lossy().to_string(),
+        )
+    }
+
+    /// Parse a test from the content of a `.vrl` file, its directives followed by the program.
+    pub(crate) fn from_content(
+        name: String,
+        category: String,
+        content: &str,
+        source_file: String,
+    ) -> Self {
         let mut source = String::new();
         let mut object = String::new();
+        let mut metadata = String::new();
+        let mut secrets = String::new();
         let mut result = String::new();
+        let mut result_metadata = String::new();
         let mut result_approx = false;
 
         let mut read_only_paths = vec![];
@@ -53,6 +75,21 @@ impl Test {
                 if line.starts_with("object:") {
                     capture_mode = CaptureMode::Object;
                     line = line.strip_prefix("object:").expect("object").trim_start();
+                } else if line.starts_with("metadata:") {
+                    capture_mode = CaptureMode::Metadata;
+                    line = line
+                        .strip_prefix("metadata:")
+                        .expect("metadata")
+                        .trim_start();
+                } else if line.starts_with("secrets:") {
+                    capture_mode = CaptureMode::Secrets;
+                    line = line.strip_prefix("secrets:").expect("secrets").trim_start();
+                } else if line.starts_with("result_metadata:") {
+                    capture_mode = CaptureMode::ResultMetadata;
+                    line = line
+                        .strip_prefix("result_metadata:")
+                        .expect("result_metadata")
+                        .trim_start();
                 } else if line.starts_with("result: ~") {
                     capture_mode = CaptureMode::Result;
                     result_approx = true;
@@ -108,6 +145,15 @@ impl Test {
                     CaptureMode::Object => {
                         object.push_str(line);
                     }
+                    CaptureMode::Metadata => {
+                        metadata.push_str(line);
+                    }
+                    CaptureMode::Secrets => {
+                        secrets.push_str(line);
+                    }
+                    CaptureMode::ResultMetadata => {
+                        result_metadata.push_str(line);
+                    }
                 }
             } else {
                 capture_mode = CaptureMode::Done;
@@ -119,7 +165,7 @@ impl Test {
 
         let mut error = None;
         let object = if object.is_empty() {
-            Value::Object(BTreeMap::default())
+            Value::Object(ObjectMap::new())
         } else {
             serde_json::from_str::<'_, Value>(&object).unwrap_or_else(|err| {
                 error = Some(format!("unable to parse object as JSON: {err}"));
@@ -127,6 +173,36 @@ impl Test {
             })
         };
 
+        let metadata = if metadata.is_empty() {
+            Value::Object(ObjectMap::new())
+        } else {
+            serde_json::from_str::<'_, Value>(&metadata).unwrap_or_else(|err| {
+                error = Some(format!("unable to parse metadata as JSON: {err}"));
+                Value::Null
+            })
+        };
+
+        let secrets = if secrets.is_empty() {
+            BTreeMap::default()
+        } else {
+            serde_json::from_str(&secrets).unwrap_or_else(|err| {
+                error = Some(format!(
+                    "unable to parse secrets as a JSON object of strings: {err}"
+                ));
+                BTreeMap::default()
+            })
+        };
+
+        let result_metadata = if result_metadata.is_empty() {
+            None
+        } else {
+            serde_json::from_str(&result_metadata)
+                .map_err(|err| {
+                    error = Some(format!("unable to parse result_metadata as JSON: {err}"));
+                })
+                .ok()
+        };
+
         {
             result = result.trim_end().to_owned();
         }
@@ -137,12 +213,15 @@ impl Test {
             error,
             source,
             object,
+            metadata,
+            secrets,
             result,
+            result_metadata,
             result_approx,
             skip: content.starts_with("# SKIP"),
             check_diagnostics: content.starts_with("# DIAGNOSTICS"),
             read_only_paths,
-            source_file: path.to_string_lossy().to_string(),
+            source_file,
             source_line: 1,
             check_type_only: false,
         }
@@ -153,7 +232,7 @@ impl Test {
             Some(input) => {
                 serde_json::from_str::<Value>(input).expect("example input should be valid JSON")
             }
-            None => Value::Object(BTreeMap::default()),
+            None => Value::Object(ObjectMap::new()),
         };
         let result = match example.result {
             Ok(string) => string.to_owned(),
@@ -166,7 +245,10 @@ impl Test {
             error: None,
             source: example.source.to_owned(),
             object,
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: BTreeMap::default(),
             result,
+            result_metadata: None,
             result_approx: false,
             skip: example.skip,
             check_diagnostics: false,
@@ -28,8 +28,10 @@ pub struct Kind {
     bytes: Option<()>,
     integer: Option<()>,
     float: Option<()>,
+    decimal: Option<()>,
     boolean: Option<()>,
     timestamp: Option<()>,
+    duration: Option<()>,
     regex: Option<()>,
     null: Option<()>,
     undefined: Option<()>,
@@ -64,12 +66,18 @@ impl std::fmt::Display for Kind {
         if self.contains_float() {
             kinds.push("float");
         }
+        if self.contains_decimal() {
+            kinds.push("decimal");
+        }
         if self.contains_boolean() {
             kinds.push("boolean");
         }
         if self.contains_timestamp() {
             kinds.push("timestamp");
         }
+        if self.contains_duration() {
+            kinds.push("duration");
+        }
         if self.contains_regex() {
             kinds.push("regex");
         }
@@ -120,12 +128,18 @@ impl PartialEq for Kind {
         if a.float != b.float {
             return false;
         }
+        if a.decimal != b.decimal {
+            return false;
+        }
         if a.boolean != b.boolean {
             return false;
         }
         if a.timestamp != b.timestamp {
             return false;
         }
+        if a.duration != b.duration {
+            return false;
+        }
         if a.regex != b.regex {
             return false;
         }
@@ -167,8 +181,10 @@ impl From<&Value> for Kind {
             Value::Bytes(_) => Self::bytes(),
             Value::Integer(_) => Self::integer(),
             Value::Float(_) => Self::float(),
+            Value::Decimal(_) => Self::decimal(),
             Value::Boolean(_) => Self::boolean(),
             Value::Timestamp(_) => Self::timestamp(),
+            Value::Duration(_) => Self::duration(),
             Value::Regex(_) => Self::regex(),
             Value::Null => Self::null(),
 
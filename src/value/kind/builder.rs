@@ -12,8 +12,10 @@ impl Kind {
             bytes: Some(()),
             integer: Some(()),
             float: Some(()),
+            decimal: Some(()),
             boolean: Some(()),
             timestamp: Some(()),
+            duration: Some(()),
             regex: Some(()),
             null: Some(()),
             undefined: Some(()),
@@ -32,8 +34,10 @@ impl Kind {
             bytes: Some(()),
             integer: Some(()),
             float: Some(()),
+            decimal: Some(()),
             boolean: Some(()),
             timestamp: None,
+            duration: None,
             regex: None,
             null: Some(()),
             undefined: None,
@@ -49,8 +53,10 @@ impl Kind {
             bytes: Some(()),
             integer: None,
             float: None,
+            decimal: None,
             boolean: None,
             timestamp: None,
+            duration: None,
             regex: None,
             null: None,
             undefined: None,
@@ -66,8 +72,10 @@ impl Kind {
             bytes: None,
             integer: Some(()),
             float: None,
+            decimal: None,
             boolean: None,
             timestamp: None,
+            duration: None,
             regex: None,
             null: None,
             undefined: None,
@@ -83,8 +91,29 @@ impl Kind {
             bytes: None,
             integer: None,
             float: Some(()),
+            decimal: None,
             boolean: None,
             timestamp: None,
+            duration: None,
+            regex: None,
+            null: None,
+            undefined: None,
+            array: None,
+            object: None,
+        }
+    }
+
+    /// The "decimal" type state.
+    #[must_use]
+    pub const fn decimal() -> Self {
+        Self {
+            bytes: None,
+            integer: None,
+            float: None,
+            decimal: Some(()),
+            boolean: None,
+            timestamp: None,
+            duration: None,
             regex: None,
             null: None,
             undefined: None,
@@ -100,8 +129,10 @@ impl Kind {
             bytes: None,
             integer: None,
             float: None,
+            decimal: None,
             boolean: Some(()),
             timestamp: None,
+            duration: None,
             regex: None,
             null: None,
             undefined: None,
@@ -117,8 +148,29 @@ impl Kind {
             bytes: None,
             integer: None,
             float: None,
+            decimal: None,
             boolean: None,
             timestamp: Some(()),
+            duration: None,
+            regex: None,
+            null: None,
+            undefined: None,
+            array: None,
+            object: None,
+        }
+    }
+
+    /// The "duration" type state.
+    #[must_use]
+    pub const fn duration() -> Self {
+        Self {
+            bytes: None,
+            integer: None,
+            float: None,
+            decimal: None,
+            boolean: None,
+            timestamp: None,
+            duration: Some(()),
             regex: None,
             null: None,
             undefined: None,
@@ -134,8 +186,10 @@ impl Kind {
             bytes: None,
             integer: None,
             float: None,
+            decimal: None,
             boolean: None,
             timestamp: None,
+            duration: None,
             regex: Some(()),
             null: None,
             undefined: None,
@@ -151,8 +205,10 @@ impl Kind {
             bytes: None,
             integer: None,
             float: None,
+            decimal: None,
             boolean: None,
             timestamp: None,
+            duration: None,
             regex: None,
             null: Some(()),
             undefined: None,
@@ -168,8 +224,10 @@ impl Kind {
             bytes: None,
             integer: None,
             float: None,
+            decimal: None,
             boolean: None,
             timestamp: None,
+            duration: None,
             regex: None,
             null: None,
             undefined: Some(()),
@@ -185,8 +243,10 @@ impl Kind {
             bytes: None,
             integer: None,
             float: None,
+            decimal: None,
             boolean: None,
             timestamp: None,
+            duration: None,
             regex: None,
             null: None,
             undefined: None,
@@ -202,8 +262,10 @@ impl Kind {
             bytes: None,
             integer: None,
             float: None,
+            decimal: None,
             boolean: None,
             timestamp: None,
+            duration: None,
             regex: None,
             null: None,
             undefined: None,
@@ -219,8 +281,10 @@ impl Kind {
             bytes: None,
             integer: None,
             float: None,
+            decimal: None,
             boolean: None,
             timestamp: None,
+            duration: None,
             regex: None,
             null: None,
             undefined: None,
@@ -259,6 +323,13 @@ impl Kind {
         self
     }
 
+    /// Add the `decimal` state to the type.
+    #[must_use]
+    pub const fn or_decimal(mut self) -> Self {
+        self.decimal = Some(());
+        self
+    }
+
     /// Add the `boolean` state to the type.
     #[must_use]
     pub const fn or_boolean(mut self) -> Self {
@@ -273,6 +344,13 @@ impl Kind {
         self
     }
 
+    /// Add the `duration` state to the type.
+    #[must_use]
+    pub const fn or_duration(mut self) -> Self {
+        self.duration = Some(());
+        self
+    }
+
     /// Add the `regex` state to the type.
     #[must_use]
     pub const fn or_regex(mut self) -> Self {
@@ -332,6 +410,13 @@ impl Kind {
         self.float.replace(()).is_none()
     }
 
+    /// Add the `decimal` state to the type.
+    ///
+    /// If the type already included this state, the function returns `false`.
+    pub fn add_decimal(&mut self) -> bool {
+        self.decimal.replace(()).is_none()
+    }
+
     /// Add the `boolean` state to the type.
     ///
     /// If the type already included this state, the function returns `false`.
@@ -346,6 +431,13 @@ impl Kind {
         self.timestamp.replace(()).is_none()
     }
 
+    /// Add the `duration` state to the type.
+    ///
+    /// If the type already included this state, the function returns `false`.
+    pub fn add_duration(&mut self) -> bool {
+        self.duration.replace(()).is_none()
+    }
+
     /// Add the `regex` state to the type.
     ///
     /// If the type already included this state, the function returns `false`.
@@ -405,6 +497,13 @@ impl Kind {
         self.float.take().is_some()
     }
 
+    /// Remove the `decimal` state from the type.
+    ///
+    /// If the type previously included this state, true is returned.
+    pub fn remove_decimal(&mut self) -> bool {
+        self.decimal.take().is_some()
+    }
+
     /// Remove the `boolean` state from the type.
     ///
     /// If the type previously included this state, true is returned.
@@ -419,6 +518,13 @@ impl Kind {
         self.timestamp.take().is_some()
     }
 
+    /// Remove the `duration` state from the type.
+    ///
+    /// If the type previously included this state, true is returned.
+    pub fn remove_duration(&mut self) -> bool {
+        self.duration.take().is_some()
+    }
+
     /// Remove the `regex` state from the type.
     ///
     /// If the type previously included this state, true is returned.
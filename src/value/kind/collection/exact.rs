@@ -11,8 +11,10 @@ pub struct Exact {
     pub(super) bytes: bool,
     pub(super) integer: bool,
     pub(super) float: bool,
+    pub(super) decimal: bool,
     pub(super) boolean: bool,
     pub(super) timestamp: bool,
+    pub(super) duration: bool,
     pub(super) regex: bool,
     pub(super) null: bool,
     pub(super) object: bool,
@@ -25,8 +27,10 @@ impl Exact {
             bytes: true,
             integer: true,
             float: true,
+            decimal: true,
             boolean: true,
             timestamp: false,
+            duration: false,
             regex: false,
             null: true,
             object: true,
@@ -50,6 +54,10 @@ impl Exact {
             return false;
         }
 
+        if (false, true) == (self.decimal, other.decimal) {
+            return false;
+        }
+
         if (false, true) == (self.boolean, other.boolean) {
             return false;
         }
@@ -58,6 +66,10 @@ impl Exact {
             return false;
         }
 
+        if (false, true) == (self.duration, other.duration) {
+            return false;
+        }
+
         if (false, true) == (self.regex, other.regex) {
             return false;
         }
@@ -86,8 +98,10 @@ impl BitOr for Exact {
             bytes: self.bytes | rhs.bytes,
             integer: self.integer | rhs.integer,
             float: self.float | rhs.float,
+            decimal: self.decimal | rhs.decimal,
             boolean: self.boolean | rhs.boolean,
             timestamp: self.timestamp | rhs.timestamp,
+            duration: self.duration | rhs.duration,
             regex: self.regex | rhs.regex,
             null: self.null | rhs.null,
             object: self.object | rhs.object,
@@ -112,6 +126,10 @@ impl From<Exact> for Kind {
             kind.add_float();
         }
 
+        if exact.decimal {
+            kind.add_decimal();
+        }
+
         if exact.boolean {
             kind.add_boolean();
         }
@@ -120,6 +138,10 @@ impl From<Exact> for Kind {
             kind.add_timestamp();
         }
 
+        if exact.duration {
+            kind.add_duration();
+        }
+
         if exact.regex {
             kind.add_regex();
         }
@@ -152,8 +174,10 @@ impl From<&Kind> for Exact {
             bytes: kind.contains_bytes(),
             integer: kind.contains_integer(),
             float: kind.contains_float(),
+            decimal: kind.contains_decimal(),
             boolean: kind.contains_boolean(),
             timestamp: kind.contains_timestamp(),
+            duration: kind.contains_duration(),
             regex: kind.contains_regex(),
             null: kind.contains_null(),
             object: kind.contains_object(),
@@ -193,8 +217,10 @@ mod tests {
                         bytes: true,
                         integer: false,
                         float: false,
+                        decimal: false,
                         boolean: false,
                         timestamp: false,
+                        duration: false,
                         regex: false,
                         null: false,
                         object: false,
@@ -204,8 +230,10 @@ mod tests {
                         bytes: true,
                         integer: false,
                         float: false,
+                        decimal: false,
                         boolean: false,
                         timestamp: false,
+                        duration: false,
                         regex: false,
                         null: false,
                         object: false,
@@ -221,8 +249,10 @@ mod tests {
                         bytes: true,
                         integer: true,
                         float: false,
+                        decimal: false,
                         boolean: true,
                         timestamp: false,
+                        duration: false,
                         regex: false,
                         null: false,
                         object: false,
@@ -232,8 +262,10 @@ mod tests {
                         bytes: true,
                         integer: true,
                         float: false,
+                        decimal: false,
                         boolean: true,
                         timestamp: false,
+                        duration: false,
                         regex: false,
                         null: false,
                         object: false,
@@ -249,8 +281,10 @@ mod tests {
                         bytes: true,
                         integer: true,
                         float: false,
+                        decimal: false,
                         boolean: true,
                         timestamp: false,
+                        duration: false,
                         regex: false,
                         null: false,
                         object: false,
@@ -260,8 +294,10 @@ mod tests {
                         bytes: true,
                         integer: true,
                         float: false,
+                        decimal: false,
                         boolean: false,
                         timestamp: false,
+                        duration: false,
                         regex: false,
                         null: false,
                         object: false,
@@ -277,8 +313,10 @@ mod tests {
                         bytes: true,
                         integer: true,
                         float: false,
+                        decimal: false,
                         boolean: true,
                         timestamp: false,
+                        duration: false,
                         regex: false,
                         null: false,
                         object: false,
@@ -288,8 +326,10 @@ mod tests {
                         bytes: true,
                         integer: true,
                         float: false,
+                        decimal: false,
                         boolean: false,
                         timestamp: false,
+                        duration: false,
                         regex: true,
                         null: false,
                         object: false,
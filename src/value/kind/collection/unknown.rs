@@ -177,8 +177,10 @@ pub(super) struct Infinite {
     bytes: Option<()>,
     integer: Option<()>,
     float: Option<()>,
+    decimal: Option<()>,
     boolean: Option<()>,
     timestamp: Option<()>,
+    duration: Option<()>,
     regex: Option<()>,
     null: Option<()>,
     array: Option<()>,
@@ -191,8 +193,10 @@ impl Infinite {
             bytes: Some(()),
             integer: Some(()),
             float: Some(()),
+            decimal: Some(()),
             boolean: Some(()),
             timestamp: Some(()),
+            duration: Some(()),
             regex: Some(()),
             null: Some(()),
             array: Some(()),
@@ -205,8 +209,10 @@ impl Infinite {
             bytes: Some(()),
             integer: Some(()),
             float: Some(()),
+            decimal: Some(()),
             boolean: Some(()),
             timestamp: None,
+            duration: None,
             regex: None,
             null: Some(()),
             array: Some(()),
@@ -219,8 +225,10 @@ impl Infinite {
         self.bytes.is_some()
             && self.integer.is_some()
             && self.float.is_some()
+            && self.decimal.is_some()
             && self.boolean.is_some()
             && self.timestamp.is_some()
+            && self.duration.is_some()
             && self.regex.is_some()
             && self.null.is_some()
             && self.array.is_some()
@@ -233,8 +241,10 @@ impl Infinite {
         self.bytes.is_some()
             && self.integer.is_some()
             && self.float.is_some()
+            && self.decimal.is_some()
             && self.boolean.is_some()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.null.is_some()
             && self.array.is_some()
@@ -258,6 +268,10 @@ impl Infinite {
             return false;
         }
 
+        if let (None, Some(())) = (self.decimal, other.decimal) {
+            return false;
+        }
+
         if let (None, Some(())) = (self.boolean, other.boolean) {
             return false;
         }
@@ -266,6 +280,10 @@ impl Infinite {
             return false;
         }
 
+        if let (None, Some(())) = (self.duration, other.duration) {
+            return false;
+        }
+
         if let (None, Some(())) = (self.regex, other.regex) {
             return false;
         }
@@ -290,8 +308,10 @@ impl Infinite {
         self.bytes = self.bytes.or(other.bytes);
         self.integer = self.integer.or(other.integer);
         self.float = self.float.or(other.float);
+        self.decimal = self.decimal.or(other.decimal);
         self.boolean = self.boolean.or(other.boolean);
         self.timestamp = self.timestamp.or(other.timestamp);
+        self.duration = self.duration.or(other.duration);
         self.regex = self.regex.or(other.regex);
         self.null = self.null.or(other.null);
         self.array = self.array.or(other.array);
@@ -315,6 +335,10 @@ impl From<Infinite> for Kind {
             kind.add_float();
         }
 
+        if infinite.decimal.is_some() {
+            kind.add_decimal();
+        }
+
         if infinite.boolean.is_some() {
             kind.add_boolean();
         }
@@ -323,6 +347,10 @@ impl From<Infinite> for Kind {
             kind.add_timestamp();
         }
 
+        if infinite.duration.is_some() {
+            kind.add_duration();
+        }
+
         if infinite.regex.is_some() {
             kind.add_regex();
         }
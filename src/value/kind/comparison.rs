@@ -10,8 +10,10 @@ impl Kind {
         self.contains_bytes()
             && self.contains_integer()
             && self.contains_float()
+            && self.contains_decimal()
             && self.contains_boolean()
             && self.contains_timestamp()
+            && self.contains_duration()
             && self.contains_regex()
             && self.contains_null()
             && self.contains_undefined()
@@ -25,8 +27,10 @@ impl Kind {
         self.contains_bytes()
             && self.contains_integer()
             && self.contains_float()
+            && self.contains_decimal()
             && self.contains_boolean()
             && !self.contains_timestamp()
+            && !self.contains_duration()
             && !self.contains_regex()
             && self.contains_null()
             && self.contains_undefined()
@@ -44,8 +48,10 @@ impl Kind {
         !self.contains_bytes()
             && !self.contains_integer()
             && !self.contains_float()
+            && !self.contains_decimal()
             && !self.contains_boolean()
             && !self.contains_timestamp()
+            && !self.contains_duration()
             && !self.contains_regex()
             && !self.contains_null()
             && !self.contains_undefined()
@@ -56,8 +62,10 @@ impl Kind {
     pub const fn is_bytes(&self) -> bool {
         self.integer.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.null.is_none()
             && self.undefined.is_none()
@@ -70,8 +78,10 @@ impl Kind {
     pub const fn is_integer(&self) -> bool {
         self.bytes.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.null.is_none()
             && self.undefined.is_none()
@@ -85,8 +95,10 @@ impl Kind {
         self.bytes.is_none()
             && self.integer.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.null.is_none()
             && self.undefined.is_none()
@@ -99,8 +111,26 @@ impl Kind {
     pub const fn is_float(&self) -> bool {
         self.bytes.is_none()
             && self.integer.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
+            && self.regex.is_none()
+            && self.null.is_none()
+            && self.undefined.is_none()
+            && self.array.is_none()
+            && self.object.is_none()
+    }
+
+    /// Returns `true` if the type is `decimal`.
+    #[must_use]
+    pub const fn is_decimal(&self) -> bool {
+        self.bytes.is_none()
+            && self.integer.is_none()
+            && self.float.is_none()
+            && self.boolean.is_none()
+            && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.null.is_none()
             && self.undefined.is_none()
@@ -114,7 +144,9 @@ impl Kind {
         self.bytes.is_none()
             && self.integer.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.null.is_none()
             && self.undefined.is_none()
@@ -128,7 +160,25 @@ impl Kind {
         self.bytes.is_none()
             && self.integer.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
+            && self.boolean.is_none()
+            && self.duration.is_none()
+            && self.regex.is_none()
+            && self.null.is_none()
+            && self.undefined.is_none()
+            && self.array.is_none()
+            && self.object.is_none()
+    }
+
+    /// Returns `true` if the type is `duration`.
+    #[must_use]
+    pub const fn is_duration(&self) -> bool {
+        self.bytes.is_none()
+            && self.integer.is_none()
+            && self.float.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
+            && self.timestamp.is_none()
             && self.regex.is_none()
             && self.null.is_none()
             && self.undefined.is_none()
@@ -142,8 +192,10 @@ impl Kind {
         self.bytes.is_none()
             && self.integer.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.null.is_none()
             && self.undefined.is_none()
             && self.array.is_none()
@@ -156,8 +208,10 @@ impl Kind {
         self.bytes.is_none()
             && self.integer.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.undefined.is_none()
             && self.array.is_none()
@@ -170,8 +224,10 @@ impl Kind {
         self.bytes.is_none()
             && self.integer.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.null.is_none()
             && self.array.is_none()
@@ -184,8 +240,10 @@ impl Kind {
         self.bytes.is_none()
             && self.integer.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.null.is_none()
             && self.undefined.is_none()
@@ -198,8 +256,10 @@ impl Kind {
         self.bytes.is_none()
             && self.integer.is_none()
             && self.float.is_none()
+            && self.decimal.is_none()
             && self.boolean.is_none()
             && self.timestamp.is_none()
+            && self.duration.is_none()
             && self.regex.is_none()
             && self.null.is_none()
             && self.undefined.is_none()
@@ -212,8 +272,10 @@ impl Kind {
         self.is_bytes()
             || self.is_integer()
             || self.is_float()
+            || self.is_decimal()
             || self.is_boolean()
             || self.is_timestamp()
+            || self.is_duration()
             || self.is_regex()
             || self.is_null()
             || self.is_undefined()
@@ -246,6 +308,10 @@ impl Kind {
             return Err(OwnedValuePath::root());
         }
 
+        if let (None, Some(())) = (self.decimal, other.decimal) {
+            return Err(OwnedValuePath::root());
+        }
+
         if let (None, Some(())) = (self.boolean, other.boolean) {
             return Err(OwnedValuePath::root());
         }
@@ -254,6 +320,10 @@ impl Kind {
             return Err(OwnedValuePath::root());
         }
 
+        if let (None, Some(())) = (self.duration, other.duration) {
+            return Err(OwnedValuePath::root());
+        }
+
         if let (None, Some(())) = (self.regex, other.regex) {
             return Err(OwnedValuePath::root());
         }
@@ -305,6 +375,10 @@ impl Kind {
             return true;
         }
 
+        if self.contains_decimal() && other.contains_decimal() {
+            return true;
+        }
+
         if self.contains_boolean() && other.contains_boolean() {
             return true;
         }
@@ -313,6 +387,10 @@ impl Kind {
             return true;
         }
 
+        if self.contains_duration() && other.contains_duration() {
+            return true;
+        }
+
         if self.contains_regex() && other.contains_regex() {
             return true;
         }
@@ -357,6 +435,12 @@ impl Kind {
         self.float.is_some() || self.is_never()
     }
 
+    /// Returns `true` if the type is _at least_ `decimal`.
+    #[must_use]
+    pub const fn contains_decimal(&self) -> bool {
+        self.decimal.is_some() || self.is_never()
+    }
+
     /// Returns `true` if the type is _at least_ `boolean`.
     #[must_use]
     pub const fn contains_boolean(&self) -> bool {
@@ -369,6 +453,12 @@ impl Kind {
         self.timestamp.is_some() || self.is_never()
     }
 
+    /// Returns `true` if the type is _at least_ `duration`.
+    #[must_use]
+    pub const fn contains_duration(&self) -> bool {
+        self.duration.is_some() || self.is_never()
+    }
+
     /// Returns `true` if the type is _at least_ `regex`.
     #[must_use]
     pub const fn contains_regex(&self) -> bool {
@@ -412,9 +502,11 @@ impl Kind {
             || self.null.is_some()
             || self.boolean.is_some()
             || self.float.is_some()
+            || self.decimal.is_some()
             || self.integer.is_some()
             || self.regex.is_some()
             || self.timestamp.is_some()
+            || self.duration.is_some()
             || self.undefined.is_some()
     }
 }
@@ -2,6 +2,7 @@
 
 use crate::path::{BorrowedSegment, ValuePath};
 use crate::value::Kind;
+use crate::value::kind::Collection;
 use std::borrow::Cow;
 
 impl Kind {
@@ -42,6 +43,42 @@ impl Kind {
         })
     }
 
+    /// Returns the type of the array collecting the values matched by a wildcard or slice
+    /// segment, followed by the rest of the path. Wildcards also match the fields of objects.
+    fn get_projection<'a>(
+        &self,
+        match_objects: bool,
+        iter: impl Iterator<Item = BorrowedSegment<'a>> + Clone,
+    ) -> Self {
+        let mut elements = Self::never();
+        let mut unmatched = self.without_array();
+        if let Some(array) = self.as_array() {
+            elements.merge_keep(array.reduced_kind(), false);
+        }
+        if match_objects {
+            unmatched.remove_object();
+            if let Some(object) = self.as_object() {
+                elements.merge_keep(object.reduced_kind(), false);
+            }
+        }
+        if !(self.contains_array() || match_objects && self.contains_object()) {
+            return Self::undefined();
+        }
+
+        // Matched values that don't contain the rest of the path are skipped.
+        let projected = elements.get_recursive(iter).without_undefined();
+        let mut kind = Self::array(if projected.contains_any_defined() {
+            Collection::from_unknown(projected)
+        } else {
+            Collection::empty()
+        });
+
+        if !unmatched.is_never() {
+            kind.add_undefined();
+        }
+        kind
+    }
+
     fn get_recursive<'a>(
         &self,
         mut iter: impl Iterator<Item = BorrowedSegment<'a>> + Clone,
@@ -116,6 +153,8 @@ impl Kind {
                     Self::undefined()
                 }
             }
+            Some(BorrowedSegment::Wildcard) => self.get_projection(true, iter),
+            Some(BorrowedSegment::Slice { .. }) => self.get_projection(false, iter),
             Some(BorrowedSegment::Invalid) => {
                 // Value::get returns `None` in this case, which means the value is not defined.
                 Self::undefined()
@@ -128,7 +167,7 @@ impl Kind {
 #[cfg(test)]
 mod tests {
     use crate::owned_value_path;
-    use crate::path::OwnedValuePath;
+    use crate::path::{OwnedSegment, OwnedValuePath};
     use std::collections::BTreeMap;
 
     use super::*;
@@ -379,6 +418,61 @@ mod tests {
                     want: Kind::never(),
                 },
             ),
+            (
+                "wildcard field of array elements",
+                TestCase {
+                    kind: Kind::array(BTreeMap::from([
+                        (
+                            0.into(),
+                            Kind::object(BTreeMap::from([("price".into(), Kind::integer())])),
+                        ),
+                        (
+                            1.into(),
+                            Kind::object(BTreeMap::from([("price".into(), Kind::float())])),
+                        ),
+                    ])),
+                    path: owned_value_path!(OwnedSegment::Wildcard, "price"),
+                    want: Kind::array(Collection::from_unknown(Kind::integer().or_float())),
+                },
+            ),
+            (
+                "wildcard of object fields",
+                TestCase {
+                    kind: Kind::object(BTreeMap::from([
+                        ("a".into(), Kind::bytes()),
+                        ("b".into(), Kind::boolean()),
+                    ])),
+                    path: owned_value_path!(OwnedSegment::Wildcard),
+                    want: Kind::array(Collection::from_unknown(Kind::bytes().or_boolean())),
+                },
+            ),
+            (
+                "wildcard of maybe an array",
+                TestCase {
+                    kind: Kind::array(Collection::from_unknown(Kind::integer())).or_null(),
+                    path: owned_value_path!(OwnedSegment::Wildcard),
+                    want: Kind::array(Collection::from_unknown(Kind::integer())).or_undefined(),
+                },
+            ),
+            (
+                "wildcard of empty array",
+                TestCase {
+                    kind: Kind::array(Collection::empty()),
+                    path: owned_value_path!(OwnedSegment::Wildcard, "foo"),
+                    want: Kind::array(Collection::empty()),
+                },
+            ),
+            (
+                "slice of object",
+                TestCase {
+                    kind: Kind::object(Collection::any()),
+                    path: owned_value_path!(OwnedSegment::Slice {
+                        start: Some(1),
+                        end: None
+                    }),
+                    want: Kind::undefined(),
+                },
+            ),
         ] {
             assert_eq!(kind.at_path(&path), want, "test: {title}");
         }
@@ -182,6 +182,21 @@ impl Kind {
                         .or_insert(unknown_kind)
                         .insert_recursive(iter, kind);
                 }
+                BorrowedSegment::Wildcard => {
+                    // Only existing values are matched, so no collections are created.
+                    if let Some(array) = self.as_array_mut() {
+                        insert_matched(array, &iter, &kind, true);
+                    }
+                    if let Some(object) = self.as_object_mut() {
+                        insert_matched(object, &iter, &kind, true);
+                    }
+                }
+                BorrowedSegment::Slice { .. } => {
+                    // The slice may or may not match each of the elements.
+                    if let Some(array) = self.as_array_mut() {
+                        insert_matched(array, &iter, &kind, false);
+                    }
+                }
                 BorrowedSegment::Invalid => { /* An invalid path does nothing. */ }
             }
         } else {
@@ -190,12 +205,42 @@ impl Kind {
     }
 }
 
+/// Inserts the `Kind` at the remaining path of the elements of the collection. If `every` is
+/// false, the elements may also be left unchanged.
+fn insert_matched<'a, T: Ord + Clone>(
+    collection: &mut Collection<T>,
+    iter: &(impl Iterator<Item = BorrowedSegment<'a>> + Clone),
+    kind: &Kind,
+    every: bool,
+) {
+    let insert = |element: &mut Kind| {
+        let mut inserted = element.clone();
+        inserted.insert_recursive(iter.clone(), kind.clone());
+        *element = if every {
+            inserted
+        } else {
+            element.union(inserted)
+        };
+    };
+
+    for element in collection.known_mut().values_mut() {
+        insert(element);
+    }
+
+    let unknown_kind = collection.unknown_kind();
+    if unknown_kind.contains_any_defined() {
+        let mut element = unknown_kind.without_undefined();
+        insert(&mut element);
+        collection.set_unknown(element);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
     use crate::owned_value_path;
-    use crate::path::{OwnedValuePath, parse_value_path};
+    use crate::path::{OwnedSegment, OwnedValuePath, parse_value_path};
     use crate::value::kind::Collection;
 
     use super::*;
@@ -643,6 +688,58 @@ mod tests {
                     ),
                 },
             ),
+            (
+                "wildcard insert into array elements",
+                TestCase {
+                    this: Kind::array(
+                        Collection::from(BTreeMap::from([(
+                            0.into(),
+                            Kind::object(BTreeMap::from([("a".into(), Kind::float())])),
+                        )]))
+                        .with_unknown(Kind::object(Collection::empty())),
+                    ),
+                    path: owned_value_path!(OwnedSegment::Wildcard, "a"),
+                    kind: Kind::integer(),
+                    expected: Kind::array(
+                        Collection::from(BTreeMap::from([(
+                            0.into(),
+                            Kind::object(BTreeMap::from([("a".into(), Kind::integer())])),
+                        )]))
+                        .with_unknown(Kind::object(BTreeMap::from([(
+                            "a".into(),
+                            Kind::integer(),
+                        )]))),
+                    ),
+                },
+            ),
+            (
+                "wildcard insert into object fields keeps non-collections",
+                TestCase {
+                    this: Kind::object(BTreeMap::from([("a".into(), Kind::bytes())])).or_null(),
+                    path: owned_value_path!(OwnedSegment::Wildcard),
+                    kind: Kind::integer(),
+                    expected: Kind::object(BTreeMap::from([("a".into(), Kind::integer())]))
+                        .or_null(),
+                },
+            ),
+            (
+                "slice insert may leave elements unchanged",
+                TestCase {
+                    this: Kind::array(BTreeMap::from([
+                        (0.into(), Kind::bytes()),
+                        (1.into(), Kind::bytes()),
+                    ])),
+                    path: owned_value_path!(OwnedSegment::Slice {
+                        start: Some(1),
+                        end: None
+                    }),
+                    kind: Kind::integer(),
+                    expected: Kind::array(BTreeMap::from([
+                        (0.into(), Kind::bytes().or_integer()),
+                        (1.into(), Kind::bytes().or_integer()),
+                    ])),
+                },
+            ),
         ] {
             this.insert(&path, kind);
             assert_eq!(this, expected, "{title}");
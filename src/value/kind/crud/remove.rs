@@ -114,6 +114,21 @@ impl Kind {
                         CompactOptions::Never
                     }
                 }
+
+                OwnedSegment::Wildcard | OwnedSegment::Slice { .. } => {
+                    let every = first.is_wildcard();
+                    let mut options = CompactOptions::Never;
+                    if let Some(array) = self.as_array_mut() {
+                        options = remove_matched(array, &segments[1..], compact, every);
+                    }
+                    if every && let Some(object) = self.as_object_mut() {
+                        let object_options = remove_matched(object, &segments[1..], compact, true);
+                        if object_options != CompactOptions::Never {
+                            options = CompactOptions::Maybe;
+                        }
+                    }
+                    options
+                }
             }
         } else {
             CompactOptions::new(self.contains_any_defined(), self.contains_undefined())
@@ -121,6 +136,57 @@ impl Kind {
     }
 }
 
+/// Removes the remaining path from the elements of the collection matched by a wildcard or
+/// slice. If `every` is false, the elements may also be left unchanged.
+fn remove_matched<T: Ord + Clone>(
+    collection: &mut Collection<T>,
+    segments: &[OwnedSegment],
+    compact: bool,
+    every: bool,
+) -> CompactOptions {
+    if segments.is_empty() {
+        if every {
+            *collection = Collection::empty();
+        } else {
+            // Removing some of the elements shifts the ones after them.
+            collection.anonymize();
+        }
+    } else {
+        let mut maybe_pruned = false;
+        let mut remove = |element: &mut Kind| {
+            let mut removed = element.clone();
+            maybe_pruned |= removed.remove_inner(segments, compact) != CompactOptions::Never;
+            *element = if every {
+                removed
+            } else {
+                element.union(removed)
+            };
+        };
+
+        for element in collection.known_mut().values_mut() {
+            remove(element);
+        }
+
+        let unknown_kind = collection.unknown_kind();
+        if unknown_kind.contains_any_defined() {
+            let mut element = unknown_kind.without_undefined();
+            remove(&mut element);
+            collection.set_unknown(element);
+        }
+
+        if maybe_pruned {
+            // Elements left empty may be removed, so their positions are no longer known.
+            collection.anonymize();
+        }
+    }
+
+    if compact && !matches!(collection.is_empty(), EmptyState::Never) {
+        CompactOptions::Maybe
+    } else {
+        CompactOptions::Never
+    }
+}
+
 /// A type definition might not know for sure if compaction will occur or not, so this
 /// keeps track of the current state
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -22,8 +22,10 @@ fn insert_kind(tree: &mut ObjectMap, kind: &Kind, show_unknown: bool) {
         insert_if_true(tree, "bytes", kind.contains_bytes());
         insert_if_true(tree, "integer", kind.contains_integer());
         insert_if_true(tree, "float", kind.contains_float());
+        insert_if_true(tree, "decimal", kind.contains_decimal());
         insert_if_true(tree, "boolean", kind.contains_boolean());
         insert_if_true(tree, "timestamp", kind.contains_timestamp());
+        insert_if_true(tree, "duration", kind.contains_duration());
         insert_if_true(tree, "regex", kind.contains_regex());
         insert_if_true(tree, "null", kind.contains_null());
         insert_if_true(tree, "undefined", kind.contains_undefined());
@@ -12,6 +12,7 @@ pub mod value;
 
 mod btreemap;
 mod keystring;
+pub mod object_map;
 
 pub use kind::Kind;
 
@@ -33,13 +34,13 @@ macro_rules! value {
     });
 
     ({}) => ({
-        $crate::value::Value::Object(::std::collections::BTreeMap::default())
+        $crate::value::Value::Object($crate::value::ObjectMap::default())
     });
 
     ({$($($k1:literal)? $($k2:ident)?: $v:tt),+ $(,)?}) => ({
         let map = vec![$((String::from($($k1)? $(stringify!($k2))?).into(), $crate::value!($v))),+]
             .into_iter()
-            .collect::<::std::collections::BTreeMap<_, $crate::value::Value>>();
+            .collect::<$crate::value::ObjectMap>();
 
         $crate::value::Value::Object(map)
     });
@@ -0,0 +1,538 @@
+//! The map of the fields of an object [`Value`].
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::ops::Index;
+
+use serde::{Deserialize, Serialize};
+
+use super::{KeyString, Value};
+
+#[cfg(not(feature = "preserve_order"))]
+mod inner {
+    use std::collections::btree_map;
+
+    use crate::value::{KeyString, Value};
+
+    pub type Map = btree_map::BTreeMap<KeyString, Value>;
+    pub type Entry<'a> = btree_map::Entry<'a, KeyString, Value>;
+    pub type VacantEntry<'a> = btree_map::VacantEntry<'a, KeyString, Value>;
+    pub type OccupiedEntry<'a> = btree_map::OccupiedEntry<'a, KeyString, Value>;
+    pub type Iter<'a> = btree_map::Iter<'a, KeyString, Value>;
+    pub type IterMut<'a> = btree_map::IterMut<'a, KeyString, Value>;
+    pub type IntoIter = btree_map::IntoIter<KeyString, Value>;
+    pub type Keys<'a> = btree_map::Keys<'a, KeyString, Value>;
+    pub type Values<'a> = btree_map::Values<'a, KeyString, Value>;
+    pub type ValuesMut<'a> = btree_map::ValuesMut<'a, KeyString, Value>;
+    pub type IntoKeys = btree_map::IntoKeys<KeyString, Value>;
+    pub type IntoValues = btree_map::IntoValues<KeyString, Value>;
+
+    pub fn remove_entry(map: &mut Map, key: &str) -> Option<(KeyString, Value)> {
+        map.remove_entry(key)
+    }
+
+    pub fn remove_occupied(entry: OccupiedEntry<'_>) -> (KeyString, Value) {
+        entry.remove_entry()
+    }
+
+    /// The fields are already sorted by key.
+    pub fn sorted(map: &Map) -> Iter<'_> {
+        map.iter()
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+mod inner {
+    use indexmap::map;
+
+    use crate::value::{KeyString, Value};
+
+    pub type Map = map::IndexMap<KeyString, Value>;
+    pub type Entry<'a> = map::Entry<'a, KeyString, Value>;
+    pub type VacantEntry<'a> = map::VacantEntry<'a, KeyString, Value>;
+    pub type OccupiedEntry<'a> = map::OccupiedEntry<'a, KeyString, Value>;
+    pub type Iter<'a> = map::Iter<'a, KeyString, Value>;
+    pub type IterMut<'a> = map::IterMut<'a, KeyString, Value>;
+    pub type IntoIter = map::IntoIter<KeyString, Value>;
+    pub type Keys<'a> = map::Keys<'a, KeyString, Value>;
+    pub type Values<'a> = map::Values<'a, KeyString, Value>;
+    pub type ValuesMut<'a> = map::ValuesMut<'a, KeyString, Value>;
+    pub type IntoKeys = map::IntoKeys<KeyString, Value>;
+    pub type IntoValues = map::IntoValues<KeyString, Value>;
+
+    /// Removing shifts the fields after the removed one, to keep their order.
+    pub fn remove_entry(map: &mut Map, key: &str) -> Option<(KeyString, Value)> {
+        map.shift_remove_entry(key)
+    }
+
+    pub fn remove_occupied(entry: OccupiedEntry<'_>) -> (KeyString, Value) {
+        entry.shift_remove_entry()
+    }
+
+    pub fn sorted(map: &Map) -> std::vec::IntoIter<(&KeyString, &Value)> {
+        let mut fields: Vec<_> = map.iter().collect();
+        fields.sort_unstable_by_key(|(key, _)| *key);
+        fields.into_iter()
+    }
+}
+
+/// The storage mapping for the `Object` variant.
+///
+/// The fields are sorted by key, unless the `preserve_order` feature is enabled, in which case
+/// they are kept in insertion order. The API is the same either way, and two objects are equal,
+/// compare and hash the same if they have the same fields, whatever their order.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ObjectMap(inner::Map);
+
+impl ObjectMap {
+    /// Creates an empty map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of fields.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the map has no fields.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes all the fields.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns the value of the field with the key.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    /// Returns the key and value of the field with the key.
+    #[must_use]
+    pub fn get_key_value(&self, key: &str) -> Option<(&KeyString, &Value)> {
+        self.0.get_key_value(key)
+    }
+
+    /// Returns a mutable reference to the value of the field with the key.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.0.get_mut(key)
+    }
+
+    /// Returns `true` if the map has a field with the key.
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Sets the value of the field with the key, returning its previous value. A new field is
+    /// added after the others when the order is preserved.
+    pub fn insert(&mut self, key: KeyString, value: Value) -> Option<Value> {
+        self.0.insert(key, value)
+    }
+
+    /// Removes the field with the key, returning its value. The other fields keep their order.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the field with the key, returning its key and value. The other fields keep their
+    /// order.
+    pub fn remove_entry(&mut self, key: &str) -> Option<(KeyString, Value)> {
+        inner::remove_entry(&mut self.0, key)
+    }
+
+    /// Keeps only the fields for which the predicate returns `true`.
+    pub fn retain(&mut self, f: impl FnMut(&KeyString, &mut Value) -> bool) {
+        self.0.retain(f);
+    }
+
+    /// Returns the entry of the field with the key, to change it in place.
+    pub fn entry(&mut self, key: KeyString) -> Entry<'_> {
+        match self.0.entry(key) {
+            inner::Entry::Vacant(entry) => Entry::Vacant(VacantEntry(entry)),
+            inner::Entry::Occupied(entry) => Entry::Occupied(OccupiedEntry(entry)),
+        }
+    }
+
+    /// Returns an iterator over the keys and values of the fields.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.0.iter())
+    }
+
+    /// Returns an iterator over the keys and mutable values of the fields.
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut(self.0.iter_mut())
+    }
+
+    /// Returns an iterator over the keys of the fields.
+    #[must_use]
+    pub fn keys(&self) -> Keys<'_> {
+        Keys(self.0.keys())
+    }
+
+    /// Returns an iterator over the values of the fields.
+    #[must_use]
+    pub fn values(&self) -> Values<'_> {
+        Values(self.0.values())
+    }
+
+    /// Returns an iterator over the mutable values of the fields.
+    pub fn values_mut(&mut self) -> ValuesMut<'_> {
+        ValuesMut(self.0.values_mut())
+    }
+
+    /// Returns an iterator over the keys of the fields, consuming the map.
+    #[must_use]
+    pub fn into_keys(self) -> IntoKeys {
+        IntoKeys(self.0.into_keys())
+    }
+
+    /// Returns an iterator over the values of the fields, consuming the map.
+    #[must_use]
+    pub fn into_values(self) -> IntoValues {
+        IntoValues(self.0.into_values())
+    }
+}
+
+impl fmt::Debug for ObjectMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Hash for ObjectMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for field in inner::sorted(&self.0) {
+            field.hash(state);
+        }
+    }
+}
+
+impl PartialOrd for ObjectMap {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        inner::sorted(&self.0).partial_cmp(inner::sorted(&other.0))
+    }
+}
+
+impl Index<&str> for ObjectMap {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).expect("no field with the key")
+    }
+}
+
+impl FromIterator<(KeyString, Value)> for ObjectMap {
+    fn from_iter<I: IntoIterator<Item = (KeyString, Value)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<(KeyString, Value)> for ObjectMap {
+    fn extend<I: IntoIterator<Item = (KeyString, Value)>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl<const N: usize> From<[(KeyString, Value); N]> for ObjectMap {
+    fn from(fields: [(KeyString, Value); N]) -> Self {
+        fields.into_iter().collect()
+    }
+}
+
+impl From<BTreeMap<KeyString, Value>> for ObjectMap {
+    fn from(map: BTreeMap<KeyString, Value>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl From<ObjectMap> for BTreeMap<KeyString, Value> {
+    fn from(map: ObjectMap) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl IntoIterator for ObjectMap {
+    type Item = (KeyString, Value);
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter(self.0.into_iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a ObjectMap {
+    type Item = (&'a KeyString, &'a Value);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut ObjectMap {
+    type Item = (&'a KeyString, &'a mut Value);
+    type IntoIter = IterMut<'a>;
+
+    fn into_iter(self) -> IterMut<'a> {
+        self.iter_mut()
+    }
+}
+
+/// The entry of a field in an [`ObjectMap`], returned by [`ObjectMap::entry`].
+pub enum Entry<'a> {
+    Vacant(VacantEntry<'a>),
+    Occupied(OccupiedEntry<'a>),
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the key of the field.
+    #[must_use]
+    pub fn key(&self) -> &KeyString {
+        match self {
+            Self::Vacant(entry) => entry.key(),
+            Self::Occupied(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts the value if the field is missing, and returns a mutable reference to its value.
+    pub fn or_insert(self, default: Value) -> &'a mut Value {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the value returned by the function if the field is missing, and returns a mutable
+    /// reference to its value.
+    pub fn or_insert_with(self, default: impl FnOnce() -> Value) -> &'a mut Value {
+        match self {
+            Self::Vacant(entry) => entry.insert(default()),
+            Self::Occupied(entry) => entry.into_mut(),
+        }
+    }
+
+    /// Inserts null if the field is missing, and returns a mutable reference to its value.
+    #[allow(clippy::must_use_candidate)] // Like `or_insert`, it is called to insert the field.
+    pub fn or_default(self) -> &'a mut Value {
+        self.or_insert_with(|| Value::Null)
+    }
+
+    /// Calls the function with the value of the field, if it exists.
+    #[must_use]
+    pub fn and_modify(mut self, f: impl FnOnce(&mut Value)) -> Self {
+        if let Self::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+/// The entry of a missing field in an [`ObjectMap`].
+pub struct VacantEntry<'a>(inner::VacantEntry<'a>);
+
+impl<'a> VacantEntry<'a> {
+    /// Returns the key of the field.
+    #[must_use]
+    pub fn key(&self) -> &KeyString {
+        self.0.key()
+    }
+
+    /// Returns the key of the field, without inserting it.
+    #[must_use]
+    pub fn into_key(self) -> KeyString {
+        self.0.into_key()
+    }
+
+    /// Inserts the field, returning a mutable reference to its value.
+    pub fn insert(self, value: Value) -> &'a mut Value {
+        self.0.insert(value)
+    }
+}
+
+/// The entry of an existing field in an [`ObjectMap`].
+pub struct OccupiedEntry<'a>(inner::OccupiedEntry<'a>);
+
+impl<'a> OccupiedEntry<'a> {
+    /// Returns the key of the field.
+    #[must_use]
+    pub fn key(&self) -> &KeyString {
+        self.0.key()
+    }
+
+    /// Returns the value of the field.
+    #[must_use]
+    pub fn get(&self) -> &Value {
+        self.0.get()
+    }
+
+    /// Returns a mutable reference to the value of the field.
+    pub fn get_mut(&mut self) -> &mut Value {
+        self.0.get_mut()
+    }
+
+    /// Returns a mutable reference to the value of the field, bound to the lifetime of the map.
+    #[must_use]
+    pub fn into_mut(self) -> &'a mut Value {
+        self.0.into_mut()
+    }
+
+    /// Sets the value of the field, returning its previous value.
+    pub fn insert(&mut self, value: Value) -> Value {
+        self.0.insert(value)
+    }
+
+    /// Removes the field, returning its value. The other fields keep their order.
+    #[must_use = "use `ObjectMap::remove` to only remove the field"]
+    pub fn remove(self) -> Value {
+        self.remove_entry().1
+    }
+
+    /// Removes the field, returning its key and value. The other fields keep their order.
+    #[must_use = "use `ObjectMap::remove` to only remove the field"]
+    pub fn remove_entry(self) -> (KeyString, Value) {
+        inner::remove_occupied(self.0)
+    }
+}
+
+/// Defines an iterator wrapping the iterator of the same name of the inner map.
+macro_rules! iterator {
+    ($(#[$attr:meta])* $name:ident $(<$lt:lifetime>)? => $item:ty) => {
+        $(#[$attr])*
+        pub struct $name$(<$lt>)?(inner::$name$(<$lt>)?);
+
+        impl$(<$lt>)? Iterator for $name$(<$lt>)? {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<$item> {
+                self.0.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+
+        impl$(<$lt>)? DoubleEndedIterator for $name$(<$lt>)? {
+            fn next_back(&mut self) -> Option<$item> {
+                self.0.next_back()
+            }
+        }
+
+        impl$(<$lt>)? ExactSizeIterator for $name$(<$lt>)? {
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        impl$(<$lt>)? FusedIterator for $name$(<$lt>)? {}
+    };
+}
+
+iterator! {
+    /// An iterator over the fields of an [`ObjectMap`].
+    #[derive(Clone)]
+    Iter<'a> => (&'a KeyString, &'a Value)
+}
+
+iterator! {
+    /// An iterator over the fields of an [`ObjectMap`], with mutable values.
+    IterMut<'a> => (&'a KeyString, &'a mut Value)
+}
+
+iterator! {
+    /// An owning iterator over the fields of an [`ObjectMap`].
+    IntoIter => (KeyString, Value)
+}
+
+iterator! {
+    /// An iterator over the keys of an [`ObjectMap`].
+    #[derive(Clone)]
+    Keys<'a> => &'a KeyString
+}
+
+iterator! {
+    /// An iterator over the values of an [`ObjectMap`].
+    #[derive(Clone)]
+    Values<'a> => &'a Value
+}
+
+iterator! {
+    /// An iterator over the mutable values of an [`ObjectMap`].
+    ValuesMut<'a> => &'a mut Value
+}
+
+iterator! {
+    /// An owning iterator over the keys of an [`ObjectMap`].
+    IntoKeys => KeyString
+}
+
+iterator! {
+    /// An owning iterator over the values of an [`ObjectMap`].
+    IntoValues => Value
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{BuildHasher, RandomState};
+
+    use super::*;
+
+    fn map(keys: &[&str]) -> ObjectMap {
+        keys.iter()
+            .map(|key| ((*key).into(), Value::from(*key)))
+            .collect()
+    }
+
+    #[test]
+    fn removes_fields_in_order() {
+        let mut map = map(&["a", "b", "c"]);
+        assert_eq!(map.remove("a"), Some(Value::from("a")));
+        assert_eq!(map.remove("a"), None);
+        assert_eq!(
+            map.keys().map(KeyString::as_str).collect::<Vec<_>>(),
+            ["b", "c"]
+        );
+
+        let Entry::Occupied(entry) = map.entry("b".into()) else {
+            panic!("field missing");
+        };
+        assert_eq!(entry.remove_entry(), ("b".into(), Value::from("b")));
+        assert_eq!(map.keys().map(KeyString::as_str).collect::<Vec<_>>(), ["c"]);
+    }
+
+    #[test]
+    fn ignores_order() {
+        let (first, second) = (map(&["a", "b"]), map(&["b", "a"]));
+        assert_eq!(first, second);
+        assert_eq!(first.partial_cmp(&second), Some(Ordering::Equal));
+        assert!(map(&["a"]) < map(&["b"]));
+
+        let hasher = RandomState::new();
+        assert_eq!(hasher.hash_one(&first), hasher.hash_one(&second));
+    }
+
+    #[test]
+    fn entries() {
+        let mut map = ObjectMap::new();
+        *map.entry("a".into()).or_insert(Value::from(1)) = Value::from(2);
+        map.entry("a".into())
+            .and_modify(|value| *value = Value::from(3))
+            .or_default();
+        map.entry("b".into()).or_default();
+        assert_eq!(
+            map,
+            ObjectMap::from([("a".into(), Value::from(3)), ("b".into(), Value::Null)])
+        );
+    }
+}
@@ -3,20 +3,25 @@
 #[allow(clippy::module_name_repetitions)]
 pub use super::value::regex::ValueRegex;
 #[allow(clippy::module_name_repetitions)]
+pub use duration::{format_duration, parse_duration, unit_nanoseconds};
+pub(crate) use duration::{from_nanoseconds, to_nanoseconds};
+#[allow(clippy::module_name_repetitions)]
 pub use iter::{IterItem, ValueIter};
 
 use bytes::{Bytes, BytesMut};
-use chrono::{DateTime, SecondsFormat, Utc};
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
 use ordered_float::NotNan;
+use rust_decimal::Decimal;
 use std::borrow::Cow;
-use std::{cmp::Ordering, collections::BTreeMap};
+use std::cmp::Ordering;
 
-use super::KeyString;
+pub use super::object_map::ObjectMap;
 use crate::path::ValuePath;
 
 mod convert;
 mod crud;
 mod display;
+mod duration;
 mod iter;
 mod path;
 mod regex;
@@ -25,14 +30,13 @@ mod regex;
 mod arbitrary;
 #[cfg(any(test, feature = "lua"))]
 mod lua;
+#[cfg(feature = "python")]
+mod python;
 mod serde;
 
 /// A boxed `std::error::Error`.
 pub type StdError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
-/// The storage mapping for the `Object` variant.
-pub type ObjectMap = BTreeMap<KeyString, Value>;
-
 /// The main value type used in Vector events, and VRL.
 #[derive(Eq, PartialEq, Hash, Debug, Clone)]
 pub enum Value {
@@ -50,12 +54,20 @@ pub enum Value {
     /// Float - not NaN.
     Float(NotNan<f64>),
 
+    /// Decimal - an exact decimal number with up to 28 digits, for numbers that do not fit an
+    /// `Integer` or that would lose precision as a `Float`, such as unsigned 64-bit counters.
+    Decimal(Decimal),
+
     /// Boolean.
     Boolean(bool),
 
     /// Timestamp (UTC).
     Timestamp(DateTime<Utc>),
 
+    /// Duration - a signed span of time with nanosecond precision, such as the difference
+    /// between two timestamps.
+    Duration(TimeDelta),
+
     /// Object.
     Object(ObjectMap),
 
@@ -72,8 +84,10 @@ impl Value {
         match self {
             Self::Bytes(_) | Self::Regex(_) => "string",
             Self::Timestamp(_) => "timestamp",
+            Self::Duration(_) => "duration",
             Self::Integer(_) => "integer",
             Self::Float(_) => "float",
+            Self::Decimal(_) => "decimal",
             Self::Boolean(_) => "boolean",
             Self::Object(_) => "map",
             Self::Array(_) => "array",
@@ -126,7 +140,9 @@ impl Value {
             | Self::Bytes(_)
             | Self::Regex(_)
             | Self::Timestamp(_)
+            | Self::Duration(_)
             | Self::Float(_)
+            | Self::Decimal(_)
             | Self::Integer(_) => false,
             Self::Null => true,
             Self::Object(v) => v.is_empty(),
@@ -163,6 +179,16 @@ impl Value {
         crud::get(self, path.segment_iter())
     }
 
+    /// Returns the value at a path that may contain wildcard and slice segments.
+    ///
+    /// The values matched by a wildcard or slice are collected into an array, skipping the
+    /// ones that don't contain the rest of the path. Without such segments, this returns a
+    /// copy of the value [`Value::get`] would return.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn project<'a>(&self, path: impl ValuePath<'a>) -> Option<Self> {
+        crud::project(self, path.segment_iter())
+    }
+
     /// Get a mutable borrow of the value by path
     #[allow(clippy::needless_pass_by_value)]
     pub fn get_mut<'a>(&mut self, path: impl ValuePath<'a>) -> Option<&mut Self> {
@@ -185,8 +211,10 @@ impl PartialOrd for Value {
             (Self::Regex(a), Self::Regex(b)) => a.partial_cmp(b),
             (Self::Integer(a), Self::Integer(b)) => a.partial_cmp(b),
             (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+            (Self::Decimal(a), Self::Decimal(b)) => a.partial_cmp(b),
             (Self::Boolean(a), Self::Boolean(b)) => a.partial_cmp(b),
             (Self::Timestamp(a), Self::Timestamp(b)) => a.partial_cmp(b),
+            (Self::Duration(a), Self::Duration(b)) => a.partial_cmp(b),
             (Self::Object(a), Self::Object(b)) => a.partial_cmp(b),
             (Self::Array(a), Self::Array(b)) => a.partial_cmp(b),
             _ => None,
@@ -234,7 +262,7 @@ mod test {
 
         #[test]
         fn remove_prune_map_with_map() {
-            let mut value = Value::from(BTreeMap::default());
+            let mut value = Value::from(ObjectMap::default());
             let key = "foo.bar";
             let marker = Value::from(true);
             assert_eq!(value.insert(key, marker.clone()), None);
@@ -245,7 +273,7 @@ mod test {
 
         #[test]
         fn remove_prune_map_with_array() {
-            let mut value = Value::from(BTreeMap::default());
+            let mut value = Value::from(ObjectMap::default());
             let key = "foo[0]";
             let marker = Value::from(true);
             assert_eq!(value.insert(key, marker.clone()), None);
@@ -280,7 +308,7 @@ mod test {
     #[test]
     fn quickcheck_value() {
         fn inner(mut path: Vec<BorrowedSegment<'static>>) -> TestResult {
-            let mut value = Value::from(BTreeMap::default());
+            let mut value = Value::from(ObjectMap::default());
             let mut marker = Value::from(true);
 
             // Push a field at the start of the path so the top level is a map.
@@ -4,9 +4,10 @@ use std::{borrow::Cow, num::NonZero};
 use crate::value::value::regex::ValueRegex;
 use crate::value::value::simdutf_bytes_utf8_lossy;
 use bytes::Bytes;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use ordered_float::NotNan;
 use regex::Regex;
+use rust_decimal::Decimal;
 
 use super::super::{KeyString, Kind, ObjectMap, Value};
 
@@ -35,6 +36,14 @@ impl Value {
         }
     }
 
+    /// Returns self as `TimeDelta`, only if self is `Value::Duration`.
+    pub fn as_duration(&self) -> Option<TimeDelta> {
+        match self {
+            Self::Duration(duration) => Some(*duration),
+            _ => None,
+        }
+    }
+
     /// Returns self as a `DateTime<Utc>`.
     ///
     /// # Panics
@@ -101,6 +110,19 @@ impl Value {
         matches!(self, Self::Float(_))
     }
 
+    /// Returns true if self is `Value::Decimal`.
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, Self::Decimal(_))
+    }
+
+    /// Returns self as `Decimal`, only if self is `Value::Decimal`.
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Self::Decimal(v) => Some(*v),
+            _ => None,
+        }
+    }
+
     // This replaces the more implicit "From<f64>", but keeps the same behavior.
     // Ideally https://github.com/vectordotdev/vector/issues/11177 will remove this entirely
     /// Creates a Value from an f64. If the value is Nan, it is converted to 0.0
@@ -145,6 +167,7 @@ impl Value {
             Self::Bytes(bytes) => Ok(bytes.clone()),
             Self::Integer(i) => Ok(Bytes::copy_from_slice(&i.to_le_bytes())),
             Self::Float(f) => Ok(Bytes::copy_from_slice(&f.into_inner().to_le_bytes())),
+            Self::Decimal(d) => Ok(Bytes::copy_from_slice(&d.serialize())),
             Self::Boolean(b) => Ok(if *b {
                 Bytes::copy_from_slice(&[1_u8])
             } else {
@@ -153,6 +176,9 @@ impl Value {
             Self::Object(_o) => Err("cannot convert object to bytes.".to_string()),
             Self::Array(_a) => Err("cannot convert array to bytes.".to_string()),
             Self::Timestamp(t) => Ok(Bytes::copy_from_slice(&t.timestamp().to_le_bytes())),
+            Self::Duration(d) => Ok(Bytes::copy_from_slice(
+                &d.num_nanoseconds().unwrap_or(i64::MAX).to_le_bytes(),
+            )),
             Self::Regex(r) => Ok(r.to_string().into()),
             Self::Null => Ok(Bytes::copy_from_slice(&[0_u8])),
         }
@@ -244,6 +270,11 @@ impl Value {
         matches!(self, Self::Timestamp(_))
     }
 
+    /// Returns true if self is `Value::Duration`.
+    pub fn is_duration(&self) -> bool {
+        matches!(self, Self::Duration(_))
+    }
+
     /// Returns the `Kind` of this `Value`
     pub fn kind(&self) -> Kind {
         self.into()
@@ -306,6 +337,12 @@ impl From<DateTime<Utc>> for Value {
     }
 }
 
+impl From<TimeDelta> for Value {
+    fn from(duration: TimeDelta) -> Self {
+        Self::Duration(duration)
+    }
+}
+
 impl From<()> for Value {
     fn from((): ()) -> Self {
         Self::Null
@@ -350,6 +387,12 @@ impl From<ObjectMap> for Value {
     }
 }
 
+impl From<std::collections::BTreeMap<KeyString, Value>> for Value {
+    fn from(value: std::collections::BTreeMap<KeyString, Value>) -> Self {
+        Self::Object(value.into_iter().collect())
+    }
+}
+
 impl FromIterator<Self> for Value {
     fn from_iter<I: IntoIterator<Item = Self>>(iter: I) -> Self {
         Self::Array(iter.into_iter().collect::<Vec<Self>>())
@@ -444,8 +487,15 @@ impl From<usize> for Value {
     }
 }
 impl From<u64> for Value {
+    /// Values above `i64::MAX` are converted to a `Value::Decimal`, to keep them intact.
     fn from(value: u64) -> Self {
-        Self::Integer(value as i64)
+        i64::try_from(value).map_or_else(|_| Self::Decimal(Decimal::from(value)), Self::Integer)
+    }
+}
+
+impl From<Decimal> for Value {
+    fn from(value: Decimal) -> Self {
+        Self::Decimal(value)
     }
 }
 
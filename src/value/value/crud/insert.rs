@@ -1,13 +1,12 @@
-use super::ValueCollection;
+use super::{ValueCollection, array_range};
 use crate::path::BorrowedSegment;
-use crate::value::Value;
+use crate::value::{ObjectMap, Value};
 use std::borrow::Borrow;
-use std::collections::BTreeMap;
 
 pub fn insert<'a, T: ValueCollection>(
     value: &mut T,
     key: T::Key,
-    mut path_iter: impl Iterator<Item = BorrowedSegment<'a>>,
+    mut path_iter: impl Iterator<Item = BorrowedSegment<'a>> + Clone,
     insert_value: Value,
 ) -> Option<Value> {
     match path_iter.next() {
@@ -15,7 +14,7 @@ pub fn insert<'a, T: ValueCollection>(
             if let Some(Value::Object(map)) = value.get_mut_value(key.borrow()) {
                 insert(map, field.to_string().into(), path_iter, insert_value)
             } else {
-                let mut map = BTreeMap::new();
+                let mut map = ObjectMap::new();
                 let prev_value =
                     insert(&mut map, field.to_string().into(), path_iter, insert_value);
                 value.insert_value(key, Value::Object(map));
@@ -37,11 +36,39 @@ pub fn insert<'a, T: ValueCollection>(
                 prev_value
             }
         }
+        Some(BorrowedSegment::Wildcard) => {
+            // Wildcards only match existing values, so nothing is created if there are none.
+            match value.get_mut_value(key.borrow()) {
+                Some(Value::Array(array)) => {
+                    insert_all(array.iter_mut(), &path_iter, &insert_value);
+                }
+                Some(Value::Object(map)) => insert_all(map.values_mut(), &path_iter, &insert_value),
+                _ => {}
+            }
+            None
+        }
+        Some(BorrowedSegment::Slice { start, end }) => {
+            if let Some(Value::Array(array)) = value.get_mut_value(key.borrow()) {
+                let range = array_range(array, start, end);
+                insert_all(array[range].iter_mut(), &path_iter, &insert_value);
+            }
+            None
+        }
         Some(BorrowedSegment::Invalid) => None,
         None => value.insert_value(key, insert_value),
     }
 }
 
+fn insert_all<'a, 'b>(
+    values: impl Iterator<Item = &'b mut Value>,
+    path_iter: &(impl Iterator<Item = BorrowedSegment<'a>> + Clone),
+    insert_value: &Value,
+) {
+    for value in values {
+        insert(value, (), path_iter.clone(), insert_value.clone());
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -86,4 +113,30 @@ mod test {
         assert_eq!(value.insert("[-1][1]", 3), None);
         assert_eq!(value, Value::from(json!([2, [null, 3]])));
     }
+
+    #[test]
+    fn test_insert_wildcard() {
+        let mut value = Value::from(json!({
+            "items": [{ "price": 1 }, { "price": 2 }, 3],
+            "headers": { "host": "a", "accept": "b" },
+        }));
+        assert_eq!(value.insert("items[*].price", 0), None);
+        assert_eq!(value.insert("headers.*", "redacted"), None);
+        assert_eq!(value.insert("missing[*].price", 0), None);
+
+        let expected = Value::from(json!({
+            "items": [{ "price": 0 }, { "price": 0 }, { "price": 0 }],
+            "headers": { "host": "redacted", "accept": "redacted" },
+        }));
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_insert_slice() {
+        let mut value = Value::from(json!([0, 1, 2, 3, 4]));
+        value.insert("[1:3]", true);
+        value.insert("[-1:]", false);
+        value.insert("[10:]", false);
+        assert_eq!(value, Value::from(json!([0, true, true, 3, false])));
+    }
 }
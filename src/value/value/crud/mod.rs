@@ -1,14 +1,17 @@
 use crate::value::{KeyString, ObjectMap, Value};
 use std::borrow::Borrow;
+use std::ops::Range;
 
 mod get;
 mod get_mut;
 mod insert;
+mod project;
 mod remove;
 
 pub use self::get::get;
 pub use self::get_mut::get_mut;
 pub use self::insert::insert;
+pub use self::project::project;
 pub use self::remove::remove;
 
 pub trait ValueCollection {
@@ -91,6 +94,21 @@ fn array_index(array: &[Value], index: isize) -> Option<usize> {
     }
 }
 
+/// Returns the indices of the array covered by a slice. Negative bounds count from the end of
+/// the array, and bounds past either end of the array are clamped to it.
+fn array_range(array: &[Value], start: Option<isize>, end: Option<isize>) -> Range<usize> {
+    let bound = |bound: isize| {
+        if bound >= 0 {
+            (bound as usize).min(array.len())
+        } else {
+            array.len().saturating_sub(bound.unsigned_abs())
+        }
+    };
+    let start = start.map_or(0, bound);
+    let end = end.map_or(array.len(), bound);
+    start..end.max(start)
+}
+
 impl ValueCollection for Vec<Value> {
     type Key = isize;
     type BorrowedKey = isize;
@@ -0,0 +1,79 @@
+use super::{ValueCollection, array_range};
+use crate::path::BorrowedSegment;
+use crate::value::Value;
+
+/// Returns the value at the path, collecting the values matched by wildcard and slice segments
+/// into arrays. Matched values that don't contain the rest of the path are skipped.
+pub fn project<'a>(
+    value: &Value,
+    mut path_iter: impl Iterator<Item = BorrowedSegment<'a>> + Clone,
+) -> Option<Value> {
+    match (path_iter.next(), value) {
+        (None, _) => Some(value.clone()),
+        (Some(BorrowedSegment::Field(key)), Value::Object(map)) => {
+            project(map.get_value(key.as_ref())?, path_iter)
+        }
+        (Some(BorrowedSegment::Index(index)), Value::Array(array)) => {
+            project(array.get_value(&index)?, path_iter)
+        }
+        (Some(BorrowedSegment::Wildcard), Value::Array(array)) => {
+            Some(project_all(array.iter(), &path_iter))
+        }
+        (Some(BorrowedSegment::Wildcard), Value::Object(map)) => {
+            Some(project_all(map.values(), &path_iter))
+        }
+        (Some(BorrowedSegment::Slice { start, end }), Value::Array(array)) => {
+            let range = array_range(array, start, end);
+            Some(project_all(array[range].iter(), &path_iter))
+        }
+        _ => None,
+    }
+}
+
+fn project_all<'a, 'b>(
+    values: impl Iterator<Item = &'b Value>,
+    path_iter: &(impl Iterator<Item = BorrowedSegment<'a>> + Clone),
+) -> Value {
+    values
+        .filter_map(|value| project(value, path_iter.clone()))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_project() {
+        let value = Value::from(json!({
+            "items": [
+                { "price": 1, "tags": ["a", "b"] },
+                { "price": 2 },
+                { "tags": ["c"] },
+            ],
+            "headers": { "accept": "*/*", "host": "example.com" },
+        }));
+
+        let queries = [
+            ("items[1].price", Some(json!(2))),
+            ("items[*].price", Some(json!([1, 2]))),
+            ("items[*].tags", Some(json!([["a", "b"], ["c"]]))),
+            ("items[*].tags[*]", Some(json!([["a", "b"], ["c"]]))),
+            ("items[*].tags[0]", Some(json!(["a", "c"]))),
+            ("items[1:].price", Some(json!([2]))),
+            ("items[:-1].price", Some(json!([1, 2]))),
+            ("items[5:].price", Some(json!([]))),
+            ("items[2:1]", Some(json!([]))),
+            ("headers.*", Some(json!(["*/*", "example.com"]))),
+            ("headers[0:1]", None),
+            ("items.price", None),
+            ("missing[*]", None),
+        ];
+
+        for (query, expected) in queries {
+            assert_eq!(value.project(query), expected.map(Value::from), "{query}");
+        }
+    }
+}
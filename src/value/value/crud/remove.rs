@@ -1,11 +1,12 @@
-use super::ValueCollection;
+use super::{ValueCollection, array_range};
 use crate::path::BorrowedSegment;
-use crate::value::Value;
+use crate::value::{KeyString, ObjectMap, Value};
+use std::ops::Range;
 
 pub fn remove<'a, T: ValueCollection>(
     value: &mut T,
     key: &T::BorrowedKey,
-    mut path_iter: impl Iterator<Item = BorrowedSegment<'a>>,
+    mut path_iter: impl Iterator<Item = BorrowedSegment<'a>> + Clone,
     prune: bool,
 ) -> Option<(Value, bool)> {
     match (value.get_mut_value(key), path_iter.next()) {
@@ -24,11 +25,66 @@ pub fn remove<'a, T: ValueCollection>(
             }
             Some(prev_value)
         }
+        (Some(Value::Array(array)), Some(BorrowedSegment::Wildcard)) => {
+            let range = 0..array.len();
+            let (prev_value, empty) = remove_range(array, range, &path_iter, prune);
+            if prune && empty {
+                value.remove_value(key);
+            }
+            Some(prev_value)
+        }
+        (Some(Value::Array(array)), Some(BorrowedSegment::Slice { start, end })) => {
+            let range = array_range(array, start, end);
+            let (prev_value, empty) = remove_range(array, range, &path_iter, prune);
+            if prune && empty {
+                value.remove_value(key);
+            }
+            Some(prev_value)
+        }
+        (Some(Value::Object(map)), Some(BorrowedSegment::Wildcard)) => {
+            let (prev_value, empty) = remove_fields(map, &path_iter, prune);
+            if prune && empty {
+                value.remove_value(key);
+            }
+            Some(prev_value)
+        }
         _ => return None,
     }
     .map(|prev| (prev, value.is_empty_collection()))
 }
 
+/// Removes the path from every element in the range, returning the removed values as an array.
+fn remove_range<'a>(
+    array: &mut Vec<Value>,
+    range: Range<usize>,
+    path_iter: &(impl Iterator<Item = BorrowedSegment<'a>> + Clone),
+    prune: bool,
+) -> (Value, bool) {
+    // Removing (or pruning) an element shifts the ones after it, so go from back to front.
+    let mut removed = range
+        .rev()
+        .filter_map(|index| remove(array, &(index as isize), path_iter.clone(), prune))
+        .map(|(prev_value, _)| prev_value)
+        .collect::<Vec<_>>();
+    removed.reverse();
+    (removed.into(), array.is_empty())
+}
+
+/// Removes the path from every field of the object, returning the removed values as an array.
+fn remove_fields<'a>(
+    map: &mut ObjectMap,
+    path_iter: &(impl Iterator<Item = BorrowedSegment<'a>> + Clone),
+    prune: bool,
+) -> (Value, bool) {
+    let keys = map.keys().cloned().collect::<Vec<KeyString>>();
+    let removed = keys
+        .iter()
+        .filter_map(|key| remove(map, key.as_str(), path_iter.clone(), prune))
+        .map(|(prev_value, _)| prev_value)
+        .collect::<Vec<_>>();
+    (removed.into(), map.is_empty())
+}
+
 #[cfg(test)]
 mod test {
     use crate::value::Value;
@@ -127,4 +183,47 @@ mod test {
         assert_eq!(value.remove("a.b.c[0]", true), Some(Value::Integer(5)));
         assert_eq!(value, Value::from(json!({})));
     }
+
+    #[test]
+    fn remove_wildcard() {
+        let mut value = Value::from(json!({
+            "items": [{ "price": 1, "name": "a" }, { "name": "b" }, { "price": 3 }],
+            "headers": { "accept": "*/*", "host": "example.com" },
+        }));
+
+        assert_eq!(
+            value.remove("items[*].price", false),
+            Some(Value::from(json!([1, 3])))
+        );
+        assert_eq!(
+            value.remove("headers.*", false),
+            Some(Value::from(json!(["*/*", "example.com"])))
+        );
+        assert_eq!(value.remove("missing[*]", false), None);
+        assert_eq!(
+            value,
+            Value::from(json!({
+                "items": [{ "name": "a" }, { "name": "b" }, {}],
+                "headers": {},
+            }))
+        );
+
+        assert_eq!(
+            value.remove("items[*].name", true),
+            Some(Value::from(json!(["a", "b"])))
+        );
+        assert_eq!(value, Value::from(json!({ "items": [{}], "headers": {} })));
+    }
+
+    #[test]
+    fn remove_slice() {
+        let mut value = Value::from(json!([0, 1, 2, 3, 4]));
+        assert_eq!(
+            value.remove("[1:3]", false),
+            Some(Value::from(json!([1, 2])))
+        );
+        assert_eq!(value.remove("[-1:]", false), Some(Value::from(json!([4]))));
+        assert_eq!(value.remove("[5:]", false), Some(Value::from(json!([]))));
+        assert_eq!(value, Value::from(json!([0, 3])));
+    }
 }
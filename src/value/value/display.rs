@@ -17,6 +17,7 @@ impl fmt::Display for Value {
             ),
             Self::Integer(val) => write!(f, "{val}"),
             Self::Float(val) => write!(f, "{val}"),
+            Self::Decimal(val) => write!(f, "{val}"),
             Self::Boolean(val) => write!(f, "{val}"),
             Self::Object(map) => {
                 let joined = map
@@ -37,6 +38,7 @@ impl fmt::Display for Value {
             Self::Timestamp(val) => {
                 write!(f, "t'{}'", val.to_rfc3339_opts(SecondsFormat::AutoSi, true))
             }
+            Self::Duration(val) => write!(f, "d'{}'", super::format_duration(*val)),
             Self::Regex(regex) => write!(f, "r'{}'", **regex),
             Self::Null => write!(f, "null"),
         }
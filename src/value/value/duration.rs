@@ -0,0 +1,206 @@
+use chrono::TimeDelta;
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// The units accepted in a duration, with their length in nanoseconds.
+const UNITS: [(&str, i64); 11] = [
+    ("w", 604_800_000_000_000),
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ds", 100_000_000),
+    ("cs", 10_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ns", 1),
+];
+
+/// The units a duration is formatted with, from largest to smallest.
+const FORMAT_UNITS: [(&str, i128); 7] = [
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+/// Returns the length of a duration in nanoseconds.
+pub(crate) fn to_nanoseconds(duration: TimeDelta) -> i128 {
+    #[allow(clippy::arithmetic_side_effects)] // any duration fits an i128 of nanoseconds
+    let nanos =
+        i128::from(duration.num_seconds()) * NANOS_PER_SECOND + i128::from(duration.subsec_nanos());
+    nanos
+}
+
+/// Returns the duration of the given number of nanoseconds, if it fits a [`TimeDelta`].
+pub(crate) fn from_nanoseconds(nanos: i128) -> Option<TimeDelta> {
+    let seconds = i64::try_from(nanos.checked_div(NANOS_PER_SECOND)?).ok()?;
+    let subsec_nanos = i64::try_from(nanos.checked_rem(NANOS_PER_SECOND)?).ok()?;
+
+    TimeDelta::try_seconds(seconds)?.checked_add(&TimeDelta::nanoseconds(subsec_nanos))
+}
+
+/// Returns the length in nanoseconds of a duration unit, such as `ms` or `h`.
+#[must_use]
+pub fn unit_nanoseconds(unit: &str) -> Option<i64> {
+    UNITS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(unit))
+        .map(|(_, nanos)| *nanos)
+}
+
+/// Parses a duration made of one or more numbers followed by a unit, such as `1h30m`, `1.5s`
+/// or `-250ms`. Whitespace is allowed between the parts.
+///
+/// # Errors
+///
+/// If the input is not a valid duration, or does not fit a [`TimeDelta`] with nanosecond
+/// precision.
+pub fn parse_duration(input: &str) -> Result<TimeDelta, String> {
+    let invalid = || format!("invalid duration: '{input}'");
+
+    let input_trimmed = input.trim();
+    let (negative, mut rest) = match input_trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, input_trimmed),
+    };
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut nanos = Decimal::ZERO;
+    while !rest.is_empty() {
+        let (number, tail) = rest.split_at(
+            rest.find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len()),
+        );
+        let number = Decimal::from_str_exact(number).map_err(|_| invalid())?;
+
+        let tail = tail.trim_start();
+        let (unit, tail) = tail.split_at(
+            tail.find(|c: char| !c.is_alphabetic())
+                .unwrap_or(tail.len()),
+        );
+        let unit =
+            unit_nanoseconds(unit).ok_or_else(|| format!("unknown duration unit: '{unit}'"))?;
+
+        nanos = number
+            .checked_mul(Decimal::from(unit))
+            .and_then(|part| nanos.checked_add(part))
+            .ok_or_else(invalid)?;
+        rest = tail.trim_start();
+    }
+
+    nanos
+        .round()
+        .to_i128()
+        .and_then(|nanos| {
+            if negative {
+                nanos.checked_neg()
+            } else {
+                Some(nanos)
+            }
+        })
+        .and_then(from_nanoseconds)
+        .ok_or_else(|| format!("duration is out of range: '{input}'"))
+}
+
+/// Formats a duration with the largest units first, such as `1d2h30m` or `1s500ms`, which
+/// [`parse_duration`] reads back. A zero duration is formatted as `0s`.
+#[must_use]
+pub fn format_duration(duration: TimeDelta) -> String {
+    let nanos = to_nanoseconds(duration);
+
+    let mut formatted = String::new();
+    if nanos < 0 {
+        formatted.push('-');
+    }
+
+    let mut remainder = nanos.unsigned_abs();
+    for (unit, length) in FORMAT_UNITS {
+        let length = length.unsigned_abs();
+        let count = remainder.checked_div(length).unwrap_or_default();
+        if count > 0 {
+            formatted.push_str(&count.to_string());
+            formatted.push_str(unit);
+            remainder = remainder.checked_rem(length).unwrap_or_default();
+        }
+    }
+
+    if nanos == 0 {
+        formatted.push_str("0s");
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("1h30m"), Ok(TimeDelta::minutes(90)));
+        assert_eq!(parse_duration("1.5s"), Ok(TimeDelta::milliseconds(1500)));
+        assert_eq!(
+            parse_duration(" 1s 1ms "),
+            Ok(TimeDelta::milliseconds(1001))
+        );
+        assert_eq!(parse_duration("-250MS"), Ok(TimeDelta::milliseconds(-250)));
+        assert_eq!(parse_duration("2w"), Ok(TimeDelta::days(14)));
+        assert_eq!(parse_duration("3µs"), Ok(TimeDelta::microseconds(3)));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert_eq!(parse_duration(""), Err("invalid duration: ''".to_owned()));
+        assert_eq!(
+            parse_duration("5"),
+            Err("unknown duration unit: ''".to_owned())
+        );
+        assert_eq!(
+            parse_duration("5y"),
+            Err("unknown duration unit: 'y'".to_owned())
+        );
+        assert_eq!(
+            parse_duration("1h-5m"),
+            Err("invalid duration: '1h-5m'".to_owned())
+        );
+        assert_eq!(
+            parse_duration("200000000000d"),
+            Err("duration is out of range: '200000000000d'".to_owned())
+        );
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(TimeDelta::zero()), "0s");
+        assert_eq!(format_duration(TimeDelta::minutes(90)), "1h30m");
+        assert_eq!(format_duration(TimeDelta::milliseconds(-1500)), "-1s500ms");
+        assert_eq!(
+            format_duration(TimeDelta::days(1) + TimeDelta::nanoseconds(1)),
+            "1d1ns"
+        );
+        assert_eq!(
+            format_duration(TimeDelta::MAX),
+            "106751991167d7h12m55s807ms"
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for duration in [
+            TimeDelta::nanoseconds(123_456_789_012),
+            TimeDelta::seconds(-86_399),
+            TimeDelta::weeks(3),
+            TimeDelta::MAX,
+            TimeDelta::MIN,
+        ] {
+            assert_eq!(parse_duration(&format_duration(duration)), Ok(duration));
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::{marker::PhantomData, ops::IndexMut};
 
-use super::{KeyString, Value};
+use crate::value::{KeyString, Value};
 
 impl Value {
     /// Create an iterator over the `Value`.
@@ -314,7 +314,7 @@ mod tests {
             (
                 "object non-recursive",
                 TestCase {
-                    value: Value::Object(BTreeMap::from([("foo".into(), true.into())])),
+                    value: Value::from(BTreeMap::from([("foo".into(), true.into())])),
                     recursive: false,
                     items: vec![true.into()],
                 },
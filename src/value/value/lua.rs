@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
+
 use mlua::prelude::LuaResult;
 use mlua::{FromLua, IntoLua, Lua, Value as LuaValue};
 use ordered_float::NotNan;
+use rust_decimal::prelude::ToPrimitive;
 
-use crate::value::Value;
+use crate::value::{KeyString, Value};
 
 impl IntoLua for Value {
     #![allow(clippy::wrong_self_convention)] // this trait is defined by mlua
@@ -14,8 +17,15 @@ impl IntoLua for Value {
                 .map(LuaValue::String),
             Self::Integer(i) => Ok(LuaValue::Integer(i)),
             Self::Float(f) => Ok(LuaValue::Number(f.into_inner())),
+            // Lua has no decimal type, so decimals become Lua numbers.
+            Self::Decimal(d) => Ok(LuaValue::Number(d.to_f64().unwrap_or_default())),
             Self::Boolean(b) => Ok(LuaValue::Boolean(b)),
             Self::Timestamp(t) => timestamp_to_table(lua, t).map(LuaValue::Table),
+            // Durations become a number of seconds, like the result of `os.difftime`.
+            #[allow(clippy::cast_precision_loss)]
+            Self::Duration(d) => Ok(LuaValue::Number(
+                d.num_seconds() as f64 + f64::from(d.subsec_nanos()) / 1e9,
+            )),
             Self::Object(m) => lua.create_table_from(m).map(LuaValue::Table),
             Self::Array(a) => lua.create_sequence_from(a).map(LuaValue::Table),
             Self::Null => lua.create_string("").map(LuaValue::String),
@@ -43,7 +53,8 @@ impl FromLua for Value {
                 } else if table_is_timestamp(&t)? {
                     table_to_timestamp(t).map(Self::Timestamp)
                 } else {
-                    <_>::from_lua(LuaValue::Table(t), lua).map(Self::Object)
+                    // Lua tables have no order, so the fields are sorted by key.
+                    BTreeMap::<KeyString, Self>::from_lua(LuaValue::Table(t), lua).map(Self::from)
                 }
             }
             other => Err(mlua::Error::FromLuaConversionError {
@@ -0,0 +1,230 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use ordered_float::NotNan;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{
+    PyBool, PyByteArray, PyBytes, PyDateTime, PyDelta, PyDict, PyFloat, PyInt, PyList, PyString,
+    PyTuple, PyTzInfo, PyTzInfoAccess,
+};
+use rust_decimal::Decimal;
+
+use crate::value::{ObjectMap, Value};
+
+impl<'py> IntoPyObject<'py> for Value {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    /// Strings that are valid UTF-8 are converted to `str`, and other strings to `bytes`.
+    /// Timestamps are converted to `datetime` objects in UTC, durations to `timedelta` and
+    /// decimals to `decimal.Decimal`.
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        Ok(match self {
+            Self::Bytes(b) => match std::str::from_utf8(&b) {
+                Ok(s) => PyString::new(py, s).into_any(),
+                Err(_) => PyBytes::new(py, &b).into_any(),
+            },
+            Self::Regex(regex) => PyString::new(py, regex.as_str()).into_any(),
+            Self::Integer(i) => i.into_pyobject(py)?.into_any(),
+            Self::Float(f) => f.into_inner().into_pyobject(py)?.into_any(),
+            Self::Decimal(d) => py
+                .import("decimal")?
+                .getattr("Decimal")?
+                .call1((d.to_string(),))?,
+            Self::Boolean(b) => PyBool::new(py, b).to_owned().into_any(),
+            Self::Timestamp(t) => t.into_pyobject(py)?.into_any(),
+            Self::Duration(d) => d.into_pyobject(py)?.into_any(),
+            Self::Object(m) => {
+                let dict = PyDict::new(py);
+                for (key, value) in m {
+                    dict.set_item(key.as_str(), value)?;
+                }
+                dict.into_any()
+            }
+            Self::Array(a) => PyList::new(py, a)?.into_any(),
+            Self::Null => py.None().into_bound(py),
+        })
+    }
+}
+
+impl FromPyObject<'_> for Value {
+    /// Naive `datetime` objects are assumed to be in UTC. Integers that do not fit an `i64` and
+    /// `decimal.Decimal` objects are converted to decimals.
+    fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if ob.is_none() {
+            Ok(Self::Null)
+        } else if let Ok(b) = ob.downcast::<PyBool>() {
+            // `bool` is a subclass of `int`, so it has to be checked first.
+            Ok(Self::Boolean(b.is_true()))
+        } else if ob.is_instance_of::<PyInt>() {
+            ob.extract()
+                .map(Self::Integer)
+                .or_else(|_| extract_decimal(ob))
+        } else if let Ok(f) = ob.downcast::<PyFloat>() {
+            NotNan::new(f.value())
+                .map(Self::Float)
+                .map_err(|_| PyValueError::new_err("NaN not supported"))
+        } else if ob.is_instance(&ob.py().import("decimal")?.getattr("Decimal")?)? {
+            extract_decimal(ob)
+        } else if let Ok(s) = ob.downcast::<PyString>() {
+            Ok(Self::Bytes(s.to_cow()?.into_owned().into()))
+        } else if let Ok(b) = ob.downcast::<PyBytes>() {
+            Ok(Self::Bytes(b.as_bytes().to_vec().into()))
+        } else if let Ok(b) = ob.downcast::<PyByteArray>() {
+            Ok(Self::Bytes(b.to_vec().into()))
+        } else if let Ok(dt) = ob.downcast::<PyDateTime>() {
+            datetime_to_timestamp(dt).map(Self::Timestamp)
+        } else if ob.is_instance_of::<PyDelta>() {
+            ob.extract().map(Self::Duration)
+        } else if let Ok(dict) = ob.downcast::<PyDict>() {
+            dict.iter()
+                .map(|(key, value)| {
+                    let key = key.downcast::<PyString>().map_err(|_| {
+                        PyTypeError::new_err(format!(
+                            "object keys must be strings, not {}",
+                            type_name(&key)
+                        ))
+                    })?;
+                    Ok((key.to_cow()?.into_owned().into(), value.extract()?))
+                })
+                .collect::<PyResult<ObjectMap>>()
+                .map(Self::Object)
+        } else if let Ok(list) = ob.downcast::<PyList>() {
+            list.iter()
+                .map(|item| item.extract())
+                .collect::<PyResult<_>>()
+                .map(Self::Array)
+        } else if let Ok(tuple) = ob.downcast::<PyTuple>() {
+            tuple
+                .iter()
+                .map(|item| item.extract())
+                .collect::<PyResult<_>>()
+                .map(Self::Array)
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "cannot convert {} to a VRL value",
+                type_name(ob)
+            )))
+        }
+    }
+}
+
+fn extract_decimal(ob: &Bound<'_, PyAny>) -> PyResult<Value> {
+    ob.str()?
+        .to_str()?
+        .parse::<Decimal>()
+        .map(Value::Decimal)
+        .map_err(|e| PyValueError::new_err(format!("unsupported decimal: {e}")))
+}
+
+/// Converts a `datetime` to a UTC timestamp, assuming UTC for naive datetimes.
+///
+/// # Errors
+///
+/// This function will fail if the datetime cannot be converted to UTC.
+pub fn datetime_to_timestamp(dt: &Bound<'_, PyDateTime>) -> PyResult<DateTime<Utc>> {
+    if dt.get_tzinfo().is_some() {
+        dt.call_method1("astimezone", (PyTzInfo::utc(dt.py())?,))?
+            .extract()
+    } else {
+        dt.extract::<NaiveDateTime>().map(|dt| dt.and_utc())
+    }
+}
+
+fn type_name(ob: &Bound<'_, PyAny>) -> String {
+    ob.get_type()
+        .name()
+        .map_or_else(|_| "unknown".to_owned(), |name| name.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+    use pyo3::ffi::c_str;
+
+    use super::*;
+
+    fn eval(source: &std::ffi::CStr) -> PyResult<Value> {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| py.eval(source, None, None)?.extract())
+    }
+
+    #[test]
+    fn from_python() {
+        let pairs = [
+            (
+                c_str!("'\u{237a}\u{3b2}\u{3b3}'"),
+                Value::from("\u{237a}\u{3b2}\u{3b3}"),
+            ),
+            (c_str!("b'\\xff'"), Value::Bytes(vec![0xff].into())),
+            (c_str!("123"), Value::Integer(123)),
+            (c_str!("4.333"), Value::from(4.333)),
+            (c_str!("True"), Value::Boolean(true)),
+            (c_str!("None"), Value::Null),
+            (
+                c_str!("{'x': 1, 'y': '2', 'nested': {'other': [5.678, (1,)]}}"),
+                crate::value!({ x: 1, y: "2", nested: { other: [5.678, [1]] } }),
+            ),
+            (
+                c_str!(
+                    "__import__('datetime').datetime(2020, 1, 2, 4, 4, 5, 6, __import__('datetime').timezone(__import__('datetime').timedelta(hours=1)))"
+                ),
+                Value::Timestamp(
+                    Utc.with_ymd_and_hms(2020, 1, 2, 3, 4, 5).unwrap()
+                        + chrono::Duration::microseconds(6),
+                ),
+            ),
+            (
+                c_str!("__import__('datetime').datetime(2020, 1, 2, 3, 4, 5)"),
+                Value::Timestamp(Utc.with_ymd_and_hms(2020, 1, 2, 3, 4, 5).unwrap()),
+            ),
+        ];
+
+        for (source, expected) in pairs {
+            assert_eq!(eval(source).unwrap(), expected, "{source:?}");
+        }
+    }
+
+    #[test]
+    fn from_python_errors() {
+        for (source, error) in [
+            (c_str!("float('nan')"), "ValueError: NaN not supported"),
+            (
+                c_str!("{1: 2}"),
+                "TypeError: object keys must be strings, not int",
+            ),
+            (
+                c_str!("object()"),
+                "TypeError: cannot convert object to a VRL value",
+            ),
+        ] {
+            assert_eq!(eval(source).unwrap_err().to_string(), error);
+        }
+    }
+
+    #[test]
+    fn to_python() {
+        pyo3::prepare_freethreaded_python();
+        let value = crate::value!({
+            string: "\u{237a}\u{3b2}\u{3b3}",
+            bytes: (Value::Bytes(vec![0xff].into())),
+            integer: 123,
+            float: 4.333,
+            boolean: true,
+            null: null,
+            array: [1, "two"],
+            timestamp: (Utc.with_ymd_and_hms(2020, 1, 2, 3, 4, 5).unwrap()),
+        });
+
+        Python::with_gil(|py| {
+            let object = value.into_pyobject(py).unwrap();
+            let repr = object.repr().unwrap().to_string();
+            assert_eq!(
+                repr,
+                "{'array': [1, 'two'], 'boolean': True, 'bytes': b'\\xff', 'float': 4.333, \
+                 'integer': 123, 'null': None, 'string': '\u{237a}\u{3b2}\u{3b3}', \
+                 'timestamp': datetime.datetime(2020, 1, 2, 3, 4, 5, tzinfo=datetime.timezone.utc)}"
+            );
+        });
+    }
+}
@@ -1,8 +1,11 @@
-use std::{borrow::Cow, collections::BTreeMap, fmt};
+use std::{borrow::Cow, fmt};
 
-use crate::value::value::{StdError, Value, simdutf_bytes_utf8_lossy, timestamp_to_string};
+use crate::value::value::{
+    ObjectMap, StdError, Value, format_duration, simdutf_bytes_utf8_lossy, timestamp_to_string,
+};
 use bytes::Bytes;
 use ordered_float::NotNan;
+use rust_decimal::{Decimal, prelude::ToPrimitive};
 use serde::de::Error as SerdeError;
 use serde::de::{MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Serialize, Serializer};
@@ -17,8 +20,10 @@ impl Value {
             Self::Bytes(bytes) => bytes.clone(), // cloning `Bytes` is cheap
             Self::Regex(regex) => regex.as_bytes(),
             Self::Timestamp(timestamp) => Bytes::from(timestamp_to_string(timestamp)),
+            Self::Duration(duration) => Bytes::from(format_duration(*duration)),
             Self::Integer(num) => Bytes::from(num.to_string()),
             Self::Float(num) => Bytes::from(num.to_string()),
+            Self::Decimal(num) => Bytes::from(num.to_string()),
             Self::Boolean(b) => Bytes::from(b.to_string()),
             Self::Object(map) => {
                 Bytes::from(serde_json::to_vec(map).expect("Cannot serialize map"))
@@ -39,8 +44,10 @@ impl Value {
             Self::Bytes(bytes) => simdutf_bytes_utf8_lossy(bytes),
             Self::Regex(regex) => regex.as_str().into(),
             Self::Timestamp(timestamp) => timestamp_to_string(timestamp).into(),
+            Self::Duration(duration) => format_duration(*duration).into(),
             Self::Integer(num) => num.to_string().into(),
             Self::Float(num) => num.to_string().into(),
+            Self::Decimal(num) => num.to_string().into(),
             Self::Boolean(b) => b.to_string().into(),
             Self::Object(map) => serde_json::to_string(map)
                 .expect("Cannot serialize map")
@@ -61,9 +68,11 @@ impl Serialize for Value {
         match &self {
             Self::Integer(i) => serializer.serialize_i64(*i),
             Self::Float(f) => serializer.serialize_f64(f.into_inner()),
+            Self::Decimal(d) => serialize_decimal(*d, serializer),
             Self::Boolean(b) => serializer.serialize_bool(*b),
             Self::Bytes(b) => serializer.serialize_str(simdutf_bytes_utf8_lossy(b).as_ref()),
             Self::Timestamp(ts) => serializer.serialize_str(&timestamp_to_string(ts)),
+            Self::Duration(d) => serializer.serialize_str(&format_duration(*d)),
             Self::Regex(regex) => serializer.serialize_str(regex.as_str()),
             Self::Object(m) => serializer.collect_map(m),
             Self::Array(a) => serializer.collect_seq(a),
@@ -72,8 +81,33 @@ impl Serialize for Value {
     }
 }
 
+/// Serializes a decimal exactly: whole numbers and fractions whose nearest `f64` has the same
+/// value as numbers, and other fractions as a string of their decimal text.
+fn serialize_decimal<S: Serializer>(decimal: Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+    if decimal.is_integer() {
+        if let Some(i) = decimal.to_i64() {
+            return serializer.serialize_i64(i);
+        }
+        if let Some(u) = decimal.to_u64() {
+            return serializer.serialize_u64(u);
+        }
+        if let Some(i) = decimal.to_i128() {
+            return serializer.serialize_i128(i);
+        }
+    }
+    // The shortest representation of the `f64` is what gets written, so it has to be the same
+    // value for the number to be exact.
+    if let Some(float) = decimal.to_f64()
+        && float.to_string().parse::<Decimal>() == Ok(decimal)
+    {
+        return serializer.serialize_f64(float);
+    }
+    serializer.serialize_str(&decimal.to_string())
+}
+
 impl<'de> Deserialize<'de> for Value {
     #[inline]
+    #[allow(clippy::too_many_lines)]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
@@ -98,23 +132,34 @@ impl<'de> Deserialize<'de> for Value {
             }
 
             #[inline]
-            fn visit_u64<E>(self, value: u64) -> Result<Value, E>
+            fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+                Ok(value.into())
+            }
+
+            #[inline]
+            fn visit_i128<E>(self, value: i128) -> Result<Value, E>
             where
                 E: serde::de::Error,
             {
                 if let Ok(value) = i64::try_from(value) {
-                    Ok(value.into())
-                } else {
-                    // TODO: Address this issue by providing a lossless conversion option.
-                    #[allow(clippy::cast_precision_loss)] //TODO evaluate removal options
-                    let converted_value = value as f64;
-                    let wrapped_value = NotNan::new(converted_value).map_err(|_| {
-                        SerdeError::invalid_value(
-                            serde::de::Unexpected::Float(converted_value),
-                            &self,
-                        )
-                    })?;
-                    Ok(Value::Float(wrapped_value))
+                    return Ok(value.into());
+                }
+                match Decimal::try_from_i128_with_scale(value, 0) {
+                    Ok(decimal) => Ok(decimal.into()),
+                    #[allow(clippy::cast_precision_loss)]
+                    Err(_) => self.visit_f64(value as f64),
+                }
+            }
+
+            #[inline]
+            fn visit_u128<E>(self, value: u128) -> Result<Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match i128::try_from(value) {
+                    Ok(value) => self.visit_i128(value),
+                    #[allow(clippy::cast_precision_loss)]
+                    Err(_) => self.visit_f64(value as f64),
                 }
             }
 
@@ -177,7 +222,7 @@ impl<'de> Deserialize<'de> for Value {
             where
                 V: MapAccess<'de>,
             {
-                let mut map = BTreeMap::new();
+                let mut map = ObjectMap::new();
                 while let Some((key, value)) = visitor.next_entry()? {
                     map.insert(key, value);
                 }
@@ -199,7 +244,9 @@ impl From<serde_json::Value> for Value {
                 // JSON doesn't support NaN values
                 NotNan::new(n.as_f64().unwrap()).unwrap().into()
             }
-            serde_json::Value::Number(n) => n.to_string().into(),
+            serde_json::Value::Number(n) => {
+                n.as_u64().map_or_else(|| n.to_string().into(), Self::from)
+            }
             serde_json::Value::String(s) => Self::Bytes(Bytes::from(s)),
             serde_json::Value::Object(obj) => Self::Object(
                 obj.into_iter()
@@ -226,12 +273,14 @@ impl TryInto<serde_json::Value> for Value {
             Self::Boolean(v) => Ok(serde_json::Value::from(v)),
             Self::Integer(v) => Ok(serde_json::Value::from(v)),
             Self::Float(v) => Ok(serde_json::Value::from(v.into_inner())),
+            Self::Decimal(v) => Ok(serde_json::to_value(Self::Decimal(v))?),
             Self::Bytes(v) => Ok(serde_json::Value::from(simdutf8::compat::from_utf8(&v)?)),
             Self::Regex(regex) => Ok(serde_json::Value::from(regex.as_str().to_string())),
             Self::Object(v) => Ok(serde_json::to_value(v)?),
             Self::Array(v) => Ok(serde_json::to_value(v)?),
             Self::Null => Ok(serde_json::Value::Null),
             Self::Timestamp(v) => Ok(serde_json::Value::from(timestamp_to_string(&v))),
+            Self::Duration(v) => Ok(serde_json::Value::from(format_duration(v))),
         }
     }
 }
@@ -288,6 +337,7 @@ mod test {
                                 let is_match = match vector_value {
                                     Value::Boolean(_) => expected_type.eq("boolean"),
                                     Value::Integer(_) => expected_type.eq("integer"),
+                                    Value::Decimal(_) => expected_type.eq("decimal"),
                                     Value::Bytes(_) => expected_type.eq("bytes"),
                                     Value::Array { .. } => expected_type.eq("array"),
                                     Value::Object(_) => expected_type.eq("map"),
@@ -308,4 +358,53 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn unsigned_64_bit_round_trip() {
+        let json = "[18446744073709551615,9223372036854775808,-9223372036854775808]";
+
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                Value::Decimal(u64::MAX.into()),
+                Value::Decimal((1_u64 << 63).into()),
+                Value::Integer(i64::MIN),
+            ])
+        );
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+
+        let fraction = Value::Decimal(rust_decimal::Decimal::new(1999, 2));
+        assert_eq!(serde_json::to_string(&fraction).unwrap(), "19.99");
+    }
+
+    #[test]
+    fn precise_decimal_round_trip() {
+        let decimal: rust_decimal::Decimal = "12345678901234567.89".parse().unwrap();
+
+        let json = serde_json::to_string(&Value::Decimal(decimal)).unwrap();
+        assert_eq!(json, r#""12345678901234567.89""#);
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value
+                .as_str()
+                .unwrap()
+                .parse::<rust_decimal::Decimal>()
+                .unwrap(),
+            decimal
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "preserve_order")]
+    fn keeps_key_order() {
+        let json = r#"{"z":1,"a":{"y":true,"b":null},"m":[{"q":"x","c":2}]}"#;
+
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+
+        let value = crate::value!({"z": 1, "a": {"y": true, "b": null}, "m": [{"q": "x", "c": 2}]});
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+    }
 }
@@ -0,0 +1,344 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run_vrl(input: &str, args: &[&str]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_vrl"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn vrl process");
+
+    // Results are written while the input is read, so the input is written on its own thread.
+    let mut stdin = child
+        .stdin
+        .take()
+        .expect("failed to take stdin for child vrl cli");
+    let input = input.to_owned();
+    let writer = std::thread::spawn(move || {
+        stdin
+            .write_all(input.as_bytes())
+            .expect("failed to write input to stdin");
+    });
+
+    let output = child.wait_with_output().expect("failed to wait on child");
+    writer.join().expect("failed to write input");
+    output
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+#[test]
+fn text_input_ndjson_output() {
+    let output = run_vrl(
+        "foo\nbar\n",
+        &[
+            "--input-format",
+            "text",
+            "--output-format",
+            "ndjson",
+            "-o",
+            ".length = strlen(string!(.message))",
+        ],
+    );
+
+    assert!(output.status.success());
+    // The `preserve_order` feature keeps the fields in insertion order.
+    let expected = if cfg!(feature = "preserve_order") {
+        "{\"message\":\"foo\",\"length\":3}\n{\"message\":\"bar\",\"length\":3}\n"
+    } else {
+        "{\"length\":3,\"message\":\"foo\"}\n{\"length\":3,\"message\":\"bar\"}\n"
+    };
+    assert_eq!(stdout(&output), expected);
+    assert_eq!(
+        stderr(&output),
+        "processed 2 event(s): 2 succeeded, 0 aborted, 0 errored\n"
+    );
+}
+
+#[test]
+fn input_field_order() {
+    let ndjson = run_vrl(
+        "{\"z\": 1, \"a\": {\"y\": 2, \"b\": 3}}\n",
+        &["-q", "--output-format", "ndjson", "-o", "."],
+    );
+    let csv = run_vrl(
+        "z,a\n1,2\n",
+        &[
+            "-q",
+            "--input-format",
+            "csv",
+            "--output-format",
+            "ndjson",
+            "-o",
+            ".",
+        ],
+    );
+
+    assert!(ndjson.status.success());
+    assert!(csv.status.success());
+    // The `preserve_order` feature keeps the fields in the order of the input.
+    if cfg!(feature = "preserve_order") {
+        assert_eq!(stdout(&ndjson), "{\"z\":1,\"a\":{\"y\":2,\"b\":3}}\n");
+        assert_eq!(stdout(&csv), "{\"z\":\"1\",\"a\":\"2\"}\n");
+    } else {
+        assert_eq!(stdout(&ndjson), "{\"a\":{\"b\":3,\"y\":2},\"z\":1}\n");
+        assert_eq!(stdout(&csv), "{\"a\":\"2\",\"z\":\"1\"}\n");
+    }
+}
+
+#[test]
+fn json_input_json_output() {
+    let output = run_vrl(
+        r#"[{"a": 1}, {"a": 2}]"#,
+        &[
+            "-q",
+            "--input-format",
+            "json",
+            "--output-format",
+            "json",
+            ".a",
+        ],
+    );
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "[1,2]\n");
+    assert_eq!(stderr(&output), "");
+}
+
+#[test]
+fn json_input_numbers() {
+    // Integers above `i64::MAX` are decoded as exact decimals rather than clamped.
+    let input = "{\"big\": 18446744073709551615, \"f\": 1.5, \"n\": -3}\n";
+    let program = "[.big, .big + 1 ?? null, .f, .n]";
+
+    for format in ["ndjson", "json"] {
+        let output = run_vrl(input, &["-q", "--input-format", format, program]);
+        assert!(output.status.success(), "{format}");
+        assert_eq!(
+            stdout(&output),
+            "[18446744073709551615, 18446744073709551616, 1.5, -3]\n",
+            "{format}"
+        );
+    }
+}
+
+#[test]
+fn vector_metadata_is_read_only() {
+    let output = run_vrl("{}\n", &["-q", "%vector.source = \"cli\""]);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("mutation of read-only value"));
+}
+
+#[test]
+fn errored_events_exit_code() {
+    let output = run_vrl(
+        "{\"a\": 1}\n{\"a\": \"foo\"}\n{\"abort\": true}\n",
+        &["if .abort == true { abort }; to_int!(.a)"],
+    );
+
+    assert_eq!(output.status.code(), Some(exitcode::DATAERR));
+    assert_eq!(stdout(&output), "1\n");
+    assert!(stderr(&output).ends_with("processed 3 event(s): 1 succeeded, 1 aborted, 1 errored\n"));
+}
+
+#[test]
+fn aborted_events_exit_code() {
+    let output = run_vrl("{}\n", &["-q", "abort"]);
+
+    assert_eq!(output.status.code(), Some(exitcode::OK));
+}
+
+#[test]
+fn metadata_and_secrets() {
+    let dir = std::env::temp_dir().join(format!("vrl-cli-metadata-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let metadata = dir.join("metadata.json");
+    let secrets = dir.join("secrets.json");
+    std::fs::write(&metadata, r#"{"source": "kafka"}"#).unwrap();
+    std::fs::write(&secrets, r#"{"token": "foo"}"#).unwrap();
+
+    let output = run_vrl(
+        "{}\n",
+        &[
+            "-q",
+            "--metadata",
+            metadata.to_str().unwrap(),
+            "--secrets",
+            secrets.to_str().unwrap(),
+            "%source",
+        ],
+    );
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "\"kafka\"\n");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn diff_programs() {
+    let dir = std::env::temp_dir().join(format!("vrl-cli-diff-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let old = dir.join("old.vrl");
+    let new = dir.join("new.vrl");
+    std::fs::write(&old, ".foo = 1").unwrap();
+    std::fs::write(&new, ".foo = 1\nif .bar == true { .foo = 2 }").unwrap();
+
+    let output = run_vrl(
+        "{\"bar\": false}\n{\"bar\": true}\n",
+        &[
+            "diff",
+            old.to_str().unwrap(),
+            new.to_str().unwrap(),
+            "--json",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(exitcode::DATAERR));
+    let diff: serde_json::Value = serde_json::from_str(stdout(&output).trim()).unwrap();
+    assert_eq!(diff["event"], 1);
+    assert_eq!(
+        diff["differences"],
+        serde_json::json!([{ "kind": "value", "path": ".foo", "old": 1, "new": 2 }])
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn threads_preserve_input_order() {
+    // Enough events for several batches per thread.
+    let input = (0..10_000)
+        .map(|i| format!("{{\"i\": {i}}}\n"))
+        .collect::<String>();
+    let output = run_vrl(&input, &["-q", "--threads", "4", ".i"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        (0..10_000).map(|i| format!("{i}\n")).collect::<String>()
+    );
+}
+
+#[test]
+fn threads_summary() {
+    let output = run_vrl(
+        "{\"a\": 1}\n{\"a\": \"foo\"}\n{\"abort\": true}\n",
+        &["--threads", "2", "if .abort == true { abort }; to_int!(.a)"],
+    );
+
+    assert_eq!(output.status.code(), Some(exitcode::DATAERR));
+    assert_eq!(stdout(&output), "1\n");
+    assert!(stderr(&output).ends_with("processed 3 event(s): 1 succeeded, 1 aborted, 1 errored\n"));
+}
+
+#[test]
+fn threads_json_output() {
+    let output = run_vrl(
+        "{\"a\": 1}\nnot json\n{\"a\": 2}\n",
+        &["-q", "--threads", "2", "--output-format", "json", ".a"],
+    );
+
+    assert_eq!(output.status.code(), Some(exitcode::DATAERR));
+    assert_eq!(stdout(&output), "[1,2]\n");
+}
+
+#[test]
+fn import_datadog_pipeline() {
+    let dir = std::env::temp_dir().join(format!("vrl-cli-import-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let program = dir.join("pipeline.vrl");
+
+    let output = run_vrl(
+        "",
+        &[
+            "import-datadog",
+            "tests/data/datadog/pipeline.json",
+            "-o",
+            program.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success());
+
+    let output = run_vrl(
+        r#"{"source": "nginx", "message": "127.0.0.1 - - [13/Jul/2016:10:55:36 +0000] \"GET /index.html HTTP/1.1\" 200 2326"}"#,
+        &[
+            "-q",
+            "-o",
+            "-p",
+            program.to_str().unwrap(),
+            "--output-format",
+            "ndjson",
+        ],
+    );
+    assert!(output.status.success());
+
+    let event: serde_json::Value = serde_json::from_str(stdout(&output).trim()).unwrap();
+    assert_eq!(event["http"]["status_code"], 200);
+    assert_eq!(event["http"]["status_category"], "OK");
+    assert_eq!(event["status_code"], "200");
+    assert_eq!(event["network"]["client"]["ip"], "127.0.0.1");
+    assert_eq!(event["timestamp"], "2016-07-13T10:55:36Z");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn grok_debug() {
+    let dir = std::env::temp_dir().join(format!("vrl-cli-grok-debug-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let patterns = dir.join("patterns");
+    std::fs::write(
+        &patterns,
+        "%{_client} %{word:method} %{integer:status}\n%{_client} %{data:rest}\n",
+    )
+    .unwrap();
+    let aliases = dir.join("aliases");
+    std::fs::write(&aliases, "_client %{ipOrHost:client}\n").unwrap();
+
+    let output = run_vrl(
+        "127.0.0.1 GET abc\n",
+        &[
+            "grok-debug",
+            patterns.to_str().unwrap(),
+            "--aliases",
+            aliases.to_str().unwrap(),
+            "--json",
+        ],
+    );
+    assert!(output.status.success());
+
+    let line: serde_json::Value = serde_json::from_str(stdout(&output).trim()).unwrap();
+    assert_eq!(line["rules"][0]["matched"], false);
+    assert_eq!(
+        line["rules"][0]["matched_pattern"],
+        "%{ipOrHost:client} %{word:method} "
+    );
+    assert_eq!(line["rules"][0]["failed_pattern"], "%{integer:status}");
+    assert_eq!(line["rules"][0]["failed_field"], "status");
+    assert_eq!(line["rules"][0]["parsed"]["method"], "GET");
+    assert_eq!(line["rules"][1]["matched"], true);
+
+    let output = run_vrl(
+        "nope\n",
+        &[
+            "grok-debug",
+            patterns.to_str().unwrap(),
+            "--aliases",
+            aliases.to_str().unwrap(),
+        ],
+    );
+    assert_eq!(output.status.code(), Some(65));
+    assert!(stdout(&output).contains("rule 0: no match"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
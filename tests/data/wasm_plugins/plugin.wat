@@ -0,0 +1,158 @@
+;; A VRL plugin component exporting five functions, used to test the `wasm_plugins` feature:
+;;
+;; * `wasm_echo(value)` returns its argument.
+;; * `wasm_fail()` always returns an error.
+;; * `wasm_spin()` never returns, and runs out of fuel.
+;; * `wasm_count()` returns the number of calls to its instance, including itself.
+;; * `wasm_grow()` grows the memory by 4 MiB, and fails if it can't.
+;;
+;; It implements the `plugin` world of `src/compiler/function/wasm_plugin.wit`, with the types
+;; laid out in memory by the canonical ABI. The heap only holds the arguments of a call, so it is
+;; reset after every call instead of freeing memory.
+(component
+  (core module $plugin
+    (memory (export "memory") 1)
+    (global $heap (mut i32) (i32.const 4096))
+    (global $calls (mut i64) (i64.const 0))
+
+    ;; 16: the `list<function>` returned by `functions`.
+    ;; 32: the `result<value, string>` returned by `resolve`.
+    ;; 64: a `null` node.
+    ;; 96: an `integer` node.
+    ;; 256: the `function` records, followed by the `parameter` records at 400.
+    (data (i32.const 96) "\02")
+    (data (i32.const 1024) "the plugin failed")
+    (data (i32.const 1041) "the plugin is out of memory")
+    (data (i32.const 1068) "wasm_echo")
+    (data (i32.const 1077) "Returns the value.")
+    (data (i32.const 1095) "value")
+    (data (i32.const 1100) "The value to return.")
+    (data (i32.const 1120) "wasm_fail")
+    (data (i32.const 1129) "Always fails.")
+    (data (i32.const 1142) "wasm_spin")
+    (data (i32.const 1151) "Never returns.")
+    (data (i32.const 1165) "wasm_count")
+    (data (i32.const 1175) "Returns the number of calls to this instance of the plugin.")
+    (data (i32.const 1234) "wasm_grow")
+    (data (i32.const 1243) "Grows the memory of the plugin by 4 MiB.")
+
+    (func (export "realloc") (param $old i32) (param $old_size i32) (param $align i32) (param $size i32) (result i32)
+      (local $ptr i32)
+      (local.set $ptr
+        (i32.and
+          (i32.add (global.get $heap) (i32.sub (local.get $align) (i32.const 1)))
+          (i32.sub (i32.const 0) (local.get $align))))
+      (global.set $heap (i32.add (local.get $ptr) (local.get $size)))
+      (block $done
+        (loop $grow
+          (br_if $done
+            (i32.le_u (global.get $heap) (i32.mul (memory.size) (i32.const 65536))))
+          (if (i32.eq (memory.grow (i32.const 1)) (i32.const -1))
+            (then unreachable))
+          (br $grow)))
+      (local.get $ptr))
+
+    (func $function (param $at i32) (param $identifier i32) (param $identifier_len i32) (param $usage i32) (param $usage_len i32) (param $parameters i32) (param $parameters_len i32) (param $kinds i32)
+      (i32.store offset=0 (local.get $at) (local.get $identifier))
+      (i32.store offset=4 (local.get $at) (local.get $identifier_len))
+      (i32.store offset=8 (local.get $at) (local.get $usage))
+      (i32.store offset=12 (local.get $at) (local.get $usage_len))
+      (i32.store offset=16 (local.get $at) (local.get $parameters))
+      (i32.store offset=20 (local.get $at) (local.get $parameters_len))
+      (i32.store16 offset=24 (local.get $at) (local.get $kinds)))
+
+    (func $parameter (param $at i32) (param $keyword i32) (param $keyword_len i32) (param $kinds i32) (param $required i32) (param $description i32) (param $description_len i32)
+      (i32.store offset=0 (local.get $at) (local.get $keyword))
+      (i32.store offset=4 (local.get $at) (local.get $keyword_len))
+      (i32.store16 offset=8 (local.get $at) (local.get $kinds))
+      (i32.store8 offset=10 (local.get $at) (local.get $required))
+      (i32.store offset=12 (local.get $at) (local.get $description))
+      (i32.store offset=16 (local.get $at) (local.get $description_len)))
+
+    (func (export "functions") (result i32)
+      ;; wasm_echo
+      (call $parameter (i32.const 400) (i32.const 1095) (i32.const 5) (i32.const 2047) (i32.const 1) (i32.const 1100) (i32.const 20))
+      (call $function (i32.const 256) (i32.const 1068) (i32.const 9) (i32.const 1077) (i32.const 18) (i32.const 400) (i32.const 1) (i32.const 2047))
+      ;; wasm_fail
+      (call $function (i32.const 284) (i32.const 1120) (i32.const 9) (i32.const 1129) (i32.const 13) (i32.const 0) (i32.const 0) (i32.const 1024))
+      ;; wasm_spin
+      (call $function (i32.const 312) (i32.const 1142) (i32.const 9) (i32.const 1151) (i32.const 14) (i32.const 0) (i32.const 0) (i32.const 1024))
+      ;; wasm_count
+      (call $function (i32.const 340) (i32.const 1165) (i32.const 10) (i32.const 1175) (i32.const 59) (i32.const 0) (i32.const 0) (i32.const 2))
+      ;; wasm_grow
+      (call $function (i32.const 368) (i32.const 1234) (i32.const 9) (i32.const 1243) (i32.const 40) (i32.const 0) (i32.const 0) (i32.const 1024))
+      (i32.store (i32.const 16) (i32.const 256))
+      (i32.store (i32.const 20) (i32.const 5))
+      (i32.const 16))
+
+    ;; Returns `ok` of the `len` nodes at `nodes`.
+    (func $ok (param $nodes i32) (param $len i32) (result i32)
+      (i32.store8 (i32.const 32) (i32.const 0))
+      (i32.store (i32.const 36) (local.get $nodes))
+      (i32.store (i32.const 40) (local.get $len))
+      (i32.const 32))
+
+    ;; Returns `err` of the `len` bytes of the message at `message`.
+    (func $err (param $message i32) (param $len i32) (result i32)
+      (i32.store8 (i32.const 32) (i32.const 1))
+      (i32.store (i32.const 36) (local.get $message))
+      (i32.store (i32.const 40) (local.get $len))
+      (i32.const 32))
+
+    (func (export "resolve") (param $index i32) (param $args i32) (param $len i32) (result i32)
+      (global.set $calls (i64.add (global.get $calls) (i64.const 1)))
+      (block $grow
+        (block $count
+          (block $spin
+            (block $fail
+              (block $echo
+                (br_table $echo $fail $spin $count $grow (local.get $index)))
+              ;; The first argument is a `some` of a value, with its nodes after the tag.
+              (return
+                (call $ok
+                  (i32.load offset=4 (local.get $args))
+                  (i32.load offset=8 (local.get $args)))))
+            (return (call $err (i32.const 1024) (i32.const 17))))
+          (loop $forever (br $forever)))
+        (i64.store (i32.const 104) (global.get $calls))
+        (return (call $ok (i32.const 96) (i32.const 1))))
+      (if (result i32) (i32.eq (memory.grow (i32.const 64)) (i32.const -1))
+        (then (call $err (i32.const 1041) (i32.const 27)))
+        (else (call $ok (i32.const 64) (i32.const 1)))))
+
+    (func (export "post-resolve") (param i32)
+      (global.set $heap (i32.const 4096))))
+
+  (core instance $plugin (instantiate $plugin))
+
+  (type $kinds' (flags "bytes" "integer" "float" "decimal" "boolean" "object" "array" "timestamp" "duration" "regex" "null"))
+  (export $kinds "kinds" (type $kinds'))
+  (type $parameter' (record (field "keyword" string) (field "kinds" $kinds) (field "required" bool) (field "description" string)))
+  (export $parameter "parameter" (type $parameter'))
+  (type $function' (record (field "identifier" string) (field "usage" string) (field "parameters" (list $parameter)) (field "return-kinds" $kinds)))
+  (export $function "function" (type $function'))
+  (type $datetime' (record (field "seconds" s64) (field "nanoseconds" u32)))
+  (export $datetime "datetime" (type $datetime'))
+  (type $node' (variant
+    (case "null")
+    (case "boolean" bool)
+    (case "integer" s64)
+    (case "float" f64)
+    (case "decimal" string)
+    (case "bytes" (list u8))
+    (case "timestamp" $datetime)
+    (case "duration" s64)
+    (case "regex" string)
+    (case "array" (list u32))
+    (case "object" (list (tuple string u32)))))
+  (export $node "node" (type $node'))
+  (type $value' (list $node))
+  (export $value "value" (type $value'))
+
+  (func (export "functions") (result (list $function))
+    (canon lift (core func $plugin "functions") (memory $plugin "memory")))
+  (func (export "resolve") (param "index" u32) (param "arguments" (list (option $value))) (result (result $value (error string)))
+    (canon lift (core func $plugin "resolve")
+      (memory $plugin "memory")
+      (realloc (func $plugin "realloc"))
+      (post-return (func $plugin "post-resolve")))))